@@ -1,11 +1,14 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::break_content::{BreakContentProvider, BreakContext, DefaultBreakContent};
 use crate::database::models::{
-    Session as DbSession, SessionStats as DbSessionStats, SessionType,
-    UserSettings as DbUserSettings,
+    BypassAttempt, DailyGoal, DashboardSnapshot, PeriodStats as DbPeriodStats, PersonalRecords,
+    Session as DbSession, SessionStats as DbSessionStats, SessionType, StrictModeInterval,
+    TagStats as DbTagStats, UserSettings as DbUserSettings,
 };
+use crate::util::parse_stored_timestamp;
 
 /// API model for user settings - simplified for frontend use
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,17 +26,68 @@ pub struct UserSettings {
 }
 
 impl Default for UserSettings {
+    /// Same values as the database defaults, converted to minutes
     fn default() -> Self {
+        DbUserSettings::default().into()
+    }
+}
+
+/// Fields of the stored settings that are bookkeeping, secrets or transient
+/// state rather than preferences, so they are left out of the defaults diff
+const NON_PREFERENCE_SETTINGS: &[&str] = &[
+    "id",
+    "created_at",
+    "updated_at",
+    "pin_hash",
+    "cycles_per_long_break", // superseded by cycles_per_long_break_v2
+    "notifications_muted_until",
+];
+
+/// One setting next to its default value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingVsDefault {
+    /// Field name as returned by `get_user_settings`
+    pub key: String,
+    pub current: serde_json::Value,
+    pub default: serde_json::Value,
+    pub customized: bool,
+}
+
+/// How the stored settings differ from `UserSettings::default()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsVsDefaults {
+    /// Ordered by key
+    pub settings: Vec<SettingVsDefault>,
+    pub customized_count: u32,
+}
+
+impl SettingsVsDefaults {
+    pub fn new(current: &DbUserSettings) -> Self {
+        let fields = |settings: &DbUserSettings| match serde_json::to_value(settings) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => serde_json::Map::new(),
+        };
+        let current = fields(current);
+
+        let settings: Vec<SettingVsDefault> = fields(&DbUserSettings::default())
+            .into_iter()
+            .filter(|(key, _)| !NON_PREFERENCE_SETTINGS.contains(&key.as_str()))
+            .map(|(key, default)| {
+                let current = current.get(&key).cloned().unwrap_or_default();
+                SettingVsDefault {
+                    customized: current != default,
+                    key,
+                    current,
+                    default,
+                }
+            })
+            .collect();
+
         Self {
-            focus_duration: 25,      // 25 minutes
-            short_break_duration: 5, // 5 minutes
-            long_break_duration: 15, // 15 minutes
-            cycles_per_long_break: 4,
-            pre_alert_seconds: 120, // 2 minutes
-            strict_mode: false,
-            pin_hash: None,
-            emergency_key_combination: None,
-            break_transition_seconds: 10, // 10 seconds
+            customized_count: settings.iter().filter(|s| s.customized).count() as u32,
+            settings,
         }
     }
 }
@@ -72,6 +126,8 @@ pub struct BreakSession {
     pub remaining: u32, // remaining time in seconds
     pub activity: BreakActivity,
     pub allow_emergency: bool,
+    /// The user's own note for the break screen (`set_break_message`)
+    pub message: Option<String>,
 }
 
 /// Break type enumeration
@@ -80,6 +136,8 @@ pub struct BreakSession {
 pub enum BreakType {
     Short,
     Long,
+    /// End-of-day wind-down, stored as a long break
+    WindDown,
 }
 
 /// Break activity suggestions
@@ -102,6 +160,799 @@ pub struct SessionStats {
     pub evasion_attempts: u32,
 }
 
+/// Per-tag focus statistics for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagStats {
+    pub tag: Option<String>,
+    pub focus_minutes: u32,
+    pub sessions_count: u32,
+    pub sessions_completed: u32,
+    pub completion_rate: f64,
+}
+
+/// Focus sessions started today, completed or not, for a compact badge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TodaySessionCount {
+    pub started: u32,
+    pub completed: u32,
+}
+
+/// Focus statistics for one calendar period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodStats {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub focus_minutes: u32,
+    pub sessions_count: u32,
+    pub sessions_completed: u32,
+    pub completion_rate: f64,
+}
+
+/// A period's stats next to the previous period's, with percentage changes.
+/// Deltas are `None` when the previous period has no data to compare against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodComparison {
+    pub current: PeriodStats,
+    pub previous: PeriodStats,
+    pub focus_minutes_delta: Option<f64>,
+    pub sessions_delta: Option<f64>,
+    pub completion_rate_delta: Option<f64>,
+}
+
+impl PeriodComparison {
+    pub fn new(current: PeriodStats, previous: PeriodStats) -> Self {
+        let percent_change = |current: f64, previous: f64| {
+            if previous == 0.0 {
+                None
+            } else {
+                Some((current - previous) / previous * 100.0)
+            }
+        };
+
+        Self {
+            focus_minutes_delta: percent_change(
+                current.focus_minutes as f64,
+                previous.focus_minutes as f64,
+            ),
+            sessions_delta: percent_change(
+                current.sessions_count as f64,
+                previous.sessions_count as f64,
+            ),
+            completion_rate_delta: percent_change(
+                current.completion_rate,
+                previous.completion_rate,
+            ),
+            current,
+            previous,
+        }
+    }
+}
+
+/// Focus sessions still needed today to reach a daily goal.
+/// Everything is zero once the goal is met.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionsToGoal {
+    pub achieved_minutes: u32,
+    pub goal_minutes: u32,
+    pub sessions_remaining: u32,
+    pub leftover_minutes: u32,
+}
+
+impl SessionsToGoal {
+    pub fn new(achieved_minutes: u32, goal_minutes: u32, focus_minutes: u32) -> Self {
+        let remaining = goal_minutes.saturating_sub(achieved_minutes);
+        let (sessions_remaining, leftover_minutes) = remaining
+            .checked_div(focus_minutes)
+            .map_or((0, remaining), |sessions| {
+                (sessions, remaining % focus_minutes)
+            });
+
+        Self {
+            achieved_minutes,
+            goal_minutes,
+            sessions_remaining,
+            leftover_minutes,
+        }
+    }
+}
+
+/// Today's completed focus against the daily goal in effect. Percentages
+/// are `None` for targets that aren't set and may exceed 100.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyProgress {
+    pub date: NaiveDate,
+    pub goal: Option<DailyGoal>,
+    pub minutes_done: u32,
+    pub sessions_done: u32,
+    pub minutes_percent: Option<f64>,
+    pub sessions_percent: Option<f64>,
+}
+
+impl DailyProgress {
+    pub fn new(
+        date: NaiveDate,
+        goal: Option<DailyGoal>,
+        minutes_done: u32,
+        sessions_done: u32,
+    ) -> Self {
+        let percent = |done: u32, target: Option<u32>| {
+            target
+                .filter(|target| *target > 0)
+                .map(|target| done as f64 / target as f64 * 100.0)
+        };
+
+        Self {
+            date,
+            minutes_percent: percent(
+                minutes_done,
+                goal.as_ref().and_then(|goal| goal.target_minutes),
+            ),
+            sessions_percent: percent(
+                sessions_done,
+                goal.as_ref().and_then(|goal| goal.target_sessions),
+            ),
+            goal,
+            minutes_done,
+            sessions_done,
+        }
+    }
+}
+
+/// Focus minutes used this week (Monday to Sunday, local time) against the
+/// weekly focus budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyBudgetProgress {
+    pub used_minutes: u32,
+    /// `None` when no weekly budget is set
+    pub budget_minutes: Option<u32>,
+    pub remaining_minutes: Option<u32>,
+    pub exhausted: bool,
+    pub week_start: DateTime<Utc>,
+    pub week_end: DateTime<Utc>,
+}
+
+impl WeeklyBudgetProgress {
+    pub fn new(
+        used_minutes: u32,
+        budget_minutes: Option<u32>,
+        week_start: DateTime<Utc>,
+        week_end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            used_minutes,
+            budget_minutes,
+            remaining_minutes: budget_minutes.map(|budget| budget.saturating_sub(used_minutes)),
+            exhausted: budget_minutes.is_some_and(|budget| used_minutes >= budget),
+            week_start,
+            week_end,
+        }
+    }
+}
+
+/// Spread of daily focus minutes over a range of days.
+/// Days without any focus count as zero minutes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsistencyStats {
+    pub days: u32,
+    pub active_days: u32,
+    pub total_focus_minutes: u32,
+    pub mean_focus_minutes: f64,
+    pub std_dev_focus_minutes: f64,
+    /// Standard deviation relative to the mean; `None` when the mean is zero
+    pub coefficient_of_variation: Option<f64>,
+    pub min_focus_minutes: u32,
+    pub max_focus_minutes: u32,
+}
+
+impl ConsistencyStats {
+    /// Build from the focus minutes of the days that had sessions
+    pub fn from_daily_minutes(days: u32, daily_minutes: &[u32]) -> Self {
+        let active_days = daily_minutes.iter().filter(|minutes| **minutes > 0).count() as u32;
+        let total_focus_minutes: u32 = daily_minutes.iter().sum();
+
+        // Pad with the days that had no focus at all
+        let missing_days = (days as usize).saturating_sub(daily_minutes.len());
+        let values: Vec<f64> = daily_minutes
+            .iter()
+            .map(|minutes| *minutes as f64)
+            .chain(std::iter::repeat_n(0.0, missing_days))
+            .collect();
+
+        let count = values.len().max(1) as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let variance = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let std_dev = variance.sqrt();
+
+        let min_focus_minutes = if missing_days > 0 {
+            0
+        } else {
+            daily_minutes.iter().copied().min().unwrap_or(0)
+        };
+
+        Self {
+            days,
+            active_days,
+            total_focus_minutes,
+            mean_focus_minutes: mean,
+            std_dev_focus_minutes: std_dev,
+            coefficient_of_variation: if mean > 0.0 {
+                Some(std_dev / mean)
+            } else {
+                None
+            },
+            min_focus_minutes,
+            max_focus_minutes: daily_minutes.iter().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+/// Completed cycles and focus sessions on one local day, for the cycle heatmap.
+///
+/// A cycle is one full block of `cycles_per_long_break` focus sessions and is
+/// counted on the day its last focus session started. Focus sessions store
+/// their position in the running count (`cycle_number`), so a session closes a
+/// block when `cycle_number + 1` is a multiple of the block size, no matter how
+/// the block was split across days. Sessions recorded before `cycle_number`
+/// existed fall back to that day's completions divided by the block size.
+/// Focus sprints are not part of a cycle and are left out of both counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyCycleCount {
+    /// Local date, `YYYY-MM-DD`
+    pub date: String,
+    pub cycles_completed: u32,
+    pub focus_sessions_completed: u32,
+}
+
+impl DailyCycleCount {
+    /// One entry per local day from `first_day`, oldest first, with empty days as zero
+    pub fn from_sessions(
+        first_day: NaiveDate,
+        days: u32,
+        sessions: &[DbSession],
+        cycles_per_long_break: u32,
+    ) -> Vec<Self> {
+        let block_size = cycles_per_long_break.max(1);
+
+        (0..days)
+            .map(|offset| {
+                let date = first_day + Duration::days(offset as i64);
+                let completed: Vec<&DbSession> = sessions
+                    .iter()
+                    .filter(|session| {
+                        session.session_type == SessionType::Focus
+                            && session.completed
+                            && !session.is_sprint
+                            && session.start_time.with_timezone(&Local).date_naive() == date
+                    })
+                    .collect();
+
+                let closing_blocks = completed
+                    .iter()
+                    .filter_map(|session| session.cycle_number)
+                    .filter(|number| (*number as u32 + 1).is_multiple_of(block_size))
+                    .count() as u32;
+                let legacy = completed
+                    .iter()
+                    .filter(|session| session.cycle_number.is_none())
+                    .count() as u32;
+
+                Self {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    cycles_completed: closing_blocks + legacy / block_size,
+                    focus_sessions_completed: completed.len() as u32,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Completed focus minutes inside and outside work hours on one local day,
+/// going by each session's `within_work_hours` flag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkHoursDay {
+    /// Local date, `YYYY-MM-DD`
+    pub date: String,
+    pub within_minutes: u32,
+    pub outside_minutes: u32,
+}
+
+impl WorkHoursDay {
+    /// One entry per local day from `first_day`, oldest first, with empty days as zero
+    pub fn from_sessions(first_day: NaiveDate, days: u32, sessions: &[DbSession]) -> Vec<Self> {
+        let mut seconds = vec![(0i64, 0i64); days as usize];
+
+        for session in sessions {
+            if session.session_type != SessionType::Focus || !session.completed {
+                continue;
+            }
+            let offset =
+                (session.start_time.with_timezone(&Local).date_naive() - first_day).num_days();
+            let Some(day) = usize::try_from(offset)
+                .ok()
+                .and_then(|offset| seconds.get_mut(offset))
+            else {
+                continue;
+            };
+
+            let duration = session.actual_duration.unwrap_or(0).max(0) as i64;
+            if session.within_work_hours {
+                day.0 += duration;
+            } else {
+                day.1 += duration;
+            }
+        }
+
+        seconds
+            .into_iter()
+            .enumerate()
+            .map(|(offset, (within, outside))| Self {
+                date: (first_day + Duration::days(offset as i64))
+                    .format("%Y-%m-%d")
+                    .to_string(),
+                within_minutes: (within / 60) as u32,
+                outside_minutes: (outside / 60) as u32,
+            })
+            .collect()
+    }
+}
+
+/// One day of the weekly review grid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekGridDay {
+    /// Local date, `YYYY-MM-DD`
+    pub date: String,
+    pub focus_minutes: u32,
+    pub sessions_completed: u32,
+    /// Whether `focus_minutes` reached the daily goal
+    pub goal_met: bool,
+}
+
+impl WeekGridDay {
+    /// Seven entries from `monday`, counting completed focus sessions
+    /// (sprints included) by the local day they started
+    pub fn week_from_sessions(
+        monday: NaiveDate,
+        sessions: &[DbSession],
+        goal_minutes: u32,
+    ) -> Vec<Self> {
+        (0..7)
+            .map(|offset| {
+                let date = monday + Duration::days(offset);
+                let (seconds, completed) = sessions
+                    .iter()
+                    .filter(|session| {
+                        session.session_type == SessionType::Focus
+                            && session.completed
+                            && session.start_time.with_timezone(&Local).date_naive() == date
+                    })
+                    .fold((0i64, 0u32), |(seconds, completed), session| {
+                        (
+                            seconds + session.actual_duration.unwrap_or(0).max(0) as i64,
+                            completed + 1,
+                        )
+                    });
+                let focus_minutes = (seconds / 60) as u32;
+
+                Self {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    focus_minutes,
+                    sessions_completed: completed,
+                    goal_met: focus_minutes >= goal_minutes,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Format version of `AnalyticsExport`; bump when fields change meaning
+pub const ANALYTICS_EXPORT_VERSION: u32 = 1;
+
+/// Everything recorded over a date range in one document, written by
+/// `export_analytics_json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsExport {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub range_start: DateTime<Utc>,
+    pub range_end: DateTime<Utc>,
+    /// Sessions of every type that started in the range
+    pub sessions: Vec<DbSession>,
+    pub daily_cycles: Vec<DailyCycleCount>,
+    pub work_hours: Vec<WorkHoursDay>,
+    /// All-time bests, including the longest streak
+    pub personal_records: PersonalRecords,
+    pub bypass_attempts: Vec<BypassAttempt>,
+    pub strict_mode_history: Vec<StrictModeInterval>,
+}
+
+/// Completed focus minutes per local weekday over the last `weeks` weeks.
+///
+/// The window is the last `weeks * 7` days ending today, so every weekday
+/// occurs exactly `weeks` times and `minutes / weeks` is its weekly average.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusByWeekday {
+    /// Monday first, Sunday last
+    pub minutes: [u32; 7],
+    pub weeks: u32,
+}
+
+/// Average length of completed sessions of one type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AverageDuration {
+    pub average_seconds: u32,
+    /// Sessions behind the average, so small samples can be flagged
+    pub sessions: u32,
+}
+
+/// Average completed session length per type over the last `days` days,
+/// e.g. to check whether breaks are taken in full.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AverageDurations {
+    pub focus: AverageDuration,
+    pub short_break: AverageDuration,
+    pub long_break: AverageDuration,
+    pub days: u32,
+}
+
+impl AverageDurations {
+    /// Build from `(session_type, average_seconds, sessions, total_seconds)` rows
+    pub fn from_rows(days: u32, rows: &[(String, u32, u32, u32)]) -> Self {
+        let mut averages = Self {
+            focus: AverageDuration::default(),
+            short_break: AverageDuration::default(),
+            long_break: AverageDuration::default(),
+            days,
+        };
+
+        for (session_type, average_seconds, sessions, _) in rows {
+            let average = AverageDuration {
+                average_seconds: *average_seconds,
+                sessions: *sessions,
+            };
+            match session_type.as_str() {
+                "focus" => averages.focus = average,
+                "short_break" => averages.short_break = average,
+                "long_break" => averages.long_break = average,
+                _ => {}
+            }
+        }
+
+        averages
+    }
+}
+
+/// Break-to-focus ratio below which rest is flagged as too little
+/// (a 25/5 cycle sits at 0.2)
+const UNDER_RESTING_RATIO: f64 = 0.1;
+
+/// Completed break time against completed focus time over the last `days` days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakFocusRatio {
+    pub focus_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub break_minutes: u32,
+    /// Break time per unit of focus time; `None` without any focus
+    pub ratio: Option<f64>,
+    pub under_resting: bool,
+    pub days: u32,
+}
+
+impl BreakFocusRatio {
+    /// Build from `(session_type, average_seconds, sessions, total_seconds)` rows
+    pub fn from_rows(days: u32, rows: &[(String, u32, u32, u32)]) -> Self {
+        let total_seconds = |wanted: &str| {
+            rows.iter()
+                .filter(|(session_type, ..)| session_type == wanted)
+                .map(|(.., total)| *total)
+                .sum::<u32>()
+        };
+
+        let focus = total_seconds("focus");
+        let short_break = total_seconds("short_break");
+        let long_break = total_seconds("long_break");
+
+        let ratio = (focus > 0).then(|| (short_break + long_break) as f64 / focus as f64);
+
+        Self {
+            focus_minutes: focus / 60,
+            short_break_minutes: short_break / 60,
+            long_break_minutes: long_break / 60,
+            break_minutes: (short_break + long_break) / 60,
+            ratio,
+            under_resting: ratio.is_some_and(|ratio| ratio < UNDER_RESTING_RATIO),
+            days,
+        }
+    }
+}
+
+/// Average mood of the sessions rated on one local day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyMood {
+    /// Local date, `YYYY-MM-DD`
+    pub date: String,
+    pub average_mood: f64,
+    pub sessions: u32,
+}
+
+/// Session mood ratings (1 to 5) over the last `days` days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoodTrends {
+    /// Days with at least one rated session, oldest first
+    pub daily: Vec<DailyMood>,
+    pub rated_sessions: u32,
+    pub average_mood: Option<f64>,
+    pub average_mood_completed: Option<f64>,
+    pub average_mood_incomplete: Option<f64>,
+    /// Correlation between mood and completing the session, from -1 to 1;
+    /// `None` while either of them never varies
+    pub mood_completion_correlation: Option<f64>,
+    pub days: u32,
+}
+
+impl MoodTrends {
+    /// Build from `(start_time, mood, completed)` rows, oldest first
+    pub fn from_rows(days: u32, rows: &[(DateTime<Utc>, u8, bool)]) -> Self {
+        let average = |moods: &[f64]| {
+            (!moods.is_empty()).then(|| moods.iter().sum::<f64>() / moods.len() as f64)
+        };
+
+        let mut daily: Vec<(NaiveDate, Vec<f64>)> = Vec::new();
+        for (start_time, mood, _) in rows {
+            let date = start_time.with_timezone(&Local).date_naive();
+            match daily.last_mut() {
+                Some((last, moods)) if *last == date => moods.push(*mood as f64),
+                _ => daily.push((date, vec![*mood as f64])),
+            }
+        }
+
+        let moods: Vec<f64> = rows.iter().map(|(_, mood, _)| *mood as f64).collect();
+        let completions: Vec<f64> = rows
+            .iter()
+            .map(|(_, _, completed)| if *completed { 1.0 } else { 0.0 })
+            .collect();
+        let moods_where = |completed: bool| -> Vec<f64> {
+            rows.iter()
+                .filter(|(.., done)| *done == completed)
+                .map(|(_, mood, _)| *mood as f64)
+                .collect()
+        };
+
+        Self {
+            daily: daily
+                .iter()
+                .map(|(date, moods)| DailyMood {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    average_mood: average(moods).unwrap_or_default(),
+                    sessions: moods.len() as u32,
+                })
+                .collect(),
+            rated_sessions: rows.len() as u32,
+            average_mood: average(&moods),
+            average_mood_completed: average(&moods_where(true)),
+            average_mood_incomplete: average(&moods_where(false)),
+            mood_completion_correlation: correlation(&moods, &completions),
+            days,
+        }
+    }
+}
+
+/// Pearson correlation of two equally long series; `None` when either is constant
+fn correlation(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let count = xs.len().min(ys.len());
+    if count < 2 {
+        return None;
+    }
+
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / count as f64;
+    let (mean_x, mean_y) = (mean(&xs[..count]), mean(&ys[..count]));
+
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x).powi(2);
+        variance_y += (y - mean_y).powi(2);
+    }
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+
+    Some(covariance / (variance_x * variance_y).sqrt())
+}
+
+/// Totals over the dashboard range
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardTotals {
+    pub focus_minutes: u32,
+    pub break_minutes: u32,
+    pub focus_sessions: u32,
+    pub focus_sessions_completed: u32,
+}
+
+/// Everything the home screen shows, computed from one database snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dashboard {
+    /// Consecutive days with a completed focus session, ending today, or
+    /// yesterday while today has none yet
+    pub current_streak: u32,
+    /// Progress towards today's goal, `None` when no goal was given
+    pub daily_goal: Option<SessionsToGoal>,
+    /// Completed focus sessions over focus sessions started in the range
+    pub completion_rate: f64,
+    /// Completed focus minutes per local hour of the day, hour 0 first
+    pub focus_by_hour: [u32; 24],
+    /// Latest sessions of any type, newest first
+    pub recent_sessions: Vec<DbSession>,
+    pub totals: DashboardTotals,
+    pub days: u32,
+}
+
+impl Dashboard {
+    pub fn new(
+        days: u32,
+        today: NaiveDate,
+        goal_minutes: Option<u32>,
+        focus_minutes: u32,
+        snapshot: DashboardSnapshot,
+    ) -> Self {
+        let mut totals = DashboardTotals::default();
+        let mut focus_seconds_by_hour = [0i64; 24];
+        let (mut focus_seconds, mut break_seconds, mut today_seconds) = (0i64, 0i64, 0i64);
+
+        for session in &snapshot.sessions {
+            let seconds = session.actual_duration.unwrap_or(0).max(0) as i64;
+            match session.session_type {
+                SessionType::Focus => {
+                    totals.focus_sessions += 1;
+                    if !session.completed {
+                        continue;
+                    }
+                    totals.focus_sessions_completed += 1;
+                    focus_seconds += seconds;
+
+                    let start = session.start_time.with_timezone(&Local);
+                    focus_seconds_by_hour[start.hour() as usize] += seconds;
+                    if start.date_naive() == today {
+                        today_seconds += seconds;
+                    }
+                }
+                SessionType::ShortBreak | SessionType::LongBreak if session.completed => {
+                    break_seconds += seconds;
+                }
+                _ => {}
+            }
+        }
+        totals.focus_minutes = (focus_seconds / 60) as u32;
+        totals.break_minutes = (break_seconds / 60) as u32;
+
+        let completion_rate = if totals.focus_sessions > 0 {
+            totals.focus_sessions_completed as f64 / totals.focus_sessions as f64
+        } else {
+            0.0
+        };
+
+        Self {
+            current_streak: current_streak(&snapshot.focus_days, today),
+            daily_goal: goal_minutes
+                .map(|goal| SessionsToGoal::new((today_seconds / 60) as u32, goal, focus_minutes)),
+            completion_rate,
+            focus_by_hour: focus_seconds_by_hour.map(|total| (total / 60) as u32),
+            recent_sessions: snapshot.recent_sessions,
+            totals,
+            days,
+        }
+    }
+}
+
+/// Length of the run of consecutive days in `days` (sorted, distinct) that
+/// ends today, or yesterday when today isn't in it yet
+fn current_streak(days: &[NaiveDate], today: NaiveDate) -> u32 {
+    let mut expected = match days.last() {
+        Some(last) if *last == today => today,
+        Some(last) if last.succ_opt() == Some(today) => *last,
+        _ => return 0,
+    };
+
+    let mut streak = 0;
+    for day in days.iter().rev() {
+        if *day != expected {
+            break;
+        }
+        streak += 1;
+        match expected.pred_opt() {
+            Some(previous) => expected = previous,
+            None => break,
+        }
+    }
+    streak
+}
+
+/// Bypass attempts per local hour of the day over the last `days` days.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BypassAttemptsByHour {
+    /// Hour 0 (midnight) first
+    pub counts: [u32; 24],
+    pub days: u32,
+    /// Rows whose timestamp could not be parsed
+    pub skipped: u32,
+}
+
+impl BypassAttemptsByHour {
+    pub fn from_timestamps(days: u32, since: DateTime<Utc>, timestamps: &[String]) -> Self {
+        let mut counts = [0u32; 24];
+        let mut skipped = 0;
+
+        for raw in timestamps {
+            match parse_stored_timestamp(raw) {
+                Some(timestamp) if timestamp >= since => {
+                    counts[timestamp.with_timezone(&Local).hour() as usize] += 1;
+                }
+                Some(_) => {}
+                None => {
+                    println!(
+                        "⚠️ [Rust] Skipping bypass attempt with malformed timestamp: {:?}",
+                        raw
+                    );
+                    skipped += 1;
+                }
+            }
+        }
+
+        Self {
+            counts,
+            days,
+            skipped,
+        }
+    }
+}
+
+impl FocusByWeekday {
+    pub fn from_sessions(weeks: u32, sessions: &[DbSession]) -> Self {
+        let mut seconds = [0i64; 7];
+
+        for session in sessions {
+            if session.session_type != SessionType::Focus || !session.completed {
+                continue;
+            }
+            let weekday = session.start_time.with_timezone(&Local).weekday();
+            seconds[weekday.num_days_from_monday() as usize] +=
+                session.actual_duration.unwrap_or(0).max(0) as i64;
+        }
+
+        Self {
+            minutes: seconds.map(|total| (total / 60) as u32),
+            weeks,
+        }
+    }
+}
+
 /// Conversion functions between database models and API models
 
 impl From<DbUserSettings> for UserSettings {
@@ -138,6 +989,7 @@ impl From<UserSettings> for DbUserSettings {
             break_transition_seconds: api_settings.break_transition_seconds as i32,
             created_at: now,
             updated_at: now,
+            ..Self::default()
         }
     }
 }
@@ -150,17 +1002,13 @@ impl FocusSession {
         }
 
         let elapsed_seconds = if let Some(end_time) = db_session.end_time {
-            (end_time - db_session.start_time).num_seconds() as u32
+            (end_time - db_session.start_time).num_seconds().max(0) as u32
         } else {
-            (current_time - db_session.start_time).num_seconds() as u32
+            (current_time - db_session.start_time).num_seconds().max(0) as u32
         };
 
-        let duration = db_session.planned_duration as u32;
-        let remaining = if elapsed_seconds >= duration {
-            0
-        } else {
-            duration - elapsed_seconds
-        };
+        let duration = db_session.planned_duration.max(0) as u32;
+        let remaining = duration.saturating_sub(elapsed_seconds);
 
         let is_running = db_session.end_time.is_none() && !db_session.completed;
 
@@ -192,7 +1040,7 @@ impl FocusSession {
         let end_time = if !self.is_running {
             Some(
                 self.start_time
-                    + chrono::Duration::seconds(self.duration as i64 - self.remaining as i64),
+                    + chrono::Duration::seconds(self.duration.saturating_sub(self.remaining) as i64),
             )
         } else {
             None
@@ -205,7 +1053,7 @@ impl FocusSession {
             end_time,
             planned_duration: self.duration as i32,
             actual_duration: if end_time.is_some() {
-                Some((self.duration - self.remaining) as i32)
+                Some(self.duration.saturating_sub(self.remaining) as i32)
             } else {
                 None
             },
@@ -216,6 +1064,8 @@ impl FocusSession {
             within_work_hours: false, // Default value, should be set by orchestrator
             cycle_number: None,       // Default value, should be set by orchestrator
             is_long_break: false,     // Focus sessions are not breaks
+            tag: None,
+            is_sprint: false,
         }
     }
 }
@@ -223,30 +1073,13 @@ impl FocusSession {
 impl BreakSession {
     /// Create a new break session
     pub fn new(break_type: BreakType, duration_minutes: u32, allow_emergency: bool) -> Self {
-        let activity = match break_type {
-            BreakType::Short => BreakActivity {
-                title: "Quick Refresh".to_string(),
-                description: "Take a moment to recharge with these quick activities".to_string(),
-                checklist: vec![
-                    "💧 Drink a glass of water".to_string(),
-                    "👀 Look away from the screen (20-20-20 rule)".to_string(),
-                    "🧘 Take 3 deep breaths".to_string(),
-                    "🚶 Stand up and stretch".to_string(),
-                ],
+        let activity = DefaultBreakContent.activity_for(
+            &break_type,
+            &BreakContext {
+                cycle_count: 0,
+                duration: duration_minutes * 60,
             },
-            BreakType::Long => BreakActivity {
-                title: "Extended Break".to_string(),
-                description: "Time for a longer break to fully recharge".to_string(),
-                checklist: vec![
-                    "🚶‍♂️ Take a short walk".to_string(),
-                    "💧 Hydrate with water or herbal tea".to_string(),
-                    "🥗 Have a healthy snack".to_string(),
-                    "🧘‍♀️ Do some light stretching or meditation".to_string(),
-                    "🌱 Step outside for fresh air".to_string(),
-                    "📱 Check in with a friend or family member".to_string(),
-                ],
-            },
-        };
+        );
 
         Self {
             id: Uuid::new_v4().to_string(),
@@ -255,6 +1088,7 @@ impl BreakSession {
             remaining: duration_minutes * 60,
             activity,
             allow_emergency,
+            message: None,
         }
     }
 
@@ -262,7 +1096,7 @@ impl BreakSession {
     pub fn to_db_session(&self, start_time: DateTime<Utc>) -> DbSession {
         let session_type = match self.break_type {
             BreakType::Short => SessionType::ShortBreak,
-            BreakType::Long => SessionType::LongBreak,
+            BreakType::Long | BreakType::WindDown => SessionType::LongBreak,
         };
 
         DbSession {
@@ -278,7 +1112,9 @@ impl BreakSession {
             created_at: start_time,
             within_work_hours: false, // Default value, should be set by orchestrator
             cycle_number: None,       // Default value, should be set by orchestrator
-            is_long_break: matches!(self.break_type, BreakType::Long),
+            is_long_break: matches!(self.break_type, BreakType::Long | BreakType::WindDown),
+            tag: None,
+            is_sprint: false,
         }
     }
 }
@@ -294,3 +1130,28 @@ impl From<DbSessionStats> for SessionStats {
         }
     }
 }
+
+impl From<DbTagStats> for TagStats {
+    fn from(db_stats: DbTagStats) -> Self {
+        Self {
+            tag: db_stats.tag,
+            focus_minutes: db_stats.focus_minutes,
+            sessions_count: db_stats.sessions_count,
+            sessions_completed: db_stats.sessions_completed,
+            completion_rate: db_stats.completion_rate,
+        }
+    }
+}
+
+impl From<DbPeriodStats> for PeriodStats {
+    fn from(db_stats: DbPeriodStats) -> Self {
+        Self {
+            period_start: db_stats.period_start,
+            period_end: db_stats.period_end,
+            focus_minutes: db_stats.focus_minutes,
+            sessions_count: db_stats.sessions_count,
+            sessions_completed: db_stats.sessions_completed,
+            completion_rate: db_stats.completion_rate,
+        }
+    }
+}
@@ -2,15 +2,229 @@ use std::sync::Mutex;
 use tauri::Manager;
 
 use crate::handlers::{
-    auth_handler, cycle_config_handler, cycle_handler, notification_handler, onboarding_handler,
-    stats_handler, strict_mode_handler, telemetry_handler, work_schedule_handler,
+    auth_handler, command_handler, cycle_config_handler, cycle_handler, data_handler,
+    diagnostics_handler, hotkey_handler, notification_handler, onboarding_handler, stats_handler,
+    strict_mode_handler, telemetry_handler, work_schedule_handler,
+};
+use crate::{
+    config::AppConfig, hotkey_manager::HotkeyManager, onboarding::OnboardingManager, shutdown,
+    state::AppState, theme, tray_icon,
 };
-use crate::{config::AppConfig, onboarding::OnboardingManager, state::AppState};
 
 // Menu bar text temporarily disabled
 // #[cfg(target_os = "macos")]
 // use crate::menu_bar_text;
 
+/// Build the invoke handler and `COMMAND_PATHS` from the same list, so the
+/// commands reported by `list_commands` are exactly the registered ones
+macro_rules! app_commands {
+    ($($($segment:ident)::+),* $(,)?) => {
+        /// Every registered command, as written in the list below (`module::name`)
+        pub const COMMAND_PATHS: &[&str] = &[$(stringify!($($segment)::+)),*];
+
+        fn invoke_handler() -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static {
+            tauri::generate_handler![$($($segment)::+),*]
+        }
+    };
+}
+
+app_commands![
+    auth_handler::login_with_google,
+    auth_handler::read_tokens,
+    auth_handler::logout,
+    auth_handler::get_user_info,
+    onboarding_handler::start_onboarding,
+    onboarding_handler::next_onboarding_step,
+    onboarding_handler::previous_onboarding_step,
+    onboarding_handler::complete_onboarding,
+    onboarding_handler::quick_start,
+    onboarding_handler::get_onboarding_status,
+    onboarding_handler::is_first_launch,
+    onboarding_handler::reset_onboarding_for_testing,
+    onboarding_handler::apply_onboarding_config_to_settings,
+    onboarding_handler::validate_onboarding_config,
+    onboarding_handler::validate_step_config,
+    onboarding_handler::create_configuration_backup,
+    onboarding_handler::list_configuration_backups,
+    onboarding_handler::restore_configuration_backup,
+    data_handler::get_data_directory,
+    data_handler::move_data_directory,
+    data_handler::ensure_schema,
+    diagnostics_handler::get_runtime_stats,
+    diagnostics_handler::get_performance_counters,
+    diagnostics_handler::reset_performance_counters,
+    command_handler::list_commands,
+    onboarding_handler::get_configuration_health_check,
+    onboarding_handler::force_database_migration,
+    work_schedule_handler::save_work_schedule,
+    work_schedule_handler::get_work_schedule,
+    work_schedule_handler::is_within_work_hours,
+    work_schedule_handler::is_within_work_hours_now,
+    work_schedule_handler::is_work_day_today,
+    work_schedule_handler::get_system_timezone_info,
+    work_schedule_handler::validate_work_hours,
+    cycle_config_handler::save_cycle_config,
+    cycle_config_handler::get_cycle_config,
+    cycle_config_handler::get_user_settings,
+    cycle_config_handler::get_settings_vs_defaults,
+    cycle_config_handler::update_user_name,
+    cycle_config_handler::save_strict_mode_config,
+    cycle_config_handler::get_strict_mode_config,
+    cycle_config_handler::set_strict_focus_show_widget,
+    cycle_config_handler::get_strict_focus_show_widget,
+    cycle_config_handler::set_show_break_transition,
+    cycle_config_handler::get_show_break_transition,
+    cycle_config_handler::set_strict_require_ack,
+    cycle_config_handler::get_strict_require_ack,
+    cycle_config_handler::set_focus_widget_pause_behavior,
+    cycle_config_handler::get_focus_widget_pause_behavior,
+    cycle_config_handler::set_work_hours_enforcement,
+    cycle_config_handler::get_work_hours_enforcement,
+    cycle_config_handler::set_tray_icon_style,
+    cycle_config_handler::get_tray_icon_style,
+    cycle_config_handler::set_tray_click_action,
+    cycle_config_handler::get_tray_click_action,
+    cycle_config_handler::set_tick_interval,
+    cycle_config_handler::set_power_save,
+    cycle_config_handler::get_tick_interval,
+    cycle_config_handler::set_break_message,
+    cycle_config_handler::get_break_message,
+    cycle_config_handler::set_phase_colors,
+    cycle_config_handler::get_phase_colors,
+    cycle_config_handler::set_sound_config,
+    cycle_config_handler::get_sound_config,
+    cycle_config_handler::save_cycle_preset,
+    cycle_config_handler::list_cycle_presets,
+    cycle_config_handler::delete_cycle_preset,
+    cycle_config_handler::apply_cycle_preset,
+    cycle_config_handler::set_phase_command_hooks,
+    cycle_config_handler::get_phase_command_hooks,
+    cycle_config_handler::set_theme,
+    cycle_config_handler::get_theme,
+    cycle_config_handler::set_overlay_escape_action,
+    cycle_config_handler::get_overlay_escape_action,
+    cycle_config_handler::set_soft_overlay_dismiss_action,
+    cycle_config_handler::get_soft_overlay_dismiss_action,
+    cycle_config_handler::update_pre_alert_config,
+    cycle_config_handler::get_pre_alert_config,
+    cycle_config_handler::get_settings,
+    cycle_config_handler::update_settings,
+    cycle_handler::initialize_cycle_orchestrator,
+    cycle_handler::start_focus_session,
+    cycle_handler::start_focus_sprint,
+    cycle_handler::set_next_focus_duration,
+    cycle_handler::start_break_session,
+    cycle_handler::break_now,
+    cycle_handler::start_wind_down,
+    cycle_handler::skip_break_session,
+    cycle_handler::pause_cycle,
+    cycle_handler::resume_cycle,
+    cycle_handler::end_cycle_session,
+    cycle_handler::get_cycle_state,
+    cycle_handler::format_remaining,
+    cycle_handler::get_cycle_dots,
+    cycle_handler::get_clock_diagnostics,
+    cycle_handler::get_phase_end_time,
+    cycle_handler::get_config_drift,
+    cycle_handler::reload_cycle_config,
+    cycle_handler::project_today,
+    cycle_handler::get_recent_cycle_events,
+    cycle_handler::get_current_break,
+    cycle_handler::preview_break_overlay,
+    cycle_handler::cycle_tick,
+    cycle_handler::reset_cycle_count,
+    cycle_handler::log_bypass_attempt,
+    cycle_handler::handle_overlay_escape,
+    cycle_handler::handle_overlay_dismiss,
+    cycle_handler::schedule_focus,
+    cycle_handler::cancel_scheduled_focus,
+    cycle_handler::get_scheduled_focus,
+    cycle_handler::get_work_schedule_info,
+    cycle_handler::get_work_hours_stats,
+    cycle_handler::get_incomplete_sessions,
+    cycle_handler::get_session_history,
+    cycle_handler::close_incomplete_sessions,
+    cycle_handler::delete_sessions_in_range,
+    cycle_handler::find_session_anomalies,
+    cycle_handler::emit_test_cycle_event,
+    stats_handler::get_session_stats,
+    stats_handler::get_stats_by_tag,
+    stats_handler::set_session_tag,
+    stats_handler::set_session_mood,
+    stats_handler::get_mood_trends,
+    stats_handler::compare_periods,
+    stats_handler::get_consistency_stats,
+    stats_handler::get_sessions_to_goal,
+    stats_handler::set_daily_goal,
+    stats_handler::get_daily_progress,
+    stats_handler::get_today_session_count,
+    stats_handler::get_dashboard,
+    stats_handler::get_daily_cycle_counts,
+    stats_handler::get_work_hours_timeseries,
+    stats_handler::get_week_grid,
+    stats_handler::export_analytics_json,
+    stats_handler::get_focus_by_weekday,
+    stats_handler::get_personal_records,
+    stats_handler::get_average_durations,
+    stats_handler::get_break_focus_ratio,
+    stats_handler::get_weekly_budget_progress,
+    cycle_config_handler::set_weekly_focus_budget,
+    cycle_config_handler::set_max_break_overrun,
+    cycle_config_handler::set_winddown_minutes,
+    cycle_config_handler::set_break_ratio,
+    cycle_config_handler::set_prevent_display_sleep_during_focus,
+    cycle_config_handler::set_strict_disable_delay,
+    cycle_config_handler::set_session_save_interval,
+    cycle_config_handler::describe_configuration,
+    stats_handler::get_bypass_attempts_by_hour,
+    notification_handler::update_notification_user_name,
+    notification_handler::get_notification_user_name,
+    notification_handler::get_notification_history,
+    notification_handler::clear_notification_history,
+    notification_handler::set_notification_history_capacity,
+    notification_handler::get_notification_history_capacity,
+    notification_handler::mute_notifications,
+    notification_handler::unmute_notifications,
+    notification_handler::get_notification_mute,
+    strict_mode_handler::activate_strict_mode,
+    strict_mode_handler::deactivate_strict_mode,
+    strict_mode_handler::get_strict_mode_state,
+    strict_mode_handler::get_system_lock_status,
+    strict_mode_handler::repair_strict_mode_windows,
+    strict_mode_handler::show_menu_bar_popover,
+    strict_mode_handler::hide_menu_bar_popover,
+    strict_mode_handler::stop_break_transition_countdown,
+    strict_mode_handler::start_break_from_transition,
+    strict_mode_handler::acknowledge_transition,
+    strict_mode_handler::hide_fullscreen_break_overlay,
+    strict_mode_handler::emergency_exit_strict_mode,
+    strict_mode_handler::register_emergency_hotkey,
+    strict_mode_handler::unregister_emergency_hotkey,
+    strict_mode_handler::test_emergency_hotkey,
+    strict_mode_handler::report_emergency_hotkey_press,
+    strict_mode_handler::is_shortcut_available,
+    strict_mode_handler::get_strict_mode_uptime,
+    strict_mode_handler::get_strict_mode_history,
+    hotkey_handler::get_hotkey_configs,
+    hotkey_handler::update_hotkey_config,
+    hotkey_handler::set_hotkey_enabled,
+    hotkey_handler::route_hotkey_event,
+    hotkey_handler::save_hotkey_configurations,
+    hotkey_handler::load_custom_hotkeys,
+    hotkey_handler::reset_hotkeys_to_defaults,
+    hotkey_handler::check_hotkey_conflicts,
+    hotkey_handler::export_hotkeys_json,
+    hotkey_handler::import_hotkeys_json,
+    hotkey_handler::get_available_modifiers,
+    telemetry_handler::send_error_event,
+    telemetry_handler::send_login_event,
+    telemetry_handler::send_metric,
+    telemetry_handler::flush_telemetry,
+    crate::window_manager::get_windows_geometry,
+    crate::window_manager::recreate_window,
+    shutdown::prepare_for_quit,
+];
+
 pub fn run() -> Result<(), String> {
     // Load environment variables from .env file
     dotenv::dotenv().ok();
@@ -19,14 +233,25 @@ pub fn run() -> Result<(), String> {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .setup(move |app| {
             let state = AppState::init(app.handle(), cfg.clone())?;
             app.manage(state);
 
+            // Re-arm a focus start scheduled before the last quit
+            cycle_handler::restore_scheduled_focus(app.handle());
+
+            // Re-arm a strict mode disable still in its cool-down
+            strict_mode_handler::restore_pending_strict_disable(app.handle());
+
             // Initialize onboarding manager
             let onboarding_manager = OnboardingManager::new();
             app.manage(Mutex::new(onboarding_manager));
 
+            let hotkey_manager = HotkeyManager::new(app.handle().clone());
+            hotkey_manager.initialize();
+            app.manage(hotkey_manager);
+
             // Initialize native menu bar text support on macOS
             // TEMPORARILY DISABLED: This was causing fatal runtime errors
             // The Objective-C code may be throwing exceptions that Rust cannot catch
@@ -46,7 +271,7 @@ pub fn run() -> Result<(), String> {
 
             // Setup tray icon with context menu (similar to Docker Desktop)
             println!("🔍 [App] Looking for tray icon with id 'main-tray'");
-            if let Some(tray) = app.tray_by_id("main-tray") {
+            if let Some(tray) = app.tray_by_id(tray_icon::MAIN_TRAY_ID) {
                 println!("✅ [App] Tray icon found, setting up menu");
                 use tauri::{menu::{Menu, MenuItem, PredefinedMenuItem}, tray::MouseButton};
                 
@@ -129,26 +354,49 @@ pub fn run() -> Result<(), String> {
                     }
                 });
                 
-                // Also handle left click to show/hide window (macOS behavior)
+                // Left click runs the configured tray click action (once, on release)
                 tray.on_tray_icon_event(|tray, event| {
-                    if let tauri::tray::TrayIconEvent::Click { button: MouseButton::Left, .. } = event {
-                        let app_handle = tray.app_handle().clone();
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            if window.is_visible().unwrap_or(false) {
-                                let _ = window.hide();
-                            } else {
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                        }
+                    if let tauri::tray::TrayIconEvent::Click {
+                        button: MouseButton::Left,
+                        button_state: tauri::tray::MouseButtonState::Up,
+                        ..
+                    } = event
+                    {
+                        tray_icon::handle_left_click(tray.app_handle());
                     }
                 });
                 println!("✅ [App] Tray icon event handlers registered");
+
+                // Reapply the persisted tray icon style
+                let tray_icon_style = app
+                    .state::<AppState>()
+                    .database
+                    .get_user_settings()
+                    .ok()
+                    .flatten()
+                    .map(|settings| settings.tray_icon_style)
+                    .unwrap_or_default();
+                if let Err(e) = tray_icon::apply_tray_icon_style(app.handle(), tray_icon_style) {
+                    eprintln!("⚠️ [App] Failed to apply tray icon style: {}", e);
+                }
             } else {
                 eprintln!("❌ [App] Tray icon 'main-tray' not found! Check tauri.conf.json configuration.");
                 eprintln!("⚠️ [App] Make sure trayIcon is configured in tauri.conf.json");
             }
 
+            // Apply the saved theme to all windows
+            let app_theme = app
+                .state::<AppState>()
+                .database
+                .get_user_settings()
+                .ok()
+                .flatten()
+                .map(|settings| settings.theme)
+                .unwrap_or_default();
+            if let Err(e) = theme::apply_theme(app.handle(), app_theme) {
+                eprintln!("⚠️ [App] Failed to apply theme: {}", e);
+            }
+
             // Ensure the main window starts centered
             if let Some(main_win) = app.get_webview_window("main") {
                 let _ = main_win.center();
@@ -156,73 +404,28 @@ pub fn run() -> Result<(), String> {
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            auth_handler::login_with_google,
-            auth_handler::read_tokens,
-            auth_handler::logout,
-            auth_handler::get_user_info,
-            onboarding_handler::start_onboarding,
-            onboarding_handler::next_onboarding_step,
-            onboarding_handler::previous_onboarding_step,
-            onboarding_handler::complete_onboarding,
-            onboarding_handler::get_onboarding_status,
-            onboarding_handler::is_first_launch,
-            onboarding_handler::reset_onboarding_for_testing,
-            onboarding_handler::apply_onboarding_config_to_settings,
-            onboarding_handler::validate_onboarding_config,
-            onboarding_handler::validate_step_config,
-            onboarding_handler::create_configuration_backup,
-            onboarding_handler::list_configuration_backups,
-            onboarding_handler::restore_configuration_backup,
-            onboarding_handler::get_configuration_health_check,
-            onboarding_handler::force_database_migration,
-            work_schedule_handler::save_work_schedule,
-            work_schedule_handler::get_work_schedule,
-            work_schedule_handler::is_within_work_hours,
-            work_schedule_handler::get_system_timezone_info,
-            work_schedule_handler::validate_work_hours,
-            cycle_config_handler::save_cycle_config,
-            cycle_config_handler::get_cycle_config,
-            cycle_config_handler::get_user_settings,
-            cycle_config_handler::update_user_name,
-            cycle_config_handler::save_strict_mode_config,
-            cycle_config_handler::get_strict_mode_config,
-            cycle_config_handler::update_pre_alert_config,
-            cycle_config_handler::get_pre_alert_config,
-            cycle_config_handler::get_settings,
-            cycle_config_handler::update_settings,
-            cycle_handler::initialize_cycle_orchestrator,
-            cycle_handler::start_focus_session,
-            cycle_handler::start_break_session,
-            cycle_handler::pause_cycle,
-            cycle_handler::resume_cycle,
-            cycle_handler::end_cycle_session,
-            cycle_handler::get_cycle_state,
-            cycle_handler::get_current_break,
-            cycle_handler::cycle_tick,
-            cycle_handler::reset_cycle_count,
-            cycle_handler::log_bypass_attempt,
-            cycle_handler::get_work_schedule_info,
-            cycle_handler::get_work_hours_stats,
-            stats_handler::get_session_stats,
-            notification_handler::update_notification_user_name,
-            notification_handler::get_notification_user_name,
-            strict_mode_handler::activate_strict_mode,
-            strict_mode_handler::deactivate_strict_mode,
-            strict_mode_handler::get_strict_mode_state,
-            strict_mode_handler::show_menu_bar_popover,
-            strict_mode_handler::hide_menu_bar_popover,
-            strict_mode_handler::stop_break_transition_countdown,
-            strict_mode_handler::start_break_from_transition,
-            strict_mode_handler::hide_fullscreen_break_overlay,
-            strict_mode_handler::emergency_exit_strict_mode,
-            strict_mode_handler::register_emergency_hotkey,
-            strict_mode_handler::unregister_emergency_hotkey,
-            telemetry_handler::send_error_event,
-            telemetry_handler::send_login_event,
-            telemetry_handler::send_metric,
-            telemetry_handler::flush_telemetry
-        ])
-        .run(tauri::generate_context!())
-        .map_err(|e| e.to_string())
+        .on_window_event(|window, event| {
+            // Follow OS appearance changes while the theme is set to System
+            if let tauri::WindowEvent::ThemeChanged(native) = event {
+                theme::handle_system_theme_change(window.app_handle(), *native);
+            }
+        })
+        .invoke_handler(invoke_handler())
+        .build(tauri::generate_context!())
+        .map_err(|e| e.to_string())?
+        .run(|app_handle, event| {
+            // Flush everything once before the process exits
+            if let tauri::RunEvent::ExitRequested { code, api, .. } = event {
+                if !shutdown::is_complete() {
+                    api.prevent_exit();
+                    let app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        shutdown::shutdown(&app_handle).await;
+                        app_handle.exit(code.unwrap_or(0));
+                    });
+                }
+            }
+        });
+
+    Ok(())
 }
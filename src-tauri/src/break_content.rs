@@ -0,0 +1,61 @@
+use crate::api_models::{BreakActivity, BreakType};
+
+/// What a provider knows about the break it's picking content for
+#[derive(Debug, Clone)]
+pub struct BreakContext {
+    /// Focus sessions completed so far in the current cycle group
+    pub cycle_count: u32,
+    /// Break length in seconds
+    pub duration: u32,
+}
+
+/// Source of the activity shown on the break overlay.
+///
+/// Swap the provider on `AppState` to change the break experience
+/// (stretching routine, breathing exercise, quote of the day, ...).
+pub trait BreakContentProvider: Send + Sync {
+    fn activity_for(&self, break_type: &BreakType, context: &BreakContext) -> BreakActivity;
+}
+
+/// Built-in checklists, mirroring the frontend defaults
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultBreakContent;
+
+impl BreakContentProvider for DefaultBreakContent {
+    fn activity_for(&self, break_type: &BreakType, _context: &BreakContext) -> BreakActivity {
+        match break_type {
+            BreakType::Short => BreakActivity {
+                title: "Quick Refresh".to_string(),
+                description: "Take a moment to recharge with these quick activities".to_string(),
+                checklist: vec![
+                    "💧 Drink a glass of water".to_string(),
+                    "👀 Look away from the screen (20-20-20 rule)".to_string(),
+                    "🧘 Take 3 deep breaths".to_string(),
+                    "🚶 Stand up and stretch".to_string(),
+                ],
+            },
+            BreakType::Long => BreakActivity {
+                title: "Extended Break".to_string(),
+                description: "Time for a longer break to fully recharge".to_string(),
+                checklist: vec![
+                    "🚶‍♂️ Take a short walk".to_string(),
+                    "💧 Hydrate with water or herbal tea".to_string(),
+                    "🥗 Have a healthy snack".to_string(),
+                    "🧘‍♀️ Do some light stretching or meditation".to_string(),
+                    "🌱 Step outside for fresh air".to_string(),
+                    "📱 Check in with a friend or family member".to_string(),
+                ],
+            },
+            BreakType::WindDown => BreakActivity {
+                title: "Wind Down".to_string(),
+                description: "Your work day is almost over, close it out calmly".to_string(),
+                checklist: vec![
+                    "📝 Write down where you left off".to_string(),
+                    "✅ Pick the first task for tomorrow".to_string(),
+                    "🗂️ Close the tabs and apps you no longer need".to_string(),
+                    "🧘 Take a few slow breaths before you log off".to_string(),
+                ],
+            },
+        }
+    }
+}
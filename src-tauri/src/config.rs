@@ -1,6 +1,9 @@
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use tauri::Manager;
 
+use crate::database::connection::DATABASE_FILE_NAME;
+
 #[derive(Clone)]
 pub struct AppConfig {
     pub client_id: String,
@@ -24,3 +27,72 @@ pub fn tokens_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
         .map_err(|e| format!("no app data dir: {}", e))?;
     Ok(dir.join("auth").join("google_tokens.json"))
 }
+
+pub fn window_states_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir()
+        .map_err(|e| format!("no app data dir: {}", e))?;
+    Ok(dir.join("window_states.json"))
+}
+
+/// Pointer file recording where the database lives when it was moved.
+/// It stays in the default app data directory so it can be read before
+/// the database is opened.
+pub fn data_location_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir()
+        .map_err(|e| format!("no app data dir: {}", e))?;
+    Ok(dir.join("data_location.json"))
+}
+
+#[derive(Serialize, Deserialize)]
+struct DataLocation {
+    data_directory: PathBuf,
+}
+
+/// Directory holding the database and backups, honouring a previous move.
+///
+/// A recorded directory without a database (e.g. a synced drive that isn't
+/// mounted) would otherwise get a fresh, empty one. The default directory,
+/// which keeps the copy from before the move, is used instead when it has a
+/// database; with nothing to fall back to, startup fails.
+pub fn data_directory(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let default_dir = app.path().app_data_dir()
+        .map_err(|e| format!("no app data dir: {}", e))?;
+    let location = std::fs::read_to_string(data_location_path(app)?)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<DataLocation>(&raw).ok());
+
+    let Some(location) = location else {
+        return Ok(default_dir);
+    };
+    if location.data_directory.join(DATABASE_FILE_NAME).exists() {
+        return Ok(location.data_directory);
+    }
+
+    if default_dir.join(DATABASE_FILE_NAME).exists() {
+        eprintln!(
+            "⚠️ [Config] No database in data directory {}, falling back to {}",
+            location.data_directory.display(),
+            default_dir.display()
+        );
+        return Ok(default_dir);
+    }
+
+    Err(format!(
+        "No database found in data directory {}; reconnect it or delete {} to start over",
+        location.data_directory.display(),
+        data_location_path(app)?.display()
+    ))
+}
+
+/// Record `dir` as the data directory used from now on
+pub fn save_data_directory(app: &tauri::AppHandle, dir: &Path) -> Result<(), String> {
+    let path = data_location_path(app)?;
+    let raw = serde_json::to_string_pretty(&DataLocation { data_directory: dir.to_path_buf() })
+        .map_err(|e| format!("serialize data location: {}", e))?;
+
+    // Write then rename so a crash never leaves a half-written pointer
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, raw)
+        .and_then(|_| std::fs::rename(&temp, &path))
+        .map_err(|e| format!("write data location: {}", e))
+}
@@ -1,7 +1,12 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Instant;
 
-use crate::database::models::{UserSettings, WorkSchedule};
+use crate::database::models::{
+    FocusWidgetPauseBehavior, PhaseColors, SavedCycleState, UserSettings, WorkHoursEnforcement,
+    WorkSchedule,
+};
 
 /// Represents the current phase of the work cycle
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -24,6 +29,20 @@ impl std::fmt::Display for CyclePhase {
     }
 }
 
+impl std::str::FromStr for CyclePhase {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "idle" => Ok(CyclePhase::Idle),
+            "focus" => Ok(CyclePhase::Focus),
+            "short_break" => Ok(CyclePhase::ShortBreak),
+            "long_break" => Ok(CyclePhase::LongBreak),
+            other => Err(format!("Unknown cycle phase: {}", other)),
+        }
+    }
+}
+
 /// Current state of the work cycle
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CycleState {
@@ -35,6 +54,15 @@ pub struct CycleState {
     pub session_id: Option<String>,
     pub started_at: Option<DateTime<Utc>>,
     pub within_work_hours: bool,
+    /// Set while a standalone focus sprint is running
+    #[serde(default)]
+    pub is_sprint: bool,
+    /// Set while the end-of-day wind-down break is running
+    #[serde(default)]
+    pub is_wind_down: bool,
+    /// Configured color of the current phase, `None` while idle
+    #[serde(default)]
+    pub phase_color: Option<String>,
 }
 
 impl Default for CycleState {
@@ -48,6 +76,9 @@ impl Default for CycleState {
             session_id: None,
             started_at: None,
             within_work_hours: true,
+            is_sprint: false,
+            is_wind_down: false,
+            phase_color: None,
         }
     }
 }
@@ -64,6 +95,50 @@ pub struct CycleConfig {
     pub emergency_key: Option<String>,
     pub user_name: Option<String>,
     pub pre_alert_seconds: u32, // seconds before end to send pre-alert
+    pub pause_behavior: FocusWidgetPauseBehavior,
+    pub work_hours_enforcement: WorkHoursEnforcement,
+    pub phase_colors: PhaseColors,
+    /// Shorter first focus after a long break, to ease back in; `None` uses `focus_duration`
+    #[serde(default)]
+    pub post_long_break_focus_duration: Option<u32>, // seconds
+    /// Minutes a break may run past its planned length before `BreakOverran`; `None` disables it
+    #[serde(default)]
+    pub max_break_overrun_minutes: Option<u32>,
+    /// Least time between two saves of the running session's progress
+    #[serde(default = "default_session_save_interval_seconds")]
+    pub session_save_interval_seconds: u32,
+    /// Scale short breaks with `focus_duration` instead of using `break_duration`
+    #[serde(default)]
+    pub break_ratio_mode: bool,
+    /// Short break length as a fraction of `focus_duration` in ratio mode
+    #[serde(default = "default_break_ratio")]
+    pub break_ratio: f64,
+    #[serde(default = "default_break_ratio_min_duration")]
+    pub break_ratio_min_duration: u32, // seconds
+    #[serde(default = "default_break_ratio_max_duration")]
+    pub break_ratio_max_duration: u32, // seconds
+    /// Keep the display awake while a focus phase runs
+    #[serde(default)]
+    pub prevent_display_sleep_during_focus: bool,
+    /// Length of the wind-down break offered as work hours end; `None` turns it off
+    #[serde(default)]
+    pub winddown_minutes: Option<u32>,
+}
+
+fn default_session_save_interval_seconds() -> u32 {
+    30
+}
+
+fn default_break_ratio() -> f64 {
+    0.2
+}
+
+fn default_break_ratio_min_duration() -> u32 {
+    300
+}
+
+fn default_break_ratio_max_duration() -> u32 {
+    1800
 }
 
 impl CycleConfig {
@@ -79,8 +154,182 @@ impl CycleConfig {
             emergency_key: settings.emergency_key_combination,
             user_name: settings.user_name,
             pre_alert_seconds: settings.pre_alert_seconds as u32,
+            pause_behavior: settings.focus_widget_pause_behavior,
+            work_hours_enforcement: settings.work_hours_enforcement,
+            phase_colors: settings.phase_colors,
+            post_long_break_focus_duration: settings
+                .post_long_break_focus_duration
+                .map(|seconds| seconds.max(0) as u32),
+            max_break_overrun_minutes: settings
+                .max_break_overrun_minutes
+                .map(|minutes| minutes.max(0) as u32),
+            session_save_interval_seconds: settings.session_save_interval_seconds.max(1) as u32,
+            break_ratio_mode: settings.break_ratio_mode,
+            break_ratio: settings.break_ratio,
+            break_ratio_min_duration: settings.break_ratio_min_duration.max(0) as u32,
+            break_ratio_max_duration: settings.break_ratio_max_duration.max(0) as u32,
+            prevent_display_sleep_during_focus: settings.prevent_display_sleep_during_focus,
+            winddown_minutes: settings
+                .winddown_minutes
+                .filter(|minutes| *minutes > 0)
+                .map(|minutes| minutes as u32),
+        }
+    }
+
+    /// Length of a short break in seconds: `break_duration`, or in ratio mode
+    /// `focus_duration * break_ratio` kept within the ratio min/max
+    pub fn short_break_duration(&self) -> u32 {
+        if !self.break_ratio_mode {
+            return self.break_duration;
+        }
+
+        let scaled = (self.focus_duration as f64 * self.break_ratio).round();
+        let max = self
+            .break_ratio_max_duration
+            .max(self.break_ratio_min_duration);
+        (scaled.max(0.0) as u32).clamp(self.break_ratio_min_duration, max)
+    }
+
+    /// List the fields where `saved` differs from this (active) configuration
+    pub fn drift_from(&self, saved: &CycleConfig) -> Vec<ConfigDrift> {
+        let (Ok(serde_json::Value::Object(active)), Ok(serde_json::Value::Object(saved))) =
+            (serde_json::to_value(self), serde_json::to_value(saved))
+        else {
+            return Vec::new();
+        };
+
+        active
+            .into_iter()
+            .filter_map(|(field, active_value)| {
+                let saved_value = saved.get(&field).cloned().unwrap_or_default();
+                let differs = if field == "work_schedule" {
+                    schedule_fields(&saved_value) != schedule_fields(&active_value)
+                } else {
+                    saved_value != active_value
+                };
+
+                differs.then_some(ConfigDrift {
+                    field,
+                    saved_value,
+                    active_value,
+                })
+            })
+            .collect()
+    }
+
+    /// Project today's focus time if the cycle is followed back to back for
+    /// the whole work window, scaled by `compliance_percent` (clamped to 0-100).
+    ///
+    /// Only complete focus sessions count. Without an enabled schedule the
+    /// window is a default workday of `DEFAULT_WORKDAY_MINUTES`.
+    pub fn project_day(&self, compliance_percent: u32) -> DayProjection {
+        let compliance_percent = compliance_percent.min(100);
+
+        let schedule = self
+            .work_schedule
+            .as_ref()
+            .filter(|schedule| schedule.use_work_schedule);
+        let parse =
+            |value: Option<&String>| value.and_then(|v| NaiveTime::parse_from_str(v, "%H:%M").ok());
+        let window_minutes = match schedule.map(|schedule| {
+            (
+                parse(schedule.work_start_time.as_ref()),
+                parse(schedule.work_end_time.as_ref()),
+            )
+        }) {
+            Some((Some(start), Some(end))) => {
+                let minutes = (end - start).num_minutes();
+                // Overnight schedules wrap past midnight
+                if minutes > 0 {
+                    minutes as u32
+                } else {
+                    (minutes + 24 * 60) as u32
+                }
+            }
+            _ => DEFAULT_WORKDAY_MINUTES,
+        };
+
+        // Walk the cycle through the window: focus, then a short or long break
+        let window_seconds = window_minutes * 60;
+        let mut elapsed = 0;
+        let mut focus_sessions = 0;
+        while self.focus_duration > 0 && elapsed + self.focus_duration <= window_seconds {
+            elapsed += self.focus_duration;
+            focus_sessions += 1;
+            elapsed += if self.cycles_per_long_break > 0
+                && focus_sessions % self.cycles_per_long_break == 0
+            {
+                self.long_break_duration
+            } else {
+                self.short_break_duration()
+            };
+        }
+
+        let planned_focus_minutes = focus_sessions * self.focus_duration / 60;
+
+        DayProjection {
+            window_minutes,
+            focus_sessions,
+            planned_focus_minutes,
+            projected_focus_minutes: planned_focus_minutes * compliance_percent / 100,
+            assumptions: ProjectionAssumptions {
+                compliance_percent,
+                schedule_enabled: schedule.is_some(),
+                work_start_time: schedule.and_then(|s| s.work_start_time.clone()),
+                work_end_time: schedule.and_then(|s| s.work_end_time.clone()),
+                focus_minutes: self.focus_duration / 60,
+                short_break_minutes: self.short_break_duration() / 60,
+                long_break_minutes: self.long_break_duration / 60,
+                cycles_per_long_break: self.cycles_per_long_break,
+            },
+        }
+    }
+}
+
+/// Work window assumed by `project_day` when no schedule is enabled
+const DEFAULT_WORKDAY_MINUTES: u32 = 8 * 60;
+
+/// Inputs a day projection was computed from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionAssumptions {
+    pub compliance_percent: u32,
+    pub schedule_enabled: bool,
+    pub work_start_time: Option<String>,
+    pub work_end_time: Option<String>,
+    pub focus_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub cycles_per_long_break: u32,
+}
+
+/// Focus time achievable in a day by following the configured cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayProjection {
+    pub window_minutes: u32,
+    pub focus_sessions: u32,
+    pub planned_focus_minutes: u32,
+    /// `planned_focus_minutes` scaled by the assumed compliance
+    pub projected_focus_minutes: u32,
+    pub assumptions: ProjectionAssumptions,
+}
+
+/// Only the schedule fields that affect the cycle, ignoring row metadata
+fn schedule_fields(schedule: &serde_json::Value) -> serde_json::Value {
+    let mut schedule = schedule.clone();
+    if let Some(object) = schedule.as_object_mut() {
+        for key in ["id", "user_id", "created_at", "updated_at"] {
+            object.remove(key);
         }
     }
+    schedule
+}
+
+/// A config field whose saved value hasn't been applied to the running cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDrift {
+    pub field: String,
+    pub saved_value: serde_json::Value,
+    pub active_value: serde_json::Value,
 }
 
 /// Events that can be emitted by the cycle orchestrator
@@ -108,12 +357,141 @@ pub enum CycleEvent {
     LongBreakReached {
         cycles_completed: u32,
     },
+    Paused {
+        phase: CyclePhase,
+        remaining: u32,
+        pause_behavior: FocusWidgetPauseBehavior,
+    },
+    Resumed {
+        phase: CyclePhase,
+        remaining: u32,
+    },
+    OutsideWorkHours {
+        next_work_start: Option<DateTime<Utc>>,
+    },
+    ClockChanged {
+        divergence_seconds: i64,
+        remaining: u32,
+    },
+    /// A focus session started with this week's focus budget already used up
+    WeeklyBudgetReached {
+        used_minutes: u32,
+        budget_minutes: u32,
+    },
+    /// A break ran past its planned length plus the allowed overrun,
+    /// typically because it was paused or snoozed. Sent once per break.
+    BreakOverran {
+        phase: CyclePhase,
+        overrun_seconds: u32,
+    },
+    /// A focus session ended too close to the end of work hours for another
+    /// one; offers the wind-down break started by `start_wind_down`
+    WindDownSuggested {
+        minutes_until_work_end: u32,
+        duration: u32,
+    },
+}
+
+/// Wall-clock and monotonic time diverging by at least this much between two
+/// ticks is treated as a system clock change (NTP sync, manual change, sleep)
+const CLOCK_CHANGE_THRESHOLD_SECONDS: i64 = 5;
+
+/// Last wall-clock/monotonic divergence detected by the orchestrator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockDiagnostics {
+    pub last_divergence_seconds: Option<i64>,
+    pub last_detected_at: Option<DateTime<Utc>>,
+    pub detections: u32,
+}
+
+/// When the current phase is expected to end
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseEndTime {
+    pub phase: CyclePhase,
+    /// Wall-clock end of the phase; `None` while paused
+    pub ends_at: Option<DateTime<Utc>>,
+    pub paused: bool,
+}
+
+/// Tracks the running phase against both clocks
+#[derive(Debug, Clone)]
+struct ClockTracker {
+    session_id: Option<String>,
+    anchor: Instant,
+    anchor_remaining: u32,
+    last_wall: DateTime<Utc>,
+    last_instant: Instant,
+}
+
+/// Errors returned by the cycle orchestrator
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CycleError {
+    /// Focus was refused because work hours enforcement is `Block`
+    OutsideWorkHours {
+        next_work_start: Option<DateTime<Utc>>,
+    },
+    /// The requested transition is not valid from the current state
+    InvalidState { message: String },
+}
+
+impl std::fmt::Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CycleError::OutsideWorkHours { .. } => {
+                write!(f, "Cannot start focus session outside work hours")
+            }
+            CycleError::InvalidState { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Commands reject with the JSON form of `OutsideWorkHours`, so the frontend
+/// can offer the override and show when work starts again
+impl From<CycleError> for String {
+    fn from(error: CycleError) -> Self {
+        match error {
+            CycleError::OutsideWorkHours { .. } => {
+                serde_json::to_string(&error).unwrap_or_else(|_| error.to_string())
+            }
+            CycleError::InvalidState { .. } => error.to_string(),
+        }
+    }
+}
+
+/// Longest a single phase can last; a remaining time above this can only
+/// come from a negative value wrapping around
+const MAX_PHASE_SECONDS: u32 = 24 * 60 * 60;
+
+/// Number of recent events kept for debugging
+pub const EVENT_LOG_CAPACITY: usize = 50;
+
+/// A cycle event together with when the orchestrator produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCycleEvent {
+    pub event: CycleEvent,
+    pub timestamp: DateTime<Utc>,
 }
 
 /// Orchestrates work cycles with focus and break periods
 pub struct CycleOrchestrator {
     config: CycleConfig,
     state: CycleState,
+    clock_tracker: Option<ClockTracker>,
+    clock_diagnostics: ClockDiagnostics,
+    event_log: VecDeque<RecordedCycleEvent>,
+    /// Set when a long break completes, consumed by the next focus session
+    long_break_just_completed: bool,
+    /// One-off focus length in seconds, consumed by the next focus session
+    next_focus_duration: Option<u32>,
+    /// Break session that already got its `BreakOverran` event
+    overrun_reported_for: Option<String>,
+    /// Session whose progress was last saved, and when
+    progress_saved_at: Option<(String, Instant)>,
+    /// Break session paused by a snooze, until anything resumes it
+    snoozed_session: Option<String>,
 }
 
 impl CycleOrchestrator {
@@ -122,6 +500,18 @@ impl CycleOrchestrator {
         Self {
             config,
             state: CycleState::default(),
+            clock_tracker: None,
+            clock_diagnostics: ClockDiagnostics {
+                last_divergence_seconds: None,
+                last_detected_at: None,
+                detections: 0,
+            },
+            event_log: VecDeque::with_capacity(EVENT_LOG_CAPACITY),
+            long_break_just_completed: false,
+            next_focus_duration: None,
+            overrun_reported_for: None,
+            progress_saved_at: None,
+            snoozed_session: None,
         }
     }
 
@@ -132,7 +522,24 @@ impl CycleOrchestrator {
 
     /// Get the current cycle state
     pub fn get_state(&self) -> CycleState {
-        self.state.clone()
+        let colors = &self.config.phase_colors;
+        CycleState {
+            phase_color: match self.state.phase {
+                CyclePhase::Idle => None,
+                CyclePhase::Focus => Some(colors.focus.clone()),
+                CyclePhase::ShortBreak => Some(colors.short_break.clone()),
+                CyclePhase::LongBreak => Some(colors.long_break.clone()),
+            },
+            ..self.state.clone()
+        }
+    }
+
+    /// Whether the display should be kept awake right now: a focus phase is
+    /// running and the setting is on
+    pub fn keeps_display_awake(&self) -> bool {
+        self.config.prevent_display_sleep_during_focus
+            && self.state.is_running
+            && self.state.phase == CyclePhase::Focus
     }
 
     /// Get a copy of the current configuration
@@ -147,22 +554,15 @@ impl CycleOrchestrator {
                 return true; // No work schedule restriction
             }
 
-            if let (Some(ref start), Some(ref end)) =
-                (&schedule.work_start_time, &schedule.work_end_time)
-            {
-                let now = chrono::Local::now();
-                let current_time = now.format("%H:%M").to_string();
-
-                // Simple string comparison works for HH:MM format
-                return current_time >= *start && current_time <= *end;
-            }
+            // Handles overnight schedules and unparsable times (treated as unrestricted)
+            return schedule.status_at(chrono::Local::now()).within_work_hours;
         }
 
         true // Default to allowing if no schedule configured
     }
 
     /// Start a focus session with optional override for work hours
-    pub fn start_focus_session(&mut self) -> Result<Vec<CycleEvent>, String> {
+    pub fn start_focus_session(&mut self) -> Result<Vec<CycleEvent>, CycleError> {
         self.start_focus_session_with_override(false)
     }
 
@@ -170,39 +570,133 @@ impl CycleOrchestrator {
     pub fn start_focus_session_with_override(
         &mut self,
         override_work_hours: bool,
-    ) -> Result<Vec<CycleEvent>, String> {
-        // Check if we can start (work hours validation)
-        if !override_work_hours && !self.is_within_work_hours() {
-            return Err("Cannot start focus session outside work hours".to_string());
+    ) -> Result<Vec<CycleEvent>, CycleError> {
+        // Track if within work hours
+        let within_work_hours = self.is_within_work_hours();
+        let mut events = vec![];
+
+        // Apply the configured work hours enforcement
+        if !within_work_hours {
+            let next_work_start = self
+                .config
+                .work_schedule
+                .as_ref()
+                .and_then(|schedule| schedule.status_at(chrono::Local::now()).next_boundary);
+
+            match self.config.work_hours_enforcement {
+                WorkHoursEnforcement::Off => {}
+                WorkHoursEnforcement::Warn => {
+                    events.push(CycleEvent::OutsideWorkHours { next_work_start });
+                }
+                WorkHoursEnforcement::Block => {
+                    if !override_work_hours {
+                        return Err(CycleError::OutsideWorkHours { next_work_start });
+                    }
+                }
+            }
         }
 
         // Can only start from idle state
         if self.state.phase != CyclePhase::Idle {
-            return Err(format!(
-                "Cannot start focus session from {} state",
-                self.state.phase
-            ));
+            return Err(CycleError::InvalidState {
+                message: format!("Cannot start focus session from {} state", self.state.phase),
+            });
         }
 
+        // A one-off length wins, otherwise ease back in after a long break, once
+        let duration = match (
+            self.next_focus_duration.take(),
+            self.config.post_long_break_focus_duration,
+        ) {
+            (Some(duration), _) => duration,
+            (None, Some(duration)) if self.long_break_just_completed => duration,
+            _ => self.config.focus_duration,
+        };
+        self.long_break_just_completed = false;
+
         // Generate session ID
         let session_id = uuid::Uuid::new_v4().to_string();
 
-        // Track if within work hours
-        let within_work_hours = self.is_within_work_hours();
-
         // Update state
         self.state.phase = CyclePhase::Focus;
-        self.state.remaining_seconds = self.config.focus_duration;
+        self.state.remaining_seconds = duration;
         self.state.is_running = true;
         self.state.session_id = Some(session_id);
         self.state.started_at = Some(Utc::now());
         self.state.within_work_hours = within_work_hours;
+        self.start_countdown();
 
-        Ok(vec![CycleEvent::PhaseStarted {
+        events.push(CycleEvent::PhaseStarted {
             phase: CyclePhase::Focus,
-            duration: self.config.focus_duration,
+            duration,
+            cycle_count: self.state.cycle_count,
+        });
+
+        self.record_events(&events);
+        Ok(events)
+    }
+
+    /// Make only the next focus session last `minutes`, or drop a pending
+    /// override with `None`. Bounds match the configured focus duration.
+    pub fn set_next_focus_duration(&mut self, minutes: Option<u32>) -> Result<(), CycleError> {
+        if let Some(minutes) = minutes {
+            if !(1..=120).contains(&minutes) {
+                return Err(CycleError::InvalidState {
+                    message: "Focus duration must be between 1 and 120 minutes".to_string(),
+                });
+            }
+        }
+
+        self.next_focus_duration = minutes.map(|minutes| minutes * 60);
+        Ok(())
+    }
+
+    /// Start a standalone focus sprint of the given length.
+    ///
+    /// Sprints sit outside the cycle: finishing one doesn't count towards
+    /// `cycle_count` and returns to idle instead of starting a break.
+    pub fn start_focus_sprint(&mut self, minutes: u32) -> Result<Vec<CycleEvent>, CycleError> {
+        if minutes == 0 {
+            return Err(CycleError::InvalidState {
+                message: "Sprint duration must be at least one minute".to_string(),
+            });
+        }
+
+        if minutes > MAX_PHASE_SECONDS / 60 {
+            return Err(CycleError::InvalidState {
+                message: format!(
+                    "Sprint duration must be at most {} minutes",
+                    MAX_PHASE_SECONDS / 60
+                ),
+            });
+        }
+
+        if self.state.phase != CyclePhase::Idle {
+            return Err(CycleError::InvalidState {
+                message: format!("Cannot start focus sprint from {} state", self.state.phase),
+            });
+        }
+
+        let duration = minutes * 60;
+        let session_id = uuid::Uuid::new_v4().to_string();
+
+        self.state.phase = CyclePhase::Focus;
+        self.state.remaining_seconds = duration;
+        self.state.is_running = true;
+        self.state.session_id = Some(session_id);
+        self.state.started_at = Some(Utc::now());
+        self.state.within_work_hours = self.is_within_work_hours();
+        self.state.is_sprint = true;
+        self.start_countdown();
+
+        let events = vec![CycleEvent::PhaseStarted {
+            phase: CyclePhase::Focus,
+            duration,
             cycle_count: self.state.cycle_count,
-        }])
+        }];
+
+        self.record_events(&events);
+        Ok(events)
     }
 
     /// Start a break (short or long based on cycle count)
@@ -223,7 +717,7 @@ impl CycleOrchestrator {
         let (phase, duration) = if is_long_break {
             (CyclePhase::LongBreak, self.config.long_break_duration)
         } else {
-            (CyclePhase::ShortBreak, self.config.break_duration)
+            (CyclePhase::ShortBreak, self.config.short_break_duration())
         };
 
         // Generate session ID
@@ -239,6 +733,7 @@ impl CycleOrchestrator {
         self.state.session_id = Some(session_id);
         self.state.started_at = Some(Utc::now());
         self.state.within_work_hours = within_work_hours;
+        self.start_countdown();
 
         let mut events = vec![CycleEvent::PhaseStarted {
             phase: phase.clone(),
@@ -253,21 +748,106 @@ impl CycleOrchestrator {
             });
         }
 
+        self.record_events(&events);
+        Ok(events)
+    }
+
+    /// Suggest winding down when work hours end before another focus session
+    /// and the wind-down could finish, i.e. within `focus_duration` plus the
+    /// wind-down length of `now`
+    fn check_wind_down(&self, now: DateTime<Local>) -> Option<CycleEvent> {
+        let duration = self.config.winddown_minutes? * 60;
+        let status = self.config.work_schedule.as_ref()?.status_at(now);
+
+        if !status.schedule_enabled || !status.within_work_hours {
+            return None;
+        }
+
+        let seconds_until_end = status.seconds_until_boundary?;
+        if seconds_until_end <= 0
+            || seconds_until_end > (self.config.focus_duration + duration) as i64
+        {
+            return None;
+        }
+
+        Some(CycleEvent::WindDownSuggested {
+            minutes_until_work_end: (seconds_until_end as u32).div_ceil(60),
+            duration,
+        })
+    }
+
+    /// Start the end-of-day wind-down: a long break of `winddown_minutes`
+    /// that doesn't touch the cycle count and returns to idle when it ends
+    pub fn start_wind_down(&mut self) -> Result<Vec<CycleEvent>, CycleError> {
+        let duration = match self.config.winddown_minutes {
+            Some(minutes) => minutes * 60,
+            None => {
+                return Err(CycleError::InvalidState {
+                    message: "Wind-down is turned off".to_string(),
+                })
+            }
+        };
+
+        if self.state.phase != CyclePhase::Idle {
+            return Err(CycleError::InvalidState {
+                message: format!("Cannot start wind-down from {} state", self.state.phase),
+            });
+        }
+
+        let session_id = uuid::Uuid::new_v4().to_string();
+
+        self.state.phase = CyclePhase::LongBreak;
+        self.state.remaining_seconds = duration;
+        self.state.is_running = true;
+        self.state.session_id = Some(session_id);
+        self.state.started_at = Some(Utc::now());
+        self.state.within_work_hours = self.is_within_work_hours();
+        self.state.is_wind_down = true;
+        self.start_countdown();
+
+        let events = vec![CycleEvent::PhaseStarted {
+            phase: CyclePhase::LongBreak,
+            duration,
+            cycle_count: self.state.cycle_count,
+        }];
+
+        self.record_events(&events);
         Ok(events)
     }
 
-    /// Pause the current session
-    pub fn pause(&mut self) -> Result<(), String> {
+    /// Pause the current focus session or break, freezing its remaining
+    /// time. Strict breaks can't be paused, since that would end them early
+    /// in everything but name.
+    pub fn pause(&mut self) -> Result<Vec<CycleEvent>, String> {
         if !self.state.is_running {
             return Err("No active session to pause".to_string());
         }
 
+        let in_break = matches!(
+            self.state.phase,
+            CyclePhase::ShortBreak | CyclePhase::LongBreak
+        );
+        if in_break && self.config.strict_mode {
+            return Err("Breaks can't be paused while strict mode is active".to_string());
+        }
+
+        // Keep the time that passed since the last tick
+        self.state.remaining_seconds = self.countdown_remaining().max(1);
         self.state.is_running = false;
-        Ok(())
+        self.clock_tracker = None;
+
+        let events = vec![CycleEvent::Paused {
+            phase: self.state.phase.clone(),
+            remaining: self.state.remaining_seconds,
+            pause_behavior: self.config.pause_behavior,
+        }];
+
+        self.record_events(&events);
+        Ok(events)
     }
 
     /// Resume the current session
-    pub fn resume(&mut self) -> Result<(), String> {
+    pub fn resume(&mut self) -> Result<Vec<CycleEvent>, String> {
         if self.state.is_running {
             return Err("Session is already running".to_string());
         }
@@ -277,11 +857,42 @@ impl CycleOrchestrator {
         }
 
         self.state.is_running = true;
-        Ok(())
+        self.snoozed_session = None;
+        self.start_countdown();
+
+        let events = vec![CycleEvent::Resumed {
+            phase: self.state.phase.clone(),
+            remaining: self.state.remaining_seconds,
+        }];
+
+        self.record_events(&events);
+        Ok(events)
+    }
+
+    /// Mark the current pause as a break snooze, so only that pause is
+    /// resumed when the snooze runs out
+    pub fn mark_paused_by_snooze(&mut self) {
+        if !self.state.is_running {
+            self.snoozed_session = self.state.session_id.clone();
+        }
+    }
+
+    /// Whether `session_id` is still paused by its snooze
+    pub fn is_snoozed(&self, session_id: &str) -> bool {
+        !self.state.is_running
+            && self.state.session_id.as_deref() == Some(session_id)
+            && self.snoozed_session.as_deref() == Some(session_id)
     }
 
     /// End the current session and transition to idle
     pub fn end_session(&mut self, completed: bool) -> Result<Vec<CycleEvent>, String> {
+        let events = self.finish_session(completed)?;
+        self.record_events(&events);
+        Ok(events)
+    }
+
+    /// Reset to idle after a session, without recording the events
+    fn finish_session(&mut self, completed: bool) -> Result<Vec<CycleEvent>, String> {
         let current_phase = self.state.phase.clone();
 
         if current_phase == CyclePhase::Idle {
@@ -293,35 +904,63 @@ impl CycleOrchestrator {
             completed,
         }];
 
-        // If a focus session was completed, increment cycle count
-        if completed && current_phase == CyclePhase::Focus {
+        // If a focus session was completed, increment cycle count (sprints don't count)
+        if completed && current_phase == CyclePhase::Focus && !self.state.is_sprint {
             self.state.cycle_count += 1;
             events.push(CycleEvent::CycleCompleted {
                 cycle_count: self.state.cycle_count,
             });
         }
 
+        if completed {
+            self.long_break_just_completed = current_phase == CyclePhase::LongBreak;
+        }
+
         // Reset to idle state
         self.state.phase = CyclePhase::Idle;
         self.state.remaining_seconds = 0;
         self.state.is_running = false;
         self.state.session_id = None;
         self.state.started_at = None;
+        self.state.is_sprint = false;
+        self.state.is_wind_down = false;
 
         Ok(events)
     }
 
     /// Handle a timer tick (called every second)
     pub fn tick(&mut self) -> Result<Vec<CycleEvent>, String> {
+        // Checked before the running test, since a paused break is the usual way to overrun
+        let overrun = self.check_break_overrun();
+
         if !self.state.is_running || self.state.phase == CyclePhase::Idle {
-            return Ok(vec![]);
+            self.clock_tracker = None;
+            let events: Vec<CycleEvent> = overrun.into_iter().collect();
+            self.record_events(&events);
+            return Ok(events);
         }
 
-        let mut events = vec![];
+        let mut events: Vec<CycleEvent> = overrun.into_iter().collect();
+
+        if let Some(event) = self.check_clock() {
+            events.push(event);
+        }
+
+        // Remaining time longer than any phase can last is a negative value
+        // that wrapped around; treat it as out of time so the phase ends
+        if self.state.remaining_seconds > MAX_PHASE_SECONDS {
+            println!(
+                "⚠️ [CycleOrchestrator] Remaining time {}s is out of range, ending {} phase",
+                self.state.remaining_seconds, self.state.phase
+            );
+            self.state.remaining_seconds = 0;
+        }
 
-        // Decrement remaining time
+        // Count down by the time that actually passed rather than one second
+        // per tick, so the timer stays accurate at any tick interval
         if self.state.remaining_seconds > 0 {
-            self.state.remaining_seconds -= 1;
+            let previous_remaining = self.state.remaining_seconds;
+            self.state.remaining_seconds = self.countdown_remaining().min(previous_remaining);
 
             // Emit tick event
             events.push(CycleEvent::Tick {
@@ -331,92 +970,429 @@ impl CycleOrchestrator {
             // Check for pre-alert (configurable seconds before end, only for focus sessions)
             if self.config.pre_alert_seconds > 0
                 && self.state.phase == CyclePhase::Focus
-                && self.state.remaining_seconds == self.config.pre_alert_seconds
+                && previous_remaining > self.config.pre_alert_seconds
+                && self.state.remaining_seconds <= self.config.pre_alert_seconds
             {
                 events.push(CycleEvent::PreAlert {
                     remaining: self.state.remaining_seconds,
                 });
             }
+        }
 
-            // Check if session completed
-            if self.state.remaining_seconds == 0 {
-                let completed_phase = self.state.phase.clone();
-
-                // Auto-complete the session
-                let completion_events = self.end_session(true)?;
-
-                events.extend(completion_events);
-
-                // If focus session completed, automatically start break
-                if completed_phase == CyclePhase::Focus {
-                    // Determine if this should be a long break
-                    let is_long_break = self.state.cycle_count > 0
-                        && self.state.cycle_count % self.config.cycles_per_long_break == 0;
-
-                    let (phase, duration) = if is_long_break {
-                        (CyclePhase::LongBreak, self.config.long_break_duration)
-                    } else {
-                        (CyclePhase::ShortBreak, self.config.break_duration)
-                    };
-
-                    // Generate session ID
-                    let session_id = uuid::Uuid::new_v4().to_string();
-
-                    // Track if within work hours
-                    let within_work_hours = self.is_within_work_hours();
-
-                    // Update state to break IMMEDIATELY (before emitting events)
-                    // This ensures the state is correct when the frontend queries it
-                    self.state.phase = phase.clone();
-                    self.state.remaining_seconds = duration;
-                    self.state.is_running = true;
-                    self.state.session_id = Some(session_id.clone());
-                    self.state.started_at = Some(Utc::now());
-                    self.state.within_work_hours = within_work_hours;
-
-                    events.push(CycleEvent::PhaseStarted {
-                        phase: phase.clone(),
-                        duration,
-                        cycle_count: self.state.cycle_count,
-                    });
+        // Check if session completed. This also ends a running phase that was
+        // already out of time, instead of leaving it stuck at zero.
+        if self.state.remaining_seconds == 0 {
+            let completed_phase = self.state.phase.clone();
+            let was_sprint = self.state.is_sprint;
+
+            // Auto-complete the session
+            let completion_events = self.finish_session(true)?;
+
+            events.extend(completion_events);
+
+            // If focus session completed, automatically start break
+            if completed_phase == CyclePhase::Focus && was_sprint {
+                // A finished sprint returns to idle without a break
+            } else if completed_phase == CyclePhase::Focus {
+                // Determine if this should be a long break
+                let is_long_break = self.state.cycle_count > 0
+                    && self
+                        .state
+                        .cycle_count
+                        .is_multiple_of(self.config.cycles_per_long_break);
+
+                let (phase, duration) = if is_long_break {
+                    (CyclePhase::LongBreak, self.config.long_break_duration)
+                } else {
+                    (CyclePhase::ShortBreak, self.config.short_break_duration())
+                };
+
+                // Generate session ID
+                let session_id = uuid::Uuid::new_v4().to_string();
+
+                // Track if within work hours
+                let within_work_hours = self.is_within_work_hours();
+
+                // Update state to break IMMEDIATELY (before emitting events)
+                // This ensures the state is correct when the frontend queries it
+                self.state.phase = phase.clone();
+                self.state.remaining_seconds = duration;
+                self.state.is_running = true;
+                self.state.session_id = Some(session_id.clone());
+                self.state.started_at = Some(Utc::now());
+                self.state.within_work_hours = within_work_hours;
+                self.start_countdown();
+
+                events.push(CycleEvent::PhaseStarted {
+                    phase: phase.clone(),
+                    duration,
+                    cycle_count: self.state.cycle_count,
+                });
 
-                    // Emit long break event if applicable
-                    if is_long_break {
-                        events.push(CycleEvent::LongBreakReached {
-                            cycles_completed: self.state.cycle_count,
-                        });
-                    }
-                } else if completed_phase == CyclePhase::ShortBreak {
-                    // Automatically start the next focus session after a short break
-                    let session_id = uuid::Uuid::new_v4().to_string();
-                    let within_work_hours = self.is_within_work_hours();
-
-                    self.state.phase = CyclePhase::Focus;
-                    self.state.remaining_seconds = self.config.focus_duration;
-                    self.state.is_running = true;
-                    self.state.session_id = Some(session_id.clone());
-                    self.state.started_at = Some(Utc::now());
-                    self.state.within_work_hours = within_work_hours;
-
-                    events.push(CycleEvent::PhaseStarted {
-                        phase: CyclePhase::Focus,
-                        duration: self.config.focus_duration,
-                        cycle_count: self.state.cycle_count,
+                // Emit long break event if applicable
+                if is_long_break {
+                    events.push(CycleEvent::LongBreakReached {
+                        cycles_completed: self.state.cycle_count,
                     });
-                } else if completed_phase == CyclePhase::LongBreak {
-                    // After a long break (end of configured cycle group), remain idle.
                 }
+
+                if let Some(event) = self.check_wind_down(Local::now()) {
+                    events.push(event);
+                }
+            } else if completed_phase == CyclePhase::ShortBreak {
+                // Automatically start the next focus session after a short break
+                events.push(self.begin_next_focus());
+            } else if completed_phase == CyclePhase::LongBreak {
+                // After a long break (end of configured cycle group), remain idle.
             }
         }
 
+        self.record_events(&events);
+        Ok(events)
+    }
+
+    /// Start the focus session that follows a break, without the checks
+    /// `start_focus_session` makes for a user-started one
+    fn begin_next_focus(&mut self) -> CycleEvent {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let within_work_hours = self.is_within_work_hours();
+
+        self.state.phase = CyclePhase::Focus;
+        self.state.remaining_seconds = self.config.focus_duration;
+        self.state.is_running = true;
+        self.state.session_id = Some(session_id);
+        self.state.started_at = Some(Utc::now());
+        self.state.within_work_hours = within_work_hours;
+        self.start_countdown();
+
+        CycleEvent::PhaseStarted {
+            phase: CyclePhase::Focus,
+            duration: self.config.focus_duration,
+            cycle_count: self.state.cycle_count,
+        }
+    }
+
+    /// End the current break as not completed and go straight into the next
+    /// focus session. The cycle count is left alone, since it already moved
+    /// on when the focus before the break completed. Skipping the wind-down
+    /// only ends it, as the work day is over.
+    pub fn skip_break(&mut self) -> Result<Vec<CycleEvent>, CycleError> {
+        if !matches!(
+            self.state.phase,
+            CyclePhase::ShortBreak | CyclePhase::LongBreak
+        ) {
+            return Err(CycleError::InvalidState {
+                message: format!("Cannot skip a break from {} state", self.state.phase),
+            });
+        }
+
+        let was_wind_down = self.state.is_wind_down;
+        let mut events = self
+            .finish_session(false)
+            .map_err(|message| CycleError::InvalidState { message })?;
+
+        if !was_wind_down {
+            events.push(self.begin_next_focus());
+        }
+
+        self.record_events(&events);
         Ok(events)
     }
 
+    /// Whether the running session's progress is due to be saved, at most
+    /// once per `session_save_interval_seconds`. A session that just started
+    /// only starts the interval, since it was saved when it was created.
+    pub fn progress_save_due(&mut self) -> bool {
+        let session_id = match &self.state.session_id {
+            Some(id) if self.state.is_running => id,
+            _ => return false,
+        };
+
+        let now = Instant::now();
+        let due = match &self.progress_saved_at {
+            Some((saved_for, saved_at)) if saved_for == session_id => {
+                now.duration_since(*saved_at).as_secs()
+                    >= self.config.session_save_interval_seconds as u64
+            }
+            _ => false,
+        };
+
+        if due || self.progress_saved_at.as_ref().map(|(id, _)| id) != Some(session_id) {
+            self.progress_saved_at = Some((session_id.clone(), now));
+        }
+        due
+    }
+
+    /// Emit `BreakOverran` once when the current break has been going on
+    /// (by wall clock, pauses included) longer than planned plus the allowance
+    fn check_break_overrun(&mut self) -> Option<CycleEvent> {
+        let allowance_minutes = self.config.max_break_overrun_minutes?;
+
+        let planned = match self.state.phase {
+            CyclePhase::ShortBreak => self.config.short_break_duration(),
+            CyclePhase::LongBreak => self.config.long_break_duration,
+            _ => return None,
+        };
+
+        let session_id = self.state.session_id.clone()?;
+        if self.overrun_reported_for.as_deref() == Some(session_id.as_str()) {
+            return None;
+        }
+
+        let elapsed = (Utc::now() - self.state.started_at?).num_seconds().max(0) as u32;
+        if elapsed <= planned + allowance_minutes * 60 {
+            return None;
+        }
+
+        self.overrun_reported_for = Some(session_id);
+        Some(CycleEvent::BreakOverran {
+            phase: self.state.phase.clone(),
+            overrun_seconds: elapsed - planned,
+        })
+    }
+
+    /// Start measuring the running phase from now
+    fn start_countdown(&mut self) {
+        let now_instant = Instant::now();
+        self.clock_tracker = Some(ClockTracker {
+            session_id: self.state.session_id.clone(),
+            anchor: now_instant,
+            anchor_remaining: self.state.remaining_seconds,
+            last_wall: Utc::now(),
+            last_instant: now_instant,
+        });
+    }
+
+    /// Remaining seconds according to the monotonic clock, rounded to the
+    /// nearest second. Falls back to the stored value when nothing is running.
+    fn countdown_remaining(&self) -> u32 {
+        match self.clock_tracker.as_ref() {
+            Some(tracker) if tracker.session_id == self.state.session_id => {
+                let elapsed = (tracker.anchor.elapsed().as_millis() + 500) / 1000;
+                let elapsed = u32::try_from(elapsed).unwrap_or(u32::MAX);
+                tracker.anchor_remaining.saturating_sub(elapsed)
+            }
+            _ => self.state.remaining_seconds,
+        }
+    }
+
+    /// Add events to the debug log, dropping the oldest past capacity.
+    /// Per-second ticks are left out so they don't push everything else out.
+    fn record_events(&mut self, events: &[CycleEvent]) {
+        let timestamp = Utc::now();
+
+        for event in events {
+            if matches!(event, CycleEvent::Tick { .. }) {
+                continue;
+            }
+
+            if self.event_log.len() >= EVENT_LOG_CAPACITY {
+                self.event_log.pop_front();
+            }
+            self.event_log.push_back(RecordedCycleEvent {
+                event: event.clone(),
+                timestamp,
+            });
+        }
+    }
+
+    /// Get recently produced events, newest first
+    pub fn get_recent_events(&self) -> Vec<RecordedCycleEvent> {
+        self.event_log.iter().rev().cloned().collect()
+    }
+
+    /// Number of events currently held in the event log
+    pub fn event_log_len(&self) -> usize {
+        self.event_log.len()
+    }
+
+    /// Compare wall-clock and monotonic time since the last tick.
+    ///
+    /// On a jump, `remaining_seconds` is recomputed from the monotonic clock and
+    /// `started_at` is shifted so wall-clock durations stay consistent.
+    fn check_clock(&mut self) -> Option<CycleEvent> {
+        let now_wall = Utc::now();
+        let now_instant = Instant::now();
+
+        let tracker = match self.clock_tracker.as_mut() {
+            Some(tracker) if tracker.session_id == self.state.session_id => tracker,
+            _ => {
+                // New phase (or just resumed): start tracking from here
+                self.clock_tracker = Some(ClockTracker {
+                    session_id: self.state.session_id.clone(),
+                    anchor: now_instant,
+                    anchor_remaining: self.state.remaining_seconds,
+                    last_wall: now_wall,
+                    last_instant: now_instant,
+                });
+                return None;
+            }
+        };
+
+        let wall_elapsed_ms = (now_wall - tracker.last_wall).num_milliseconds();
+        let monotonic_elapsed_ms =
+            now_instant.duration_since(tracker.last_instant).as_millis() as i64;
+        let divergence_seconds = (wall_elapsed_ms - monotonic_elapsed_ms) / 1000;
+
+        tracker.last_wall = now_wall;
+        tracker.last_instant = now_instant;
+
+        if divergence_seconds.abs() < CLOCK_CHANGE_THRESHOLD_SECONDS {
+            return None;
+        }
+
+        // Remaining time according to the monotonic clock; keep at least one
+        // second so this tick still completes the phase normally
+        let monotonic_elapsed =
+            u32::try_from(now_instant.duration_since(tracker.anchor).as_secs()).unwrap_or(u32::MAX);
+        let remaining = tracker
+            .anchor_remaining
+            .saturating_sub(monotonic_elapsed)
+            .max(1);
+
+        println!(
+            "⏰ [CycleOrchestrator] Clock changed by {}s, remaining recomputed: {} -> {}",
+            divergence_seconds, self.state.remaining_seconds, remaining
+        );
+
+        self.state.remaining_seconds = remaining;
+        if let Some(started_at) = self.state.started_at {
+            self.state.started_at =
+                Some(started_at + chrono::Duration::seconds(divergence_seconds));
+        }
+
+        self.clock_diagnostics.last_divergence_seconds = Some(divergence_seconds);
+        self.clock_diagnostics.last_detected_at = Some(now_wall);
+        self.clock_diagnostics.detections += 1;
+
+        Some(CycleEvent::ClockChanged {
+            divergence_seconds,
+            remaining,
+        })
+    }
+
+    /// When the current phase will end, or `None` when idle.
+    /// A paused phase has no end time until it is resumed.
+    pub fn get_phase_end_time(&self) -> Option<PhaseEndTime> {
+        if self.state.phase == CyclePhase::Idle {
+            return None;
+        }
+
+        let paused = !self.state.is_running;
+        let ends_at = (!paused)
+            .then(|| Utc::now() + chrono::Duration::seconds(self.countdown_remaining() as i64));
+
+        Some(PhaseEndTime {
+            phase: self.state.phase.clone(),
+            ends_at,
+            paused,
+        })
+    }
+
+    /// Snapshot of the current phase, saved so `restore` can continue it
+    /// after a restart
+    pub fn saved_state(&self) -> SavedCycleState {
+        let remaining_seconds = if self.state.is_running {
+            self.countdown_remaining()
+        } else {
+            self.state.remaining_seconds
+        };
+
+        // Measured from `started_at`, so time spent paused moves the end back
+        let elapsed = self.state.started_at.map_or(0, |started_at| {
+            (Utc::now() - started_at).num_seconds().max(0) as u32
+        });
+
+        SavedCycleState {
+            phase: self.state.phase.to_string(),
+            remaining_seconds,
+            phase_seconds: elapsed.saturating_add(remaining_seconds),
+            cycle_count: self.state.cycle_count,
+            is_running: self.state.is_running,
+            session_id: self.state.session_id.clone(),
+            started_at: self.state.started_at,
+            within_work_hours: self.state.within_work_hours,
+            is_sprint: self.state.is_sprint,
+            is_wind_down: self.state.is_wind_down,
+        }
+    }
+
+    /// Continue a phase saved before a restart. A running phase counts the
+    /// time the app was closed, recomputed from `started_at`; one whose time
+    /// ran out meanwhile ends as completed instead of resuming below zero.
+    /// A paused phase stays paused with the time it had left.
+    pub fn restore(&mut self, saved: &SavedCycleState, now: DateTime<Utc>) -> Vec<CycleEvent> {
+        let phase = match saved.phase.parse::<CyclePhase>() {
+            Ok(phase) => phase,
+            Err(e) => {
+                eprintln!("⚠️ [CycleOrchestrator] Not restoring saved state: {}", e);
+                return vec![];
+            }
+        };
+
+        self.state.cycle_count = saved.cycle_count;
+
+        if phase == CyclePhase::Idle || saved.session_id.is_none() {
+            return vec![];
+        }
+
+        let remaining_seconds = match (saved.is_running, saved.started_at) {
+            (true, Some(started_at)) => {
+                let elapsed = (now - started_at).num_seconds().max(0);
+                (saved.phase_seconds as i64 - elapsed).max(0) as u32
+            }
+            _ => saved.remaining_seconds,
+        };
+
+        self.state.phase = phase;
+        self.state.remaining_seconds = remaining_seconds;
+        self.state.is_running = saved.is_running;
+        self.state.session_id = saved.session_id.clone();
+        self.state.started_at = saved.started_at;
+        self.state.within_work_hours = saved.within_work_hours;
+        self.state.is_sprint = saved.is_sprint;
+        self.state.is_wind_down = saved.is_wind_down;
+
+        if remaining_seconds == 0 {
+            let events = self.finish_session(true).unwrap_or_default();
+            self.record_events(&events);
+            return events;
+        }
+
+        if self.state.is_running {
+            self.start_countdown();
+        }
+        vec![]
+    }
+
+    /// Get the last detected clock change
+    pub fn get_clock_diagnostics(&self) -> ClockDiagnostics {
+        self.clock_diagnostics.clone()
+    }
+
     /// Reset the cycle counter (useful after a long break)
     pub fn reset_cycle_count(&mut self) {
         self.state.cycle_count = 0;
     }
 
+    /// Progress toward the next long break, one entry per focus session in the block.
+    ///
+    /// Uses the same rule as `start_break`: once `cycle_count` reaches a multiple of
+    /// `cycles_per_long_break`, every dot is filled until the next focus completes.
+    pub fn get_cycle_dots(&self) -> Vec<bool> {
+        let total = self.config.cycles_per_long_break;
+        if total == 0 {
+            return vec![];
+        }
+
+        let filled = match self.state.cycle_count % total {
+            0 if self.state.cycle_count > 0 => total,
+            remainder => remainder,
+        };
+
+        (0..total).map(|index| index < filled).collect()
+    }
+
     /// Get work schedule information for UI display
     pub fn get_work_schedule_info(&self) -> Option<WorkScheduleInfo> {
         if let Some(ref schedule) = self.config.work_schedule {
@@ -441,3 +1417,297 @@ pub struct WorkScheduleInfo {
     pub timezone: String,
     pub is_within_hours: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn orchestrator() -> CycleOrchestrator {
+        CycleOrchestrator::new(CycleConfig::from_user_settings(
+            UserSettings::default(),
+            None,
+        ))
+    }
+
+    fn phase_ended(events: &[CycleEvent]) -> bool {
+        events.iter().any(|event| {
+            matches!(
+                event,
+                CycleEvent::PhaseEnded {
+                    phase: CyclePhase::Focus,
+                    completed: true,
+                }
+            )
+        })
+    }
+
+    #[test]
+    fn test_tick_past_planned_duration_ends_phase() {
+        let mut orchestrator = orchestrator();
+        orchestrator.start_focus_sprint(1).unwrap();
+
+        // Pretend the one minute sprint started two minutes ago
+        let tracker = orchestrator.clock_tracker.as_mut().unwrap();
+        tracker.anchor = Instant::now()
+            .checked_sub(Duration::from_secs(120))
+            .unwrap();
+
+        let events = orchestrator.tick().unwrap();
+
+        assert!(phase_ended(&events));
+        assert_eq!(orchestrator.get_state().remaining_seconds, 0);
+        assert_eq!(orchestrator.get_state().phase, CyclePhase::Idle);
+    }
+
+    #[test]
+    fn test_tick_with_wrapped_remaining_ends_phase() {
+        let mut orchestrator = orchestrator();
+        orchestrator.start_focus_sprint(1).unwrap();
+
+        // A negative remaining time that wrapped around
+        orchestrator.state.remaining_seconds = u32::MAX - 5;
+        orchestrator.clock_tracker = None;
+
+        let events = orchestrator.tick().unwrap();
+
+        assert!(phase_ended(&events));
+        assert_eq!(orchestrator.get_state().remaining_seconds, 0);
+    }
+
+    #[test]
+    fn test_next_focus_duration_applies_once() {
+        let mut orchestrator = orchestrator();
+        let configured = orchestrator.get_config().focus_duration;
+
+        assert!(orchestrator.set_next_focus_duration(Some(0)).is_err());
+        orchestrator.set_next_focus_duration(Some(50)).unwrap();
+
+        orchestrator.start_focus_session().unwrap();
+        assert_eq!(orchestrator.get_state().remaining_seconds, 50 * 60);
+
+        orchestrator.end_session(false).unwrap();
+        orchestrator.start_focus_session().unwrap();
+        assert_eq!(orchestrator.get_state().remaining_seconds, configured);
+    }
+
+    #[test]
+    fn test_wind_down_suggested_near_end_of_work_hours() {
+        let schedule = WorkSchedule {
+            use_work_schedule: true,
+            work_start_time: Some("09:00".to_string()),
+            work_end_time: Some("18:00".to_string()),
+            ..WorkSchedule::default()
+        };
+        let settings = UserSettings {
+            winddown_minutes: Some(15),
+            ..UserSettings::default()
+        };
+        let mut orchestrator =
+            CycleOrchestrator::new(CycleConfig::from_user_settings(settings, Some(schedule)));
+        let at = |time: &str| {
+            chrono::NaiveDate::from_ymd_opt(2024, 3, 4)
+                .unwrap()
+                .and_time(NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+                .and_local_timezone(Local)
+                .unwrap()
+        };
+
+        // 25 minute focus plus 15 minute wind-down still fits before 18:00
+        assert!(orchestrator.check_wind_down(at("17:00")).is_none());
+        assert!(matches!(
+            orchestrator.check_wind_down(at("17:30")),
+            Some(CycleEvent::WindDownSuggested {
+                minutes_until_work_end: 30,
+                duration: 900,
+            })
+        ));
+        assert!(orchestrator.check_wind_down(at("18:30")).is_none());
+
+        orchestrator.start_wind_down().unwrap();
+        let state = orchestrator.get_state();
+        assert!(state.is_wind_down);
+        assert_eq!(state.phase, CyclePhase::LongBreak);
+        assert_eq!(state.remaining_seconds, 900);
+
+        orchestrator.end_session(true).unwrap();
+        assert!(!orchestrator.get_state().is_wind_down);
+        assert_eq!(orchestrator.get_state().cycle_count, 0);
+    }
+
+    #[test]
+    fn test_outside_work_hours_error_keeps_next_work_start() {
+        let next_work_start = "2024-03-05T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let error: String = CycleError::OutsideWorkHours {
+            next_work_start: Some(next_work_start),
+        }
+        .into();
+
+        let parsed: serde_json::Value = serde_json::from_str(&error).unwrap();
+        assert_eq!(parsed["type"], "outside_work_hours");
+        assert_eq!(parsed["next_work_start"], "2024-03-05T09:00:00Z");
+
+        // Other errors stay plain messages
+        let invalid: String = CycleError::InvalidState {
+            message: "Not now".to_string(),
+        }
+        .into();
+        assert_eq!(invalid, "Not now");
+    }
+
+    #[test]
+    fn test_skip_break_starts_next_focus() {
+        let mut orchestrator = orchestrator();
+        assert!(orchestrator.skip_break().is_err());
+
+        orchestrator.start_focus_session().unwrap();
+        orchestrator.end_session(true).unwrap();
+        orchestrator.start_break(false).unwrap();
+        let break_session = orchestrator.get_state().session_id;
+
+        let events = orchestrator.skip_break().unwrap();
+
+        assert!(matches!(
+            events.as_slice(),
+            [
+                CycleEvent::PhaseEnded {
+                    phase: CyclePhase::ShortBreak,
+                    completed: false,
+                },
+                CycleEvent::PhaseStarted {
+                    phase: CyclePhase::Focus,
+                    cycle_count: 1,
+                    ..
+                },
+            ]
+        ));
+        let state = orchestrator.get_state();
+        assert_eq!(state.phase, CyclePhase::Focus);
+        assert_eq!(state.cycle_count, 1);
+        assert!(state.is_running);
+        assert_ne!(state.session_id, break_session);
+    }
+
+    #[test]
+    fn test_pause_and_resume_break() {
+        let mut orchestrator = orchestrator();
+        orchestrator.start_break(false).unwrap();
+        let duration = orchestrator.get_state().remaining_seconds;
+
+        let events = orchestrator.pause().unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [CycleEvent::Paused {
+                phase: CyclePhase::ShortBreak,
+                ..
+            }]
+        ));
+        let paused = orchestrator.get_state();
+        assert!(!paused.is_running);
+        assert!(paused.remaining_seconds <= duration);
+
+        // A paused break doesn't count down
+        let events = orchestrator.tick().unwrap();
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, CycleEvent::Tick { .. })));
+        assert_eq!(
+            orchestrator.get_state().remaining_seconds,
+            paused.remaining_seconds
+        );
+
+        orchestrator.resume().unwrap();
+        let resumed = orchestrator.get_state();
+        assert!(resumed.is_running);
+        assert_eq!(resumed.phase, CyclePhase::ShortBreak);
+        assert_eq!(resumed.remaining_seconds, paused.remaining_seconds);
+    }
+
+    #[test]
+    fn test_snooze_only_covers_its_own_pause() {
+        let mut orchestrator = orchestrator();
+        orchestrator.start_break(false).unwrap();
+        let session_id = orchestrator.get_state().session_id.unwrap();
+
+        orchestrator.pause().unwrap();
+        orchestrator.mark_paused_by_snooze();
+        assert!(orchestrator.is_snoozed(&session_id));
+
+        // Resuming by hand ends the snooze; a later pause is the user's own
+        orchestrator.resume().unwrap();
+        orchestrator.pause().unwrap();
+        assert!(!orchestrator.is_snoozed(&session_id));
+    }
+
+    #[test]
+    fn test_strict_break_cannot_be_paused() {
+        let settings = UserSettings {
+            strict_mode: true,
+            ..UserSettings::default()
+        };
+        let mut orchestrator =
+            CycleOrchestrator::new(CycleConfig::from_user_settings(settings, None));
+
+        orchestrator.start_focus_session().unwrap();
+        orchestrator.pause().unwrap();
+        orchestrator.end_session(false).unwrap();
+
+        orchestrator.start_break(true).unwrap();
+        assert!(orchestrator.pause().is_err());
+        assert!(orchestrator.get_state().is_running);
+    }
+
+    #[test]
+    fn test_restore_continues_running_phase_from_started_at() {
+        let mut saved = orchestrator();
+        saved.start_focus_sprint(25).unwrap();
+        let mut snapshot = saved.saved_state();
+
+        // Pretend the app was closed ten minutes into the sprint
+        let started_at = Utc::now() - chrono::Duration::minutes(10);
+        snapshot.started_at = Some(started_at);
+        snapshot.phase_seconds = 25 * 60;
+
+        let mut restored = orchestrator();
+        let events = restored.restore(&snapshot, Utc::now());
+        let state = restored.get_state();
+
+        assert!(events.is_empty());
+        assert_eq!(state.phase, CyclePhase::Focus);
+        assert!(state.is_running && state.is_sprint);
+        assert_eq!(state.session_id, snapshot.session_id);
+        assert!((899..=900).contains(&state.remaining_seconds));
+    }
+
+    #[test]
+    fn test_restore_ends_phase_that_ran_out() {
+        let mut saved = orchestrator();
+        saved.start_focus_session().unwrap();
+        let mut snapshot = saved.saved_state();
+        snapshot.started_at = Some(Utc::now() - chrono::Duration::hours(2));
+
+        let mut restored = orchestrator();
+        let events = restored.restore(&snapshot, Utc::now());
+
+        assert!(phase_ended(&events));
+        assert_eq!(restored.get_state().phase, CyclePhase::Idle);
+        assert_eq!(restored.get_state().cycle_count, 1);
+    }
+
+    #[test]
+    fn test_restore_keeps_paused_phase_paused() {
+        let mut saved = orchestrator();
+        saved.start_focus_session().unwrap();
+        saved.pause().unwrap();
+        let mut snapshot = saved.saved_state();
+        snapshot.started_at = Some(Utc::now() - chrono::Duration::hours(2));
+
+        let mut restored = orchestrator();
+        restored.restore(&snapshot, Utc::now());
+        let state = restored.get_state();
+
+        assert_eq!(state.phase, CyclePhase::Focus);
+        assert!(!state.is_running);
+        assert_eq!(state.remaining_seconds, snapshot.remaining_seconds);
+    }
+}
@@ -1,20 +1,92 @@
 use crate::database::migrations::MigrationManager;
-use crate::database::models::{Session, UserSettings};
+use crate::database::models::{
+    BypassAttempt, CyclePreset, DailyGoal, DashboardSnapshot, DayRecord, PersonalRecords,
+    SavedCycleState, Session, SessionAnomaly, SessionAnomalyKind, SessionAnomalyReport,
+    SessionHistoryFilter, StoredHotkeyConfig, StrictModeInterval, StrictModeUptime, UserSettings,
+};
 use crate::database::{DatabaseError, DatabaseResult};
-use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, OpenFlags};
-use std::path::PathBuf;
+use crate::performance::PerformanceCounters;
+use crate::util::parse_stored_timestamp;
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{params, Connection, ErrorCode, OpenFlags, OptionalExtension, Transaction};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::RuntimeFlavor;
+
+/// How the connection copes with another connection holding the database lock
+#[derive(Debug, Clone)]
+pub struct BusyRetryOptions {
+    /// How long SQLite itself waits on a lock before reporting `SQLITE_BUSY`
+    pub busy_timeout: Duration,
+    /// Total attempts for a transaction that keeps hitting a busy or locked database
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after each further attempt
+    pub initial_backoff: Duration,
+}
+
+impl Default for BusyRetryOptions {
+    fn default() -> Self {
+        Self {
+            busy_timeout: Duration::from_millis(2000),
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(50),
+        }
+    }
+}
+
+/// Whether an error is a transient lock conflict worth retrying
+fn is_busy(error: &DatabaseError) -> bool {
+    matches!(
+        error,
+        DatabaseError::Sqlite(rusqlite::Error::SqliteFailure(failure, _))
+            if matches!(failure.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Wait out a retry backoff without stalling the async runtime.
+///
+/// On a multi-threaded tokio worker the wait is handed to `block_in_place`
+/// so the worker's other tasks move elsewhere meanwhile. A current-thread
+/// runtime has no other thread to hand them to, so there is no retry there
+/// and `false` is returned.
+fn wait_before_retry(backoff: Duration) -> bool {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| std::thread::sleep(backoff));
+            true
+        }
+        Ok(_) => false,
+        Err(_) => {
+            std::thread::sleep(backoff);
+            true
+        }
+    }
+}
 
 /// Database connection manager with connection pooling
 pub struct DatabaseManager {
     connection: Arc<Mutex<Connection>>,
-    db_path: PathBuf,
+    db_path: Mutex<PathBuf>,
+    retry: BusyRetryOptions,
+    counters: Arc<PerformanceCounters>,
 }
 
+/// File name of the database inside the data directory
+pub const DATABASE_FILE_NAME: &str = "pausa.db";
+
+/// Directory holding configuration backups inside the data directory
+const BACKUPS_DIR_NAME: &str = "backups";
+
 impl DatabaseManager {
     /// Create a new database manager
     pub fn new(db_path: PathBuf) -> DatabaseResult<Self> {
+        Self::with_retry_options(db_path, BusyRetryOptions::default())
+    }
+
+    /// Create a new database manager with custom lock handling
+    pub fn with_retry_options(db_path: PathBuf, retry: BusyRetryOptions) -> DatabaseResult<Self> {
         // Ensure the parent directory exists
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
@@ -22,21 +94,13 @@ impl DatabaseManager {
             })?;
         }
 
-        // Open connection with appropriate flags
-        let connection = Connection::open_with_flags(
-            &db_path,
-            OpenFlags::SQLITE_OPEN_READ_WRITE
-                | OpenFlags::SQLITE_OPEN_CREATE
-                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
-        )
-        .map_err(DatabaseError::Sqlite)?;
-
-        // Configure SQLite settings for optimal performance
-        Self::configure_connection(&connection)?;
+        let connection = Self::open_connection(&db_path, &retry)?;
 
         let manager = DatabaseManager {
             connection: Arc::new(Mutex::new(connection)),
-            db_path,
+            db_path: Mutex::new(db_path),
+            retry,
+            counters: Arc::new(PerformanceCounters::new()),
         };
 
         // Initialize or migrate database
@@ -50,7 +114,16 @@ impl DatabaseManager {
         Arc::clone(&self.connection)
     }
 
-    /// Execute a function with the database connection
+    /// Counters this manager records its writes and busy retries in
+    pub fn performance_counters(&self) -> Arc<PerformanceCounters> {
+        Arc::clone(&self.counters)
+    }
+
+    /// Execute a function with the database connection.
+    ///
+    /// `f` runs once: a busy or locked database is only waited on for the
+    /// connection's busy timeout. Work that should be retried belongs in
+    /// [`Self::with_transaction`].
     pub fn with_connection<F, R>(&self, f: F) -> DatabaseResult<R>
     where
         F: FnOnce(&Connection) -> DatabaseResult<R>,
@@ -59,7 +132,70 @@ impl DatabaseManager {
             DatabaseError::ConnectionPool(format!("Failed to acquire connection lock: {}", e))
         })?;
 
-        f(&*conn)
+        let changes_before = conn.total_changes();
+        let result = f(&conn);
+        if result.is_ok() && conn.total_changes() > changes_before {
+            self.counters.record_db_write();
+        }
+        result
+    }
+
+    /// Execute a function inside a transaction, committing when it succeeds.
+    ///
+    /// A failed attempt is rolled back, so if the database is busy or locked
+    /// by another connection `f` is run again with exponential backoff, up to
+    /// `max_attempts` times; the last error is returned once retries run out.
+    pub fn with_transaction<F, R>(&self, mut f: F) -> DatabaseResult<R>
+    where
+        F: FnMut(&Transaction) -> DatabaseResult<R>,
+    {
+        let mut backoff = self.retry.initial_backoff;
+        let mut attempt = 1;
+
+        loop {
+            let result = self.with_connection(|conn| {
+                let tx = conn
+                    .unchecked_transaction()
+                    .map_err(DatabaseError::Sqlite)?;
+                let value = f(&tx)?;
+                tx.commit().map_err(DatabaseError::Sqlite)?;
+                Ok(value)
+            });
+
+            match result {
+                Err(error)
+                    if is_busy(&error)
+                        && attempt < self.retry.max_attempts
+                        && wait_before_retry(backoff) =>
+                {
+                    self.counters.record_db_retry();
+                    eprintln!(
+                        "⚠️ [Database] Database busy (attempt {}/{}), retried after {:?}",
+                        attempt, self.retry.max_attempts, backoff
+                    );
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Open and configure a connection to the database file at `db_path`
+    fn open_connection(db_path: &Path, retry: &BusyRetryOptions) -> DatabaseResult<Connection> {
+        // Open connection with appropriate flags
+        let connection = Connection::open_with_flags(
+            db_path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE
+                | OpenFlags::SQLITE_OPEN_CREATE
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Configure SQLite settings for optimal performance
+        Self::configure_connection(&connection, retry)?;
+
+        Ok(connection)
     }
 
     /// Initialize database or run migrations
@@ -72,6 +208,13 @@ impl DatabaseManager {
                 MigrationManager::migrate_to_current(conn)?;
             }
 
+            // Restore tables and columns lost to an interrupted migration
+            // before validating
+            let repaired = MigrationManager::ensure_schema(conn)?;
+            if !repaired.is_empty() {
+                println!("⚠️ [Database] Repaired schema: {}", repaired.join(", "));
+            }
+
             // Validate database integrity
             MigrationManager::validate_database(conn)?;
 
@@ -80,9 +223,13 @@ impl DatabaseManager {
     }
 
     /// Configure SQLite connection settings
-    fn configure_connection(conn: &Connection) -> DatabaseResult<()> {
+    fn configure_connection(conn: &Connection, retry: &BusyRetryOptions) -> DatabaseResult<()> {
         println!("Configuring database connection...");
 
+        // Wait on locks held by other connections before giving up with SQLITE_BUSY
+        conn.busy_timeout(retry.busy_timeout)
+            .map_err(DatabaseError::Sqlite)?;
+
         // Enable foreign key constraints
         conn.execute("PRAGMA foreign_keys = ON", [])
             .map_err(DatabaseError::Sqlite)?;
@@ -114,14 +261,109 @@ impl DatabaseManager {
     }
 
     /// Get database file path
-    pub fn get_db_path(&self) -> &PathBuf {
-        &self.db_path
+    pub fn get_db_path(&self) -> PathBuf {
+        self.db_path
+            .lock()
+            .map(|path| path.clone())
+            .unwrap_or_default()
+    }
+
+    /// Directory holding the database and its configuration backups
+    pub fn data_directory(&self) -> PathBuf {
+        self.get_db_path()
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
     }
 
     /// Create a backup of the database
     pub fn backup(&self, backup_path: &str) -> DatabaseResult<()> {
-        let source_path = self.db_path.to_string_lossy();
-        MigrationManager::backup_database(&source_path, backup_path)
+        let source_path = self.get_db_path();
+        MigrationManager::backup_database(&source_path.to_string_lossy(), backup_path)
+    }
+
+    /// Move the database and its configuration backups into `new_dir`.
+    ///
+    /// The connection lock is held for the whole move so no writes land in
+    /// the old file midway. The database is checkpointed, copied next to its
+    /// destination under a temporary name, verified and only then renamed
+    /// into place. `record` is called with the new directory once it opens,
+    /// before the live connection is swapped, so callers can persist the
+    /// location; if any step fails, the copies and any directories created
+    /// for them are removed and the old connection stays in use. The old
+    /// files are left in place as a fallback.
+    pub fn relocate<F>(&self, new_dir: &Path, record: F) -> DatabaseResult<PathBuf>
+    where
+        F: FnOnce(&Path) -> DatabaseResult<()>,
+    {
+        let mut conn = self.connection.lock().map_err(|e| {
+            DatabaseError::ConnectionPool(format!("Failed to acquire connection lock: {}", e))
+        })?;
+
+        let old_path = self.get_db_path();
+        let old_dir = old_path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let new_path = new_dir.join(DATABASE_FILE_NAME);
+
+        let created_dirs =
+            prepare_target_directory(&old_dir, new_dir, data_size(&old_path, &old_dir))?;
+
+        // Flush the write-ahead log so the main file holds every change
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(DatabaseError::Sqlite)?;
+
+        let mut copied = Vec::new();
+        let result = copy_data_files(&old_path, &old_dir, new_dir, &mut copied)
+            .and_then(|_| Self::open_connection(&new_path, &self.retry))
+            .and_then(|new_conn| record(new_dir).map(|_| new_conn));
+
+        let new_conn = match result {
+            Ok(new_conn) => new_conn,
+            Err(e) => {
+                println!(
+                    "❌ [Database] Moving data directory failed, rolling back: {}",
+                    e
+                );
+                for suffix in ["-wal", "-shm"] {
+                    let _ =
+                        fs::remove_file(new_dir.join(format!("{}{}", DATABASE_FILE_NAME, suffix)));
+                }
+                for path in copied.iter().rev() {
+                    let _ = if path.is_dir() {
+                        fs::remove_dir(path)
+                    } else {
+                        fs::remove_file(path)
+                    };
+                }
+                remove_created_dirs(&created_dirs);
+                return Err(e);
+            }
+        };
+
+        // Dropping the old connection closes it
+        *conn = new_conn;
+        if let Ok(mut db_path) = self.db_path.lock() {
+            *db_path = new_path.clone();
+        }
+
+        println!(
+            "✅ [Database] Data directory moved to {}",
+            new_dir.display()
+        );
+        Ok(new_path)
+    }
+
+    /// Create any required table or column that is missing and return what
+    /// was added
+    pub fn ensure_schema(&self) -> DatabaseResult<Vec<String>> {
+        self.with_connection(MigrationManager::ensure_schema)
+    }
+
+    /// Flush the write-ahead log into the main database file
+    pub fn checkpoint(&self) -> DatabaseResult<()> {
+        self.with_connection(|conn| {
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+                .map_err(DatabaseError::Sqlite)
+        })
     }
 
     /// Get database statistics
@@ -171,13 +413,8 @@ impl DatabaseManager {
             )?;
 
             let query = if has_new_columns {
-                "SELECT id, focus_duration, short_break_duration, long_break_duration, 
-                    cycles_per_long_break, cycles_per_long_break_v2,
-                    pre_alert_seconds, strict_mode, pin_hash, 
-                    user_name, emergency_key_combination,
-                    created_at, updated_at 
-                 FROM user_settings 
-                 WHERE id = 1"
+                // Newer optional columns are read by name with defaults in from_row
+                "SELECT * FROM user_settings WHERE id = 1"
             } else {
                 "SELECT id, focus_duration, short_break_duration, long_break_duration, 
                     cycles_per_long_break, cycles_per_long_break as cycles_per_long_break_v2,
@@ -224,7 +461,7 @@ impl DatabaseManager {
 
     /// Save user settings
     pub fn save_user_settings(&self, settings: &UserSettings) -> DatabaseResult<()> {
-        self.with_connection(|conn| {
+        self.with_transaction(|conn| {
             // Check if new columns exist
             let has_new_columns = self.check_columns_exist(
                 conn,
@@ -242,8 +479,40 @@ impl DatabaseManager {
                      (id, focus_duration, short_break_duration, long_break_duration, 
                       cycles_per_long_break, cycles_per_long_break_v2, pre_alert_seconds, 
                       strict_mode, pin_hash, user_name, emergency_key_combination,
+                      break_transition_seconds,
+                      strict_focus_show_widget,
+                      focus_widget_pause_behavior,
+                      work_hours_enforcement,
+                      tray_icon_style,
+                      show_break_transition,
+                      strict_require_ack,
+                      overlay_escape_action,
+                      theme,
+                      tray_click_action,
+                      tick_interval_ms,
+                      power_save,
+                      break_message,
+                      phase_colors,
+                      post_long_break_focus_duration,
+                      soft_overlay_dismiss_action,
+                      break_snooze_minutes,
+                      notifications_muted_until,
+                      phase_command_hooks,
+                      weekly_focus_budget_minutes,
+                      max_break_overrun_minutes,
+                      scheduled_focus_at,
+                      session_save_interval_seconds,
+                      break_ratio_mode,
+                      break_ratio,
+                      break_ratio_min_duration,
+                      break_ratio_max_duration,
+                      prevent_display_sleep_during_focus,
+                      strict_disable_delay_seconds,
+                      strict_disable_at,
+                      winddown_minutes,
+                      sound_config,
                       created_at, updated_at)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36, ?37, ?38, ?39, ?40, ?41, ?42, ?43, ?44, ?45)",
                     params![
                         settings.id,
                         settings.focus_duration,
@@ -256,6 +525,38 @@ impl DatabaseManager {
                         settings.pin_hash,
                         settings.user_name,
                         settings.emergency_key_combination,
+                        settings.break_transition_seconds,
+                        settings.strict_focus_show_widget,
+                        settings.focus_widget_pause_behavior.to_string(),
+                        settings.work_hours_enforcement.to_string(),
+                        settings.tray_icon_style.to_string(),
+                        settings.show_break_transition,
+                        settings.strict_require_ack,
+                        settings.overlay_escape_action.to_string(),
+                        settings.theme.to_string(),
+                        settings.tray_click_action.to_string(),
+                        settings.tick_interval_ms,
+                        settings.power_save,
+                        settings.break_message,
+                        serde_json::to_string(&settings.phase_colors).ok(),
+                        settings.post_long_break_focus_duration,
+                        settings.soft_overlay_dismiss_action.to_string(),
+                        settings.break_snooze_minutes,
+                        settings.notifications_muted_until,
+                        serde_json::to_string(&settings.phase_command_hooks).ok(),
+                        settings.weekly_focus_budget_minutes,
+                        settings.max_break_overrun_minutes,
+                        settings.scheduled_focus_at,
+                        settings.session_save_interval_seconds,
+                        settings.break_ratio_mode,
+                        settings.break_ratio,
+                        settings.break_ratio_min_duration,
+                        settings.break_ratio_max_duration,
+                        settings.prevent_display_sleep_during_focus,
+                        settings.strict_disable_delay_seconds,
+                        settings.strict_disable_at,
+                        settings.winddown_minutes,
+                        serde_json::to_string(&settings.sound_config).ok(),
                         settings.created_at,
                         settings.updated_at,
                     ],
@@ -291,23 +592,29 @@ impl DatabaseManager {
 
     /// Session Management Methods
 
-    /// Create a new session
+    /// Create a new session; a session whose id already exists is left untouched
     pub fn create_session(&self, session: &Session) -> DatabaseResult<()> {
-        self.with_connection(|conn| {
+        self.with_transaction(|conn| {
             // Check if new columns exist
             let has_new_columns = self.check_columns_exist(
                 conn,
                 "sessions",
-                &["within_work_hours", "cycle_number", "is_long_break"],
+                &[
+                    "within_work_hours",
+                    "cycle_number",
+                    "is_long_break",
+                    "tag",
+                    "is_sprint",
+                ],
             )?;
 
             if has_new_columns {
                 conn.execute(
-                    "INSERT INTO sessions 
+                    "INSERT OR IGNORE INTO sessions 
                      (id, session_type, start_time, end_time, planned_duration, 
                       actual_duration, strict_mode, completed, notes, created_at,
-                      within_work_hours, cycle_number, is_long_break)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                      within_work_hours, cycle_number, is_long_break, tag, is_sprint)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
                     params![
                         session.id,
                         session.session_type.to_string(),
@@ -322,13 +629,15 @@ impl DatabaseManager {
                         session.within_work_hours,
                         session.cycle_number,
                         session.is_long_break,
+                        session.tag,
+                        session.is_sprint,
                     ],
                 )
                 .map_err(DatabaseError::Sqlite)?;
             } else {
                 // Fallback for older database schema
                 conn.execute(
-                    "INSERT INTO sessions 
+                    "INSERT OR IGNORE INTO sessions 
                      (id, session_type, start_time, end_time, planned_duration, 
                       actual_duration, strict_mode, completed, notes, created_at)
                      VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
@@ -354,12 +663,18 @@ impl DatabaseManager {
 
     /// Update an existing session
     pub fn update_session(&self, session: &Session) -> DatabaseResult<()> {
-        self.with_connection(|conn| {
+        self.with_transaction(|conn| {
             // Check if new columns exist
             let has_new_columns = self.check_columns_exist(
                 conn,
                 "sessions",
-                &["within_work_hours", "cycle_number", "is_long_break"],
+                &[
+                    "within_work_hours",
+                    "cycle_number",
+                    "is_long_break",
+                    "tag",
+                    "is_sprint",
+                ],
             )?;
 
             if has_new_columns {
@@ -368,7 +683,8 @@ impl DatabaseManager {
                      SET session_type = ?2, start_time = ?3, end_time = ?4, 
                          planned_duration = ?5, actual_duration = ?6, strict_mode = ?7, 
                          completed = ?8, notes = ?9, within_work_hours = ?10,
-                         cycle_number = ?11, is_long_break = ?12
+                         cycle_number = ?11, is_long_break = ?12, tag = ?13,
+                         is_sprint = ?14
                      WHERE id = ?1",
                     params![
                         session.id,
@@ -383,6 +699,8 @@ impl DatabaseManager {
                         session.within_work_hours,
                         session.cycle_number,
                         session.is_long_break,
+                        session.tag,
+                        session.is_sprint,
                     ],
                 )
                 .map_err(DatabaseError::Sqlite)?;
@@ -418,8 +736,9 @@ impl DatabaseManager {
         self.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
-                    "SELECT id, session_type, start_time, end_time, planned_duration, 
-                        actual_duration, strict_mode, completed, notes, created_at
+                    "SELECT id, session_type, start_time, end_time, planned_duration,
+                        actual_duration, strict_mode, completed, notes, created_at,
+                        within_work_hours, cycle_number, is_long_break, tag, is_sprint
                  FROM sessions 
                  WHERE completed = FALSE AND end_time IS NULL
                  ORDER BY start_time DESC 
@@ -437,47 +756,25 @@ impl DatabaseManager {
         })
     }
 
-    /// Get session by ID
-    pub fn get_session(&self, session_id: &str) -> DatabaseResult<Option<Session>> {
-        self.with_connection(|conn| {
-            let mut stmt = conn
-                .prepare(
-                    "SELECT id, session_type, start_time, end_time, planned_duration, 
-                        actual_duration, strict_mode, completed, notes, created_at
-                 FROM sessions 
-                 WHERE id = ?1",
-                )
-                .map_err(DatabaseError::Sqlite)?;
-
-            let result = stmt.query_row([session_id], |row| Session::from_row(row));
-
-            match result {
-                Ok(session) => Ok(Some(session)),
-                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                Err(e) => Err(DatabaseError::Sqlite(e)),
-            }
-        })
-    }
-
-    /// Get sessions within a date range
-    pub fn get_sessions_in_range(
+    /// Get all sessions left open (no end_time), optionally excluding the live one
+    pub fn get_incomplete_sessions(
         &self,
-        start_date: DateTime<Utc>,
-        end_date: DateTime<Utc>,
+        exclude_session_id: Option<&str>,
     ) -> DatabaseResult<Vec<Session>> {
         self.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
-                    "SELECT id, session_type, start_time, end_time, planned_duration, 
-                        actual_duration, strict_mode, completed, notes, created_at
-                 FROM sessions 
-                 WHERE start_time >= ?1 AND start_time <= ?2
+                    "SELECT id, session_type, start_time, end_time, planned_duration,
+                        actual_duration, strict_mode, completed, notes, created_at,
+                        within_work_hours, cycle_number, is_long_break, tag, is_sprint
+                 FROM sessions
+                 WHERE end_time IS NULL AND (?1 IS NULL OR id != ?1)
                  ORDER BY start_time ASC",
                 )
                 .map_err(DatabaseError::Sqlite)?;
 
             let session_iter = stmt
-                .query_map([start_date, end_date], |row| Session::from_row(row))
+                .query_map([exclude_session_id], |row| Session::from_row(row))
                 .map_err(DatabaseError::Sqlite)?;
 
             let mut sessions = Vec::new();
@@ -489,148 +786,1227 @@ impl DatabaseManager {
         })
     }
 
-    /// Get session statistics for the last N days
-    pub fn get_session_stats(
+    /// Close dangling sessions left open (e.g. after a crash).
+    ///
+    /// Each session gets `end_time = start_time + planned_duration`, capped at now,
+    /// and is marked as not completed. Sessions with saved progress end where
+    /// that progress left off instead. Returns the number of sessions closed.
+    pub fn close_incomplete_sessions(
         &self,
-        days: u32,
-    ) -> DatabaseResult<Vec<crate::database::models::SessionStats>> {
-        use chrono::Duration;
+        exclude_session_id: Option<&str>,
+    ) -> DatabaseResult<usize> {
+        let sessions = self.get_incomplete_sessions(exclude_session_id)?;
+
+        self.with_transaction(|tx| {
+            let now = Utc::now();
+            let mut closed = 0;
+
+            for session in &sessions {
+                let duration = session.actual_duration.unwrap_or(session.planned_duration);
+                let planned_end = session.start_time + chrono::Duration::seconds(duration as i64);
+                let end_time = planned_end.min(now);
+                let actual_duration = (end_time - session.start_time).num_seconds().max(0) as i32;
+
+                closed += tx
+                    .execute(
+                        "UPDATE sessions
+                         SET end_time = ?2, actual_duration = ?3, completed = FALSE
+                         WHERE id = ?1 AND end_time IS NULL",
+                        params![session.id, end_time, actual_duration],
+                    )
+                    .map_err(DatabaseError::Sqlite)?;
+            }
 
-        self.with_connection(|conn| {
-            let end_date = Utc::now();
-            let start_date = end_date - Duration::days(days as i64);
+            Ok(closed)
+        })
+    }
 
-            // First, let's check what sessions exist
-            let mut check_stmt = conn
-                .prepare(
-                    "SELECT id, session_type, completed, actual_duration, start_time 
-                 FROM sessions 
-                 WHERE start_time >= ?1 AND start_time <= ?2
-                 ORDER BY start_time DESC",
+    /// Delete the sessions that started in `[start, end)` along with their
+    /// evasion attempts, bypass attempts and notification history, all in one
+    /// transaction. `exclude_session_id` (the running session) is kept.
+    /// Returns the number of sessions deleted.
+    pub fn delete_sessions_in_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        exclude_session_id: Option<&str>,
+    ) -> DatabaseResult<usize> {
+        self.with_transaction(|tx| {
+            let in_range = "SELECT id FROM sessions
+                 WHERE start_time >= ?1 AND start_time < ?2
+                   AND (?3 IS NULL OR id != ?3)";
+
+            // Rows pointing at the sessions go first so the foreign keys hold
+            for table in [
+                "evasion_attempts",
+                "bypass_attempts",
+                "notification_history",
+            ] {
+                tx.execute(
+                    &format!("DELETE FROM {} WHERE session_id IN ({})", table, in_range),
+                    params![start, end, exclude_session_id],
                 )
                 .map_err(DatabaseError::Sqlite)?;
+            }
 
-            let session_check = check_stmt
-                .query_map([start_date, end_date], |row| {
-                    Ok((
-                        row.get::<_, String>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, bool>(2)?,
-                        row.get::<_, Option<i32>>(3)?,
-                        row.get::<_, String>(4)?,
-                    ))
-                })
+            let deleted = tx
+                .execute(
+                    "DELETE FROM sessions
+                     WHERE start_time >= ?1 AND start_time < ?2
+                       AND (?3 IS NULL OR id != ?3)",
+                    params![start, end, exclude_session_id],
+                )
                 .map_err(DatabaseError::Sqlite)?;
 
-            for session in session_check {
-                match session {
-                    Ok((_id, _session_type, _completed, _actual_duration, _start_time)) => {
-                        // Session exists, continue processing
-                    }
-                    Err(e) => eprintln!("❌ [Database] Error reading session: {}", e),
-                }
-            }
+            Ok(deleted)
+        })
+    }
 
-            let mut stmt = conn
+    /// Scan every session for impossible or inconsistent rows.
+    ///
+    /// With `fix`, sessions that end before they start are collapsed to zero
+    /// length and completed sessions missing an end are marked not completed,
+    /// so stats stop counting them. Overlaps and overlong sessions are only
+    /// reported.
+    pub fn find_session_anomalies(&self, fix: bool) -> DatabaseResult<SessionAnomalyReport> {
+        self.with_transaction(|tx| {
+            let mut stmt = tx
                 .prepare(
-                    "SELECT 
-                    DATE(start_time) as date,
-                    SUM(CASE 
-                        WHEN session_type = 'focus' AND completed = 1 
-                        THEN COALESCE(actual_duration, 0) / 60 
-                        ELSE 0 
-                    END) as focus_minutes,
-                    COUNT(CASE 
-                        WHEN session_type IN ('short_break', 'long_break') AND completed = 1 
-                        THEN 1 
-                    END) as breaks_completed,
-                    COUNT(CASE 
-                        WHEN session_type = 'focus' AND completed = 1 
-                        THEN 1 
-                    END) as sessions_completed,
-                    COUNT(CASE 
-                        WHEN session_type = 'focus' 
-                        THEN (SELECT COUNT(*) FROM evasion_attempts WHERE session_id = sessions.id)
-                    END) as evasion_attempts
-                 FROM sessions 
-                 WHERE start_time >= ?1 AND start_time <= ?2
-                 GROUP BY DATE(start_time)
-                 ORDER BY date DESC",
+                    "SELECT id, CAST(start_time AS TEXT), CAST(end_time AS TEXT),
+                        planned_duration, actual_duration, completed
+                     FROM sessions",
                 )
                 .map_err(DatabaseError::Sqlite)?;
 
-            let stats_iter = stmt
-                .query_map([start_date, end_date], |row| {
-                    Ok(crate::database::models::SessionStats {
-                        date: row.get::<_, String>("date")?,
-                        focus_minutes: row.get::<_, i64>("focus_minutes")? as u32,
-                        breaks_completed: row.get::<_, i64>("breaks_completed")? as u32,
-                        sessions_completed: row.get::<_, i64>("sessions_completed")? as u32,
-                        evasion_attempts: row.get::<_, i64>("evasion_attempts")? as u32,
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(SessionIntegrityRow {
+                        id: row.get(0)?,
+                        start_time: row.get(1)?,
+                        end_time: row.get(2)?,
+                        planned_duration: row.get(3)?,
+                        actual_duration: row.get(4)?,
+                        completed: row.get::<_, Option<bool>>(5)?.unwrap_or(false),
                     })
                 })
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
                 .map_err(DatabaseError::Sqlite)?;
 
-            let mut stats = Vec::new();
-            for stat in stats_iter {
-                let stat_result = stat.map_err(DatabaseError::Sqlite)?;
+            let anomalies = detect_session_anomalies(&rows);
+            if !fix {
+                return Ok(SessionAnomalyReport {
+                    anomalies,
+                    fixed: 0,
+                });
+            }
 
-                stats.push(stat_result);
+            let mut fixed = 0;
+
+            for anomaly in anomalies.iter().filter(|anomaly| anomaly.fixable) {
+                let sql = match anomaly.kind {
+                    SessionAnomalyKind::EndBeforeStart => {
+                        "UPDATE sessions
+                         SET end_time = start_time, actual_duration = 0, completed = FALSE
+                         WHERE id = ?1"
+                    }
+                    SessionAnomalyKind::MissingField => {
+                        "UPDATE sessions SET completed = FALSE WHERE id = ?1"
+                    }
+                    _ => continue,
+                };
+                fixed += tx
+                    .execute(sql, [&anomaly.session_id])
+                    .map_err(DatabaseError::Sqlite)?;
             }
 
-            Ok(stats)
+            Ok(SessionAnomalyReport { anomalies, fixed })
         })
     }
 
-    /// Onboarding Methods
+    /// Set or clear the tag on a session
+    pub fn set_session_tag(&self, session_id: &str, tag: Option<&str>) -> DatabaseResult<bool> {
+        self.with_connection(|conn| {
+            let updated = conn
+                .execute(
+                    "UPDATE sessions SET tag = ?2 WHERE id = ?1",
+                    params![session_id, tag],
+                )
+                .map_err(DatabaseError::Sqlite)?;
 
-    /// Save onboarding completion record
-    pub fn save_onboarding_completion(
+            Ok(updated > 0)
+        })
+    }
+
+    /// Save how long a still-running session has been going, so a crash
+    /// loses at most the time since the last save. Ended sessions are left
+    /// untouched.
+    pub fn save_session_progress(
         &self,
-        version: &str,
-        config_snapshot: Option<&str>,
-    ) -> DatabaseResult<()> {
+        session_id: &str,
+        elapsed_seconds: i32,
+    ) -> DatabaseResult<bool> {
         self.with_connection(|conn| {
-            conn.execute(
-                "INSERT INTO onboarding_completion (version, config_snapshot) VALUES (?1, ?2)",
-                params![version, config_snapshot],
-            )
-            .map_err(DatabaseError::Sqlite)?;
+            let updated = conn
+                .execute(
+                    "UPDATE sessions SET actual_duration = ?2
+                     WHERE id = ?1 AND end_time IS NULL",
+                    params![session_id, elapsed_seconds],
+                )
+                .map_err(DatabaseError::Sqlite)?;
 
-            Ok(())
+            Ok(updated > 0)
         })
     }
 
-    /// Check if onboarding has been completed
-    pub fn is_onboarding_completed(&self) -> DatabaseResult<bool> {
+    /// Record how far past its planned length a break ran when the overrun
+    /// reminder fired
+    pub fn set_session_overrun(
+        &self,
+        session_id: &str,
+        overrun_seconds: u32,
+    ) -> DatabaseResult<bool> {
         self.with_connection(|conn| {
-            let count: i64 = conn
-                .query_row("SELECT COUNT(*) FROM onboarding_completion", [], |row| {
-                    row.get(0)
-                })
+            let updated = conn
+                .execute(
+                    "UPDATE sessions SET overrun_seconds = ?2 WHERE id = ?1",
+                    params![session_id, overrun_seconds],
+                )
                 .map_err(DatabaseError::Sqlite)?;
 
-            Ok(count > 0)
+            Ok(updated > 0)
         })
     }
 
-    /// Get the latest onboarding completion record
-    pub fn get_latest_onboarding_completion(
+    /// Set or clear how a session felt, rated 1 to 5
+    pub fn set_session_mood(&self, session_id: &str, mood: Option<u8>) -> DatabaseResult<bool> {
+        self.with_connection(|conn| {
+            let updated = conn
+                .execute(
+                    "UPDATE sessions SET mood = ?2 WHERE id = ?1",
+                    params![session_id, mood],
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(updated > 0)
+        })
+    }
+
+    /// `(start_time, mood, completed)` of rated sessions started since `since`, oldest first
+    pub fn get_session_moods(
         &self,
-    ) -> DatabaseResult<Option<crate::database::models::OnboardingCompletion>> {
+        since: DateTime<Utc>,
+    ) -> DatabaseResult<Vec<(DateTime<Utc>, u8, bool)>> {
         self.with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
-                    "SELECT id, completed_at, version, config_snapshot 
-                     FROM onboarding_completion 
-                     ORDER BY completed_at DESC 
-                     LIMIT 1",
+                    "SELECT start_time, mood, completed
+                     FROM sessions
+                     WHERE start_time >= ?1 AND mood IS NOT NULL
+                     ORDER BY start_time ASC",
                 )
                 .map_err(DatabaseError::Sqlite)?;
 
-            let result = stmt.query_row([], |row| {
-                crate::database::models::OnboardingCompletion::from_row(row)
-            });
+            let moods = stmt
+                .query_map([since], |row| {
+                    Ok((
+                        row.get::<_, DateTime<Utc>>(0)?,
+                        row.get::<_, u8>(1)?,
+                        row.get::<_, bool>(2)?,
+                    ))
+                })
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(moods)
+        })
+    }
+
+    /// Get session by ID
+    pub fn get_session(&self, session_id: &str) -> DatabaseResult<Option<Session>> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, session_type, start_time, end_time, planned_duration,
+                        actual_duration, strict_mode, completed, notes, created_at,
+                        within_work_hours, cycle_number, is_long_break, tag, is_sprint
+                 FROM sessions 
+                 WHERE id = ?1",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let result = stmt.query_row([session_id], |row| Session::from_row(row));
+
+            match result {
+                Ok(session) => Ok(Some(session)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(DatabaseError::Sqlite(e)),
+            }
+        })
+    }
+
+    /// Get sessions within a date range
+    pub fn get_sessions_in_range(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> DatabaseResult<Vec<Session>> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, session_type, start_time, end_time, planned_duration,
+                        actual_duration, strict_mode, completed, notes, created_at,
+                        within_work_hours, cycle_number, is_long_break, tag, is_sprint
+                 FROM sessions 
+                 WHERE start_time >= ?1 AND start_time <= ?2
+                 ORDER BY start_time ASC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let session_iter = stmt
+                .query_map([start_date, end_date], |row| Session::from_row(row))
+                .map_err(DatabaseError::Sqlite)?;
+
+            let mut sessions = Vec::new();
+            for session in session_iter {
+                sessions.push(session.map_err(DatabaseError::Sqlite)?);
+            }
+
+            Ok(sessions)
+        })
+    }
+
+    /// `limit` sessions matching `session_type_filter`, newest first, after
+    /// skipping `offset`, along with how many match in total
+    pub fn get_sessions_paginated(
+        &self,
+        offset: u32,
+        limit: u32,
+        session_type_filter: Option<SessionHistoryFilter>,
+    ) -> DatabaseResult<(Vec<Session>, u32)> {
+        let condition = SessionHistoryFilter::condition(session_type_filter);
+
+        self.with_transaction(|tx| {
+            // Count and page in one transaction so they agree
+            let total: i64 = tx
+                .query_row(
+                    &format!("SELECT COUNT(*) FROM sessions WHERE {}", condition),
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let mut stmt = tx
+                .prepare(&format!(
+                    "SELECT id, session_type, start_time, end_time, planned_duration,
+                        actual_duration, strict_mode, completed, notes, created_at,
+                        within_work_hours, cycle_number, is_long_break, tag, is_sprint
+                     FROM sessions
+                     WHERE {}
+                     ORDER BY start_time DESC, id ASC
+                     LIMIT ?1 OFFSET ?2",
+                    condition
+                ))
+                .map_err(DatabaseError::Sqlite)?;
+
+            let sessions = stmt
+                .query_map(params![limit, offset], |row| Session::from_row(row))
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+            drop(stmt);
+
+            Ok((sessions, total.max(0) as u32))
+        })
+    }
+
+    /// Read everything the dashboard needs in a single transaction: sessions
+    /// started within the range, the `recent_limit` latest sessions and
+    /// every local day with a completed focus session
+    pub fn get_dashboard_snapshot(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        recent_limit: u32,
+    ) -> DatabaseResult<DashboardSnapshot> {
+        self.with_transaction(|tx| {
+            let sessions = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT id, session_type, start_time, end_time, planned_duration,
+                            actual_duration, strict_mode, completed, notes, created_at,
+                            within_work_hours, cycle_number, is_long_break, tag, is_sprint
+                         FROM sessions
+                         WHERE start_time >= ?1 AND start_time <= ?2
+                         ORDER BY start_time ASC",
+                    )
+                    .map_err(DatabaseError::Sqlite)?;
+                let rows = stmt
+                    .query_map([start_date, end_date], |row| Session::from_row(row))
+                    .map_err(DatabaseError::Sqlite)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(DatabaseError::Sqlite)?;
+                rows
+            };
+
+            let recent_sessions = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT id, session_type, start_time, end_time, planned_duration,
+                            actual_duration, strict_mode, completed, notes, created_at,
+                            within_work_hours, cycle_number, is_long_break, tag, is_sprint
+                         FROM sessions
+                         ORDER BY start_time DESC
+                         LIMIT ?1",
+                    )
+                    .map_err(DatabaseError::Sqlite)?;
+                let rows = stmt
+                    .query_map([recent_limit], |row| Session::from_row(row))
+                    .map_err(DatabaseError::Sqlite)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(DatabaseError::Sqlite)?;
+                rows
+            };
+
+            let focus_days = {
+                let mut stmt = tx
+                    .prepare(
+                        "SELECT DISTINCT date(start_time, 'localtime') AS day
+                         FROM sessions
+                         WHERE session_type = 'focus' AND completed = 1
+                         ORDER BY day ASC",
+                    )
+                    .map_err(DatabaseError::Sqlite)?;
+                let rows = stmt
+                    .query_map([], |row| row.get::<_, String>(0))
+                    .map_err(DatabaseError::Sqlite)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(DatabaseError::Sqlite)?;
+                rows.iter()
+                    .filter_map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+                    .collect()
+            };
+
+            Ok(DashboardSnapshot {
+                sessions,
+                recent_sessions,
+                focus_days,
+            })
+        })
+    }
+
+    /// Average `actual_duration` (seconds), count and total `actual_duration`
+    /// (seconds) of completed sessions started since `since`, per session type
+    pub fn get_average_durations(
+        &self,
+        since: DateTime<Utc>,
+    ) -> DatabaseResult<Vec<(String, u32, u32, u32)>> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT session_type, ROUND(AVG(actual_duration)), COUNT(*),
+                            SUM(actual_duration)
+                     FROM sessions
+                     WHERE start_time >= ?1 AND completed = 1
+                       AND actual_duration IS NOT NULL
+                     GROUP BY session_type",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let averages = stmt
+                .query_map([since], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, f64>(1)?.max(0.0) as u32,
+                        row.get::<_, i64>(2)?.max(0) as u32,
+                        row.get::<_, i64>(3)?.max(0) as u32,
+                    ))
+                })
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(averages)
+        })
+    }
+
+    /// Raw `timestamp` text of bypass attempts logged since `since`
+    pub fn get_bypass_attempt_timestamps(
+        &self,
+        since: DateTime<Utc>,
+    ) -> DatabaseResult<Vec<String>> {
+        self.with_connection(|conn| {
+            // created_at is CURRENT_TIMESTAMP (UTC, `YYYY-MM-DD HH:MM:SS`) and
+            // cheaper to filter on than the free-form timestamp column
+            let mut stmt = conn
+                .prepare(
+                    "SELECT timestamp FROM bypass_attempts
+                     WHERE created_at >= ?1
+                     ORDER BY created_at ASC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let since = since.format("%Y-%m-%d %H:%M:%S").to_string();
+            let timestamps = stmt
+                .query_map([since], |row| row.get::<_, String>(0))
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(timestamps)
+        })
+    }
+
+    /// Bypass attempts stored between `start` and `end`, oldest first
+    pub fn get_bypass_attempts_in_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> DatabaseResult<Vec<BypassAttempt>> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT session_id, method, timestamp, created_at FROM bypass_attempts
+                     WHERE created_at >= ?1 AND created_at <= ?2
+                     ORDER BY created_at ASC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let format = |at: DateTime<Utc>| at.format("%Y-%m-%d %H:%M:%S").to_string();
+            let attempts = stmt
+                .query_map([format(start), format(end)], |row| {
+                    Ok(BypassAttempt {
+                        session_id: row.get(0)?,
+                        method: row.get(1)?,
+                        timestamp: row.get(2)?,
+                        logged_at: row
+                            .get::<_, Option<String>>(3)?
+                            .as_deref()
+                            .and_then(parse_stored_timestamp),
+                    })
+                })
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(attempts)
+        })
+    }
+
+    /// Open a strict mode stretch at `at`, unless one is already open
+    pub fn start_strict_mode_interval(&self, at: DateTime<Utc>) -> DatabaseResult<()> {
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT INTO strict_mode_sessions (activated_at)
+                 SELECT ?1
+                 WHERE NOT EXISTS (
+                     SELECT 1 FROM strict_mode_sessions WHERE deactivated_at IS NULL
+                 )",
+                params![at],
+            )
+            .map_err(DatabaseError::Sqlite)?;
+            Ok(())
+        })
+    }
+
+    /// Close the open strict mode stretch at `at`, recording what ended it
+    pub fn end_strict_mode_interval(
+        &self,
+        at: DateTime<Utc>,
+        ended_by: &str,
+    ) -> DatabaseResult<()> {
+        self.with_connection(|conn| {
+            conn.execute(
+                "UPDATE strict_mode_sessions SET deactivated_at = ?1, ended_by = ?2
+                 WHERE deactivated_at IS NULL",
+                params![at, ended_by],
+            )
+            .map_err(DatabaseError::Sqlite)?;
+            Ok(())
+        })
+    }
+
+    /// Total strict mode time and emergency exits between `start` and `end`.
+    /// Stretches are clipped to the period; one still open runs until `now`.
+    pub fn get_strict_mode_uptime(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> DatabaseResult<StrictModeUptime> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT activated_at, deactivated_at, ended_by
+                     FROM strict_mode_sessions
+                     WHERE activated_at <= ?2 AND (deactivated_at IS NULL OR deactivated_at >= ?1)
+                     ORDER BY activated_at ASC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let rows = stmt
+                .query_map(params![start, end], |row| {
+                    Ok((
+                        row.get::<_, DateTime<Utc>>(0)?,
+                        row.get::<_, Option<DateTime<Utc>>>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                })
+                .map_err(DatabaseError::Sqlite)?;
+
+            let mut uptime = StrictModeUptime {
+                period_start: start,
+                period_end: end,
+                active_seconds: 0,
+                activations: 0,
+                emergency_exits: 0,
+            };
+
+            for row in rows {
+                let (activated_at, deactivated_at, ended_by) =
+                    row.map_err(DatabaseError::Sqlite)?;
+                let stretch_end = deactivated_at.unwrap_or(now).min(end);
+                let stretch_start = activated_at.max(start);
+
+                uptime.active_seconds += (stretch_end - stretch_start).num_seconds().max(0);
+                uptime.activations += 1;
+
+                let exited_in_period = deactivated_at.is_some_and(|at| at >= start && at <= end);
+                if exited_in_period && ended_by.as_deref() == Some("emergency_exit") {
+                    uptime.emergency_exits += 1;
+                }
+            }
+
+            Ok(uptime)
+        })
+    }
+
+    /// Strict mode stretches overlapping `start`..`end`, oldest first, each
+    /// with the bypass attempts logged while it was open
+    pub fn get_strict_mode_history(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> DatabaseResult<Vec<StrictModeInterval>> {
+        self.with_connection(|conn| {
+            // bypass_attempts.created_at is CURRENT_TIMESTAMP, so compare
+            // through datetime() to normalise both sides to UTC text
+            let mut stmt = conn
+                .prepare(
+                    "SELECT s.activated_at, s.deactivated_at, s.ended_by,
+                            (SELECT COUNT(*) FROM bypass_attempts b
+                             WHERE datetime(b.created_at) >= datetime(s.activated_at)
+                               AND datetime(b.created_at)
+                                   <= datetime(COALESCE(s.deactivated_at, ?3)))
+                     FROM strict_mode_sessions s
+                     WHERE s.activated_at <= ?2
+                       AND (s.deactivated_at IS NULL OR s.deactivated_at >= ?1)
+                     ORDER BY s.activated_at ASC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let intervals = stmt
+                .query_map(params![start, end, now], |row| {
+                    let activated_at: DateTime<Utc> = row.get(0)?;
+                    let deactivated_at: Option<DateTime<Utc>> = row.get(1)?;
+                    let ended_by: Option<String> = row.get(2)?;
+
+                    Ok(StrictModeInterval {
+                        activated_at,
+                        deactivated_at,
+                        duration_seconds: (deactivated_at.unwrap_or(now) - activated_at)
+                            .num_seconds()
+                            .max(0),
+                        emergency_exit: ended_by.as_deref() == Some("emergency_exit"),
+                        ended_by,
+                        bypass_attempts: row.get(3)?,
+                    })
+                })
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(intervals)
+        })
+    }
+
+    /// Get focus statistics for sessions carrying a tag over the last N days.
+    /// A `None` tag aggregates untagged sessions.
+    pub fn get_stats_by_tag(
+        &self,
+        tag: Option<&str>,
+        days: u32,
+    ) -> DatabaseResult<crate::database::models::TagStats> {
+        use chrono::Duration;
+
+        self.with_connection(|conn| {
+            let end_date = Utc::now();
+            let start_date = end_date - Duration::days(days as i64);
+
+            let mut stmt = conn
+                .prepare(
+                    "SELECT
+                    SUM(CASE
+                        WHEN completed = 1
+                        THEN COALESCE(actual_duration, 0) / 60
+                        ELSE 0
+                    END) as focus_minutes,
+                    COUNT(*) as sessions_count,
+                    COUNT(CASE WHEN completed = 1 THEN 1 END) as sessions_completed
+                 FROM sessions
+                 WHERE session_type = 'focus'
+                   AND start_time >= ?1 AND start_time <= ?2
+                   AND ((?3 IS NULL AND tag IS NULL) OR tag = ?3)",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            stmt.query_row(params![start_date, end_date, tag], |row| {
+                let focus_minutes = row.get::<_, Option<i64>>("focus_minutes")?.unwrap_or(0);
+                let sessions_count = row.get::<_, i64>("sessions_count")?;
+                let sessions_completed = row.get::<_, i64>("sessions_completed")?;
+
+                let completion_rate = if sessions_count > 0 {
+                    sessions_completed as f64 / sessions_count as f64
+                } else {
+                    0.0
+                };
+
+                Ok(crate::database::models::TagStats {
+                    tag: tag.map(str::to_string),
+                    focus_minutes: focus_minutes as u32,
+                    sessions_count: sessions_count as u32,
+                    sessions_completed: sessions_completed as u32,
+                    completion_rate,
+                })
+            })
+            .map_err(DatabaseError::Sqlite)
+        })
+    }
+
+    /// Get focus statistics for sessions started within `[start, end)`
+    pub fn get_period_stats(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> DatabaseResult<crate::database::models::PeriodStats> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT
+                    SUM(CASE
+                        WHEN completed = 1
+                        THEN COALESCE(actual_duration, 0) / 60
+                        ELSE 0
+                    END) as focus_minutes,
+                    COUNT(*) as sessions_count,
+                    COUNT(CASE WHEN completed = 1 THEN 1 END) as sessions_completed
+                 FROM sessions
+                 WHERE session_type = 'focus'
+                   AND start_time >= ?1 AND start_time < ?2",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            stmt.query_row(params![start, end], |row| {
+                let focus_minutes = row.get::<_, Option<i64>>("focus_minutes")?.unwrap_or(0);
+                let sessions_count = row.get::<_, i64>("sessions_count")?;
+                let sessions_completed = row.get::<_, i64>("sessions_completed")?;
+
+                let completion_rate = if sessions_count > 0 {
+                    sessions_completed as f64 / sessions_count as f64
+                } else {
+                    0.0
+                };
+
+                Ok(crate::database::models::PeriodStats {
+                    period_start: start,
+                    period_end: end,
+                    focus_minutes: focus_minutes as u32,
+                    sessions_count: sessions_count as u32,
+                    sessions_completed: sessions_completed as u32,
+                    completion_rate,
+                })
+            })
+            .map_err(DatabaseError::Sqlite)
+        })
+    }
+
+    /// Focus sessions started in `[start, end)` and how many of them completed
+    pub fn count_focus_sessions(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> DatabaseResult<(u32, u32)> {
+        self.with_connection(|conn| {
+            conn.query_row(
+                "SELECT COUNT(*), COUNT(CASE WHEN completed = 1 THEN 1 END)
+                 FROM sessions
+                 WHERE session_type = 'focus'
+                   AND start_time >= ?1 AND start_time < ?2",
+                params![start, end],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?.max(0) as u32,
+                        row.get::<_, i64>(1)?.max(0) as u32,
+                    ))
+                },
+            )
+            .map_err(DatabaseError::Sqlite)
+        })
+    }
+
+    /// Goal in effect on `date`: the latest one set on or before it
+    pub fn get_daily_goal(&self, date: NaiveDate) -> DatabaseResult<Option<DailyGoal>> {
+        self.with_connection(|conn| {
+            conn.query_row(
+                "SELECT date, target_minutes, target_sessions FROM daily_goals
+                 WHERE date <= ?1
+                 ORDER BY date DESC
+                 LIMIT 1",
+                params![date],
+                |row| {
+                    Ok(DailyGoal {
+                        date: row.get(0)?,
+                        target_minutes: row.get(1)?,
+                        target_sessions: row.get(2)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(DatabaseError::Sqlite)
+        })
+    }
+
+    /// Set the goal from `date` on, replacing one already set for that day
+    pub fn set_daily_goal(
+        &self,
+        date: NaiveDate,
+        target_minutes: Option<u32>,
+        target_sessions: Option<u32>,
+    ) -> DatabaseResult<DailyGoal> {
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT INTO daily_goals (date, target_minutes, target_sessions)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(date) DO UPDATE SET
+                    target_minutes = excluded.target_minutes,
+                    target_sessions = excluded.target_sessions,
+                    updated_at = CURRENT_TIMESTAMP",
+                params![date, target_minutes, target_sessions],
+            )
+            .map_err(DatabaseError::Sqlite)?;
+
+            Ok(DailyGoal {
+                date,
+                target_minutes,
+                target_sessions,
+            })
+        })
+    }
+
+    /// The cycle orchestrator's phase saved by `save_cycle_state`, if any
+    pub fn get_cycle_state(&self) -> DatabaseResult<Option<SavedCycleState>> {
+        self.with_connection(|conn| {
+            conn.query_row(
+                "SELECT phase, remaining_seconds, phase_seconds, cycle_count, is_running,
+                        session_id, started_at, within_work_hours, is_sprint, is_wind_down
+                 FROM cycle_state WHERE id = 1",
+                [],
+                |row| {
+                    Ok(SavedCycleState {
+                        phase: row.get(0)?,
+                        remaining_seconds: row.get(1)?,
+                        phase_seconds: row.get(2)?,
+                        cycle_count: row.get(3)?,
+                        is_running: row.get(4)?,
+                        session_id: row.get(5)?,
+                        started_at: row.get(6)?,
+                        within_work_hours: row.get(7)?,
+                        is_sprint: row.get(8)?,
+                        is_wind_down: row.get(9)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(DatabaseError::Sqlite)
+        })
+    }
+
+    /// Replace the saved cycle orchestrator phase
+    pub fn save_cycle_state(&self, saved: &SavedCycleState) -> DatabaseResult<()> {
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO cycle_state
+                 (id, phase, remaining_seconds, phase_seconds, cycle_count, is_running,
+                  session_id, started_at, within_work_hours, is_sprint, is_wind_down, updated_at)
+                 VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, CURRENT_TIMESTAMP)",
+                params![
+                    saved.phase,
+                    saved.remaining_seconds,
+                    saved.phase_seconds,
+                    saved.cycle_count,
+                    saved.is_running,
+                    saved.session_id,
+                    saved.started_at,
+                    saved.within_work_hours,
+                    saved.is_sprint,
+                    saved.is_wind_down,
+                ],
+            )
+            .map_err(DatabaseError::Sqlite)?;
+            Ok(())
+        })
+    }
+
+    /// Every saved cycle preset, by name
+    pub fn list_cycle_presets(&self) -> DatabaseResult<Vec<CyclePreset>> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT * FROM cycle_presets ORDER BY name COLLATE NOCASE")
+                .map_err(DatabaseError::Sqlite)?;
+
+            let presets = stmt
+                .query_map([], CyclePreset::from_row)
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(presets)
+        })
+    }
+
+    pub fn get_cycle_preset(&self, id: i32) -> DatabaseResult<Option<CyclePreset>> {
+        self.with_connection(|conn| {
+            conn.query_row(
+                "SELECT * FROM cycle_presets WHERE id = ?1",
+                params![id],
+                CyclePreset::from_row,
+            )
+            .optional()
+            .map_err(DatabaseError::Sqlite)
+        })
+    }
+
+    /// Insert a preset without an id, or replace the one with its id.
+    /// Returns the preset as stored.
+    pub fn save_cycle_preset(&self, preset: &CyclePreset) -> DatabaseResult<CyclePreset> {
+        self.with_connection(|conn| {
+            let id = match preset.id {
+                Some(id) => {
+                    let updated = conn
+                        .execute(
+                            "UPDATE cycle_presets
+                         SET name = ?1, focus_duration = ?2, break_duration = ?3,
+                             long_break_duration = ?4, cycles_per_long_break = ?5,
+                             updated_at = CURRENT_TIMESTAMP
+                         WHERE id = ?6",
+                            params![
+                                preset.name,
+                                preset.focus_duration,
+                                preset.break_duration,
+                                preset.long_break_duration,
+                                preset.cycles_per_long_break,
+                                id,
+                            ],
+                        )
+                        .map_err(DatabaseError::Sqlite)?;
+                    if updated == 0 {
+                        return Err(DatabaseError::Validation(format!(
+                            "No cycle preset with id {}",
+                            id
+                        )));
+                    }
+                    id
+                }
+                None => {
+                    conn.execute(
+                        "INSERT INTO cycle_presets
+                         (name, focus_duration, break_duration, long_break_duration,
+                          cycles_per_long_break)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![
+                            preset.name,
+                            preset.focus_duration,
+                            preset.break_duration,
+                            preset.long_break_duration,
+                            preset.cycles_per_long_break,
+                        ],
+                    )
+                    .map_err(DatabaseError::Sqlite)?;
+                    conn.last_insert_rowid() as i32
+                }
+            };
+
+            Ok(CyclePreset {
+                id: Some(id),
+                ..preset.clone()
+            })
+        })
+    }
+
+    /// Delete a preset; returns whether it existed
+    pub fn delete_cycle_preset(&self, id: i32) -> DatabaseResult<bool> {
+        self.with_connection(|conn| {
+            let deleted = conn
+                .execute("DELETE FROM cycle_presets WHERE id = ?1", params![id])
+                .map_err(DatabaseError::Sqlite)?;
+            Ok(deleted > 0)
+        })
+    }
+
+    /// Every stored custom hotkey, by action
+    pub fn get_hotkey_configs(&self) -> DatabaseResult<Vec<StoredHotkeyConfig>> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare("SELECT action, shortcut, enabled FROM hotkey_configs ORDER BY action")
+                .map_err(DatabaseError::Sqlite)?;
+
+            let configs = stmt
+                .query_map([], |row| {
+                    Ok(StoredHotkeyConfig {
+                        action: row.get(0)?,
+                        shortcut: row.get(1)?,
+                        enabled: row.get(2)?,
+                    })
+                })
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(configs)
+        })
+    }
+
+    /// Insert or replace the hotkey stored for `config.action`
+    pub fn save_hotkey_config(&self, config: &StoredHotkeyConfig) -> DatabaseResult<()> {
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO hotkey_configs (action, shortcut, enabled, updated_at)
+                 VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP)",
+                params![config.action, config.shortcut, config.enabled],
+            )
+            .map_err(DatabaseError::Sqlite)?;
+            Ok(())
+        })
+    }
+
+    /// Personal bests over every completed focus session, grouped by local day
+    pub fn get_personal_records(&self) -> DatabaseResult<PersonalRecords> {
+        self.with_connection(|conn| {
+            let longest_session = conn
+                .query_row(
+                    "SELECT date(start_time, 'localtime'), actual_duration / 60
+                     FROM sessions
+                     WHERE session_type = 'focus' AND completed = 1
+                       AND actual_duration IS NOT NULL
+                     ORDER BY actual_duration DESC, start_time ASC
+                     LIMIT 1",
+                    [],
+                    |row| {
+                        Ok(DayRecord {
+                            date: row.get(0)?,
+                            value: row.get::<_, i64>(1)?.max(0) as u32,
+                        })
+                    },
+                )
+                .optional()
+                .map_err(DatabaseError::Sqlite)?;
+
+            let mut stmt = conn
+                .prepare(
+                    "SELECT date(start_time, 'localtime') AS day,
+                        SUM(COALESCE(actual_duration, 0)) / 60 AS focus_minutes,
+                        COUNT(*) AS sessions_completed
+                     FROM sessions
+                     WHERE session_type = 'focus' AND completed = 1
+                     GROUP BY day
+                     ORDER BY day ASC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let days = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, i64>(1)?.max(0) as u32,
+                        row.get::<_, i64>(2)?.max(0) as u32,
+                    ))
+                })
+                .map_err(DatabaseError::Sqlite)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(DatabaseError::Sqlite)?;
+
+            // Ties go to the earliest day
+            let best_day = |value: fn(&(String, u32, u32)) -> u32| {
+                days.iter()
+                    .filter(|day| value(day) > 0)
+                    .fold(None::<&(String, u32, u32)>, |best, day| match best {
+                        Some(best) if value(best) >= value(day) => Some(best),
+                        _ => Some(day),
+                    })
+                    .map(|day| DayRecord {
+                        date: day.0.clone(),
+                        value: value(day),
+                    })
+            };
+
+            let dates: Vec<NaiveDate> = days
+                .iter()
+                .filter_map(|day| NaiveDate::parse_from_str(&day.0, "%Y-%m-%d").ok())
+                .collect();
+
+            Ok(PersonalRecords {
+                longest_session,
+                most_focus_minutes: best_day(|day| day.1),
+                most_sessions: best_day(|day| day.2),
+                longest_streak: PersonalRecords::longest_streak(&dates),
+            })
+        })
+    }
+
+    /// Get session statistics for the last N days
+    pub fn get_session_stats(
+        &self,
+        days: u32,
+    ) -> DatabaseResult<Vec<crate::database::models::SessionStats>> {
+        use chrono::Duration;
+
+        self.with_connection(|conn| {
+            let end_date = Utc::now();
+            let start_date = end_date - Duration::days(days as i64);
+
+            // First, let's check what sessions exist
+            let mut check_stmt = conn
+                .prepare(
+                    "SELECT id, session_type, completed, actual_duration, start_time 
+                 FROM sessions 
+                 WHERE start_time >= ?1 AND start_time <= ?2
+                 ORDER BY start_time DESC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let session_check = check_stmt
+                .query_map([start_date, end_date], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, bool>(2)?,
+                        row.get::<_, Option<i32>>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                })
+                .map_err(DatabaseError::Sqlite)?;
+
+            for session in session_check {
+                match session {
+                    Ok((_id, _session_type, _completed, _actual_duration, _start_time)) => {
+                        // Session exists, continue processing
+                    }
+                    Err(e) => eprintln!("❌ [Database] Error reading session: {}", e),
+                }
+            }
+
+            let mut stmt = conn
+                .prepare(
+                    "SELECT 
+                    DATE(start_time) as date,
+                    SUM(CASE 
+                        WHEN session_type = 'focus' AND completed = 1 
+                        THEN COALESCE(actual_duration, 0) / 60 
+                        ELSE 0 
+                    END) as focus_minutes,
+                    COUNT(CASE 
+                        WHEN session_type IN ('short_break', 'long_break') AND completed = 1 
+                        THEN 1 
+                    END) as breaks_completed,
+                    COUNT(CASE 
+                        WHEN session_type = 'focus' AND completed = 1 
+                        THEN 1 
+                    END) as sessions_completed,
+                    COUNT(CASE 
+                        WHEN session_type = 'focus' 
+                        THEN (SELECT COUNT(*) FROM evasion_attempts WHERE session_id = sessions.id)
+                    END) as evasion_attempts
+                 FROM sessions 
+                 WHERE start_time >= ?1 AND start_time <= ?2
+                 GROUP BY DATE(start_time)
+                 ORDER BY date DESC",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let stats_iter = stmt
+                .query_map([start_date, end_date], |row| {
+                    Ok(crate::database::models::SessionStats {
+                        date: row.get::<_, String>("date")?,
+                        focus_minutes: row.get::<_, i64>("focus_minutes")? as u32,
+                        breaks_completed: row.get::<_, i64>("breaks_completed")? as u32,
+                        sessions_completed: row.get::<_, i64>("sessions_completed")? as u32,
+                        evasion_attempts: row.get::<_, i64>("evasion_attempts")? as u32,
+                    })
+                })
+                .map_err(DatabaseError::Sqlite)?;
+
+            let mut stats = Vec::new();
+            for stat in stats_iter {
+                let stat_result = stat.map_err(DatabaseError::Sqlite)?;
+
+                stats.push(stat_result);
+            }
+
+            Ok(stats)
+        })
+    }
+
+    /// Onboarding Methods
+
+    /// Save onboarding completion record
+    pub fn save_onboarding_completion(
+        &self,
+        version: &str,
+        config_snapshot: Option<&str>,
+    ) -> DatabaseResult<()> {
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT INTO onboarding_completion (version, config_snapshot) VALUES (?1, ?2)",
+                params![version, config_snapshot],
+            )
+            .map_err(DatabaseError::Sqlite)?;
+
+            Ok(())
+        })
+    }
+
+    /// Check if onboarding has been completed
+    pub fn is_onboarding_completed(&self) -> DatabaseResult<bool> {
+        self.with_connection(|conn| {
+            let count: i64 = conn
+                .query_row("SELECT COUNT(*) FROM onboarding_completion", [], |row| {
+                    row.get(0)
+                })
+                .map_err(DatabaseError::Sqlite)?;
+
+            Ok(count > 0)
+        })
+    }
+
+    /// Get the latest onboarding completion record
+    pub fn get_latest_onboarding_completion(
+        &self,
+    ) -> DatabaseResult<Option<crate::database::models::OnboardingCompletion>> {
+        self.with_connection(|conn| {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, completed_at, version, config_snapshot 
+                     FROM onboarding_completion 
+                     ORDER BY completed_at DESC 
+                     LIMIT 1",
+                )
+                .map_err(DatabaseError::Sqlite)?;
+
+            let result = stmt.query_row([], |row| {
+                crate::database::models::OnboardingCompletion::from_row(row)
+            });
 
             match result {
                 Ok(completion) => Ok(Some(completion)),
@@ -665,3 +2041,1017 @@ impl DatabaseStats {
         }
     }
 }
+
+/// A session row read as loosely as possible, so corrupt values still load
+struct SessionIntegrityRow {
+    id: String,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    planned_duration: Option<i64>,
+    actual_duration: Option<i64>,
+    completed: bool,
+}
+
+/// `actual_duration` beyond this multiple of `planned_duration` is reported
+const MAX_DURATION_OVER_PLAN: i64 = 3;
+
+/// Sessions may touch at the edges by this much without counting as overlap
+const OVERLAP_TOLERANCE_SECONDS: i64 = 1;
+
+fn detect_session_anomalies(rows: &[SessionIntegrityRow]) -> Vec<SessionAnomaly> {
+    let mut anomalies = Vec::new();
+    let mut intervals = Vec::new();
+
+    let mut report = |row: &SessionIntegrityRow, kind, detail: String, fixable| {
+        anomalies.push(SessionAnomaly {
+            session_id: row.id.clone(),
+            kind,
+            detail,
+            fixable,
+        });
+    };
+
+    for row in rows {
+        let Some(start) = row.start_time.as_deref().and_then(parse_stored_timestamp) else {
+            report(
+                row,
+                SessionAnomalyKind::MissingField,
+                "start_time is missing or unreadable".to_string(),
+                false,
+            );
+            continue;
+        };
+
+        let end = match row.end_time.as_deref() {
+            Some(raw) => {
+                let end = parse_stored_timestamp(raw);
+                if end.is_none() {
+                    report(
+                        row,
+                        SessionAnomalyKind::MissingField,
+                        "end_time is unreadable".to_string(),
+                        false,
+                    );
+                }
+                end
+            }
+            None => None,
+        };
+
+        if row.planned_duration.is_none() {
+            report(
+                row,
+                SessionAnomalyKind::MissingField,
+                "planned_duration is NULL".to_string(),
+                false,
+            );
+        }
+
+        if row.completed && (row.end_time.is_none() || row.actual_duration.is_none()) {
+            report(
+                row,
+                SessionAnomalyKind::MissingField,
+                "completed session has no end_time or actual_duration".to_string(),
+                true,
+            );
+        }
+
+        if let (Some(planned), Some(actual)) = (row.planned_duration, row.actual_duration) {
+            if planned > 0 && actual > planned * MAX_DURATION_OVER_PLAN {
+                report(
+                    row,
+                    SessionAnomalyKind::DurationExceedsPlan,
+                    format!("ran {}s against {}s planned", actual, planned),
+                    false,
+                );
+            }
+        }
+
+        match end {
+            Some(end) if end < start => report(
+                row,
+                SessionAnomalyKind::EndBeforeStart,
+                format!("ends {}s before it starts", (start - end).num_seconds()),
+                true,
+            ),
+            Some(end) => intervals.push((start, end, row)),
+            None => {}
+        }
+    }
+
+    // Sweep by start time, comparing each session with the latest-ending one before it
+    intervals.sort_by_key(|(start, _, _)| *start);
+    let tolerance = chrono::Duration::seconds(OVERLAP_TOLERANCE_SECONDS);
+    let mut latest: Option<(DateTime<Utc>, &SessionIntegrityRow)> = None;
+
+    for (start, end, row) in intervals {
+        match latest {
+            Some((latest_end, latest_row)) if start + tolerance < latest_end => {
+                report(
+                    row,
+                    SessionAnomalyKind::Overlap,
+                    format!("overlaps session {}", latest_row.id),
+                    false,
+                );
+                if end > latest_end {
+                    latest = Some((end, row));
+                }
+            }
+            Some((latest_end, _)) if end <= latest_end => {}
+            _ => latest = Some((end, row)),
+        }
+    }
+
+    anomalies
+}
+
+/// Check that `new_dir` can take `required_bytes` of data, creating it if
+/// needed. Returns the directories that had to be created, deepest first,
+/// so a failed move can remove them again.
+fn prepare_target_directory(
+    old_dir: &Path,
+    new_dir: &Path,
+    required_bytes: u64,
+) -> DatabaseResult<Vec<PathBuf>> {
+    if !new_dir.is_absolute() {
+        return Err(DatabaseError::Validation(
+            "Data directory must be an absolute path".to_string(),
+        ));
+    }
+
+    let created: Vec<PathBuf> = new_dir
+        .ancestors()
+        .take_while(|dir| !dir.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    if let Err(e) = fs::create_dir_all(new_dir) {
+        remove_created_dirs(&created);
+        return Err(DatabaseError::Validation(format!(
+            "Cannot create {}: {}",
+            new_dir.display(),
+            e
+        )));
+    }
+
+    match validate_target_directory(old_dir, new_dir, required_bytes) {
+        Ok(()) => Ok(created),
+        Err(e) => {
+            remove_created_dirs(&created);
+            Err(e)
+        }
+    }
+}
+
+/// Remove the (by now empty) directories a move created, deepest first
+fn remove_created_dirs(created: &[PathBuf]) {
+    for dir in created {
+        let _ = fs::remove_dir(dir);
+    }
+}
+
+/// Check that `new_dir` differs from the current directory, does not already
+/// hold a database, has room for `required_bytes` and is writable
+fn validate_target_directory(
+    old_dir: &Path,
+    new_dir: &Path,
+    required_bytes: u64,
+) -> DatabaseResult<()> {
+    let same_dir = match (old_dir.canonicalize(), new_dir.canonicalize()) {
+        (Ok(old), Ok(new)) => old == new,
+        _ => false,
+    };
+    if same_dir {
+        return Err(DatabaseError::Validation(
+            "Data is already stored in that directory".to_string(),
+        ));
+    }
+
+    if new_dir.join(DATABASE_FILE_NAME).exists() {
+        return Err(DatabaseError::Validation(format!(
+            "{} already contains a database",
+            new_dir.display()
+        )));
+    }
+
+    match available_space(new_dir) {
+        Some(available) if available < required_bytes => {
+            return Err(DatabaseError::Validation(format!(
+                "Not enough free space in {}: {} KB needed, {} KB available",
+                new_dir.display(),
+                required_bytes.div_ceil(1024),
+                available / 1024
+            )));
+        }
+        Some(_) => {}
+        None => println!(
+            "⚠️ [Database] Could not read free space in {}, copying anyway",
+            new_dir.display()
+        ),
+    }
+
+    let probe = new_dir.join(".pausa-write-test");
+    fs::write(&probe, b"ok")
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|e| {
+            DatabaseError::Validation(format!("{} is not writable: {}", new_dir.display(), e))
+        })
+}
+
+/// Bytes a move copies: the database plus every backup file
+fn data_size(db_path: &Path, data_dir: &Path) -> u64 {
+    let file_size = |path: &Path| fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    let backups = fs::read_dir(data_dir.join(BACKUPS_DIR_NAME))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| file_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0);
+
+    file_size(db_path) + backups
+}
+
+/// Free space available to this user on the volume holding `dir`, if the
+/// platform tool reports it
+fn available_space(dir: &Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        // POSIX output: a header, then "fs blocks used available capacity mount"
+        let output = std::process::Command::new("df")
+            .arg("-Pk")
+            .arg(dir)
+            .output()
+            .ok()?;
+        let kilobytes = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .nth(1)?
+            .split_whitespace()
+            .nth(3)?
+            .parse::<u64>()
+            .ok()?;
+        Some(kilobytes * 1024)
+    }
+
+    #[cfg(windows)]
+    {
+        let dir = dir.display().to_string().replace('\'', "''");
+        let output = std::process::Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                &format!("[System.IO.DriveInfo]::new('{}').AvailableFreeSpace", dir),
+            ])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = dir;
+        None
+    }
+}
+
+/// Copy the database and backups into `new_dir`, pushing every created path
+/// onto `copied` so a failed move can be undone.
+///
+/// The database is written under a temporary name first, so running out of
+/// space or a failed integrity check never leaves a partial `pausa.db`.
+fn copy_data_files(
+    old_path: &Path,
+    old_dir: &Path,
+    new_dir: &Path,
+    copied: &mut Vec<PathBuf>,
+) -> DatabaseResult<()> {
+    let io_error = |action: &str, path: &Path, e: std::io::Error| {
+        DatabaseError::Validation(format!("Failed to {} {}: {}", action, path.display(), e))
+    };
+
+    let temp_path = new_dir.join(format!("{}.moving", DATABASE_FILE_NAME));
+    copied.push(temp_path.clone());
+    fs::copy(old_path, &temp_path).map_err(|e| io_error("copy database to", &temp_path, e))?;
+
+    let check = Connection::open_with_flags(&temp_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .and_then(|copy| copy.query_row("PRAGMA quick_check", [], |row| row.get::<_, String>(0)));
+    // The read-only check leaves WAL sidecar files behind under the temporary name
+    for suffix in ["-wal", "-shm"] {
+        let _ = fs::remove_file(new_dir.join(format!("{}.moving{}", DATABASE_FILE_NAME, suffix)));
+    }
+    let check = check.map_err(DatabaseError::Sqlite)?;
+    if check != "ok" {
+        return Err(DatabaseError::Validation(format!(
+            "Copied database failed its integrity check: {}",
+            check
+        )));
+    }
+
+    let new_path = new_dir.join(DATABASE_FILE_NAME);
+    fs::rename(&temp_path, &new_path).map_err(|e| io_error("move database to", &new_path, e))?;
+    copied.pop();
+    copied.push(new_path);
+
+    let old_backups = old_dir.join(BACKUPS_DIR_NAME);
+    if !old_backups.is_dir() {
+        return Ok(());
+    }
+
+    let new_backups = new_dir.join(BACKUPS_DIR_NAME);
+    if !new_backups.exists() {
+        fs::create_dir(&new_backups).map_err(|e| io_error("create", &new_backups, e))?;
+        copied.push(new_backups.clone());
+    }
+
+    let entries = fs::read_dir(&old_backups).map_err(|e| io_error("read", &old_backups, e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| io_error("read", &old_backups, e))?;
+        if !entry.path().is_file() {
+            continue;
+        }
+        let target = new_backups.join(entry.file_name());
+        if target.exists() {
+            continue;
+        }
+        fs::copy(entry.path(), &target).map_err(|e| io_error("copy backup to", &target, e))?;
+        copied.push(target);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::SessionType;
+
+    fn retry_options() -> BusyRetryOptions {
+        BusyRetryOptions {
+            busy_timeout: Duration::from_millis(10),
+            max_attempts: 6,
+            initial_backoff: Duration::from_millis(20),
+        }
+    }
+
+    /// Hold an exclusive lock on the database from a second connection
+    fn lock_database(db_path: &PathBuf) -> Connection {
+        let blocker = Connection::open(db_path).unwrap();
+        blocker.execute_batch("BEGIN EXCLUSIVE;").unwrap();
+        blocker
+    }
+
+    fn write_marker(conn: &Connection) -> DatabaseResult<()> {
+        conn.execute(
+            "UPDATE user_settings SET break_message = 'contention' WHERE id = 1",
+            [],
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_database_starts_at_current_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DatabaseManager::new(dir.path().join(DATABASE_FILE_NAME)).unwrap();
+
+        let (version, has_sessions_columns): (i32, bool) = manager
+            .with_connection(|conn| {
+                Ok((
+                    MigrationManager::get_current_version(conn)?,
+                    manager.check_columns_exist(
+                        conn,
+                        "sessions",
+                        &["within_work_hours", "cycle_number", "tag"],
+                    )?,
+                ))
+            })
+            .unwrap();
+
+        assert_eq!(version, crate::database::schema::SCHEMA_VERSION);
+        assert!(has_sessions_columns);
+    }
+
+    #[test]
+    fn test_dashboard_snapshot_on_new_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DatabaseManager::new(dir.path().join(DATABASE_FILE_NAME)).unwrap();
+
+        let start_time = Utc::now() - chrono::Duration::minutes(30);
+        let session = Session {
+            id: "focus-1".to_string(),
+            session_type: SessionType::Focus,
+            start_time,
+            end_time: Some(start_time + chrono::Duration::minutes(25)),
+            planned_duration: 25,
+            actual_duration: Some(25),
+            strict_mode: false,
+            completed: true,
+            notes: None,
+            created_at: start_time,
+            within_work_hours: true,
+            cycle_number: Some(1),
+            is_long_break: false,
+            tag: Some("writing".to_string()),
+            is_sprint: false,
+        };
+        manager.create_session(&session).unwrap();
+
+        let snapshot = manager
+            .get_dashboard_snapshot(start_time - chrono::Duration::hours(1), Utc::now(), 5)
+            .unwrap();
+
+        assert_eq!(snapshot.sessions.len(), 1);
+        assert_eq!(snapshot.recent_sessions[0].tag.as_deref(), Some("writing"));
+        assert_eq!(snapshot.focus_days.len(), 1);
+    }
+
+    #[test]
+    fn test_retries_until_lock_is_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("pausa.db");
+        let manager =
+            DatabaseManager::with_retry_options(db_path.clone(), retry_options()).unwrap();
+
+        let blocker = lock_database(&db_path);
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(60));
+            blocker.execute_batch("COMMIT;").unwrap();
+        });
+
+        let mut attempts = 0;
+        let result = manager.with_transaction(|tx| {
+            attempts += 1;
+            write_marker(tx)
+        });
+        releaser.join().unwrap();
+
+        assert!(
+            result.is_ok(),
+            "write should succeed once the lock is released"
+        );
+        assert!(attempts > 1, "write should have been retried");
+    }
+
+    #[test]
+    fn test_returns_busy_error_when_retries_run_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("pausa.db");
+        let manager =
+            DatabaseManager::with_retry_options(db_path.clone(), retry_options()).unwrap();
+
+        let _blocker = lock_database(&db_path);
+
+        let mut attempts = 0;
+        let result = manager.with_transaction(|tx| {
+            attempts += 1;
+            write_marker(tx)
+        });
+
+        assert!(matches!(&result, Err(error) if is_busy(error)));
+        assert_eq!(attempts, retry_options().max_attempts);
+    }
+
+    #[test]
+    fn test_with_connection_does_not_retry() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("pausa.db");
+        let manager =
+            DatabaseManager::with_retry_options(db_path.clone(), retry_options()).unwrap();
+
+        let _blocker = lock_database(&db_path);
+
+        let mut attempts = 0;
+        let result = manager.with_connection(|conn| {
+            attempts += 1;
+            write_marker(conn)
+        });
+
+        assert!(matches!(&result, Err(error) if is_busy(error)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_relocate_moves_database_and_backups() {
+        let old_dir = tempfile::tempdir().unwrap();
+        let new_dir = tempfile::tempdir().unwrap();
+        let target = new_dir.path().join("synced");
+        let manager = DatabaseManager::new(old_dir.path().join(DATABASE_FILE_NAME)).unwrap();
+        manager
+            .with_connection(|conn| {
+                conn.execute_batch(
+                    "CREATE TABLE relocate_probe (value TEXT);
+                     INSERT INTO relocate_probe VALUES ('kept');",
+                )
+                .map_err(DatabaseError::Sqlite)
+            })
+            .unwrap();
+        fs::create_dir(old_dir.path().join(BACKUPS_DIR_NAME)).unwrap();
+        fs::write(
+            old_dir.path().join(BACKUPS_DIR_NAME).join("manual.json"),
+            "{}",
+        )
+        .unwrap();
+
+        let mut recorded = None;
+        let new_path = manager
+            .relocate(&target, |dir| {
+                recorded = Some(dir.to_path_buf());
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(new_path, target.join(DATABASE_FILE_NAME));
+        assert_eq!(manager.get_db_path(), new_path);
+        assert_eq!(recorded, Some(target.clone()));
+        assert!(target.join(BACKUPS_DIR_NAME).join("manual.json").exists());
+
+        let value: String = manager
+            .with_connection(|conn| {
+                conn.query_row("SELECT value FROM relocate_probe", [], |row| row.get(0))
+                    .map_err(DatabaseError::Sqlite)
+            })
+            .unwrap();
+        assert_eq!(value, "kept");
+    }
+
+    #[test]
+    fn test_relocate_rolls_back_when_recording_fails() {
+        let old_dir = tempfile::tempdir().unwrap();
+        let new_dir = tempfile::tempdir().unwrap();
+        let old_path = old_dir.path().join(DATABASE_FILE_NAME);
+        let manager = DatabaseManager::new(old_path.clone()).unwrap();
+        let target = new_dir.path().join("synced").join("pausa");
+
+        let result = manager.relocate(&target, |_| {
+            Err(DatabaseError::Validation("disk full".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(manager.get_db_path(), old_path);
+        // The directories created for the move are gone too
+        assert!(!new_dir.path().join("synced").exists());
+        assert!(manager.with_connection(write_marker).is_ok());
+    }
+
+    #[test]
+    fn test_relocate_rejects_relative_and_current_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DatabaseManager::new(dir.path().join(DATABASE_FILE_NAME)).unwrap();
+
+        assert!(manager
+            .relocate(Path::new("relative/dir"), |_| Ok(()))
+            .is_err());
+        assert!(manager.relocate(dir.path(), |_| Ok(())).is_err());
+    }
+
+    fn integrity_row(id: &str, start: &str, end: Option<&str>, actual: i64) -> SessionIntegrityRow {
+        SessionIntegrityRow {
+            id: id.to_string(),
+            start_time: Some(start.to_string()),
+            end_time: end.map(str::to_string),
+            planned_duration: Some(1500),
+            actual_duration: Some(actual),
+            completed: true,
+        }
+    }
+
+    #[test]
+    fn test_detects_session_anomalies() {
+        let rows = vec![
+            integrity_row(
+                "ok",
+                "2024-03-01T09:00:00Z",
+                Some("2024-03-01T09:25:00Z"),
+                1500,
+            ),
+            // Starts right as "ok" ends
+            integrity_row(
+                "adjacent",
+                "2024-03-01T09:25:00Z",
+                Some("2024-03-01T09:30:00Z"),
+                300,
+            ),
+            integrity_row(
+                "overlapping",
+                "2024-03-01T09:10:00Z",
+                Some("2024-03-01T09:20:00Z"),
+                600,
+            ),
+            integrity_row(
+                "backwards",
+                "2024-03-01T12:00:00Z",
+                Some("2024-03-01T11:00:00Z"),
+                1500,
+            ),
+            integrity_row(
+                "overlong",
+                "2024-03-02T09:00:00Z",
+                Some("2024-03-02T12:00:00Z"),
+                10800,
+            ),
+            integrity_row("unfinished", "2024-03-03T09:00:00Z", None, 1500),
+            integrity_row("garbage", "not a date", None, 0),
+        ];
+
+        let anomalies = detect_session_anomalies(&rows);
+        let found: Vec<(&str, SessionAnomalyKind, bool)> = anomalies
+            .iter()
+            .map(|a| (a.session_id.as_str(), a.kind, a.fixable))
+            .collect();
+
+        assert_eq!(found.len(), 5, "{:?}", anomalies);
+        assert!(found.contains(&("overlapping", SessionAnomalyKind::Overlap, false)));
+        assert!(found.contains(&("backwards", SessionAnomalyKind::EndBeforeStart, true)));
+        assert!(found.contains(&("overlong", SessionAnomalyKind::DurationExceedsPlan, false)));
+        assert!(found.contains(&("unfinished", SessionAnomalyKind::MissingField, true)));
+        assert!(found.contains(&("garbage", SessionAnomalyKind::MissingField, false)));
+    }
+
+    fn focus_session(id: &str, start_time: &str) -> Session {
+        let start_time = start_time.parse::<DateTime<Utc>>().unwrap();
+        Session {
+            id: id.to_string(),
+            session_type: SessionType::Focus,
+            start_time,
+            end_time: Some(start_time + chrono::Duration::minutes(25)),
+            planned_duration: 1500,
+            actual_duration: Some(1500),
+            strict_mode: false,
+            completed: true,
+            notes: None,
+            created_at: start_time,
+            within_work_hours: true,
+            cycle_number: None,
+            is_long_break: false,
+            tag: None,
+            is_sprint: false,
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_history_counts_bypass_attempts_per_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+        let at = |timestamp: &str| timestamp.parse::<DateTime<Utc>>().unwrap();
+
+        database
+            .start_strict_mode_interval(at("2024-03-02T09:00:00Z"))
+            .unwrap();
+        database
+            .end_strict_mode_interval(at("2024-03-02T10:00:00Z"), "emergency_exit")
+            .unwrap();
+        database
+            .start_strict_mode_interval(at("2024-03-02T14:00:00Z"))
+            .unwrap();
+
+        database
+            .with_connection(|conn| {
+                for created_at in [
+                    "2024-03-02 09:30:00",
+                    "2024-03-02 09:45:00",
+                    "2024-03-02 12:00:00",
+                    "2024-03-02 14:30:00",
+                ] {
+                    conn.execute(
+                        "INSERT INTO bypass_attempts (session_id, method, timestamp, created_at)
+                         VALUES ('focus', 'escape', ?1, ?1)",
+                        [created_at],
+                    )?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        let history = database
+            .get_strict_mode_history(
+                at("2024-03-02T00:00:00Z"),
+                at("2024-03-03T00:00:00Z"),
+                at("2024-03-02T15:00:00Z"),
+            )
+            .unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert!(history[0].emergency_exit);
+        assert_eq!(history[0].duration_seconds, 3600);
+        assert_eq!(history[0].bypass_attempts, 2);
+        assert_eq!(history[1].deactivated_at, None);
+        assert_eq!(history[1].duration_seconds, 3600);
+        assert_eq!(history[1].bypass_attempts, 1);
+    }
+
+    #[test]
+    fn test_delete_sessions_in_range_removes_dependent_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        for (id, start_time) in [
+            ("before", "2024-03-01T09:00:00Z"),
+            ("inside", "2024-03-02T09:00:00Z"),
+            ("running", "2024-03-02T15:00:00Z"),
+            ("after", "2024-03-03T09:00:00Z"),
+        ] {
+            database
+                .create_session(&focus_session(id, start_time))
+                .unwrap();
+        }
+
+        database
+            .with_connection(|conn| {
+                conn.execute(
+                    "INSERT INTO evasion_attempts (session_id, attempt_type, blocked_item)
+                     VALUES ('inside', 'app', 'Slack')",
+                    [],
+                )?;
+                conn.execute(
+                    "INSERT INTO bypass_attempts (session_id, method, timestamp)
+                     VALUES ('inside', 'escape', '2024-03-02T09:10:00Z')",
+                    [],
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+        let deleted = database
+            .delete_sessions_in_range(
+                "2024-03-02T00:00:00Z".parse().unwrap(),
+                "2024-03-03T00:00:00Z".parse().unwrap(),
+                Some("running"),
+            )
+            .unwrap();
+        assert_eq!(deleted, 1);
+
+        let remaining: Vec<String> = database
+            .with_connection(|conn| {
+                let mut stmt = conn.prepare("SELECT id FROM sessions ORDER BY start_time")?;
+                let ids = stmt
+                    .query_map([], |row| row.get(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ids)
+            })
+            .unwrap();
+        assert_eq!(remaining, vec!["before", "running", "after"]);
+
+        let dependents: i64 = database
+            .with_connection(|conn| {
+                Ok(conn.query_row(
+                    "SELECT (SELECT COUNT(*) FROM evasion_attempts)
+                          + (SELECT COUNT(*) FROM bypass_attempts)",
+                    [],
+                    |row| row.get(0),
+                )?)
+            })
+            .unwrap();
+        assert_eq!(dependents, 0);
+    }
+
+    #[test]
+    fn test_ensure_schema_recreates_missing_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        assert!(database.ensure_schema().unwrap().is_empty());
+
+        database
+            .with_connection(|conn| {
+                conn.execute_batch("DROP TABLE bypass_attempts; DROP TABLE strict_mode_state;")?;
+                Ok(())
+            })
+            .unwrap();
+
+        let created = database.ensure_schema().unwrap();
+        assert_eq!(created, vec!["bypass_attempts", "strict_mode_state"]);
+
+        let (indexes, state_rows): (i64, i64) = database
+            .with_connection(|conn| {
+                Ok(conn.query_row(
+                    "SELECT (SELECT COUNT(*) FROM sqlite_master
+                             WHERE type = 'index' AND tbl_name = 'bypass_attempts'),
+                            (SELECT COUNT(*) FROM strict_mode_state WHERE id = 1)",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?)
+            })
+            .unwrap();
+        assert_eq!(indexes, 2);
+        assert_eq!(state_rows, 1);
+    }
+
+    #[test]
+    fn test_ensure_schema_adds_missing_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        database
+            .with_connection(|conn| {
+                conn.execute_batch(
+                    "DROP INDEX idx_sessions_tag;
+                     ALTER TABLE sessions DROP COLUMN tag;
+                     ALTER TABLE user_settings DROP COLUMN break_ratio;",
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+        let repaired = database.ensure_schema().unwrap();
+        assert_eq!(repaired, vec!["user_settings.break_ratio", "sessions.tag"]);
+
+        let break_ratio: f64 = database
+            .with_connection(|conn| {
+                Ok(conn.query_row(
+                    "SELECT break_ratio FROM user_settings WHERE id = 1",
+                    [],
+                    |row| row.get(0),
+                )?)
+            })
+            .unwrap();
+        assert_eq!(break_ratio, 0.2);
+        assert!(database.ensure_schema().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_daily_goal_holds_until_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+
+        assert!(database.get_daily_goal(day(4)).unwrap().is_none());
+
+        database.set_daily_goal(day(4), Some(180), None).unwrap();
+        database.set_daily_goal(day(4), Some(120), Some(4)).unwrap();
+        database.set_daily_goal(day(8), None, Some(6)).unwrap();
+
+        assert!(database.get_daily_goal(day(3)).unwrap().is_none());
+
+        let goal = database.get_daily_goal(day(6)).unwrap().unwrap();
+        assert_eq!(goal.date, day(4));
+        assert_eq!(goal.target_minutes, Some(120));
+        assert_eq!(goal.target_sessions, Some(4));
+
+        let goal = database.get_daily_goal(day(10)).unwrap().unwrap();
+        assert_eq!(goal.target_minutes, None);
+        assert_eq!(goal.target_sessions, Some(6));
+    }
+
+    #[test]
+    fn test_sessions_paginated_counts_with_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        database
+            .with_connection(|conn| {
+                for (id, session_type, start) in [
+                    ("focus-1", "focus", "2024-03-02T09:00:00Z"),
+                    ("short-1", "short_break", "2024-03-02T09:25:00Z"),
+                    ("focus-2", "focus", "2024-03-02T09:30:00Z"),
+                    ("long-1", "long_break", "2024-03-02T09:55:00Z"),
+                    ("focus-3", "focus", "2024-03-02T10:10:00Z"),
+                ] {
+                    conn.execute(
+                        "INSERT INTO sessions (id, session_type, start_time, planned_duration)
+                         VALUES (?1, ?2, ?3, 1500)",
+                        params![id, session_type, start],
+                    )?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        let ids = |sessions: Vec<Session>| -> Vec<String> {
+            sessions.into_iter().map(|session| session.id).collect()
+        };
+
+        let (page, total) = database.get_sessions_paginated(1, 2, None).unwrap();
+        assert_eq!(total, 5);
+        assert_eq!(ids(page), vec!["long-1", "focus-2"]);
+
+        let (page, total) = database
+            .get_sessions_paginated(0, 10, Some(SessionHistoryFilter::Breaks))
+            .unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(ids(page), vec!["long-1", "short-1"]);
+
+        let (page, total) = database
+            .get_sessions_paginated(2, 10, Some(SessionHistoryFilter::Focus))
+            .unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(ids(page), vec!["focus-1"]);
+    }
+
+    #[test]
+    fn test_sound_config_round_trips_through_settings() {
+        use crate::database::models::{SoundConfig, SoundName};
+
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        let mut settings = UserSettings::default();
+        assert!(!settings.sound_config.enabled);
+
+        settings.sound_config = SoundConfig {
+            enabled: true,
+            focus_end: SoundName::Bell,
+            break_end: SoundName::None,
+        };
+        database.save_user_settings(&settings).unwrap();
+
+        let saved = database.get_user_settings().unwrap().unwrap();
+        assert_eq!(saved.sound_config, settings.sound_config);
+    }
+
+    #[test]
+    fn test_cycle_state_keeps_one_saved_row() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        assert!(database.get_cycle_state().unwrap().is_none());
+
+        let mut saved = SavedCycleState {
+            phase: "focus".to_string(),
+            remaining_seconds: 900,
+            phase_seconds: 1500,
+            cycle_count: 2,
+            is_running: true,
+            session_id: Some("session-1".to_string()),
+            started_at: Some(Utc::now()),
+            within_work_hours: true,
+            is_sprint: false,
+            is_wind_down: false,
+        };
+        database.save_cycle_state(&saved).unwrap();
+
+        saved.phase = "idle".to_string();
+        saved.session_id = None;
+        saved.started_at = None;
+        database.save_cycle_state(&saved).unwrap();
+
+        assert_eq!(database.get_cycle_state().unwrap(), Some(saved));
+    }
+
+    #[test]
+    fn test_cycle_presets_add_update_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        let light = database
+            .save_cycle_preset(&CyclePreset {
+                id: None,
+                name: "light".to_string(),
+                focus_duration: 25,
+                break_duration: 5,
+                long_break_duration: 15,
+                cycles_per_long_break: 4,
+            })
+            .unwrap();
+        let mut deep = database
+            .save_cycle_preset(&CyclePreset {
+                id: None,
+                name: "Deep work".to_string(),
+                focus_duration: 50,
+                break_duration: 10,
+                long_break_duration: 30,
+                cycles_per_long_break: 3,
+            })
+            .unwrap();
+        assert_ne!(light.id, deep.id);
+
+        deep.focus_duration = 45;
+        database.save_cycle_preset(&deep).unwrap();
+        assert_eq!(
+            database.get_cycle_preset(deep.id.unwrap()).unwrap(),
+            Some(deep.clone())
+        );
+        assert_eq!(
+            database.list_cycle_presets().unwrap(),
+            vec![deep.clone(), light.clone()]
+        );
+
+        assert!(database.delete_cycle_preset(light.id.unwrap()).unwrap());
+        assert!(!database.delete_cycle_preset(light.id.unwrap()).unwrap());
+        assert!(database.save_cycle_preset(&light).is_err());
+        assert_eq!(database.list_cycle_presets().unwrap(), vec![deep]);
+    }
+
+    #[test]
+    fn test_hotkey_configs_replace_by_action() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        let mut focus = StoredHotkeyConfig {
+            action: "ToggleFocusSession".to_string(),
+            shortcut: "shift+super+KeyF".to_string(),
+            enabled: true,
+        };
+        let palette = StoredHotkeyConfig {
+            action: "ToggleCommandPalette".to_string(),
+            shortcut: "super+Space".to_string(),
+            enabled: false,
+        };
+        database.save_hotkey_config(&focus).unwrap();
+        database.save_hotkey_config(&palette).unwrap();
+
+        focus.shortcut = "alt+shift+KeyF".to_string();
+        database.save_hotkey_config(&focus).unwrap();
+
+        assert_eq!(database.get_hotkey_configs().unwrap(), vec![palette, focus]);
+    }
+}
@@ -1,18 +1,69 @@
-use crate::database::schema::{INITIAL_SCHEMA, SCHEMA_VERSION};
+use crate::database::schema::{
+    TableDefinition, BYPASS_ATTEMPTS_TABLE, CREATE_SCHEMA_VERSION, CYCLE_PRESETS_TABLE,
+    CYCLE_STATE_TABLE, DAILY_GOALS_TABLE, HOTKEY_CONFIGS_TABLE, NOTIFICATION_HISTORY_TABLE,
+    REQUIRED_TABLES, SCHEMA_VERSION, STRICT_MODE_SESSIONS_TABLE, STRICT_MODE_STATE_TABLE,
+    WORK_SCHEDULE_TABLE,
+};
 use crate::database::{DatabaseError, DatabaseResult};
 use rusqlite::{Connection, OptionalExtension};
 
 /// Migration manager for handling database schema changes
 pub struct MigrationManager;
 
+/// A column as reported by `PRAGMA table_info`
+struct ColumnInfo {
+    name: String,
+    declared_type: String,
+    not_null: bool,
+    default: Option<String>,
+}
+
+impl ColumnInfo {
+    /// Column definition for `ALTER TABLE ... ADD COLUMN`
+    fn definition(&self, table: &str) -> DatabaseResult<String> {
+        let mut definition = format!("{} {}", self.name, self.declared_type);
+
+        if self.not_null {
+            // SQLite can only add a NOT NULL column that has a default
+            if self.default.is_none() {
+                return Err(DatabaseError::Migration(format!(
+                    "Cannot add required column {}.{} without a default",
+                    table, self.name
+                )));
+            }
+            definition.push_str(" NOT NULL");
+        }
+        if let Some(default) = &self.default {
+            definition.push_str(&format!(" DEFAULT {}", default));
+        }
+
+        Ok(definition)
+    }
+}
+
 impl MigrationManager {
     /// Initialize a new database with the current schema
     pub fn initialize_database(conn: &Connection) -> DatabaseResult<()> {
-        // Execute the initial schema
-        conn.execute_batch(INITIAL_SCHEMA)
+        let tx = conn
+            .unchecked_transaction()
             .map_err(DatabaseError::Sqlite)?;
 
-        Ok(())
+        // The same definitions `ensure_schema` repairs from, so a new
+        // database matches one that went through every migration
+        tx.execute(CREATE_SCHEMA_VERSION, [])
+            .map_err(DatabaseError::Sqlite)?;
+        for table in REQUIRED_TABLES {
+            Self::create_table(&tx, table)?;
+        }
+
+        // Nothing to migrate on a database created at the current version
+        tx.execute(
+            "INSERT INTO schema_version (version) VALUES (?1)",
+            [SCHEMA_VERSION],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        tx.commit().map_err(DatabaseError::Sqlite)
     }
 
     /// Check if database exists and has tables
@@ -60,7 +111,7 @@ impl MigrationManager {
     fn apply_migration(conn: &Connection, version: i32) -> DatabaseResult<()> {
         match version {
             1 => {
-                // Version 1 is the initial schema, already handled in initialize_database
+                // Version 1 is the initial schema, which predates migrations
                 Ok(())
             }
             2 => {
@@ -99,6 +150,150 @@ impl MigrationManager {
                 // Version 10: Add strict_mode_state table for runtime state persistence
                 Self::migrate_to_v10(conn)
             }
+            11 => {
+                // Version 11: Add tag to sessions table
+                Self::migrate_to_v11(conn)
+            }
+            12 => {
+                // Version 12: Add strict_focus_show_widget to user_settings
+                Self::migrate_to_v12(conn)
+            }
+            13 => {
+                // Version 13: Add focus_widget_pause_behavior to user_settings
+                Self::migrate_to_v13(conn)
+            }
+            14 => {
+                // Version 14: Add work_hours_enforcement to user_settings
+                Self::migrate_to_v14(conn)
+            }
+            15 => {
+                // Version 15: Add tray_icon_style to user_settings
+                Self::migrate_to_v15(conn)
+            }
+            16 => {
+                // Version 16: Add show_break_transition to user_settings
+                Self::migrate_to_v16(conn)
+            }
+            17 => {
+                // Version 17: Add strict_require_ack to user_settings
+                Self::migrate_to_v17(conn)
+            }
+            18 => {
+                // Version 18: Add overlay_escape_action to user_settings
+                Self::migrate_to_v18(conn)
+            }
+            19 => {
+                // Version 19: Add theme to user_settings
+                Self::migrate_to_v19(conn)
+            }
+            20 => {
+                // Version 20: Add tray_click_action to user_settings
+                Self::migrate_to_v20(conn)
+            }
+            21 => {
+                // Version 21: Add is_sprint to sessions table
+                Self::migrate_to_v21(conn)
+            }
+            22 => {
+                // Version 22: Add tick_interval_ms to user_settings
+                Self::migrate_to_v22(conn)
+            }
+            23 => {
+                // Version 23: Add power_save to user_settings
+                Self::migrate_to_v23(conn)
+            }
+            24 => {
+                // Version 24: Add break_message to user_settings
+                Self::migrate_to_v24(conn)
+            }
+            25 => {
+                // Version 25: Add phase_colors to user_settings
+                Self::migrate_to_v25(conn)
+            }
+            26 => {
+                // Version 26: Add post_long_break_focus_duration to user_settings
+                Self::migrate_to_v26(conn)
+            }
+            27 => {
+                // Version 27: Add strict_mode_sessions table
+                Self::migrate_to_v27(conn)
+            }
+            28 => {
+                // Version 28: Add soft_overlay_dismiss_action to user_settings
+                Self::migrate_to_v28(conn)
+            }
+            29 => {
+                // Version 29: Add break_snooze_minutes to user_settings
+                Self::migrate_to_v29(conn)
+            }
+            30 => {
+                // Version 30: Add notifications_muted_until to user_settings
+                Self::migrate_to_v30(conn)
+            }
+            31 => {
+                // Version 31: Add phase_command_hooks to user_settings
+                Self::migrate_to_v31(conn)
+            }
+            32 => {
+                // Version 32: Add weekly_focus_budget_minutes to user_settings
+                Self::migrate_to_v32(conn)
+            }
+            33 => {
+                // Version 33: Add max_break_overrun_minutes to user_settings
+                Self::migrate_to_v33(conn)
+            }
+            34 => {
+                // Version 34: Add overrun_seconds to sessions table
+                Self::migrate_to_v34(conn)
+            }
+            35 => {
+                // Version 35: Add scheduled_focus_at to user_settings
+                Self::migrate_to_v35(conn)
+            }
+            36 => {
+                // Version 36: Add mood to sessions table
+                Self::migrate_to_v36(conn)
+            }
+            37 => {
+                // Version 37: Add session_save_interval_seconds to user_settings
+                Self::migrate_to_v37(conn)
+            }
+            38 => {
+                // Version 38: Add break ratio settings to user_settings
+                Self::migrate_to_v38(conn)
+            }
+            39 => {
+                // Version 39: Add prevent_display_sleep_during_focus to user_settings
+                Self::migrate_to_v39(conn)
+            }
+            40 => {
+                // Version 40: Add strict mode disable delay to user_settings
+                Self::migrate_to_v40(conn)
+            }
+            41 => {
+                // Version 41: Add winddown_minutes to user_settings
+                Self::migrate_to_v41(conn)
+            }
+            42 => {
+                // Version 42: Add daily_goals table
+                Self::migrate_to_v42(conn)
+            }
+            43 => {
+                // Version 43: Add sound_config to user_settings
+                Self::migrate_to_v43(conn)
+            }
+            44 => {
+                // Version 44: Add cycle_state table
+                Self::migrate_to_v44(conn)
+            }
+            45 => {
+                // Version 45: Add cycle_presets table
+                Self::migrate_to_v45(conn)
+            }
+            46 => {
+                // Version 46: Add hotkey_configs table
+                Self::migrate_to_v46(conn)
+            }
             _ => Err(DatabaseError::Migration(format!(
                 "Unknown migration version: {}",
                 version
@@ -110,28 +305,8 @@ impl MigrationManager {
     fn migrate_to_v2(conn: &Connection) -> DatabaseResult<()> {
         println!("Applying migration to version 2: Adding work_schedule table");
 
-        // Create work_schedule table
-        conn.execute(
-            r#"
-            CREATE TABLE work_schedule (
-                id INTEGER PRIMARY KEY,
-                user_id INTEGER NOT NULL DEFAULT 1,
-                use_work_schedule BOOLEAN NOT NULL DEFAULT FALSE,
-                work_start_time TEXT,
-                work_end_time TEXT,
-                timezone TEXT NOT NULL DEFAULT 'local',
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                FOREIGN KEY (user_id) REFERENCES user_settings (id)
-            )
-            "#,
-            [],
-        )
-        .map_err(DatabaseError::Sqlite)?;
-
-        // Insert default work schedule for existing user
-        conn.execute("INSERT INTO work_schedule (id, user_id) VALUES (1, 1)", [])
-            .map_err(DatabaseError::Sqlite)?;
+        // Create work_schedule table with the default row for the existing user
+        Self::create_table(conn, &WORK_SCHEDULE_TABLE)?;
 
         // Update schema version
         conn.execute("INSERT INTO schema_version (version) VALUES (2)", [])
@@ -215,19 +390,10 @@ impl MigrationManager {
     /// Validate database integrity
     pub fn validate_database(conn: &Connection) -> DatabaseResult<()> {
         // Check that all required tables exist
-        let required_tables = vec![
-            "user_settings",
-            "block_list",
-            "sessions",
-            "evasion_attempts",
-            "insights",
-            "work_schedule",
-            "onboarding_completion",
-            "notification_history",
-            "bypass_attempts",
-            "strict_mode_state",
-            "schema_version",
-        ];
+        let required_tables = REQUIRED_TABLES
+            .iter()
+            .map(|table| table.name)
+            .chain(std::iter::once("schema_version"));
 
         for table in required_tables {
             let exists: bool = conn
@@ -283,6 +449,97 @@ impl MigrationManager {
         Ok(())
     }
 
+    /// Create every required table that is missing and add the columns an
+    /// existing one lacks, e.g. after a migration was interrupted. Returns
+    /// the tables created and the columns added, as `table.column`.
+    pub fn ensure_schema(conn: &Connection) -> DatabaseResult<Vec<String>> {
+        // Scratch database holding the tables exactly as defined, to read
+        // the expected columns from
+        let reference = Connection::open_in_memory().map_err(DatabaseError::Sqlite)?;
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(DatabaseError::Sqlite)?;
+        let mut repaired = Vec::new();
+
+        for table in REQUIRED_TABLES {
+            let existing = Self::table_columns(&tx, table.name)?;
+
+            if existing.is_empty() {
+                Self::create_table(&tx, table)?;
+                repaired.push(table.name.to_string());
+                continue;
+            }
+
+            reference
+                .execute(table.create, [])
+                .map_err(DatabaseError::Sqlite)?;
+
+            for column in Self::table_columns(&reference, table.name)? {
+                if existing.iter().any(|c| c.name == column.name) {
+                    continue;
+                }
+
+                tx.execute(
+                    &format!(
+                        "ALTER TABLE {} ADD COLUMN {}",
+                        table.name,
+                        column.definition(table.name)?
+                    ),
+                    [],
+                )
+                .map_err(DatabaseError::Sqlite)?;
+                repaired.push(format!("{}.{}", table.name, column.name));
+            }
+
+            // Indexes over the added columns
+            for index in table.indexes {
+                tx.execute(index, []).map_err(DatabaseError::Sqlite)?;
+            }
+        }
+
+        tx.commit().map_err(DatabaseError::Sqlite)?;
+        Ok(repaired)
+    }
+
+    /// Columns of `table`, empty if it does not exist
+    fn table_columns(conn: &Connection, table: &str) -> DatabaseResult<Vec<ColumnInfo>> {
+        let mut stmt = conn
+            .prepare(&format!("PRAGMA table_info({})", table))
+            .map_err(DatabaseError::Sqlite)?;
+
+        let columns = stmt
+            .query_map([], |row| {
+                Ok(ColumnInfo {
+                    name: row.get(1)?,
+                    declared_type: row.get(2)?,
+                    not_null: row.get(3)?,
+                    default: row.get(4)?,
+                })
+            })
+            .map_err(DatabaseError::Sqlite)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(DatabaseError::Sqlite)?;
+
+        Ok(columns)
+    }
+
+    /// Create `table` with its indexes and default row; safe to run on an
+    /// existing table
+    fn create_table(conn: &Connection, table: &TableDefinition) -> DatabaseResult<()> {
+        conn.execute(table.create, [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        for index in table.indexes {
+            conn.execute(index, []).map_err(DatabaseError::Sqlite)?;
+        }
+
+        if let Some(seed) = table.seed {
+            conn.execute(seed, []).map_err(DatabaseError::Sqlite)?;
+        }
+
+        Ok(())
+    }
+
     /// Create a backup of the database before migrations
     pub fn backup_database(source_path: &str, backup_path: &str) -> DatabaseResult<()> {
         std::fs::copy(source_path, backup_path).map_err(|e| {
@@ -297,35 +554,8 @@ impl MigrationManager {
     fn migrate_to_v6(conn: &Connection) -> DatabaseResult<()> {
         println!("Applying migration to version 6: Adding notification_history table");
 
-        // Create notification_history table
-        conn.execute(
-            r#"
-            CREATE TABLE notification_history (
-                id INTEGER PRIMARY KEY,
-                session_id TEXT,
-                notification_type TEXT NOT NULL,
-                title TEXT NOT NULL,
-                message TEXT NOT NULL,
-                sent_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                FOREIGN KEY (session_id) REFERENCES sessions (id)
-            )
-            "#,
-            [],
-        )
-        .map_err(DatabaseError::Sqlite)?;
-
-        // Create index for faster queries
-        conn.execute(
-            "CREATE INDEX idx_notification_history_sent_at ON notification_history (sent_at)",
-            [],
-        )
-        .map_err(DatabaseError::Sqlite)?;
-
-        conn.execute(
-            "CREATE INDEX idx_notification_history_session ON notification_history (session_id)",
-            [],
-        )
-        .map_err(DatabaseError::Sqlite)?;
+        // Create notification_history table and its indexes
+        Self::create_table(conn, &NOTIFICATION_HISTORY_TABLE)?;
 
         // Update schema version
         conn.execute("INSERT INTO schema_version (version) VALUES (6)", [])
@@ -339,33 +569,8 @@ impl MigrationManager {
     fn migrate_to_v7(conn: &Connection) -> DatabaseResult<()> {
         println!("Applying migration to version 7: Adding bypass_attempts table");
 
-        // Create bypass_attempts table
-        conn.execute(
-            r#"
-            CREATE TABLE bypass_attempts (
-                id INTEGER PRIMARY KEY,
-                session_id TEXT NOT NULL,
-                method TEXT NOT NULL,
-                timestamp TEXT NOT NULL,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            )
-            "#,
-            [],
-        )
-        .map_err(DatabaseError::Sqlite)?;
-
-        // Create index for faster queries
-        conn.execute(
-            "CREATE INDEX idx_bypass_attempts_session ON bypass_attempts (session_id)",
-            [],
-        )
-        .map_err(DatabaseError::Sqlite)?;
-
-        conn.execute(
-            "CREATE INDEX idx_bypass_attempts_created_at ON bypass_attempts (created_at)",
-            [],
-        )
-        .map_err(DatabaseError::Sqlite)?;
+        // Create bypass_attempts table and its indexes
+        Self::create_table(conn, &BYPASS_ATTEMPTS_TABLE)?;
 
         // Update schema version
         conn.execute("INSERT INTO schema_version (version) VALUES (7)", [])
@@ -437,30 +642,711 @@ impl MigrationManager {
     fn migrate_to_v10(conn: &Connection) -> DatabaseResult<()> {
         println!("Applying migration to version 10: Adding strict_mode_state table");
 
-        // Create strict_mode_state table
+        // Create strict_mode_state table with its default row
+        Self::create_table(conn, &STRICT_MODE_STATE_TABLE)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (10)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 10 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 11: Add tag to sessions table
+    fn migrate_to_v11(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 11: Adding tag to sessions");
+
+        // Add tag column to sessions table
+        conn.execute("ALTER TABLE sessions ADD COLUMN tag TEXT", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        // Create index for per-tag stats queries
+        conn.execute("CREATE INDEX idx_sessions_tag ON sessions (tag)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (11)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 11 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 12: Add strict_focus_show_widget to user_settings
+    fn migrate_to_v12(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 12: Adding strict_focus_show_widget to user_settings"
+        );
+
+        // Add strict_focus_show_widget column to user_settings table
         conn.execute(
-            r#"
-            CREATE TABLE strict_mode_state (
-                id INTEGER PRIMARY KEY,
-                is_active BOOLEAN NOT NULL DEFAULT FALSE,
-                is_locked BOOLEAN NOT NULL DEFAULT FALSE,
-                current_window_type TEXT,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            )
-            "#,
+            "ALTER TABLE user_settings ADD COLUMN strict_focus_show_widget BOOLEAN NOT NULL DEFAULT FALSE",
             [],
         )
         .map_err(DatabaseError::Sqlite)?;
 
-        // Insert default strict mode state
-        conn.execute("INSERT INTO strict_mode_state (id) VALUES (1)", [])
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (12)", [])
             .map_err(DatabaseError::Sqlite)?;
 
+        println!("Migration to version 12 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 13: Add focus_widget_pause_behavior to user_settings
+    fn migrate_to_v13(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 13: Adding focus_widget_pause_behavior to user_settings"
+        );
+
+        // Add focus_widget_pause_behavior column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN focus_widget_pause_behavior TEXT NOT NULL DEFAULT 'show_paused'",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
         // Update schema version
-        conn.execute("INSERT INTO schema_version (version) VALUES (10)", [])
+        conn.execute("INSERT INTO schema_version (version) VALUES (13)", [])
             .map_err(DatabaseError::Sqlite)?;
 
-        println!("Migration to version 10 completed successfully");
+        println!("Migration to version 13 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 14: Add work_hours_enforcement to user_settings
+    fn migrate_to_v14(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 14: Adding work_hours_enforcement to user_settings"
+        );
+
+        // Add work_hours_enforcement column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN work_hours_enforcement TEXT NOT NULL DEFAULT 'block'",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (14)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 14 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 15: Add tray_icon_style to user_settings
+    fn migrate_to_v15(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 15: Adding tray_icon_style to user_settings");
+
+        // Add tray_icon_style column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN tray_icon_style TEXT NOT NULL DEFAULT 'auto'",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (15)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 15 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 16: Add show_break_transition to user_settings
+    fn migrate_to_v16(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 16: Adding show_break_transition to user_settings");
+
+        // Add show_break_transition column to user_settings table; existing rows
+        // default to TRUE so strict mode keeps showing the transition as before
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN show_break_transition BOOLEAN NOT NULL DEFAULT TRUE",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (16)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 16 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 17: Add strict_require_ack to user_settings
+    fn migrate_to_v17(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 17: Adding strict_require_ack to user_settings");
+
+        // Add strict_require_ack column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN strict_require_ack BOOLEAN NOT NULL DEFAULT FALSE",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (17)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 17 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 18: Add overlay_escape_action to user_settings
+    fn migrate_to_v18(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 18: Adding overlay_escape_action to user_settings");
+
+        // Add overlay_escape_action column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN overlay_escape_action TEXT NOT NULL DEFAULT 'log_bypass'",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (18)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 18 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 19: Add theme to user_settings
+    fn migrate_to_v19(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 19: Adding theme to user_settings");
+
+        // Add theme column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN theme TEXT NOT NULL DEFAULT 'system'",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (19)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 19 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 20: Add tray_click_action to user_settings
+    fn migrate_to_v20(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 20: Adding tray_click_action to user_settings");
+
+        // Add tray_click_action column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN tray_click_action TEXT NOT NULL DEFAULT 'show_main_window'",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (20)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 20 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 21: Add is_sprint to sessions table
+    fn migrate_to_v21(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 21: Adding is_sprint to sessions");
+
+        // Add is_sprint column to sessions table
+        conn.execute(
+            "ALTER TABLE sessions ADD COLUMN is_sprint BOOLEAN NOT NULL DEFAULT FALSE",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (21)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 21 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 22: Add tick_interval_ms to user_settings
+    fn migrate_to_v22(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 22: Adding tick_interval_ms to user_settings");
+
+        // Add tick_interval_ms column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN tick_interval_ms INTEGER NOT NULL DEFAULT 1000",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (22)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 22 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 23: Add power_save to user_settings
+    fn migrate_to_v23(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 23: Adding power_save to user_settings");
+
+        // Add power_save column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN power_save BOOLEAN NOT NULL DEFAULT FALSE",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (23)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 23 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 24: Add break_message to user_settings
+    fn migrate_to_v24(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 24: Adding break_message to user_settings");
+
+        // Add break_message column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN break_message TEXT",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (24)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 24 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 25: Add phase_colors to user_settings
+    fn migrate_to_v25(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 25: Adding phase_colors to user_settings");
+
+        // Add phase_colors column to user_settings table
+        conn.execute("ALTER TABLE user_settings ADD COLUMN phase_colors TEXT", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (25)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 25 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 26: Add post_long_break_focus_duration to user_settings
+    fn migrate_to_v26(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 26: Adding post_long_break_focus_duration to user_settings");
+
+        // Add post_long_break_focus_duration column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN post_long_break_focus_duration INTEGER",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (26)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 26 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 27: Add strict_mode_sessions table
+    fn migrate_to_v27(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 27: Adding strict_mode_sessions table");
+
+        // One row per stretch of strict mode; deactivated_at is NULL while it is on
+        Self::create_table(conn, &STRICT_MODE_SESSIONS_TABLE)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (27)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 27 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 28: Add soft_overlay_dismiss_action to user_settings
+    fn migrate_to_v28(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 28: Adding soft_overlay_dismiss_action to user_settings"
+        );
+
+        // Add soft_overlay_dismiss_action column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN soft_overlay_dismiss_action TEXT NOT NULL DEFAULT 'hide_overlay'",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (28)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 28 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 29: Add break_snooze_minutes to user_settings
+    fn migrate_to_v29(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 29: Adding break_snooze_minutes to user_settings");
+
+        // Add break_snooze_minutes column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN break_snooze_minutes INTEGER NOT NULL DEFAULT 5",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (29)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 29 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 30: Add notifications_muted_until to user_settings
+    fn migrate_to_v30(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 30: Adding notifications_muted_until to user_settings"
+        );
+
+        // Add notifications_muted_until column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN notifications_muted_until DATETIME",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (30)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 30 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 31: Add phase_command_hooks to user_settings
+    fn migrate_to_v31(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 31: Adding phase_command_hooks to user_settings");
+
+        // Add phase_command_hooks column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN phase_command_hooks TEXT",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (31)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 31 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 32: Add weekly_focus_budget_minutes to user_settings
+    fn migrate_to_v32(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 32: Adding weekly_focus_budget_minutes to user_settings"
+        );
+
+        // Add weekly_focus_budget_minutes column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN weekly_focus_budget_minutes INTEGER",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (32)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 32 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 33: Add max_break_overrun_minutes to user_settings
+    fn migrate_to_v33(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 33: Adding max_break_overrun_minutes to user_settings"
+        );
+
+        // Add max_break_overrun_minutes column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN max_break_overrun_minutes INTEGER",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (33)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 33 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 34: Add overrun_seconds to sessions table
+    fn migrate_to_v34(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 34: Adding overrun_seconds to sessions");
+
+        // Add overrun_seconds column to sessions table
+        conn.execute(
+            "ALTER TABLE sessions ADD COLUMN overrun_seconds INTEGER",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (34)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 34 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 35: Add scheduled_focus_at to user_settings
+    fn migrate_to_v35(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 35: Adding scheduled_focus_at to user_settings");
+
+        // Add scheduled_focus_at column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN scheduled_focus_at DATETIME",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (35)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 35 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 36: Add mood to sessions table
+    fn migrate_to_v36(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 36: Adding mood to sessions");
+
+        // Add mood column to sessions table (1-5 rating, NULL when not rated)
+        conn.execute("ALTER TABLE sessions ADD COLUMN mood INTEGER", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (36)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 36 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 37: Add session_save_interval_seconds to user_settings
+    fn migrate_to_v37(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 37: Adding session_save_interval_seconds to user_settings");
+
+        // Add session_save_interval_seconds column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN session_save_interval_seconds INTEGER NOT NULL DEFAULT 30",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (37)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 37 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 38: Add break ratio settings to user_settings
+    fn migrate_to_v38(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 38: Adding break ratio settings to user_settings");
+
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN break_ratio_mode BOOLEAN NOT NULL DEFAULT FALSE",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN break_ratio REAL NOT NULL DEFAULT 0.2",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN break_ratio_min_duration INTEGER NOT NULL DEFAULT 300",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN break_ratio_max_duration INTEGER NOT NULL DEFAULT 1800",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (38)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 38 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 39: Add prevent_display_sleep_during_focus to user_settings
+    fn migrate_to_v39(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 39: Adding prevent_display_sleep_during_focus to user_settings");
+
+        // Add prevent_display_sleep_during_focus column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN prevent_display_sleep_during_focus BOOLEAN DEFAULT 0",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (39)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 39 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 40: Add strict mode disable delay to user_settings
+    fn migrate_to_v40(conn: &Connection) -> DatabaseResult<()> {
+        println!(
+            "Applying migration to version 40: Adding strict mode disable delay to user_settings"
+        );
+
+        // Add strict_disable_delay_seconds column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN strict_disable_delay_seconds INTEGER DEFAULT 0",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Add strict_disable_at column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN strict_disable_at DATETIME",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (40)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 40 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 41: Add winddown_minutes to user_settings
+    fn migrate_to_v41(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 41: Adding winddown_minutes to user_settings");
+
+        // Add winddown_minutes column to user_settings table
+        conn.execute(
+            "ALTER TABLE user_settings ADD COLUMN winddown_minutes INTEGER",
+            [],
+        )
+        .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (41)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 41 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 42: Add daily_goals table
+    fn migrate_to_v42(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 42: Adding daily_goals table");
+
+        // One row per day the goal changed; a goal holds until the next row
+        Self::create_table(conn, &DAILY_GOALS_TABLE)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (42)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 42 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 43: Add sound_config to user_settings
+    fn migrate_to_v43(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 43: Adding sound_config to user_settings");
+
+        // Add sound_config column to user_settings table
+        conn.execute("ALTER TABLE user_settings ADD COLUMN sound_config TEXT", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (43)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 43 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 44: Add cycle_state table
+    fn migrate_to_v44(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 44: Adding cycle_state table");
+
+        // A single row holding the cycle orchestrator's phase across restarts
+        Self::create_table(conn, &CYCLE_STATE_TABLE)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (44)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 44 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 45: Add cycle_presets table
+    fn migrate_to_v45(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 45: Adding cycle_presets table");
+
+        // Named focus/break durations the user can switch between
+        Self::create_table(conn, &CYCLE_PRESETS_TABLE)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (45)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 45 completed successfully");
+        Ok(())
+    }
+
+    /// Migration to version 46: Add hotkey_configs table
+    fn migrate_to_v46(conn: &Connection) -> DatabaseResult<()> {
+        println!("Applying migration to version 46: Adding hotkey_configs table");
+
+        // Custom global shortcuts, replacing the defaults at startup
+        Self::create_table(conn, &HOTKEY_CONFIGS_TABLE)?;
+
+        // Update schema version
+        conn.execute("INSERT INTO schema_version (version) VALUES (46)", [])
+            .map_err(DatabaseError::Sqlite)?;
+
+        println!("Migration to version 46 completed successfully");
         Ok(())
     }
 }
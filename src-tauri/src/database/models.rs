@@ -1,4 +1,4 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// User settings model matching the database schema
@@ -16,6 +16,68 @@ pub struct UserSettings {
     pub user_name: Option<String>,
     pub emergency_key_combination: Option<String>,
     pub break_transition_seconds: i32,
+    #[serde(default)]
+    pub strict_focus_show_widget: bool,
+    #[serde(default)]
+    pub focus_widget_pause_behavior: FocusWidgetPauseBehavior,
+    #[serde(default)]
+    pub work_hours_enforcement: WorkHoursEnforcement,
+    #[serde(default)]
+    pub tray_icon_style: TrayIconStyle,
+    #[serde(default = "default_show_break_transition")]
+    pub show_break_transition: bool,
+    #[serde(default)]
+    pub strict_require_ack: bool,
+    #[serde(default)]
+    pub overlay_escape_action: OverlayEscapeAction,
+    #[serde(default)]
+    pub theme: AppTheme,
+    #[serde(default)]
+    pub tray_click_action: TrayClickAction,
+    #[serde(default = "default_tick_interval_ms")]
+    pub tick_interval_ms: i32,
+    #[serde(default)]
+    pub power_save: bool,
+    #[serde(default)]
+    pub break_message: Option<String>,
+    #[serde(default)]
+    pub phase_colors: PhaseColors,
+    #[serde(default)]
+    pub post_long_break_focus_duration: Option<i32>,
+    #[serde(default)]
+    pub soft_overlay_dismiss_action: SoftOverlayDismissAction,
+    #[serde(default = "default_break_snooze_minutes")]
+    pub break_snooze_minutes: i32,
+    #[serde(default)]
+    pub notifications_muted_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub phase_command_hooks: PhaseCommandHooks,
+    #[serde(default)]
+    pub weekly_focus_budget_minutes: Option<i32>,
+    #[serde(default)]
+    pub max_break_overrun_minutes: Option<i32>,
+    #[serde(default)]
+    pub scheduled_focus_at: Option<DateTime<Utc>>,
+    #[serde(default = "default_session_save_interval_seconds")]
+    pub session_save_interval_seconds: i32,
+    #[serde(default)]
+    pub break_ratio_mode: bool,
+    #[serde(default = "default_break_ratio")]
+    pub break_ratio: f64,
+    #[serde(default = "default_break_ratio_min_duration")]
+    pub break_ratio_min_duration: i32,
+    #[serde(default = "default_break_ratio_max_duration")]
+    pub break_ratio_max_duration: i32,
+    #[serde(default)]
+    pub prevent_display_sleep_during_focus: bool,
+    #[serde(default)]
+    pub strict_disable_delay_seconds: i32,
+    #[serde(default)]
+    pub strict_disable_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub winddown_minutes: Option<i32>,
+    #[serde(default)]
+    pub sound_config: SoundConfig,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -36,12 +98,73 @@ impl Default for UserSettings {
             user_name: None,
             emergency_key_combination: None,
             break_transition_seconds: 10, // 10 seconds default
+            strict_focus_show_widget: false,
+            focus_widget_pause_behavior: FocusWidgetPauseBehavior::ShowPaused,
+            work_hours_enforcement: WorkHoursEnforcement::Block,
+            tray_icon_style: TrayIconStyle::Auto,
+            show_break_transition: true,
+            strict_require_ack: false,
+            overlay_escape_action: OverlayEscapeAction::LogBypass,
+            theme: AppTheme::System,
+            tray_click_action: TrayClickAction::ShowMainWindow,
+            tick_interval_ms: 1000,
+            power_save: false,
+            break_message: None,
+            phase_colors: PhaseColors::default(),
+            post_long_break_focus_duration: None,
+            soft_overlay_dismiss_action: SoftOverlayDismissAction::HideOverlay,
+            break_snooze_minutes: 5,
+            notifications_muted_until: None,
+            phase_command_hooks: PhaseCommandHooks::default(),
+            weekly_focus_budget_minutes: None,
+            max_break_overrun_minutes: None,
+            scheduled_focus_at: None,
+            session_save_interval_seconds: 30,
+            break_ratio_mode: false,
+            break_ratio: 0.2,
+            break_ratio_min_duration: 300,
+            break_ratio_max_duration: 1800,
+            prevent_display_sleep_during_focus: false,
+            strict_disable_delay_seconds: 0,
+            strict_disable_at: None,
+            winddown_minutes: None,
+            sound_config: SoundConfig::default(),
             created_at: now,
             updated_at: now,
         }
     }
 }
 
+/// On by default: the transition window always showed before this setting
+/// existed, so upgraded installs keep the same strict mode flow
+fn default_show_break_transition() -> bool {
+    true
+}
+
+fn default_tick_interval_ms() -> i32 {
+    1000
+}
+
+fn default_break_snooze_minutes() -> i32 {
+    5
+}
+
+fn default_session_save_interval_seconds() -> i32 {
+    30
+}
+
+fn default_break_ratio() -> f64 {
+    0.2
+}
+
+fn default_break_ratio_min_duration() -> i32 {
+    300
+}
+
+fn default_break_ratio_max_duration() -> i32 {
+    1800
+}
+
 /// Block list item model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockListItem {
@@ -97,6 +220,9 @@ pub struct Session {
     pub within_work_hours: bool,
     pub cycle_number: Option<i32>,
     pub is_long_break: bool,
+    pub tag: Option<String>,
+    /// Standalone focus sprint, outside the regular cycle
+    pub is_sprint: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -130,6 +256,461 @@ impl std::str::FromStr for SessionType {
     }
 }
 
+/// Which sessions a history page lists. Breaks cover both short and long ones.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionHistoryFilter {
+    Focus,
+    Breaks,
+}
+
+impl SessionHistoryFilter {
+    /// SQL condition on `session_type` matching this filter
+    pub fn condition(filter: Option<Self>) -> &'static str {
+        match filter {
+            None => "1 = 1",
+            Some(Self::Focus) => "session_type = 'focus'",
+            Some(Self::Breaks) => "session_type IN ('short_break', 'long_break')",
+        }
+    }
+}
+
+/// One page of session history, newest first, with the number of sessions
+/// matching the filter across all pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionHistoryPage {
+    pub sessions: Vec<Session>,
+    pub total: u32,
+}
+
+/// How the focus widget reacts when a session is paused
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusWidgetPauseBehavior {
+    /// Keep the widget as is, showing the paused timer
+    #[default]
+    ShowPaused,
+    /// Animate the widget so the pause is hard to miss
+    Pulse,
+    /// Expand the widget to show resume/end controls
+    ExpandControls,
+}
+
+impl std::fmt::Display for FocusWidgetPauseBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FocusWidgetPauseBehavior::ShowPaused => write!(f, "show_paused"),
+            FocusWidgetPauseBehavior::Pulse => write!(f, "pulse"),
+            FocusWidgetPauseBehavior::ExpandControls => write!(f, "expand_controls"),
+        }
+    }
+}
+
+impl std::str::FromStr for FocusWidgetPauseBehavior {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "show_paused" => Ok(FocusWidgetPauseBehavior::ShowPaused),
+            "pulse" => Ok(FocusWidgetPauseBehavior::Pulse),
+            "expand_controls" => Ok(FocusWidgetPauseBehavior::ExpandControls),
+            _ => Err(format!("Invalid focus widget pause behavior: {}", s)),
+        }
+    }
+}
+
+/// What happens when a focus session starts outside work hours
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkHoursEnforcement {
+    /// Start silently
+    Off,
+    /// Start, but emit an `OutsideWorkHours` event
+    Warn,
+    /// Refuse to start unless explicitly overridden
+    #[default]
+    Block,
+}
+
+impl std::fmt::Display for WorkHoursEnforcement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkHoursEnforcement::Off => write!(f, "off"),
+            WorkHoursEnforcement::Warn => write!(f, "warn"),
+            WorkHoursEnforcement::Block => write!(f, "block"),
+        }
+    }
+}
+
+impl std::str::FromStr for WorkHoursEnforcement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(WorkHoursEnforcement::Off),
+            "warn" => Ok(WorkHoursEnforcement::Warn),
+            "block" => Ok(WorkHoursEnforcement::Block),
+            _ => Err(format!("Invalid work hours enforcement: {}", s)),
+        }
+    }
+}
+
+/// Appearance of the tray icon
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayIconStyle {
+    /// Template image on macOS, colorful icon elsewhere
+    #[default]
+    Auto,
+    /// Monochrome icon for dark menu bars
+    Light,
+    /// Monochrome icon for light menu bars
+    Dark,
+    /// Full color app icon
+    Colorful,
+}
+
+impl std::fmt::Display for TrayIconStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrayIconStyle::Auto => write!(f, "auto"),
+            TrayIconStyle::Light => write!(f, "light"),
+            TrayIconStyle::Dark => write!(f, "dark"),
+            TrayIconStyle::Colorful => write!(f, "colorful"),
+        }
+    }
+}
+
+impl std::str::FromStr for TrayIconStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(TrayIconStyle::Auto),
+            "light" => Ok(TrayIconStyle::Light),
+            "dark" => Ok(TrayIconStyle::Dark),
+            "colorful" => Ok(TrayIconStyle::Colorful),
+            _ => Err(format!("Invalid tray icon style: {}", s)),
+        }
+    }
+}
+
+/// Color theme shared by all windows
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AppTheme {
+    Light,
+    Dark,
+    /// Follow the OS appearance
+    #[default]
+    System,
+}
+
+impl std::fmt::Display for AppTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppTheme::Light => write!(f, "light"),
+            AppTheme::Dark => write!(f, "dark"),
+            AppTheme::System => write!(f, "system"),
+        }
+    }
+}
+
+impl std::str::FromStr for AppTheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "light" => Ok(AppTheme::Light),
+            "dark" => Ok(AppTheme::Dark),
+            "system" => Ok(AppTheme::System),
+            _ => Err(format!("Invalid theme: {}", s)),
+        }
+    }
+}
+
+/// What pressing Escape on the break overlay does
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayEscapeAction {
+    /// Do nothing
+    Ignore,
+    /// Record the key press as a bypass attempt
+    #[default]
+    LogBypass,
+    /// End the break early
+    EndBreak,
+    /// End the break early once the PIN is entered
+    RequirePin,
+}
+
+impl OverlayEscapeAction {
+    /// The action actually applied: strict mode never lets Escape end a break
+    pub fn effective(self, strict_mode_active: bool) -> Self {
+        match self {
+            OverlayEscapeAction::EndBreak | OverlayEscapeAction::RequirePin
+                if strict_mode_active =>
+            {
+                OverlayEscapeAction::LogBypass
+            }
+            action => action,
+        }
+    }
+}
+
+impl std::fmt::Display for OverlayEscapeAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverlayEscapeAction::Ignore => write!(f, "ignore"),
+            OverlayEscapeAction::LogBypass => write!(f, "log_bypass"),
+            OverlayEscapeAction::EndBreak => write!(f, "end_break"),
+            OverlayEscapeAction::RequirePin => write!(f, "require_pin"),
+        }
+    }
+}
+
+impl std::str::FromStr for OverlayEscapeAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(OverlayEscapeAction::Ignore),
+            "log_bypass" => Ok(OverlayEscapeAction::LogBypass),
+            "end_break" => Ok(OverlayEscapeAction::EndBreak),
+            "require_pin" => Ok(OverlayEscapeAction::RequirePin),
+            _ => Err(format!("Invalid overlay escape action: {}", s)),
+        }
+    }
+}
+
+/// What dismissing the break overlay does while strict mode is off
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SoftOverlayDismissAction {
+    /// End the break early
+    EndBreak,
+    /// Pause the break and bring the overlay back after the snooze
+    Snooze,
+    /// Hide the overlay and let the break keep running
+    #[default]
+    HideOverlay,
+}
+
+impl std::fmt::Display for SoftOverlayDismissAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SoftOverlayDismissAction::EndBreak => write!(f, "end_break"),
+            SoftOverlayDismissAction::Snooze => write!(f, "snooze"),
+            SoftOverlayDismissAction::HideOverlay => write!(f, "hide_overlay"),
+        }
+    }
+}
+
+impl std::str::FromStr for SoftOverlayDismissAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "end_break" => Ok(SoftOverlayDismissAction::EndBreak),
+            "snooze" => Ok(SoftOverlayDismissAction::Snooze),
+            "hide_overlay" => Ok(SoftOverlayDismissAction::HideOverlay),
+            _ => Err(format!("Invalid soft overlay dismiss action: {}", s)),
+        }
+    }
+}
+
+/// What a left click on the tray icon does
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayClickAction {
+    /// Open the menu bar popover
+    ShowPopover,
+    /// Show or hide the main window
+    #[default]
+    ShowMainWindow,
+    /// Start, pause or resume the focus session
+    ToggleFocus,
+    /// Do nothing
+    None,
+}
+
+impl std::fmt::Display for TrayClickAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrayClickAction::ShowPopover => write!(f, "show_popover"),
+            TrayClickAction::ShowMainWindow => write!(f, "show_main_window"),
+            TrayClickAction::ToggleFocus => write!(f, "toggle_focus"),
+            TrayClickAction::None => write!(f, "none"),
+        }
+    }
+}
+
+impl std::str::FromStr for TrayClickAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "show_popover" => Ok(TrayClickAction::ShowPopover),
+            "show_main_window" => Ok(TrayClickAction::ShowMainWindow),
+            "toggle_focus" => Ok(TrayClickAction::ToggleFocus),
+            "none" => Ok(TrayClickAction::None),
+            _ => Err(format!("Invalid tray click action: {}", s)),
+        }
+    }
+}
+
+/// Longest command accepted for a phase hook
+const MAX_PHASE_COMMAND_LENGTH: usize = 1024;
+
+/// Command or deep link run when each phase starts, e.g. to start a focus
+/// playlist and pause it on breaks. These run user-provided commands through
+/// the shell, so nothing runs unless `enabled` is set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseCommandHooks {
+    pub enabled: bool,
+    pub focus: Option<String>,
+    pub short_break: Option<String>,
+    pub long_break: Option<String>,
+}
+
+impl PhaseCommandHooks {
+    /// Trim every command, drop empty ones and reject anything that isn't a
+    /// single line
+    pub fn normalized(&self) -> Result<Self, String> {
+        let normalize = |name: &str, value: &Option<String>| {
+            let Some(command) = value.as_deref().map(str::trim).filter(|c| !c.is_empty()) else {
+                return Ok(None);
+            };
+            if command.len() > MAX_PHASE_COMMAND_LENGTH {
+                return Err(format!(
+                    "The {} command is longer than {} characters",
+                    name, MAX_PHASE_COMMAND_LENGTH
+                ));
+            }
+            if command.chars().any(char::is_control) {
+                return Err(format!(
+                    "The {} command must be a single line without control characters",
+                    name
+                ));
+            }
+            Ok(Some(command.to_string()))
+        };
+
+        Ok(Self {
+            enabled: self.enabled,
+            focus: normalize("focus", &self.focus)?,
+            short_break: normalize("short break", &self.short_break)?,
+            long_break: normalize("long break", &self.long_break)?,
+        })
+    }
+}
+
+/// Hex color per cycle phase, shared by every window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseColors {
+    pub focus: String,
+    pub short_break: String,
+    pub long_break: String,
+}
+
+impl Default for PhaseColors {
+    fn default() -> Self {
+        Self {
+            focus: "#3b82f6".to_string(),
+            short_break: "#10b981".to_string(),
+            long_break: "#8b5cf6".to_string(),
+        }
+    }
+}
+
+impl PhaseColors {
+    /// Check every color is `#rgb` or `#rrggbb` and lowercase them
+    pub fn normalized(&self) -> Result<Self, String> {
+        let normalize = |name: &str, value: &str| {
+            let value = value.trim();
+            let digits = value.strip_prefix('#').unwrap_or("");
+            if (digits.len() == 3 || digits.len() == 6)
+                && digits.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                Ok(value.to_ascii_lowercase())
+            } else {
+                Err(format!(
+                    "Invalid {} color '{}': expected #rgb or #rrggbb",
+                    name, value
+                ))
+            }
+        };
+
+        Ok(Self {
+            focus: normalize("focus", &self.focus)?,
+            short_break: normalize("short break", &self.short_break)?,
+            long_break: normalize("long break", &self.long_break)?,
+        })
+    }
+}
+
+/// Sound played by a phase transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundName {
+    #[default]
+    Chime,
+    Bell,
+    None,
+}
+
+impl SoundName {
+    /// Bundled resource played for this sound, `None` for silence
+    pub fn resource_path(&self) -> Option<&'static str> {
+        match self {
+            SoundName::Chime => Some("sounds/chime.wav"),
+            SoundName::Bell => Some("sounds/bell.wav"),
+            SoundName::None => None,
+        }
+    }
+}
+
+/// Sounds played when a focus session or break ends. Off by default so
+/// nobody who relies on Pausa being silent is surprised.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoundConfig {
+    pub enabled: bool,
+    pub focus_end: SoundName,
+    pub break_end: SoundName,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            focus_end: SoundName::Chime,
+            break_end: SoundName::Bell,
+        }
+    }
+}
+
+/// A named set of cycle durations, e.g. "deep work" at 50/10. Durations are
+/// in minutes like `CycleConfig`; `id` is `None` until the preset is saved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CyclePreset {
+    #[serde(default)]
+    pub id: Option<i32>,
+    pub name: String,
+    pub focus_duration: i32,
+    pub break_duration: i32,
+    pub long_break_duration: i32,
+    pub cycles_per_long_break: i32,
+}
+
+/// A global shortcut as stored in `hotkey_configs`. The action and shortcut
+/// are kept as strings; the hotkey manager parses them when loading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredHotkeyConfig {
+    pub action: String,
+    pub shortcut: String,
+    pub enabled: bool,
+}
+
 /// Evasion attempt model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvasionAttempt {
@@ -161,6 +742,237 @@ pub struct SessionStats {
     pub evasion_attempts: u32,
 }
 
+/// Aggregate statistics for sessions carrying a tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagStats {
+    pub tag: Option<String>,
+    pub focus_minutes: u32,
+    pub sessions_count: u32,
+    pub sessions_completed: u32,
+    pub completion_rate: f64,
+}
+
+/// Calendar period used to bucket statistics
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsPeriod {
+    /// Local-time bounds `[start, end)` of the period `offset` periods before the
+    /// one containing `now` (offset 0 = current day/week/month). Weeks start on Monday.
+    pub fn bounds(&self, offset: u32, now: DateTime<Local>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let today = now.date_naive();
+        let offset = offset as i64;
+
+        let (start, end) = match self {
+            StatsPeriod::Day => {
+                let start = today - Duration::days(offset);
+                (start, start + Duration::days(1))
+            }
+            StatsPeriod::Week => {
+                let this_week =
+                    today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                let start = this_week - Duration::weeks(offset);
+                (start, start + Duration::weeks(1))
+            }
+            StatsPeriod::Month => {
+                let months = today.year() as i64 * 12 + today.month0() as i64 - offset;
+                let first_of = |months: i64| {
+                    NaiveDate::from_ymd_opt((months / 12) as i32, (months % 12) as u32 + 1, 1)
+                        .unwrap_or(today)
+                };
+                (first_of(months), first_of(months + 1))
+            }
+        };
+
+        let to_utc = |date: NaiveDate| {
+            date.and_time(NaiveTime::MIN)
+                .and_local_timezone(Local)
+                .earliest()
+                .map(|local| local.with_timezone(&Utc))
+                .unwrap_or_else(|| date.and_time(NaiveTime::MIN).and_utc())
+        };
+
+        (to_utc(start), to_utc(end))
+    }
+}
+
+/// Aggregate focus statistics for one period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodStats {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub focus_minutes: u32,
+    pub sessions_count: u32,
+    pub sessions_completed: u32,
+    pub completion_rate: f64,
+}
+
+/// A single day that set a record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayRecord {
+    /// Local date, `YYYY-MM-DD`
+    pub date: String,
+    pub value: u32,
+}
+
+/// What is wrong with a session row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionAnomalyKind {
+    /// `end_time` earlier than `start_time`, typically after a clock change
+    EndBeforeStart,
+    /// Runs at the same time as an earlier session
+    Overlap,
+    /// `actual_duration` far beyond `planned_duration`
+    DurationExceedsPlan,
+    /// A field the row can't be used without is NULL or unreadable
+    MissingField,
+}
+
+/// One problem found in the session history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAnomaly {
+    pub session_id: String,
+    pub kind: SessionAnomalyKind,
+    pub detail: String,
+    /// Whether the auto-fix repairs this one
+    pub fixable: bool,
+}
+
+/// Result of scanning the session history for anomalies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAnomalyReport {
+    pub anomalies: Vec<SessionAnomaly>,
+    /// Sessions repaired by the auto-fix, 0 when it wasn't requested
+    pub fixed: usize,
+}
+
+/// Personal bests across all completed focus sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonalRecords {
+    /// Longest single focus session, in minutes
+    pub longest_session: Option<DayRecord>,
+    /// Most focus minutes in one day
+    pub most_focus_minutes: Option<DayRecord>,
+    /// Most completed focus sessions in one day
+    pub most_sessions: Option<DayRecord>,
+    /// Longest run of consecutive days with a completed focus session,
+    /// dated by its last day
+    pub longest_streak: Option<DayRecord>,
+}
+
+impl PersonalRecords {
+    /// Longest run of consecutive dates in `days`, which must be sorted and distinct
+    pub fn longest_streak(days: &[NaiveDate]) -> Option<DayRecord> {
+        let mut best: Option<(u32, NaiveDate)> = None;
+        let mut run = 0;
+
+        for (index, day) in days.iter().enumerate() {
+            let continues = index > 0 && days[index - 1].succ_opt() == Some(*day);
+            run = if continues { run + 1 } else { 1 };
+
+            if best.is_none_or(|(length, _)| run > length) {
+                best = Some((run, *day));
+            }
+        }
+
+        best.map(|(length, last_day)| DayRecord {
+            date: last_day.format("%Y-%m-%d").to_string(),
+            value: length,
+        })
+    }
+}
+
+/// Rows behind the dashboard, all read within one transaction so the
+/// parts agree with each other
+#[derive(Debug, Clone)]
+pub struct DashboardSnapshot {
+    /// Sessions started within the requested range, oldest first
+    pub sessions: Vec<Session>,
+    /// Latest sessions of any age, newest first
+    pub recent_sessions: Vec<Session>,
+    /// Local days with a completed focus session, oldest first
+    pub focus_days: Vec<NaiveDate>,
+}
+
+/// How long strict mode was on within a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrictModeUptime {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Time strict mode was on inside the period, counting a still-open stretch up to now
+    pub active_seconds: i64,
+    /// Stretches of strict mode that overlap the period
+    pub activations: u32,
+    /// Emergency exits that happened inside the period
+    pub emergency_exits: u32,
+}
+
+/// Daily focus targets, set on `date` and in effect until a later goal.
+/// A `None` target isn't tracked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyGoal {
+    pub date: NaiveDate,
+    pub target_minutes: Option<u32>,
+    pub target_sessions: Option<u32>,
+}
+
+/// The cycle orchestrator's phase, saved so it survives a restart. `phase`
+/// is the `CyclePhase` name, e.g. "short_break".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedCycleState {
+    pub phase: String,
+    pub remaining_seconds: u32,
+    /// Seconds from `started_at` until the phase ends, pauses included
+    pub phase_seconds: u32,
+    pub cycle_count: u32,
+    pub is_running: bool,
+    pub session_id: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub within_work_hours: bool,
+    pub is_sprint: bool,
+    pub is_wind_down: bool,
+}
+
+/// A logged attempt to get around strict mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BypassAttempt {
+    pub session_id: String,
+    pub method: String,
+    /// Timestamp as sent by the frontend
+    pub timestamp: String,
+    /// When the attempt was stored; `None` if the row can't be parsed
+    pub logged_at: Option<DateTime<Utc>>,
+}
+
+/// One stretch of strict mode, from activation to deactivation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrictModeInterval {
+    pub activated_at: DateTime<Utc>,
+    /// `None` while strict mode is still on
+    pub deactivated_at: Option<DateTime<Utc>>,
+    /// Length of the stretch, counting a still-open one up to now
+    pub duration_seconds: i64,
+    /// 'deactivate', 'emergency_exit' or 'recovery'
+    pub ended_by: Option<String>,
+    pub emergency_exit: bool,
+    /// Bypass attempts logged while the stretch was open
+    pub bypass_attempts: u32,
+}
+
 /// Work hours compliance statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkHoursStats {
@@ -187,6 +999,24 @@ pub struct WorkSchedule {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Work hours status at a point in time, with the next boundary crossing
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkHoursStatus {
+    pub schedule_enabled: bool,
+    pub within_work_hours: bool,
+    /// When work hours next start (if outside) or end (if inside)
+    pub next_boundary: Option<DateTime<Utc>>,
+    pub seconds_until_boundary: Option<i64>,
+}
+
+/// Whether a day is a scheduled work day, with its hours when it is
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkDayStatus {
+    pub is_work_day: bool,
+    pub work_start_time: Option<String>,
+    pub work_end_time: Option<String>,
+}
+
 /// Onboarding completion tracking model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OnboardingCompletion {
@@ -213,6 +1043,89 @@ impl Default for WorkSchedule {
     }
 }
 
+/// Cycle durations that can't be saved together, naming the fields at fault
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SettingsValidationError {
+    pub fields: Vec<String>,
+    pub message: String,
+}
+
+impl SettingsValidationError {
+    fn new(fields: &[&str], message: impl Into<String>) -> Self {
+        Self {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for SettingsValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} [{}]", self.message, self.fields.join(", "))
+    }
+}
+
+impl std::error::Error for SettingsValidationError {}
+
+/// Commands return errors as strings, so the error crosses IPC as its JSON
+/// form, e.g. `{"fields":["focus_duration"],"message":"..."}`, for the
+/// frontend to point at the fields at fault
+impl From<SettingsValidationError> for String {
+    fn from(error: SettingsValidationError) -> Self {
+        serde_json::to_string(&error).unwrap_or_else(|_| error.to_string())
+    }
+}
+
+impl UserSettings {
+    /// Check the cycle durations before they are written. Every path that
+    /// saves settings goes through this so they agree on what is valid.
+    pub fn validate_durations(&self) -> Result<(), SettingsValidationError> {
+        let in_minutes =
+            |seconds: i32, max_minutes: i32| (60..=max_minutes * 60).contains(&seconds);
+
+        if !in_minutes(self.focus_duration, 120) {
+            return Err(SettingsValidationError::new(
+                &["focus_duration"],
+                "Focus duration must be between 1 and 120 minutes",
+            ));
+        }
+        if !in_minutes(self.short_break_duration, 60) {
+            return Err(SettingsValidationError::new(
+                &["short_break_duration"],
+                "Break duration must be between 1 and 60 minutes",
+            ));
+        }
+        if !in_minutes(self.long_break_duration, 120) {
+            return Err(SettingsValidationError::new(
+                &["long_break_duration"],
+                "Long break duration must be between 1 and 120 minutes",
+            ));
+        }
+        if !(1..=10).contains(&self.cycles_per_long_break_v2) {
+            return Err(SettingsValidationError::new(
+                &["cycles_per_long_break"],
+                "Cycles per long break must be between 1 and 10",
+            ));
+        }
+        if self.long_break_duration <= self.short_break_duration {
+            return Err(SettingsValidationError::new(
+                &["long_break_duration", "short_break_duration"],
+                "Long break duration must be longer than regular break duration",
+            ));
+        }
+        if let Some(seconds) = self.post_long_break_focus_duration {
+            if seconds < 60 || seconds > self.focus_duration {
+                return Err(SettingsValidationError::new(
+                    &["post_long_break_focus_duration", "focus_duration"],
+                    "Focus after a long break must be between 1 minute and the focus duration",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Database row conversion helpers
 impl UserSettings {
     pub fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
@@ -229,6 +1142,84 @@ impl UserSettings {
             user_name: row.get("user_name").ok(),
             emergency_key_combination: row.get("emergency_key_combination").ok(),
             break_transition_seconds: row.get("break_transition_seconds").unwrap_or(10),
+            strict_focus_show_widget: row.get("strict_focus_show_widget").unwrap_or(false),
+            focus_widget_pause_behavior: row
+                .get::<_, String>("focus_widget_pause_behavior")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default(),
+            work_hours_enforcement: row
+                .get::<_, String>("work_hours_enforcement")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default(),
+            tray_icon_style: row
+                .get::<_, String>("tray_icon_style")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default(),
+            show_break_transition: row.get("show_break_transition").unwrap_or(true),
+            strict_require_ack: row.get("strict_require_ack").unwrap_or(false),
+            overlay_escape_action: row
+                .get::<_, String>("overlay_escape_action")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default(),
+            theme: row
+                .get::<_, String>("theme")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default(),
+            tray_click_action: row
+                .get::<_, String>("tray_click_action")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default(),
+            tick_interval_ms: row.get("tick_interval_ms").unwrap_or(1000),
+            power_save: row.get("power_save").unwrap_or(false),
+            break_message: row.get("break_message").unwrap_or(None),
+            phase_colors: row
+                .get::<_, Option<String>>("phase_colors")
+                .ok()
+                .flatten()
+                .and_then(|value| serde_json::from_str(&value).ok())
+                .unwrap_or_default(),
+            post_long_break_focus_duration: row
+                .get("post_long_break_focus_duration")
+                .unwrap_or(None),
+            soft_overlay_dismiss_action: row
+                .get::<_, String>("soft_overlay_dismiss_action")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default(),
+            break_snooze_minutes: row.get("break_snooze_minutes").unwrap_or(5),
+            notifications_muted_until: row.get("notifications_muted_until").unwrap_or(None),
+            phase_command_hooks: row
+                .get::<_, Option<String>>("phase_command_hooks")
+                .ok()
+                .flatten()
+                .and_then(|value| serde_json::from_str(&value).ok())
+                .unwrap_or_default(),
+            weekly_focus_budget_minutes: row.get("weekly_focus_budget_minutes").unwrap_or(None),
+            max_break_overrun_minutes: row.get("max_break_overrun_minutes").unwrap_or(None),
+            scheduled_focus_at: row.get("scheduled_focus_at").unwrap_or(None),
+            session_save_interval_seconds: row.get("session_save_interval_seconds").unwrap_or(30),
+            break_ratio_mode: row.get("break_ratio_mode").unwrap_or(false),
+            break_ratio: row.get("break_ratio").unwrap_or(0.2),
+            break_ratio_min_duration: row.get("break_ratio_min_duration").unwrap_or(300),
+            break_ratio_max_duration: row.get("break_ratio_max_duration").unwrap_or(1800),
+            prevent_display_sleep_during_focus: row
+                .get("prevent_display_sleep_during_focus")
+                .unwrap_or(false),
+            strict_disable_delay_seconds: row.get("strict_disable_delay_seconds").unwrap_or(0),
+            strict_disable_at: row.get("strict_disable_at").unwrap_or(None),
+            winddown_minutes: row.get("winddown_minutes").unwrap_or(None),
+            sound_config: row
+                .get::<_, Option<String>>("sound_config")
+                .ok()
+                .flatten()
+                .and_then(|value| serde_json::from_str(&value).ok())
+                .unwrap_or_default(),
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
         })
@@ -257,6 +1248,19 @@ impl BlockListItem {
     }
 }
 
+impl CyclePreset {
+    pub fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get("id")?,
+            name: row.get("name")?,
+            focus_duration: row.get("focus_duration")?,
+            break_duration: row.get("break_duration")?,
+            long_break_duration: row.get("long_break_duration")?,
+            cycles_per_long_break: row.get("cycles_per_long_break")?,
+        })
+    }
+}
+
 impl Session {
     pub fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
         let type_str: String = row.get("session_type")?;
@@ -282,6 +1286,8 @@ impl Session {
             within_work_hours: row.get("within_work_hours").unwrap_or(true),
             cycle_number: row.get("cycle_number").ok(),
             is_long_break: row.get("is_long_break").unwrap_or(false),
+            tag: row.get("tag").unwrap_or(None),
+            is_sprint: row.get("is_sprint").unwrap_or(false),
         })
     }
 }
@@ -333,6 +1339,79 @@ impl WorkSchedule {
             updated_at: row.get("updated_at")?,
         })
     }
+
+    /// Work day status for today, or `None` when the schedule can't tell.
+    ///
+    /// The schedule only has a single set of hours with no per-weekday
+    /// settings, so there is no way to know which days are off; reporting
+    /// every day as a work day would be wrong for anyone with a day off.
+    pub fn work_day(&self) -> Option<WorkDayStatus> {
+        None
+    }
+
+    /// Compute whether `now` falls within the schedule and when that next changes.
+    /// Handles overnight schedules (e.g. 22:00 - 06:00).
+    pub fn status_at(&self, now: DateTime<Local>) -> WorkHoursStatus {
+        let always_within = WorkHoursStatus {
+            schedule_enabled: self.use_work_schedule,
+            within_work_hours: true,
+            next_boundary: None,
+            seconds_until_boundary: None,
+        };
+
+        if !self.use_work_schedule {
+            return always_within;
+        }
+
+        let parse = |value: &Option<String>| {
+            value
+                .as_deref()
+                .and_then(|v| NaiveTime::parse_from_str(v, "%H:%M").ok())
+        };
+
+        let (start, end) = match (parse(&self.work_start_time), parse(&self.work_end_time)) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return always_within,
+        };
+
+        let today = now.date_naive();
+        let tomorrow = today + Duration::days(1);
+        let current_time = now.time();
+
+        let (within_work_hours, boundary_date, boundary_time) = if end > start {
+            // Normal case: start < end (e.g., 09:00 - 17:00)
+            if current_time < start {
+                (false, today, start)
+            } else if current_time < end {
+                (true, today, end)
+            } else {
+                (false, tomorrow, start)
+            }
+        } else {
+            // Overnight case: start > end (e.g., 22:00 - 06:00)
+            if current_time >= start {
+                (true, tomorrow, end)
+            } else if current_time < end {
+                (true, today, end)
+            } else {
+                (false, today, start)
+            }
+        };
+
+        let next_boundary = boundary_date
+            .and_time(boundary_time)
+            .and_local_timezone(Local)
+            .earliest()
+            .map(|boundary| boundary.with_timezone(&Utc));
+
+        WorkHoursStatus {
+            schedule_enabled: true,
+            within_work_hours,
+            next_boundary,
+            seconds_until_boundary: next_boundary
+                .map(|boundary| (boundary - now.with_timezone(&Utc)).num_seconds()),
+        }
+    }
 }
 
 impl OnboardingCompletion {
@@ -1,135 +1,15 @@
 /// Database schema definitions for Pausa application
 /// Based on the design document specifications
 
-pub const SCHEMA_VERSION: i32 = 10;
+pub const SCHEMA_VERSION: i32 = 46;
 
-/// Initial database schema - creates all tables
-pub const INITIAL_SCHEMA: &str = r#"
--- User configuration table
-CREATE TABLE user_settings (
-    id INTEGER PRIMARY KEY,
-    focus_duration INTEGER NOT NULL DEFAULT 1500, -- 25 minutes in seconds
-    short_break_duration INTEGER NOT NULL DEFAULT 300, -- 5 minutes
-    long_break_duration INTEGER NOT NULL DEFAULT 900, -- 15 minutes
-    cycles_per_long_break INTEGER NOT NULL DEFAULT 4,
-    cycles_per_long_break_v2 INTEGER NOT NULL DEFAULT 4, -- New field for onboarding
-    pre_alert_seconds INTEGER NOT NULL DEFAULT 120,
-    strict_mode BOOLEAN NOT NULL DEFAULT FALSE,
-    pin_hash TEXT,
-    user_name TEXT, -- User's name for personalized notifications
-    emergency_key_combination TEXT, -- Emergency key combination for strict mode
-    break_transition_seconds INTEGER NOT NULL DEFAULT 10, -- Countdown before break starts
-    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-);
-
--- Blocked applications and websites
-CREATE TABLE block_list (
-    id INTEGER PRIMARY KEY,
-    type TEXT NOT NULL CHECK (type IN ('app', 'website')),
-    value TEXT NOT NULL,
-    platform TEXT, -- 'windows', 'macos', 'linux', or NULL for all
-    enabled BOOLEAN NOT NULL DEFAULT TRUE,
-    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-);
-
--- Focus and break sessions
-CREATE TABLE sessions (
-    id TEXT PRIMARY KEY,
-    session_type TEXT NOT NULL CHECK (session_type IN ('focus', 'short_break', 'long_break')),
-    start_time DATETIME NOT NULL,
-    end_time DATETIME,
-    planned_duration INTEGER NOT NULL, -- seconds
-    actual_duration INTEGER, -- seconds, NULL if not completed
-    strict_mode BOOLEAN NOT NULL DEFAULT FALSE,
-    completed BOOLEAN NOT NULL DEFAULT FALSE,
-    notes TEXT,
-    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-);
-
--- Evasion attempts (when user tries to access blocked content)
-CREATE TABLE evasion_attempts (
-    id INTEGER PRIMARY KEY,
-    session_id TEXT NOT NULL,
-    attempt_type TEXT NOT NULL CHECK (attempt_type IN ('app', 'website')),
-    blocked_item TEXT NOT NULL,
-    timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
-    FOREIGN KEY (session_id) REFERENCES sessions (id)
-);
-
--- Computed insights and statistics
-CREATE TABLE insights (
-    id INTEGER PRIMARY KEY,
-    metric_key TEXT NOT NULL,
-    metric_value REAL NOT NULL,
-    period_start DATE NOT NULL,
-    period_end DATE NOT NULL,
-    computed_at DATETIME DEFAULT CURRENT_TIMESTAMP
-);
-
--- Work schedule configuration
-CREATE TABLE work_schedule (
-    id INTEGER PRIMARY KEY,
-    user_id INTEGER NOT NULL DEFAULT 1,
-    use_work_schedule BOOLEAN NOT NULL DEFAULT FALSE,
-    work_start_time TEXT, -- "09:00"
-    work_end_time TEXT,   -- "18:00"
-    timezone TEXT NOT NULL DEFAULT 'local',
-    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-    FOREIGN KEY (user_id) REFERENCES user_settings (id)
-);
-
--- Onboarding completion tracking
-CREATE TABLE onboarding_completion (
-    id INTEGER PRIMARY KEY,
-    user_email TEXT NOT NULL, -- Email of the user who completed onboarding
-    completed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-    version TEXT NOT NULL DEFAULT '1.0',
-    config_snapshot TEXT -- JSON of final configuration
-);
-
--- Strict mode runtime state (for persistence across app restarts)
-CREATE TABLE strict_mode_state (
-    id INTEGER PRIMARY KEY,
-    is_active BOOLEAN NOT NULL DEFAULT FALSE,
-    is_locked BOOLEAN NOT NULL DEFAULT FALSE,
-    current_window_type TEXT, -- 'menu_bar_icon', 'menu_bar_popover', 'break_transition', 'fullscreen_break_overlay'
-    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-);
-
--- Schema version tracking
-CREATE TABLE schema_version (
-    version INTEGER PRIMARY KEY,
-    applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
-);
-
--- Performance indexes
-CREATE INDEX idx_sessions_start_time ON sessions (start_time);
-CREATE INDEX idx_sessions_type ON sessions (session_type);
-CREATE INDEX idx_sessions_completed ON sessions (completed);
-CREATE INDEX idx_block_list_type_value ON block_list (type, value);
-CREATE INDEX idx_block_list_enabled ON block_list (enabled);
-CREATE INDEX idx_evasion_attempts_session ON evasion_attempts (session_id);
-CREATE INDEX idx_evasion_attempts_timestamp ON evasion_attempts (timestamp);
-CREATE INDEX idx_insights_key_period ON insights (metric_key, period_start, period_end);
-
--- Insert initial schema version
-INSERT INTO schema_version (version) VALUES (4);
-
--- Insert default user settings
-INSERT INTO user_settings (id) VALUES (1);
-
--- Insert default work schedule
-INSERT INTO work_schedule (id, user_id) VALUES (1, 1);
-
--- Insert default strict mode state
-INSERT INTO strict_mode_state (id) VALUES (1);
-"#;
-
-/// SQL statements for creating individual tables (used in migrations)
+/// SQL statements for creating individual tables in their current shape.
+///
+/// New databases are created from these, and so are the tables migrations
+/// add or `MigrationManager::ensure_schema` repairs, so every database ends
+/// up matching one that went through every migration.
 pub const CREATE_USER_SETTINGS: &str = r#"
-CREATE TABLE user_settings (
+CREATE TABLE IF NOT EXISTS user_settings (
     id INTEGER PRIMARY KEY,
     focus_duration INTEGER NOT NULL DEFAULT 1500,
     short_break_duration INTEGER NOT NULL DEFAULT 300,
@@ -142,13 +22,44 @@ CREATE TABLE user_settings (
     user_name TEXT,
     emergency_key_combination TEXT,
     break_transition_seconds INTEGER NOT NULL DEFAULT 10,
+    strict_focus_show_widget BOOLEAN NOT NULL DEFAULT FALSE,
+    focus_widget_pause_behavior TEXT NOT NULL DEFAULT 'show_paused',
+    work_hours_enforcement TEXT NOT NULL DEFAULT 'block',
+    tray_icon_style TEXT NOT NULL DEFAULT 'auto',
+    show_break_transition BOOLEAN NOT NULL DEFAULT TRUE,
+    strict_require_ack BOOLEAN NOT NULL DEFAULT FALSE,
+    overlay_escape_action TEXT NOT NULL DEFAULT 'log_bypass',
+    theme TEXT NOT NULL DEFAULT 'system',
+    tray_click_action TEXT NOT NULL DEFAULT 'show_main_window',
+    tick_interval_ms INTEGER NOT NULL DEFAULT 1000,
+    power_save BOOLEAN NOT NULL DEFAULT FALSE,
+    break_message TEXT,
+    phase_colors TEXT,
+    post_long_break_focus_duration INTEGER,
+    soft_overlay_dismiss_action TEXT NOT NULL DEFAULT 'hide_overlay',
+    break_snooze_minutes INTEGER NOT NULL DEFAULT 5,
+    notifications_muted_until DATETIME,
+    phase_command_hooks TEXT,
+    weekly_focus_budget_minutes INTEGER,
+    max_break_overrun_minutes INTEGER,
+    scheduled_focus_at DATETIME,
+    session_save_interval_seconds INTEGER NOT NULL DEFAULT 30,
+    break_ratio_mode BOOLEAN NOT NULL DEFAULT FALSE,
+    break_ratio REAL NOT NULL DEFAULT 0.2,
+    break_ratio_min_duration INTEGER NOT NULL DEFAULT 300,
+    break_ratio_max_duration INTEGER NOT NULL DEFAULT 1800,
+    prevent_display_sleep_during_focus BOOLEAN DEFAULT 0,
+    strict_disable_delay_seconds INTEGER DEFAULT 0,
+    strict_disable_at DATETIME,
+    winddown_minutes INTEGER,
+    sound_config TEXT,
     created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
     updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
 )
 "#;
 
 pub const CREATE_BLOCK_LIST: &str = r#"
-CREATE TABLE block_list (
+CREATE TABLE IF NOT EXISTS block_list (
     id INTEGER PRIMARY KEY,
     type TEXT NOT NULL CHECK (type IN ('app', 'website')),
     value TEXT NOT NULL,
@@ -159,7 +70,7 @@ CREATE TABLE block_list (
 "#;
 
 pub const CREATE_SESSIONS: &str = r#"
-CREATE TABLE sessions (
+CREATE TABLE IF NOT EXISTS sessions (
     id TEXT PRIMARY KEY,
     session_type TEXT NOT NULL CHECK (session_type IN ('focus', 'short_break', 'long_break')),
     start_time DATETIME NOT NULL,
@@ -169,12 +80,19 @@ CREATE TABLE sessions (
     strict_mode BOOLEAN NOT NULL DEFAULT FALSE,
     completed BOOLEAN NOT NULL DEFAULT FALSE,
     notes TEXT,
-    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    within_work_hours BOOLEAN DEFAULT TRUE,
+    cycle_number INTEGER,
+    is_long_break BOOLEAN DEFAULT FALSE,
+    tag TEXT,
+    is_sprint BOOLEAN NOT NULL DEFAULT FALSE,
+    overrun_seconds INTEGER,
+    mood INTEGER
 )
 "#;
 
 pub const CREATE_EVASION_ATTEMPTS: &str = r#"
-CREATE TABLE evasion_attempts (
+CREATE TABLE IF NOT EXISTS evasion_attempts (
     id INTEGER PRIMARY KEY,
     session_id TEXT NOT NULL,
     attempt_type TEXT NOT NULL CHECK (attempt_type IN ('app', 'website')),
@@ -185,7 +103,7 @@ CREATE TABLE evasion_attempts (
 "#;
 
 pub const CREATE_INSIGHTS: &str = r#"
-CREATE TABLE insights (
+CREATE TABLE IF NOT EXISTS insights (
     id INTEGER PRIMARY KEY,
     metric_key TEXT NOT NULL,
     metric_value REAL NOT NULL,
@@ -196,14 +114,14 @@ CREATE TABLE insights (
 "#;
 
 pub const CREATE_SCHEMA_VERSION: &str = r#"
-CREATE TABLE schema_version (
+CREATE TABLE IF NOT EXISTS schema_version (
     version INTEGER PRIMARY KEY,
     applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
 )
 "#;
 
 pub const CREATE_WORK_SCHEDULE: &str = r#"
-CREATE TABLE work_schedule (
+CREATE TABLE IF NOT EXISTS work_schedule (
     id INTEGER PRIMARY KEY,
     user_id INTEGER NOT NULL DEFAULT 1,
     use_work_schedule BOOLEAN NOT NULL DEFAULT FALSE,
@@ -217,9 +135,9 @@ CREATE TABLE work_schedule (
 "#;
 
 pub const CREATE_ONBOARDING_COMPLETION: &str = r#"
-CREATE TABLE onboarding_completion (
+CREATE TABLE IF NOT EXISTS onboarding_completion (
     id INTEGER PRIMARY KEY,
-    user_email TEXT NOT NULL,
+    user_email TEXT, -- Added by migration 5, which cannot make it NOT NULL
     completed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
     version TEXT NOT NULL DEFAULT '1.0',
     config_snapshot TEXT
@@ -227,7 +145,7 @@ CREATE TABLE onboarding_completion (
 "#;
 
 pub const CREATE_NOTIFICATION_HISTORY: &str = r#"
-CREATE TABLE notification_history (
+CREATE TABLE IF NOT EXISTS notification_history (
     id INTEGER PRIMARY KEY,
     session_id TEXT,
     notification_type TEXT NOT NULL,
@@ -237,3 +155,226 @@ CREATE TABLE notification_history (
     FOREIGN KEY (session_id) REFERENCES sessions (id)
 )
 "#;
+
+pub const CREATE_BYPASS_ATTEMPTS: &str = r#"
+CREATE TABLE IF NOT EXISTS bypass_attempts (
+    id INTEGER PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    method TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+pub const CREATE_STRICT_MODE_STATE: &str = r#"
+CREATE TABLE IF NOT EXISTS strict_mode_state (
+    id INTEGER PRIMARY KEY,
+    is_active BOOLEAN NOT NULL DEFAULT FALSE,
+    is_locked BOOLEAN NOT NULL DEFAULT FALSE,
+    current_window_type TEXT,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+pub const CREATE_STRICT_MODE_SESSIONS: &str = r#"
+CREATE TABLE IF NOT EXISTS strict_mode_sessions (
+    id INTEGER PRIMARY KEY,
+    activated_at DATETIME NOT NULL,
+    deactivated_at DATETIME,
+    ended_by TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+pub const CREATE_DAILY_GOALS: &str = r#"
+CREATE TABLE IF NOT EXISTS daily_goals (
+    date TEXT PRIMARY KEY,
+    target_minutes INTEGER,
+    target_sessions INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+pub const CREATE_CYCLE_STATE: &str = r#"
+CREATE TABLE IF NOT EXISTS cycle_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    phase TEXT NOT NULL,
+    remaining_seconds INTEGER NOT NULL,
+    phase_seconds INTEGER NOT NULL, -- From started_at to the end of the phase, pauses included
+    cycle_count INTEGER NOT NULL DEFAULT 0,
+    is_running BOOLEAN NOT NULL DEFAULT FALSE,
+    session_id TEXT,
+    started_at DATETIME,
+    within_work_hours BOOLEAN NOT NULL DEFAULT TRUE,
+    is_sprint BOOLEAN NOT NULL DEFAULT FALSE,
+    is_wind_down BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+pub const CREATE_CYCLE_PRESETS: &str = r#"
+CREATE TABLE IF NOT EXISTS cycle_presets (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    focus_duration INTEGER NOT NULL, -- minutes
+    break_duration INTEGER NOT NULL, -- minutes
+    long_break_duration INTEGER NOT NULL, -- minutes
+    cycles_per_long_break INTEGER NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+pub const CREATE_HOTKEY_CONFIGS: &str = r#"
+CREATE TABLE IF NOT EXISTS hotkey_configs (
+    action TEXT PRIMARY KEY, -- HotkeyAction name, e.g. "ToggleFocusSession"
+    shortcut TEXT NOT NULL, -- Normalized shortcut, e.g. "shift+super+KeyF"
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+)
+"#;
+
+/// A table the app reads or writes, with everything needed to recreate it
+pub struct TableDefinition {
+    pub name: &'static str,
+    pub create: &'static str,
+    pub indexes: &'static [&'static str],
+    /// Default row the app expects to find after creating the table
+    pub seed: Option<&'static str>,
+}
+
+pub const WORK_SCHEDULE_TABLE: TableDefinition = TableDefinition {
+    name: "work_schedule",
+    create: CREATE_WORK_SCHEDULE,
+    indexes: &[],
+    seed: Some("INSERT OR IGNORE INTO work_schedule (id, user_id) VALUES (1, 1)"),
+};
+
+pub const NOTIFICATION_HISTORY_TABLE: TableDefinition = TableDefinition {
+    name: "notification_history",
+    create: CREATE_NOTIFICATION_HISTORY,
+    indexes: &[
+        "CREATE INDEX IF NOT EXISTS idx_notification_history_sent_at ON notification_history (sent_at)",
+        "CREATE INDEX IF NOT EXISTS idx_notification_history_session ON notification_history (session_id)",
+    ],
+    seed: None,
+};
+
+pub const BYPASS_ATTEMPTS_TABLE: TableDefinition = TableDefinition {
+    name: "bypass_attempts",
+    create: CREATE_BYPASS_ATTEMPTS,
+    indexes: &[
+        "CREATE INDEX IF NOT EXISTS idx_bypass_attempts_session ON bypass_attempts (session_id)",
+        "CREATE INDEX IF NOT EXISTS idx_bypass_attempts_created_at ON bypass_attempts (created_at)",
+    ],
+    seed: None,
+};
+
+pub const STRICT_MODE_STATE_TABLE: TableDefinition = TableDefinition {
+    name: "strict_mode_state",
+    create: CREATE_STRICT_MODE_STATE,
+    indexes: &[],
+    seed: Some("INSERT OR IGNORE INTO strict_mode_state (id) VALUES (1)"),
+};
+
+pub const STRICT_MODE_SESSIONS_TABLE: TableDefinition = TableDefinition {
+    name: "strict_mode_sessions",
+    create: CREATE_STRICT_MODE_SESSIONS,
+    indexes: &[
+        "CREATE INDEX IF NOT EXISTS idx_strict_mode_sessions_activated_at ON strict_mode_sessions (activated_at)",
+    ],
+    seed: None,
+};
+
+pub const DAILY_GOALS_TABLE: TableDefinition = TableDefinition {
+    name: "daily_goals",
+    create: CREATE_DAILY_GOALS,
+    indexes: &[],
+    seed: None,
+};
+
+pub const CYCLE_STATE_TABLE: TableDefinition = TableDefinition {
+    name: "cycle_state",
+    create: CREATE_CYCLE_STATE,
+    indexes: &[],
+    seed: None,
+};
+
+pub const CYCLE_PRESETS_TABLE: TableDefinition = TableDefinition {
+    name: "cycle_presets",
+    create: CREATE_CYCLE_PRESETS,
+    indexes: &[],
+    seed: None,
+};
+
+pub const HOTKEY_CONFIGS_TABLE: TableDefinition = TableDefinition {
+    name: "hotkey_configs",
+    create: CREATE_HOTKEY_CONFIGS,
+    indexes: &[],
+    seed: None,
+};
+
+/// Every table the app needs besides `schema_version`, which only migrations
+/// may create since an empty one would replay them all
+pub const REQUIRED_TABLES: &[TableDefinition] = &[
+    TableDefinition {
+        name: "user_settings",
+        create: CREATE_USER_SETTINGS,
+        indexes: &[],
+        seed: Some("INSERT OR IGNORE INTO user_settings (id) VALUES (1)"),
+    },
+    TableDefinition {
+        name: "block_list",
+        create: CREATE_BLOCK_LIST,
+        indexes: &[
+            "CREATE INDEX IF NOT EXISTS idx_block_list_type_value ON block_list (type, value)",
+            "CREATE INDEX IF NOT EXISTS idx_block_list_enabled ON block_list (enabled)",
+        ],
+        seed: None,
+    },
+    TableDefinition {
+        name: "sessions",
+        create: CREATE_SESSIONS,
+        indexes: &[
+            "CREATE INDEX IF NOT EXISTS idx_sessions_start_time ON sessions (start_time)",
+            "CREATE INDEX IF NOT EXISTS idx_sessions_type ON sessions (session_type)",
+            "CREATE INDEX IF NOT EXISTS idx_sessions_completed ON sessions (completed)",
+            "CREATE INDEX IF NOT EXISTS idx_sessions_within_work_hours ON sessions (within_work_hours)",
+            "CREATE INDEX IF NOT EXISTS idx_sessions_tag ON sessions (tag)",
+        ],
+        seed: None,
+    },
+    TableDefinition {
+        name: "evasion_attempts",
+        create: CREATE_EVASION_ATTEMPTS,
+        indexes: &[
+            "CREATE INDEX IF NOT EXISTS idx_evasion_attempts_session ON evasion_attempts (session_id)",
+            "CREATE INDEX IF NOT EXISTS idx_evasion_attempts_timestamp ON evasion_attempts (timestamp)",
+        ],
+        seed: None,
+    },
+    TableDefinition {
+        name: "insights",
+        create: CREATE_INSIGHTS,
+        indexes: &[
+            "CREATE INDEX IF NOT EXISTS idx_insights_key_period ON insights (metric_key, period_start, period_end)",
+        ],
+        seed: None,
+    },
+    WORK_SCHEDULE_TABLE,
+    TableDefinition {
+        name: "onboarding_completion",
+        create: CREATE_ONBOARDING_COMPLETION,
+        indexes: &[],
+        seed: None,
+    },
+    NOTIFICATION_HISTORY_TABLE,
+    BYPASS_ATTEMPTS_TABLE,
+    STRICT_MODE_STATE_TABLE,
+    STRICT_MODE_SESSIONS_TABLE,
+    DAILY_GOALS_TABLE,
+    CYCLE_STATE_TABLE,
+    CYCLE_PRESETS_TABLE,
+    HOTKEY_CONFIGS_TABLE,
+];
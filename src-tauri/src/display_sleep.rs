@@ -0,0 +1,204 @@
+/// Keeps the display from sleeping while held.
+///
+/// Every platform ties the request to this process, so the OS drops it even
+/// if the app crashes or is killed before `release` runs:
+/// - macOS: `caffeinate -d -w <pid>` exits together with the app
+/// - Linux: `systemd-inhibit` holds an idle lock around `tail --pid=<pid>`
+/// - Windows: `SetThreadExecutionState` on a thread that dies with the app
+///
+/// Where none of these are available, acquiring logs a warning and does nothing.
+pub struct DisplaySleepInhibitor {
+    held: Option<platform::Hold>,
+    /// Set by `suspend`; keeps `sync` from acquiring until focus ends
+    suspended: bool,
+}
+
+impl DisplaySleepInhibitor {
+    pub fn new() -> Self {
+        Self {
+            held: None,
+            suspended: false,
+        }
+    }
+
+    /// Hold the display awake while `keep_awake`, release it otherwise
+    pub fn sync(&mut self, keep_awake: bool) {
+        if !keep_awake {
+            self.suspended = false;
+            self.release();
+        } else if !self.suspended {
+            self.acquire();
+        }
+    }
+
+    /// Release now and stay released until the current focus phase ends,
+    /// e.g. after an emergency exit
+    pub fn suspend(&mut self) {
+        self.suspended = true;
+        self.release();
+    }
+
+    /// Keep the display awake until `release`; does nothing if already held
+    pub fn acquire(&mut self) {
+        if self.held.is_some() {
+            return;
+        }
+
+        match platform::hold() {
+            Ok(hold) => {
+                println!("☀️ [DisplaySleep] Keeping the display awake");
+                self.held = Some(hold);
+            }
+            Err(e) => eprintln!("⚠️ [DisplaySleep] Could not keep the display awake: {}", e),
+        }
+    }
+
+    /// Let the display sleep again; does nothing if not held
+    pub fn release(&mut self) {
+        if let Some(hold) = self.held.take() {
+            platform::release(hold);
+            println!("🌙 [DisplaySleep] Display may sleep again");
+        }
+    }
+}
+
+impl Default for DisplaySleepInhibitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for DisplaySleepInhibitor {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::io;
+    use std::process::{Child, Command, Stdio};
+
+    pub type Hold = Child;
+
+    pub fn hold() -> io::Result<Hold> {
+        Command::new("caffeinate")
+            .args(["-d", "-w", &std::process::id().to_string()])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    }
+
+    pub fn release(mut hold: Hold) {
+        let _ = hold.kill();
+        let _ = hold.wait();
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::io;
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, Command, Stdio};
+
+    pub type Hold = Child;
+
+    pub fn hold() -> io::Result<Hold> {
+        // `tail` exits when the app does, which ends the inhibitor with it.
+        // Its own process group lets `release` stop `tail` too.
+        Command::new("systemd-inhibit")
+            .args([
+                "--what=idle",
+                "--who=Pausa",
+                "--why=Focus session in progress",
+                "--mode=block",
+                "tail",
+                &format!("--pid={}", std::process::id()),
+                "-f",
+                "/dev/null",
+            ])
+            .process_group(0)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    }
+
+    pub fn release(mut hold: Hold) {
+        let killed_group = Command::new("kill")
+            .args(["--", &format!("-{}", hold.id())])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+        if !killed_group {
+            let _ = hold.kill();
+        }
+        let _ = hold.wait();
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::io;
+    use std::sync::mpsc::{self, Sender};
+    use std::thread::JoinHandle;
+
+    const ES_CONTINUOUS: u32 = 0x8000_0000;
+    const ES_DISPLAY_REQUIRED: u32 = 0x0000_0002;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetThreadExecutionState(flags: u32) -> u32;
+    }
+
+    /// The execution state belongs to the thread that set it, so a thread
+    /// is kept alive for as long as the display should stay awake
+    pub struct Hold {
+        stop: Sender<()>,
+        thread: JoinHandle<()>,
+    }
+
+    pub fn hold() -> io::Result<Hold> {
+        let (stop, stopped) = mpsc::channel();
+        let (ready, is_ready) = mpsc::channel();
+
+        let thread = std::thread::spawn(move || {
+            let held = unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_DISPLAY_REQUIRED) } != 0;
+            let _ = ready.send(held);
+            if held {
+                let _ = stopped.recv();
+                unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+            }
+        });
+
+        match is_ready.recv() {
+            Ok(true) => Ok(Hold { stop, thread }),
+            _ => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "SetThreadExecutionState failed",
+            )),
+        }
+    }
+
+    pub fn release(hold: Hold) {
+        let _ = hold.stop.send(());
+        let _ = hold.thread.join();
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+mod platform {
+    use std::io;
+
+    pub struct Hold;
+
+    pub fn hold() -> io::Result<Hold> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not supported on this platform",
+        ))
+    }
+
+    pub fn release(_hold: Hold) {}
+}
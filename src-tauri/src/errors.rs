@@ -20,6 +20,8 @@ pub enum AppError {
     Cycle(String),
     #[error("onboarding: {0}")]
     Onboarding(String),
+    #[error("confirmation required: {0}")]
+    ConfirmationRequired(String),
 }
 
 impl From<reqwest::Error> for AppError {
@@ -60,6 +62,9 @@ impl AppError {
             AppError::Onboarding(_) => {
                 "Setup error. Please restart the onboarding process.".to_string()
             }
+            AppError::ConfirmationRequired(_) => {
+                "This action deletes data and must be confirmed.".to_string()
+            }
         }
     }
 
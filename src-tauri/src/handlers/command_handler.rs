@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::state::AppState;
+
+/// App state a command needs before it can succeed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandRequirement {
+    /// `initialize_cycle_orchestrator` has run
+    CycleOrchestrator,
+    /// The strict mode orchestrator exists (strict mode enabled in settings)
+    StrictModeOrchestrator,
+    /// Strict mode is currently active
+    StrictModeActive,
+}
+
+/// Commands that fail until their requirement is met; anything not listed
+/// can be called at any time
+const COMMAND_REQUIREMENTS: &[(&str, CommandRequirement)] = &[
+    ("start_focus_session", CommandRequirement::CycleOrchestrator),
+    (
+        "set_next_focus_duration",
+        CommandRequirement::CycleOrchestrator,
+    ),
+    ("start_focus_sprint", CommandRequirement::CycleOrchestrator),
+    ("start_break_session", CommandRequirement::CycleOrchestrator),
+    ("break_now", CommandRequirement::CycleOrchestrator),
+    ("start_wind_down", CommandRequirement::CycleOrchestrator),
+    ("pause_cycle", CommandRequirement::CycleOrchestrator),
+    ("resume_cycle", CommandRequirement::CycleOrchestrator),
+    ("end_cycle_session", CommandRequirement::CycleOrchestrator),
+    ("skip_break_session", CommandRequirement::CycleOrchestrator),
+    ("get_cycle_state", CommandRequirement::CycleOrchestrator),
+    ("format_remaining", CommandRequirement::CycleOrchestrator),
+    ("get_cycle_dots", CommandRequirement::CycleOrchestrator),
+    (
+        "get_clock_diagnostics",
+        CommandRequirement::CycleOrchestrator,
+    ),
+    ("get_phase_end_time", CommandRequirement::CycleOrchestrator),
+    (
+        "get_recent_cycle_events",
+        CommandRequirement::CycleOrchestrator,
+    ),
+    ("reload_cycle_config", CommandRequirement::CycleOrchestrator),
+    ("get_current_break", CommandRequirement::CycleOrchestrator),
+    ("cycle_tick", CommandRequirement::CycleOrchestrator),
+    ("reset_cycle_count", CommandRequirement::CycleOrchestrator),
+    (
+        "handle_overlay_escape",
+        CommandRequirement::CycleOrchestrator,
+    ),
+    (
+        "handle_overlay_dismiss",
+        CommandRequirement::CycleOrchestrator,
+    ),
+    (
+        "get_work_schedule_info",
+        CommandRequirement::CycleOrchestrator,
+    ),
+    (
+        "activate_strict_mode",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "deactivate_strict_mode",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "repair_strict_mode_windows",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "show_menu_bar_popover",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "hide_menu_bar_popover",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "stop_break_transition_countdown",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "acknowledge_transition",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "start_break_from_transition",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "hide_fullscreen_break_overlay",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "register_emergency_hotkey",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "unregister_emergency_hotkey",
+        CommandRequirement::StrictModeOrchestrator,
+    ),
+    (
+        "emergency_exit_strict_mode",
+        CommandRequirement::StrictModeActive,
+    ),
+];
+
+/// One registered command and whether it can be called right now
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandInfo {
+    pub name: String,
+    pub available: bool,
+    /// What the command waits for, `None` when it has no requirement
+    pub requires: Option<CommandRequirement>,
+}
+
+/// Registered commands of one module, in registration order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandGroup {
+    pub module: String,
+    pub commands: Vec<CommandInfo>,
+}
+
+/// Split a `module::name` path from the command list into module and name
+fn split_command_path(path: &str) -> (String, String) {
+    let segments: Vec<&str> = path.split("::").map(str::trim).collect();
+    let name = segments.last().copied().unwrap_or_default().to_string();
+    let module = segments
+        .len()
+        .checked_sub(2)
+        .map(|index| segments[index].to_string())
+        .unwrap_or_default();
+    (module, name)
+}
+
+/// Every command the backend exposes, grouped by module, flagged with
+/// whether it is callable given the current app state
+#[tauri::command]
+pub async fn list_commands(state: State<'_, AppState>) -> Result<Vec<CommandGroup>, String> {
+    println!("📖 [Rust] list_commands called");
+
+    let cycle_ready = state.cycle_orchestrator.lock().await.is_some();
+    let (strict_ready, strict_active) = state
+        .strict_mode_orchestrator
+        .lock()
+        .await
+        .as_ref()
+        .map_or((false, false), |orchestrator| {
+            (true, orchestrator.get_state().is_active)
+        });
+
+    let mut groups: Vec<CommandGroup> = Vec::new();
+    for path in crate::app::COMMAND_PATHS {
+        let (module, name) = split_command_path(path);
+        let requires = COMMAND_REQUIREMENTS
+            .iter()
+            .find(|(command, _)| *command == name)
+            .map(|(_, requirement)| *requirement);
+        let available = match requires {
+            None => true,
+            Some(CommandRequirement::CycleOrchestrator) => cycle_ready,
+            Some(CommandRequirement::StrictModeOrchestrator) => strict_ready,
+            Some(CommandRequirement::StrictModeActive) => strict_active,
+        };
+
+        let info = CommandInfo {
+            name,
+            available,
+            requires,
+        };
+        match groups.iter_mut().find(|group| group.module == module) {
+            Some(group) => group.commands.push(info),
+            None => groups.push(CommandGroup {
+                module,
+                commands: vec![info],
+            }),
+        }
+    }
+
+    Ok(groups)
+}
@@ -1,10 +1,17 @@
-use crate::api_models::UserSettings as ApiUserSettings;
-use crate::database::models::UserSettings;
+use super::strict_mode_handler;
+use crate::api_models::{SettingsVsDefaults, UserSettings as ApiUserSettings};
+use crate::database::models::{
+    AppTheme, CyclePreset, FocusWidgetPauseBehavior, OverlayEscapeAction, PhaseColors,
+    PhaseCommandHooks, SettingsValidationError, SoftOverlayDismissAction, SoundConfig,
+    TrayClickAction, TrayIconStyle, UserSettings, WorkHoursEnforcement, WorkSchedule,
+};
 use crate::state::AppState;
+use crate::strict_mode::Shortcut;
+use crate::util::format_compact;
 use chrono::Utc;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CycleConfig {
@@ -12,6 +19,9 @@ pub struct CycleConfig {
     pub break_duration: i32,      // minutes
     pub long_break_duration: i32, // minutes
     pub cycles_per_long_break: i32,
+    /// First focus after a long break, in minutes; `None` uses `focus_duration`
+    #[serde(default)]
+    pub post_long_break_focus_minutes: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,34 +30,52 @@ pub struct StrictModeConfig {
     pub emergency_key_combination: Option<String>,
 }
 
-/// Validate cycle configuration
-fn validate_cycle_config(config: &CycleConfig) -> Result<(), String> {
-    // Validate focus duration (1-120 minutes)
-    if config.focus_duration < 1 || config.focus_duration > 120 {
-        return Err("Focus duration must be between 1 and 120 minutes".to_string());
-    }
+/// Allowed range for the cycle timer tick interval, in milliseconds
+const TICK_INTERVAL_RANGE_MS: std::ops::RangeInclusive<u32> = 250..=10_000;
 
-    // Validate break duration (1-60 minutes)
-    if config.break_duration < 1 || config.break_duration > 60 {
-        return Err("Break duration must be between 1 and 60 minutes".to_string());
-    }
+/// Tick interval used while idle with power saving on
+const POWER_SAVE_IDLE_TICK_INTERVAL_MS: u32 = 5_000;
 
-    // Validate long break duration (1-120 minutes)
-    if config.long_break_duration < 1 || config.long_break_duration > 120 {
-        return Err("Long break duration must be between 1 and 120 minutes".to_string());
-    }
+/// How often the frontend drives `cycle_tick`. Remaining time is measured
+/// from the clock, so a wider interval only makes updates less frequent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickInterval {
+    pub interval_ms: u32,
+    pub power_save: bool,
+    /// Interval to use while no session is running
+    pub idle_interval_ms: u32,
+}
 
-    // Validate cycles per long break (1-10)
-    if config.cycles_per_long_break < 1 || config.cycles_per_long_break > 10 {
-        return Err("Cycles per long break must be between 1 and 10".to_string());
-    }
+impl TickInterval {
+    fn from_settings(settings: &UserSettings) -> Self {
+        let interval_ms = settings.tick_interval_ms.max(0) as u32;
+        let idle_interval_ms = if settings.power_save {
+            interval_ms.max(POWER_SAVE_IDLE_TICK_INTERVAL_MS)
+        } else {
+            interval_ms
+        };
 
-    // Validate that long break is longer than regular break
-    if config.long_break_duration <= config.break_duration {
-        return Err("Long break duration must be longer than regular break duration".to_string());
+        Self {
+            interval_ms,
+            power_save: settings.power_save,
+            idle_interval_ms,
+        }
     }
+}
 
-    Ok(())
+/// Validate cycle configuration with the same rules as every other settings save
+fn validate_cycle_config(config: &CycleConfig) -> Result<(), SettingsValidationError> {
+    let to_seconds = |minutes: i32| minutes.saturating_mul(60);
+
+    UserSettings {
+        focus_duration: to_seconds(config.focus_duration),
+        short_break_duration: to_seconds(config.break_duration),
+        long_break_duration: to_seconds(config.long_break_duration),
+        cycles_per_long_break_v2: config.cycles_per_long_break,
+        post_long_break_focus_duration: config.post_long_break_focus_minutes.map(to_seconds),
+        ..UserSettings::default()
+    }
+    .validate_durations()
 }
 
 #[tauri::command]
@@ -69,6 +97,7 @@ pub async fn save_cycle_config(
     let focus_duration_seconds = config.focus_duration * 60;
     let break_duration_seconds = config.break_duration * 60;
     let long_break_duration_seconds = config.long_break_duration * 60;
+    let post_long_break_focus_seconds = config.post_long_break_focus_minutes.map(|m| m * 60);
 
     // Update user settings with cycle configuration
     let result = state.database.with_connection(|conn| {
@@ -79,7 +108,8 @@ pub async fn save_cycle_config(
                 short_break_duration = ?2,
                 long_break_duration = ?3,
                 cycles_per_long_break_v2 = ?4,
-                updated_at = ?5
+                post_long_break_focus_duration = ?5,
+                updated_at = ?6
             WHERE id = 1
             "#,
             params![
@@ -87,6 +117,7 @@ pub async fn save_cycle_config(
                 break_duration_seconds,
                 long_break_duration_seconds,
                 config.cycles_per_long_break,
+                post_long_break_focus_seconds,
                 now
             ],
         )
@@ -115,7 +146,7 @@ pub async fn get_cycle_config(state: State<'_, AppState>) -> Result<CycleConfig,
             .prepare(
                 r#"
                 SELECT focus_duration, short_break_duration, long_break_duration, 
-                       cycles_per_long_break_v2
+                       cycles_per_long_break_v2, post_long_break_focus_duration
                 FROM user_settings 
                 WHERE id = 1
                 "#,
@@ -129,6 +160,9 @@ pub async fn get_cycle_config(state: State<'_, AppState>) -> Result<CycleConfig,
                     break_duration: row.get::<_, i32>("short_break_duration")? / 60,
                     long_break_duration: row.get::<_, i32>("long_break_duration")? / 60,
                     cycles_per_long_break: row.get("cycles_per_long_break_v2")?,
+                    post_long_break_focus_minutes: row
+                        .get::<_, Option<i32>>("post_long_break_focus_duration")?
+                        .map(|seconds| seconds / 60),
                 })
             })
             .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
@@ -153,32 +187,17 @@ pub async fn get_cycle_config(state: State<'_, AppState>) -> Result<CycleConfig,
 pub async fn get_user_settings(state: State<'_, AppState>) -> Result<UserSettings, String> {
     println!("📖 [Rust] get_user_settings called");
 
-    let result = state.database.with_connection(|conn| {
-        let mut stmt = conn
-            .prepare(
-                r#"
-                SELECT id, focus_duration, short_break_duration, long_break_duration,
-                       cycles_per_long_break, cycles_per_long_break_v2, pre_alert_seconds,
-                       strict_mode, pin_hash, user_name, emergency_key_combination,
-                       break_transition_seconds, created_at, updated_at
-                FROM user_settings 
-                WHERE id = 1
-                "#,
-            )
-            .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
-
-        let settings = stmt
-            .query_row([], |row| UserSettings::from_row(row))
-            .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
-
-        Ok(settings)
-    });
+    let result = state.database.get_user_settings();
 
     match result {
-        Ok(settings) => {
+        Ok(Some(settings)) => {
             println!("✅ [Rust] User settings retrieved successfully");
             Ok(settings)
         }
+        Ok(None) => {
+            println!("ℹ️ [Rust] No user settings stored, returning defaults");
+            Ok(UserSettings::default())
+        }
         Err(e) => {
             let error_msg = format!("Failed to get user settings: {}", e);
             println!("❌ [Rust] {}", error_msg);
@@ -187,6 +206,22 @@ pub async fn get_user_settings(state: State<'_, AppState>) -> Result<UserSetting
     }
 }
 
+/// Every setting with its default value and whether the user changed it
+#[tauri::command]
+pub async fn get_settings_vs_defaults(
+    state: State<'_, AppState>,
+) -> Result<SettingsVsDefaults, String> {
+    println!("📖 [Rust] get_settings_vs_defaults called");
+
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|e| format!("Failed to get user settings: {}", e))?
+        .unwrap_or_default();
+
+    Ok(SettingsVsDefaults::new(&settings))
+}
+
 #[tauri::command]
 pub async fn update_user_name(user_name: String, state: State<'_, AppState>) -> Result<(), String> {
     println!("💾 [Rust] update_user_name called with name: {}", user_name);
@@ -224,8 +259,24 @@ pub async fn save_strict_mode_config(
         config
     );
 
+    // Store the emergency key in canonical form
+    let emergency_key_combination = config
+        .emergency_key_combination
+        .as_deref()
+        .map(|combination| Shortcut::parse_emergency_key(combination).map(|s| s.to_string()))
+        .transpose()?;
+
     let now = Utc::now();
 
+    // Turning strict mode off may only schedule the change
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|e| format!("Failed to get user settings: {}", e))?
+        .unwrap_or_default();
+    let (strict_mode, disable_at) =
+        strict_mode_handler::resolve_strict_mode_request(&settings, config.strict_mode, now);
+
     // Update user settings with strict mode configuration
     let result = state.database.with_connection(|conn| {
         conn.execute(
@@ -233,16 +284,21 @@ pub async fn save_strict_mode_config(
             UPDATE user_settings 
             SET strict_mode = ?1,
                 emergency_key_combination = ?2,
-                updated_at = ?3
+                strict_disable_at = ?3,
+                updated_at = ?4
             WHERE id = 1
             "#,
-            params![config.strict_mode, config.emergency_key_combination, now],
+            params![strict_mode, emergency_key_combination, disable_at, now],
         )
         .map_err(|e| crate::database::DatabaseError::Sqlite(e))
     });
 
     match result {
         Ok(_) => {
+            if let Some(at) = disable_at.filter(|_| settings.strict_disable_at.is_none()) {
+                println!("⏳ [Rust] Strict mode will turn off at {}", at);
+                strict_mode_handler::spawn_pending_strict_disable(state.app_handle.clone(), at);
+            }
             println!("✅ [Rust] Strict mode configuration saved successfully");
             Ok(())
         }
@@ -299,181 +355,1701 @@ pub async fn get_strict_mode_config(
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PreAlertConfig {
-    pub pre_alert_seconds: i32,
-    pub enabled: bool,
-}
-
-/// Update pre-alert configuration
+/// Enable or disable the focus widget during strict focus sessions
 #[tauri::command]
-pub async fn update_pre_alert_config(
-    config: PreAlertConfig,
+pub async fn set_strict_focus_show_widget(
+    enabled: bool,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     println!(
-        "💾 [Rust] update_pre_alert_config called with config: {:?}",
-        config
+        "💾 [Rust] set_strict_focus_show_widget called with enabled: {}",
+        enabled
     );
 
-    // Validate pre-alert seconds (30-300 seconds, i.e., 30 seconds to 5 minutes)
-    if config.pre_alert_seconds < 30 || config.pre_alert_seconds > 300 {
-        return Err("Pre-alert time must be between 30 and 300 seconds".to_string());
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET strict_focus_show_widget = ?1, updated_at = ?2 WHERE id = 1",
+            params![enabled, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save strict focus widget setting: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
     }
 
-    let now = Utc::now();
+    // Apply to the running strict mode orchestrator, if any
+    let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+    if let Some(orchestrator) = strict_mode_orchestrator.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.show_focus_widget = enabled;
+        orchestrator.update_config(config);
+    }
 
-    // If disabled, set to 0, otherwise use the configured value
-    let pre_alert_value = if config.enabled {
-        config.pre_alert_seconds
-    } else {
-        0
-    };
+    println!("✅ [Rust] Strict focus widget setting saved successfully");
+    Ok(())
+}
+
+/// Get whether the focus widget is shown during strict focus sessions
+#[tauri::command]
+pub async fn get_strict_focus_show_widget(state: State<'_, AppState>) -> Result<bool, String> {
+    println!("📖 [Rust] get_strict_focus_show_widget called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().strict_focus_show_widget),
+        Err(e) => {
+            let error_msg = format!("Failed to get strict focus widget setting: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Enable or disable the transition countdown before strict mode breaks
+#[tauri::command]
+pub async fn set_show_break_transition(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_show_break_transition called with enabled: {}",
+        enabled
+    );
+
+    let now = Utc::now();
 
     let result = state.database.with_connection(|conn| {
         conn.execute(
-            "UPDATE user_settings SET pre_alert_seconds = ?1, updated_at = ?2 WHERE id = 1",
-            params![pre_alert_value, now],
+            "UPDATE user_settings SET show_break_transition = ?1, updated_at = ?2 WHERE id = 1",
+            params![enabled, now],
         )
         .map_err(|e| crate::database::DatabaseError::Sqlite(e))
     });
 
-    match result {
-        Ok(_) => {
-            println!("✅ [Rust] Pre-alert configuration updated successfully");
-            Ok(())
-        }
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save break transition setting: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Apply to the running strict mode orchestrator, if any
+    let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+    if let Some(orchestrator) = strict_mode_orchestrator.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.show_break_transition = enabled;
+        orchestrator.update_config(config);
+    }
+
+    println!("✅ [Rust] Break transition setting saved successfully");
+    Ok(())
+}
+
+/// Get whether the transition countdown is shown before strict mode breaks
+#[tauri::command]
+pub async fn get_show_break_transition(state: State<'_, AppState>) -> Result<bool, String> {
+    println!("📖 [Rust] get_show_break_transition called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().show_break_transition),
         Err(e) => {
-            let error_msg = format!("Failed to update pre-alert configuration: {}", e);
+            let error_msg = format!("Failed to get break transition setting: {}", e);
             println!("❌ [Rust] {}", error_msg);
             Err(error_msg)
         }
     }
 }
 
-/// Get pre-alert configuration
+/// Require an explicit acknowledgment before strict mode breaks start
 #[tauri::command]
-pub async fn get_pre_alert_config(state: State<'_, AppState>) -> Result<PreAlertConfig, String> {
-    println!("📖 [Rust] get_pre_alert_config called");
+pub async fn set_strict_require_ack(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_strict_require_ack called with enabled: {}",
+        enabled
+    );
+
+    let now = Utc::now();
 
     let result = state.database.with_connection(|conn| {
-        let mut stmt = conn
-            .prepare("SELECT pre_alert_seconds FROM user_settings WHERE id = 1")
-            .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+        conn.execute(
+            "UPDATE user_settings SET strict_require_ack = ?1, updated_at = ?2 WHERE id = 1",
+            params![enabled, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
 
-        let pre_alert_seconds: i32 = stmt
-            .query_row([], |row| row.get(0))
-            .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save strict acknowledgment setting: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
 
-        Ok(PreAlertConfig {
-            pre_alert_seconds: if pre_alert_seconds > 0 {
-                pre_alert_seconds
-            } else {
-                120 // Default to 2 minutes if disabled
-            },
-            enabled: pre_alert_seconds > 0,
-        })
-    });
+    // Apply to the running strict mode orchestrator, if any
+    let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+    if let Some(orchestrator) = strict_mode_orchestrator.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.require_ack = enabled;
+        orchestrator.update_config(config);
+    }
 
-    match result {
-        Ok(config) => {
-            println!("✅ [Rust] Pre-alert configuration retrieved: {:?}", config);
-            Ok(config)
-        }
+    println!("✅ [Rust] Strict acknowledgment setting saved successfully");
+    Ok(())
+}
+
+/// Get whether strict mode breaks wait for an explicit acknowledgment
+#[tauri::command]
+pub async fn get_strict_require_ack(state: State<'_, AppState>) -> Result<bool, String> {
+    println!("📖 [Rust] get_strict_require_ack called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().strict_require_ack),
         Err(e) => {
-            let error_msg = format!("Failed to get pre-alert configuration: {}", e);
+            let error_msg = format!("Failed to get strict acknowledgment setting: {}", e);
             println!("❌ [Rust] {}", error_msg);
             Err(error_msg)
         }
     }
 }
 
-/// Get all user settings
+/// Set how the focus widget reacts when a session is paused
 #[tauri::command]
-pub async fn get_settings(state: State<'_, AppState>) -> Result<ApiUserSettings, String> {
-    println!("📖 [Rust] get_settings called");
+pub async fn set_focus_widget_pause_behavior(
+    behavior: FocusWidgetPauseBehavior,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_focus_widget_pause_behavior called with behavior: {}",
+        behavior
+    );
 
-    // Get user settings from database
-    let db_settings = match state.database.get_user_settings() {
-        Ok(Some(settings)) => settings,
-        Ok(None) => {
-            // Return default settings if none exist
-            return Ok(ApiUserSettings::default());
-        }
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET focus_widget_pause_behavior = ?1, updated_at = ?2 WHERE id = 1",
+            params![behavior.to_string(), now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save focus widget pause behavior: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Apply to the running cycle orchestrator, if any
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    if let Some(orchestrator) = cycle_orchestrator.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.pause_behavior = behavior;
+        orchestrator.update_config(config);
+    }
+
+    println!("✅ [Rust] Focus widget pause behavior saved successfully");
+    Ok(())
+}
+
+/// Get how the focus widget reacts when a session is paused
+#[tauri::command]
+pub async fn get_focus_widget_pause_behavior(
+    state: State<'_, AppState>,
+) -> Result<FocusWidgetPauseBehavior, String> {
+    println!("📖 [Rust] get_focus_widget_pause_behavior called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().focus_widget_pause_behavior),
         Err(e) => {
-            let error_msg = format!("Failed to get user settings: {}", e);
+            let error_msg = format!("Failed to get focus widget pause behavior: {}", e);
             println!("❌ [Rust] {}", error_msg);
-            return Err(error_msg);
+            Err(error_msg)
         }
-    };
+    }
+}
 
-    // Convert to API model
-    let api_settings = ApiUserSettings {
-        focus_duration: (db_settings.focus_duration / 60) as u32,
-        short_break_duration: (db_settings.short_break_duration / 60) as u32,
-        long_break_duration: (db_settings.long_break_duration / 60) as u32,
-        cycles_per_long_break: db_settings.cycles_per_long_break as u32,
-        pre_alert_seconds: db_settings.pre_alert_seconds as u32,
-        strict_mode: db_settings.strict_mode,
-        pin_hash: db_settings.pin_hash,
-        emergency_key_combination: db_settings.emergency_key_combination,
-        break_transition_seconds: db_settings.break_transition_seconds as u32,
-    };
+/// Set what happens when a focus session starts outside work hours
+#[tauri::command]
+pub async fn set_work_hours_enforcement(
+    enforcement: WorkHoursEnforcement,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_work_hours_enforcement called with enforcement: {}",
+        enforcement
+    );
 
-    println!("✅ [Rust] Settings retrieved successfully");
-    Ok(api_settings)
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET work_hours_enforcement = ?1, updated_at = ?2 WHERE id = 1",
+            params![enforcement.to_string(), now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save work hours enforcement: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Apply to the running cycle orchestrator, if any
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    if let Some(orchestrator) = cycle_orchestrator.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.work_hours_enforcement = enforcement;
+        orchestrator.update_config(config);
+    }
+
+    println!("✅ [Rust] Work hours enforcement saved successfully");
+    Ok(())
 }
 
-/// Update all user settings including blocked apps and websites
+/// Get what happens when a focus session starts outside work hours
 #[tauri::command]
-pub async fn update_settings(
-    settings: ApiUserSettings,
+pub async fn get_work_hours_enforcement(
+    state: State<'_, AppState>,
+) -> Result<WorkHoursEnforcement, String> {
+    println!("📖 [Rust] get_work_hours_enforcement called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().work_hours_enforcement),
+        Err(e) => {
+            let error_msg = format!("Failed to get work hours enforcement: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Set the tray icon style and apply it immediately
+#[tauri::command]
+pub async fn set_tray_icon_style(
+    style: TrayIconStyle,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    println!("💾 [Rust] update_settings called");
+    println!("💾 [Rust] set_tray_icon_style called with style: {}", style);
 
     let now = Utc::now();
 
-    // Get existing settings to preserve user_name, emergency_key_combination, and created_at
-    let existing_settings = state
-        .database
-        .get_user_settings()
-        .map_err(|e| format!("Failed to get existing settings: {}", e))?;
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET tray_icon_style = ?1, updated_at = ?2 WHERE id = 1",
+            params![style.to_string(), now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
 
-    // Convert API settings to database model
-    let db_settings = UserSettings {
-        id: 1,
-        focus_duration: (settings.focus_duration * 60) as i32, // Convert minutes to seconds
-        short_break_duration: (settings.short_break_duration * 60) as i32,
-        long_break_duration: (settings.long_break_duration * 60) as i32,
-        cycles_per_long_break: settings.cycles_per_long_break as i32,
-        cycles_per_long_break_v2: settings.cycles_per_long_break as i32,
-        pre_alert_seconds: settings.pre_alert_seconds as i32,
-        strict_mode: settings.strict_mode,
-        pin_hash: settings.pin_hash,
-        user_name: existing_settings.as_ref().and_then(|s| s.user_name.clone()),
-        emergency_key_combination: settings.emergency_key_combination.or_else(|| {
-            existing_settings
-                .as_ref()
-                .and_then(|s| s.emergency_key_combination.clone())
-        }),
-        break_transition_seconds: existing_settings
-            .as_ref()
-            .map(|s| s.break_transition_seconds)
-            .unwrap_or(10),
-        created_at: existing_settings
-            .as_ref()
-            .map(|s| s.created_at)
-            .unwrap_or(now),
-        updated_at: now,
-    };
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save tray icon style: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
 
-    // Save user settings
-    state
-        .database
-        .save_user_settings(&db_settings)
-        .map_err(|e| format!("Failed to save user settings: {}", e))?;
+    crate::tray_icon::apply_tray_icon_style(&app, style)?;
 
-    println!("✅ [Rust] Settings updated successfully");
+    println!("✅ [Rust] Tray icon style saved successfully");
     Ok(())
 }
+
+/// Get the persisted tray icon style
+#[tauri::command]
+pub async fn get_tray_icon_style(state: State<'_, AppState>) -> Result<TrayIconStyle, String> {
+    println!("📖 [Rust] get_tray_icon_style called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().tray_icon_style),
+        Err(e) => {
+            let error_msg = format!("Failed to get tray icon style: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Set what a left click on the tray icon does
+#[tauri::command]
+pub async fn set_tray_click_action(
+    action: TrayClickAction,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_tray_click_action called with action: {}",
+        action
+    );
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET tray_click_action = ?1, updated_at = ?2 WHERE id = 1",
+            params![action.to_string(), now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save tray click action: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Tray click action saved successfully");
+    Ok(())
+}
+
+/// Get the configured tray click action
+#[tauri::command]
+pub async fn get_tray_click_action(state: State<'_, AppState>) -> Result<TrayClickAction, String> {
+    println!("📖 [Rust] get_tray_click_action called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().tray_click_action),
+        Err(e) => {
+            let error_msg = format!("Failed to get tray click action: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Persist the tick settings and tell the frontend timer to pick them up
+fn save_tick_setting(
+    column: &str,
+    value: &dyn rusqlite::ToSql,
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+) -> Result<TickInterval, String> {
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            &format!(
+                "UPDATE user_settings SET {} = ?1, updated_at = ?2 WHERE id = 1",
+                column
+            ),
+            params![value, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save {}: {}", column, e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|e| format!("Failed to get user settings: {}", e))?
+        .unwrap_or_default();
+    let interval = TickInterval::from_settings(&settings);
+    state
+        .performance_counters
+        .set_tick_interval_ms(interval.interval_ms);
+
+    if let Err(e) = app.emit("tick-interval-changed", &interval) {
+        eprintln!("Failed to emit tick-interval-changed: {}", e);
+    }
+
+    Ok(interval)
+}
+
+/// Set how often the cycle timer ticks, in milliseconds
+#[tauri::command]
+pub async fn set_tick_interval(
+    ms: u32,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<TickInterval, String> {
+    println!("💾 [Rust] set_tick_interval called with ms: {}", ms);
+
+    if !TICK_INTERVAL_RANGE_MS.contains(&ms) {
+        return Err(format!(
+            "Tick interval must be between {} and {} ms",
+            TICK_INTERVAL_RANGE_MS.start(),
+            TICK_INTERVAL_RANGE_MS.end()
+        ));
+    }
+
+    let interval = save_tick_setting("tick_interval_ms", &ms, &app, &state)?;
+
+    println!("✅ [Rust] Tick interval saved successfully");
+    Ok(interval)
+}
+
+/// Tick less often while idle to reduce wakeups (e.g. on battery)
+#[tauri::command]
+pub async fn set_power_save(
+    enabled: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<TickInterval, String> {
+    println!("💾 [Rust] set_power_save called with enabled: {}", enabled);
+
+    let interval = save_tick_setting("power_save", &enabled, &app, &state)?;
+
+    println!("✅ [Rust] Power save setting saved successfully");
+    Ok(interval)
+}
+
+/// Get the current tick interval settings
+#[tauri::command]
+pub async fn get_tick_interval(state: State<'_, AppState>) -> Result<TickInterval, String> {
+    println!("📖 [Rust] get_tick_interval called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(TickInterval::from_settings(&settings.unwrap_or_default())),
+        Err(e) => {
+            let error_msg = format!("Failed to get tick interval: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Set the color of each phase, applied to every window
+#[tauri::command]
+pub async fn set_phase_colors(
+    colors: PhaseColors,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PhaseColors, String> {
+    println!(
+        "💾 [Rust] set_phase_colors called with colors: {:?}",
+        colors
+    );
+
+    let colors = colors.normalized()?;
+    let colors_json = serde_json::to_string(&colors)
+        .map_err(|e| format!("Failed to serialize phase colors: {}", e))?;
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET phase_colors = ?1, updated_at = ?2 WHERE id = 1",
+            params![colors_json, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save phase colors: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Apply to the running cycle so `CycleState::phase_color` follows
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    if let Some(orchestrator) = cycle_orchestrator.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.phase_colors = colors.clone();
+        orchestrator.update_config(config);
+    }
+    drop(cycle_orchestrator);
+
+    if let Err(e) = app.emit("phase-colors-changed", &colors) {
+        eprintln!("Failed to emit phase-colors-changed: {}", e);
+    }
+
+    println!("✅ [Rust] Phase colors saved successfully");
+    Ok(colors)
+}
+
+/// Get the color of each phase
+#[tauri::command]
+pub async fn get_phase_colors(state: State<'_, AppState>) -> Result<PhaseColors, String> {
+    println!("📖 [Rust] get_phase_colors called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().phase_colors),
+        Err(e) => {
+            let error_msg = format!("Failed to get phase colors: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Set the sounds played when a focus session or break ends
+#[tauri::command]
+pub async fn set_sound_config(
+    config: SoundConfig,
+    state: State<'_, AppState>,
+) -> Result<SoundConfig, String> {
+    println!(
+        "💾 [Rust] set_sound_config called with config: {:?}",
+        config
+    );
+
+    let config_json = serde_json::to_string(&config)
+        .map_err(|e| format!("Failed to serialize sound config: {}", e))?;
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET sound_config = ?1, updated_at = ?2 WHERE id = 1",
+            params![config_json, now],
+        )
+        .map_err(crate::database::DatabaseError::Sqlite)
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save sound config: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    state
+        .notification_service
+        .lock()
+        .await
+        .set_sound_config(config.clone());
+
+    println!("✅ [Rust] Sound config saved successfully");
+    Ok(config)
+}
+
+/// Get the sounds played when a focus session or break ends
+#[tauri::command]
+pub async fn get_sound_config(state: State<'_, AppState>) -> Result<SoundConfig, String> {
+    println!("📖 [Rust] get_sound_config called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().sound_config),
+        Err(e) => {
+            let error_msg = format!("Failed to get sound config: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+impl From<&CyclePreset> for CycleConfig {
+    fn from(preset: &CyclePreset) -> Self {
+        Self {
+            focus_duration: preset.focus_duration,
+            break_duration: preset.break_duration,
+            long_break_duration: preset.long_break_duration,
+            cycles_per_long_break: preset.cycles_per_long_break,
+            post_long_break_focus_minutes: None,
+        }
+    }
+}
+
+/// Save a named cycle preset, validated like any other cycle configuration.
+/// A preset without an id is added; one with an id replaces that preset.
+#[tauri::command]
+pub async fn save_cycle_preset(
+    preset: CyclePreset,
+    state: State<'_, AppState>,
+) -> Result<CyclePreset, String> {
+    println!(
+        "💾 [Rust] save_cycle_preset called with preset: {:?}",
+        preset
+    );
+
+    let name = preset.name.trim().to_string();
+    if name.is_empty() {
+        let error_msg = "Preset name can't be empty".to_string();
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+    validate_cycle_config(&CycleConfig::from(&preset))?;
+
+    match state
+        .database
+        .save_cycle_preset(&CyclePreset { name, ..preset })
+    {
+        Ok(saved) => {
+            println!("✅ [Rust] Cycle preset saved: {:?}", saved);
+            Ok(saved)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to save cycle preset: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn list_cycle_presets(state: State<'_, AppState>) -> Result<Vec<CyclePreset>, String> {
+    println!("📖 [Rust] list_cycle_presets called");
+
+    state.database.list_cycle_presets().map_err(|e| {
+        let error_msg = format!("Failed to list cycle presets: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        error_msg
+    })
+}
+
+/// Delete a cycle preset; returns whether it existed. Settings applied from
+/// it are kept.
+#[tauri::command]
+pub async fn delete_cycle_preset(
+    id: i32,
+    confirm: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    println!("🗑️ [Rust] delete_cycle_preset called with id: {}", id);
+
+    super::require_confirmation(&confirm, "delete_cycle_preset")?;
+
+    state.database.delete_cycle_preset(id).map_err(|e| {
+        let error_msg = format!("Failed to delete cycle preset: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        error_msg
+    })
+}
+
+/// Make a preset's durations the active cycle configuration and apply them
+/// to the running orchestrator. The current phase keeps its length; the new
+/// durations take effect from the next one.
+#[tauri::command]
+pub async fn apply_cycle_preset(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<CyclePreset, String> {
+    println!("💾 [Rust] apply_cycle_preset called with id: {}", id);
+
+    let preset = match state.database.get_cycle_preset(id) {
+        Ok(Some(preset)) => preset,
+        Ok(None) => {
+            let error_msg = format!("No cycle preset with id {}", id);
+            println!("❌ [Rust] {}", error_msg);
+            return Err(error_msg);
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to load cycle preset: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            return Err(error_msg);
+        }
+    };
+    validate_cycle_config(&CycleConfig::from(&preset))?;
+
+    let focus_seconds = preset.focus_duration * 60;
+    let break_seconds = preset.break_duration * 60;
+    let long_break_seconds = preset.long_break_duration * 60;
+
+    // A shorter post-long-break focus still fits; a longer one no longer would
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            r#"
+            UPDATE user_settings
+            SET focus_duration = ?1,
+                short_break_duration = ?2,
+                long_break_duration = ?3,
+                cycles_per_long_break_v2 = ?4,
+                post_long_break_focus_duration = CASE
+                    WHEN post_long_break_focus_duration > ?1 THEN NULL
+                    ELSE post_long_break_focus_duration
+                END,
+                updated_at = ?5
+            WHERE id = 1
+            "#,
+            params![
+                focus_seconds,
+                break_seconds,
+                long_break_seconds,
+                preset.cycles_per_long_break,
+                Utc::now()
+            ],
+        )
+        .map_err(crate::database::DatabaseError::Sqlite)
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to apply cycle preset: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    if let Some(orchestrator) = state.cycle_orchestrator.lock().await.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.focus_duration = focus_seconds as u32;
+        config.break_duration = break_seconds as u32;
+        config.long_break_duration = long_break_seconds as u32;
+        config.cycles_per_long_break = preset.cycles_per_long_break as u32;
+        if config
+            .post_long_break_focus_duration
+            .is_some_and(|seconds| seconds > config.focus_duration)
+        {
+            config.post_long_break_focus_duration = None;
+        }
+        orchestrator.update_config(config);
+    }
+
+    println!("✅ [Rust] Cycle preset \"{}\" applied", preset.name);
+    Ok(preset)
+}
+
+/// Set the commands run when each phase starts. Hooks run arbitrary
+/// user-provided shell commands, so they only fire once explicitly enabled.
+#[tauri::command]
+pub async fn set_phase_command_hooks(
+    hooks: PhaseCommandHooks,
+    state: State<'_, AppState>,
+) -> Result<PhaseCommandHooks, String> {
+    println!(
+        "💾 [Rust] set_phase_command_hooks called with hooks: {:?}",
+        hooks
+    );
+
+    let hooks = hooks.normalized()?;
+    let hooks_json = serde_json::to_string(&hooks)
+        .map_err(|e| format!("Failed to serialize phase command hooks: {}", e))?;
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET phase_command_hooks = ?1, updated_at = ?2 WHERE id = 1",
+            params![hooks_json, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save phase command hooks: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Phase command hooks saved successfully");
+    Ok(hooks)
+}
+
+/// Get the commands run when each phase starts
+#[tauri::command]
+pub async fn get_phase_command_hooks(
+    state: State<'_, AppState>,
+) -> Result<PhaseCommandHooks, String> {
+    println!("📖 [Rust] get_phase_command_hooks called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().phase_command_hooks),
+        Err(e) => {
+            let error_msg = format!("Failed to get phase command hooks: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Longest break message accepted, in characters
+const MAX_BREAK_MESSAGE_LENGTH: usize = 140;
+
+/// Set the note shown on the break overlay; an empty message clears it
+#[tauri::command]
+pub async fn set_break_message(message: String, state: State<'_, AppState>) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_break_message called with message: {:?}",
+        message
+    );
+
+    let message = message.trim();
+    if message.chars().count() > MAX_BREAK_MESSAGE_LENGTH {
+        return Err(format!(
+            "Break message must be at most {} characters",
+            MAX_BREAK_MESSAGE_LENGTH
+        ));
+    }
+    let message = (!message.is_empty()).then_some(message);
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET break_message = ?1, updated_at = ?2 WHERE id = 1",
+            params![message, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save break message: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Break message saved successfully");
+    Ok(())
+}
+
+/// Get the note shown on the break overlay, if any
+#[tauri::command]
+pub async fn get_break_message(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    println!("📖 [Rust] get_break_message called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().break_message),
+        Err(e) => {
+            let error_msg = format!("Failed to get break message: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Set the app theme and apply it to every window
+#[tauri::command]
+pub async fn set_theme(
+    theme: AppTheme,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!("💾 [Rust] set_theme called with theme: {}", theme);
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET theme = ?1, updated_at = ?2 WHERE id = 1",
+            params![theme.to_string(), now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save theme: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    crate::theme::apply_theme(&app, theme)?;
+
+    println!("✅ [Rust] Theme saved successfully");
+    Ok(())
+}
+
+/// Get the persisted app theme
+#[tauri::command]
+pub async fn get_theme(state: State<'_, AppState>) -> Result<AppTheme, String> {
+    println!("📖 [Rust] get_theme called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().theme),
+        Err(e) => {
+            let error_msg = format!("Failed to get theme: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Set what Escape does on the break overlay
+#[tauri::command]
+pub async fn set_overlay_escape_action(
+    action: OverlayEscapeAction,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_overlay_escape_action called with action: {}",
+        action
+    );
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET overlay_escape_action = ?1, updated_at = ?2 WHERE id = 1",
+            params![action.to_string(), now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save overlay escape action: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Overlay escape action saved successfully");
+    Ok(())
+}
+
+/// Get the configured overlay escape action
+#[tauri::command]
+pub async fn get_overlay_escape_action(
+    state: State<'_, AppState>,
+) -> Result<OverlayEscapeAction, String> {
+    println!("📖 [Rust] get_overlay_escape_action called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => Ok(settings.unwrap_or_default().overlay_escape_action),
+        Err(e) => {
+            let error_msg = format!("Failed to get overlay escape action: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Allowed range for how long a snoozed break stays paused, in minutes
+const BREAK_SNOOZE_MINUTES_RANGE: std::ops::RangeInclusive<u32> = 1..=60;
+
+/// Dismiss action for the break overlay outside strict mode, and the snooze length
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoftOverlayDismissConfig {
+    pub action: SoftOverlayDismissAction,
+    pub snooze_minutes: u32,
+}
+
+/// Set what dismissing the break overlay does while strict mode is off
+#[tauri::command]
+pub async fn set_soft_overlay_dismiss_action(
+    config: SoftOverlayDismissConfig,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_soft_overlay_dismiss_action called with {:?}",
+        config
+    );
+
+    if !BREAK_SNOOZE_MINUTES_RANGE.contains(&config.snooze_minutes) {
+        let error_msg = format!(
+            "Snooze must be between {} and {} minutes",
+            BREAK_SNOOZE_MINUTES_RANGE.start(),
+            BREAK_SNOOZE_MINUTES_RANGE.end()
+        );
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET soft_overlay_dismiss_action = ?1, break_snooze_minutes = ?2, updated_at = ?3 WHERE id = 1",
+            params![config.action.to_string(), config.snooze_minutes, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save soft overlay dismiss action: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Soft overlay dismiss action saved successfully");
+    Ok(())
+}
+
+/// Get what dismissing the break overlay does while strict mode is off
+#[tauri::command]
+pub async fn get_soft_overlay_dismiss_action(
+    state: State<'_, AppState>,
+) -> Result<SoftOverlayDismissConfig, String> {
+    println!("📖 [Rust] get_soft_overlay_dismiss_action called");
+
+    match state.database.get_user_settings() {
+        Ok(settings) => {
+            let settings = settings.unwrap_or_default();
+            Ok(SoftOverlayDismissConfig {
+                action: settings.soft_overlay_dismiss_action,
+                snooze_minutes: settings.break_snooze_minutes.max(1) as u32,
+            })
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to get soft overlay dismiss action: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PreAlertConfig {
+    pub pre_alert_seconds: i32,
+    pub enabled: bool,
+}
+
+/// Update pre-alert configuration
+#[tauri::command]
+pub async fn update_pre_alert_config(
+    config: PreAlertConfig,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] update_pre_alert_config called with config: {:?}",
+        config
+    );
+
+    // Validate pre-alert seconds (30-300 seconds, i.e., 30 seconds to 5 minutes)
+    if config.pre_alert_seconds < 30 || config.pre_alert_seconds > 300 {
+        return Err("Pre-alert time must be between 30 and 300 seconds".to_string());
+    }
+
+    let now = Utc::now();
+
+    // If disabled, set to 0, otherwise use the configured value
+    let pre_alert_value = if config.enabled {
+        config.pre_alert_seconds
+    } else {
+        0
+    };
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET pre_alert_seconds = ?1, updated_at = ?2 WHERE id = 1",
+            params![pre_alert_value, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    match result {
+        Ok(_) => {
+            println!("✅ [Rust] Pre-alert configuration updated successfully");
+            Ok(())
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to update pre-alert configuration: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Get pre-alert configuration
+#[tauri::command]
+pub async fn get_pre_alert_config(state: State<'_, AppState>) -> Result<PreAlertConfig, String> {
+    println!("📖 [Rust] get_pre_alert_config called");
+
+    let result = state.database.with_connection(|conn| {
+        let mut stmt = conn
+            .prepare("SELECT pre_alert_seconds FROM user_settings WHERE id = 1")
+            .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+
+        let pre_alert_seconds: i32 = stmt
+            .query_row([], |row| row.get(0))
+            .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+
+        Ok(PreAlertConfig {
+            pre_alert_seconds: if pre_alert_seconds > 0 {
+                pre_alert_seconds
+            } else {
+                120 // Default to 2 minutes if disabled
+            },
+            enabled: pre_alert_seconds > 0,
+        })
+    });
+
+    match result {
+        Ok(config) => {
+            println!("✅ [Rust] Pre-alert configuration retrieved: {:?}", config);
+            Ok(config)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to get pre-alert configuration: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+/// Get all user settings
+#[tauri::command]
+pub async fn get_settings(state: State<'_, AppState>) -> Result<ApiUserSettings, String> {
+    println!("📖 [Rust] get_settings called");
+
+    // Get user settings from database
+    let db_settings = match state.database.get_user_settings() {
+        Ok(Some(settings)) => settings,
+        Ok(None) => {
+            // Return default settings if none exist
+            return Ok(ApiUserSettings::default());
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to get user settings: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            return Err(error_msg);
+        }
+    };
+
+    // Convert to API model
+    let api_settings = ApiUserSettings {
+        focus_duration: (db_settings.focus_duration / 60) as u32,
+        short_break_duration: (db_settings.short_break_duration / 60) as u32,
+        long_break_duration: (db_settings.long_break_duration / 60) as u32,
+        cycles_per_long_break: db_settings.cycles_per_long_break as u32,
+        pre_alert_seconds: db_settings.pre_alert_seconds as u32,
+        strict_mode: db_settings.strict_mode,
+        pin_hash: db_settings.pin_hash,
+        emergency_key_combination: db_settings.emergency_key_combination,
+        break_transition_seconds: db_settings.break_transition_seconds as u32,
+    };
+
+    println!("✅ [Rust] Settings retrieved successfully");
+    Ok(api_settings)
+}
+
+/// Update all user settings including blocked apps and websites
+#[tauri::command]
+pub async fn update_settings(
+    settings: ApiUserSettings,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!("💾 [Rust] update_settings called");
+
+    let now = Utc::now();
+
+    // Get existing settings to preserve fields not exposed through the API model
+    let existing_settings = state
+        .database
+        .get_user_settings()
+        .map_err(|e| format!("Failed to get existing settings: {}", e))?;
+
+    // Apply API settings on top of the existing row so fields not exposed
+    // through the API model (user name, strict mode extras, ...) are preserved
+    let mut db_settings = existing_settings.unwrap_or_else(|| UserSettings {
+        created_at: now,
+        ..UserSettings::default()
+    });
+    db_settings.id = 1;
+    db_settings.focus_duration = (settings.focus_duration * 60) as i32; // Convert minutes to seconds
+    db_settings.short_break_duration = (settings.short_break_duration * 60) as i32;
+    db_settings.long_break_duration = (settings.long_break_duration * 60) as i32;
+    db_settings.cycles_per_long_break = settings.cycles_per_long_break as i32;
+    db_settings.cycles_per_long_break_v2 = settings.cycles_per_long_break as i32;
+    db_settings.pre_alert_seconds = settings.pre_alert_seconds as i32;
+    // Every field is resubmitted here, so `strict_mode: true` during a cool-down
+    // leaves the pending disable alone; only `save_strict_mode_config` cancels it
+    let pending_disable = db_settings.strict_disable_at;
+    if !settings.strict_mode || pending_disable.is_none() {
+        (db_settings.strict_mode, db_settings.strict_disable_at) =
+            strict_mode_handler::resolve_strict_mode_request(
+                &db_settings,
+                settings.strict_mode,
+                now,
+            );
+    }
+    db_settings.pin_hash = settings.pin_hash;
+    if let Some(combination) = settings.emergency_key_combination {
+        db_settings.emergency_key_combination =
+            Some(Shortcut::parse_emergency_key(&combination)?.to_string());
+    }
+    db_settings.updated_at = now;
+
+    db_settings.validate_durations()?;
+
+    // Save user settings
+    state
+        .database
+        .save_user_settings(&db_settings)
+        .map_err(|e| format!("Failed to save user settings: {}", e))?;
+
+    if let Some(at) = db_settings
+        .strict_disable_at
+        .filter(|_| pending_disable.is_none())
+    {
+        println!("⏳ [Rust] Strict mode will turn off at {}", at);
+        strict_mode_handler::spawn_pending_strict_disable(state.app_handle.clone(), at);
+    }
+
+    println!("✅ [Rust] Settings updated successfully");
+    Ok(())
+}
+
+/// Largest weekly focus budget, in minutes (every minute of the week)
+const MAX_WEEKLY_FOCUS_BUDGET_MINUTES: u32 = 7 * 24 * 60;
+
+/// Set the weekly focus budget in minutes, or clear it with `None`.
+/// Starting focus past the budget only warns, it never blocks.
+#[tauri::command]
+pub async fn set_weekly_focus_budget(
+    minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_weekly_focus_budget called with {:?} minutes",
+        minutes
+    );
+
+    if let Some(minutes) = minutes {
+        if minutes == 0 || minutes > MAX_WEEKLY_FOCUS_BUDGET_MINUTES {
+            let error_msg = format!(
+                "Weekly focus budget must be between 1 and {} minutes",
+                MAX_WEEKLY_FOCUS_BUDGET_MINUTES
+            );
+            println!("❌ [Rust] {}", error_msg);
+            return Err(error_msg);
+        }
+    }
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET weekly_focus_budget_minutes = ?1, updated_at = ?2 WHERE id = 1",
+            params![minutes, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save weekly focus budget: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Weekly focus budget saved successfully");
+    Ok(())
+}
+
+/// Largest break overrun allowance, in minutes
+const MAX_BREAK_OVERRUN_MINUTES: u32 = 240;
+
+/// Set how many minutes a break may run past its planned length before a
+/// reminder to get back to focus, or disable the reminder with `None`.
+/// Takes effect from the next phase.
+#[tauri::command]
+pub async fn set_max_break_overrun(
+    minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_max_break_overrun called with {:?} minutes",
+        minutes
+    );
+
+    if let Some(minutes) = minutes {
+        if minutes == 0 || minutes > MAX_BREAK_OVERRUN_MINUTES {
+            let error_msg = format!(
+                "Break overrun allowance must be between 1 and {} minutes",
+                MAX_BREAK_OVERRUN_MINUTES
+            );
+            println!("❌ [Rust] {}", error_msg);
+            return Err(error_msg);
+        }
+    }
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET max_break_overrun_minutes = ?1, updated_at = ?2 WHERE id = 1",
+            params![minutes, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save break overrun allowance: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Break overrun allowance saved successfully");
+    Ok(())
+}
+
+/// Longest wind-down break, in minutes
+const MAX_WINDDOWN_MINUTES: u32 = 60;
+
+/// Set how long the wind-down offered as work hours end lasts, or stop
+/// offering it with `None`. Needs a work schedule to have any effect.
+/// Takes effect from the next phase.
+#[tauri::command]
+pub async fn set_winddown_minutes(
+    minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_winddown_minutes called with {:?} minutes",
+        minutes
+    );
+
+    if let Some(minutes) = minutes {
+        if minutes == 0 || minutes > MAX_WINDDOWN_MINUTES {
+            let error_msg = format!(
+                "Wind-down must be between 1 and {} minutes",
+                MAX_WINDDOWN_MINUTES
+            );
+            println!("❌ [Rust] {}", error_msg);
+            return Err(error_msg);
+        }
+    }
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET winddown_minutes = ?1, updated_at = ?2 WHERE id = 1",
+            params![minutes, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save wind-down length: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Wind-down length saved successfully");
+    Ok(())
+}
+
+/// Allowed seconds between saves of a running session's progress
+const SESSION_SAVE_INTERVAL_RANGE_SECONDS: std::ops::RangeInclusive<u32> = 5..=600;
+
+/// Set how often a running session's progress is written to the database.
+/// Longer intervals mean fewer writes; a crash loses at most one interval.
+/// Phase changes and completions are always saved right away.
+#[tauri::command]
+pub async fn set_session_save_interval(
+    seconds: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_session_save_interval called with {} seconds",
+        seconds
+    );
+
+    if !SESSION_SAVE_INTERVAL_RANGE_SECONDS.contains(&seconds) {
+        let error_msg = format!(
+            "Session save interval must be between {} and {} seconds",
+            SESSION_SAVE_INTERVAL_RANGE_SECONDS.start(),
+            SESSION_SAVE_INTERVAL_RANGE_SECONDS.end()
+        );
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET session_save_interval_seconds = ?1, updated_at = ?2 WHERE id = 1",
+            params![seconds, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save session save interval: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Apply to the running orchestrator too, so it doesn't wait for a reload
+    if let Some(orchestrator) = state.cycle_orchestrator.lock().await.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.session_save_interval_seconds = seconds;
+        orchestrator.update_config(config);
+    }
+
+    println!("✅ [Rust] Session save interval saved successfully");
+    Ok(())
+}
+
+/// Allowed short break length as a fraction of the focus duration
+const BREAK_RATIO_RANGE: std::ops::RangeInclusive<f64> = 0.05..=1.0;
+
+/// Allowed bounds for a scaled short break, in minutes
+const BREAK_RATIO_BOUNDS_RANGE_MINUTES: std::ops::RangeInclusive<u32> = 1..=60;
+
+/// Turn proportional short breaks on or off. When `enabled`, a short break
+/// lasts `ratio` of the focus duration, kept between `min_minutes` and
+/// `max_minutes`; otherwise the fixed break duration is used. Long breaks
+/// keep their fixed duration either way.
+#[tauri::command]
+pub async fn set_break_ratio(
+    enabled: bool,
+    ratio: f64,
+    min_minutes: u32,
+    max_minutes: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_break_ratio called: enabled={}, ratio={}, {}-{} minutes",
+        enabled, ratio, min_minutes, max_minutes
+    );
+
+    let error_msg = if !BREAK_RATIO_RANGE.contains(&ratio) {
+        Some(format!(
+            "Break ratio must be between {} and {}",
+            BREAK_RATIO_RANGE.start(),
+            BREAK_RATIO_RANGE.end()
+        ))
+    } else if !BREAK_RATIO_BOUNDS_RANGE_MINUTES.contains(&min_minutes)
+        || !BREAK_RATIO_BOUNDS_RANGE_MINUTES.contains(&max_minutes)
+    {
+        Some(format!(
+            "Break ratio bounds must be between {} and {} minutes",
+            BREAK_RATIO_BOUNDS_RANGE_MINUTES.start(),
+            BREAK_RATIO_BOUNDS_RANGE_MINUTES.end()
+        ))
+    } else if min_minutes > max_minutes {
+        Some("Shortest break can't be longer than the longest break".to_string())
+    } else {
+        None
+    };
+
+    if let Some(error_msg) = error_msg {
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let now = Utc::now();
+    let min_seconds = min_minutes * 60;
+    let max_seconds = max_minutes * 60;
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings
+             SET break_ratio_mode = ?1, break_ratio = ?2,
+                 break_ratio_min_duration = ?3, break_ratio_max_duration = ?4,
+                 updated_at = ?5
+             WHERE id = 1",
+            params![enabled, ratio, min_seconds, max_seconds, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save break ratio: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Apply to the running orchestrator too, so the next break uses it
+    if let Some(orchestrator) = state.cycle_orchestrator.lock().await.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.break_ratio_mode = enabled;
+        config.break_ratio = ratio;
+        config.break_ratio_min_duration = min_seconds;
+        config.break_ratio_max_duration = max_seconds;
+        orchestrator.update_config(config);
+    }
+
+    println!("✅ [Rust] Break ratio saved successfully");
+    Ok(())
+}
+
+/// Keep the display from sleeping while a focus phase runs
+#[tauri::command]
+pub async fn set_prevent_display_sleep_during_focus(
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_prevent_display_sleep_during_focus called with enabled: {}",
+        enabled
+    );
+
+    let now = Utc::now();
+
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET prevent_display_sleep_during_focus = ?1, updated_at = ?2
+             WHERE id = 1",
+            params![enabled, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save display sleep setting: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Apply to the running orchestrator, taking effect on a focus already running
+    if let Some(orchestrator) = state.cycle_orchestrator.lock().await.as_mut() {
+        let mut config = orchestrator.get_config();
+        config.prevent_display_sleep_during_focus = enabled;
+        orchestrator.update_config(config);
+
+        if let Ok(mut inhibitor) = state.display_sleep_inhibitor.lock() {
+            inhibitor.sync(orchestrator.keeps_display_awake());
+        }
+    }
+
+    println!("✅ [Rust] Display sleep setting saved successfully");
+    Ok(())
+}
+
+/// Longest cool-down before turning strict mode off takes effect
+const MAX_STRICT_DISABLE_DELAY_SECONDS: u32 = 24 * 60 * 60;
+
+/// Set how long turning strict mode off in settings waits before it takes
+/// effect; 0 applies it immediately. While strict mode is on the delay can
+/// only grow, so shortening it can't be used to skip the cool-down.
+#[tauri::command]
+pub async fn set_strict_disable_delay(
+    seconds: u32,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_strict_disable_delay called with {} seconds",
+        seconds
+    );
+
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|e| format!("Failed to get user settings: {}", e))?
+        .unwrap_or_default();
+
+    let error_msg = if seconds > MAX_STRICT_DISABLE_DELAY_SECONDS {
+        Some(format!(
+            "Strict mode disable delay must be at most {} seconds",
+            MAX_STRICT_DISABLE_DELAY_SECONDS
+        ))
+    } else if settings.strict_mode && (seconds as i32) < settings.strict_disable_delay_seconds {
+        Some("The strict mode disable delay can't be shortened while strict mode is on".to_string())
+    } else {
+        None
+    };
+
+    if let Some(error_msg) = error_msg {
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let now = Utc::now();
+    let result = state.database.with_connection(|conn| {
+        conn.execute(
+            "UPDATE user_settings SET strict_disable_delay_seconds = ?1, updated_at = ?2 WHERE id = 1",
+            params![seconds, now],
+        )
+        .map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    });
+
+    if let Err(e) = result {
+        let error_msg = format!("Failed to save strict mode disable delay: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    println!("✅ [Rust] Strict mode disable delay saved successfully");
+    Ok(())
+}
+
+/// Language of the configuration summary; anything unsupported reads as English
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SummaryLanguage {
+    English,
+    Spanish,
+}
+
+impl SummaryLanguage {
+    /// Pick the language from a BCP 47 tag such as `es-MX`
+    fn from_locale(locale: Option<&str>) -> Self {
+        let language = locale
+            .and_then(|locale| locale.split(['-', '_']).next())
+            .map(str::to_ascii_lowercase);
+
+        match language.as_deref() {
+            Some("es") => SummaryLanguage::Spanish,
+            _ => SummaryLanguage::English,
+        }
+    }
+}
+
+/// One-line summary of the timer and work hours settings
+fn describe(
+    settings: &UserSettings,
+    schedule: Option<&WorkSchedule>,
+    language: SummaryLanguage,
+) -> String {
+    let minutes = |seconds: i32| format_compact(seconds.max(0) as u32);
+    let spanish = language == SummaryLanguage::Spanish;
+
+    // With break_ratio_mode the fixed break setting is ignored, so report the
+    // break the orchestrator will actually use
+    let short_break = if settings.break_ratio_mode {
+        let seconds =
+            crate::cycle_orchestrator::CycleConfig::from_user_settings(settings.clone(), None)
+                .short_break_duration();
+        let percent = (settings.break_ratio * 100.0).round();
+        if spanish {
+            format!("{} ({}% del enfoque)", format_compact(seconds), percent)
+        } else {
+            format!("{} ({}% of focus)", format_compact(seconds), percent)
+        }
+    } else {
+        minutes(settings.short_break_duration)
+    };
+
+    // The schedule has no per-weekday settings, so its hours apply every day
+    let hours = match schedule {
+        Some(WorkSchedule {
+            use_work_schedule: true,
+            work_start_time: Some(start),
+            work_end_time: Some(end),
+            ..
+        }) => Some(format!("{}–{}", start, end)),
+        _ => None,
+    };
+    let work_hours = match (hours, spanish) {
+        (Some(hours), true) => format!("{} (todos los días)", hours),
+        (Some(hours), false) => format!("{} (every day)", hours),
+        (None, true) => "desactivado".to_string(),
+        (None, false) => "off".to_string(),
+    };
+
+    if spanish {
+        format!(
+            "Enfoque {} · Descanso {} · Descanso largo {} cada {} ciclos · Estricto: {} · Horario laboral: {}",
+            minutes(settings.focus_duration),
+            short_break,
+            minutes(settings.long_break_duration),
+            settings.cycles_per_long_break_v2,
+            if settings.strict_mode { "sí" } else { "no" },
+            work_hours
+        )
+    } else {
+        format!(
+            "Focus {} · Break {} · Long break {} every {} cycles · Strict: {} · Work hours: {}",
+            minutes(settings.focus_duration),
+            short_break,
+            minutes(settings.long_break_duration),
+            settings.cycles_per_long_break_v2,
+            if settings.strict_mode { "on" } else { "off" },
+            work_hours
+        )
+    }
+}
+
+/// Describe the current configuration in one line, e.g. for a settings
+/// summary card or a support request. `locale` is the UI's language tag
+/// (English and Spanish are supported; English otherwise).
+#[tauri::command]
+pub async fn describe_configuration(
+    locale: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    println!("📖 [Rust] describe_configuration called: {:?}", locale);
+
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|e| format!("Failed to get user settings: {}", e))?
+        .unwrap_or_default();
+
+    // No saved schedule means work hours were never turned on
+    let schedule = super::work_schedule_handler::get_work_schedule(state)
+        .await
+        .ok();
+
+    Ok(describe(
+        &settings,
+        schedule.as_ref(),
+        SummaryLanguage::from_locale(locale.as_deref()),
+    ))
+}
@@ -1,10 +1,19 @@
-use crate::api_models::{BreakActivity, BreakSession, BreakType};
-use crate::cycle_orchestrator::{CycleConfig, CycleOrchestrator, CyclePhase, CycleState};
-use crate::database::models::{Session, SessionType, UserSettings, WorkSchedule};
+use crate::api_models::{BreakSession, BreakType};
+use crate::break_content::BreakContext;
+use crate::cycle_orchestrator::{
+    CycleConfig, CycleEvent, CycleOrchestrator, CyclePhase, CycleState,
+};
+use crate::database::models::{
+    OverlayEscapeAction, Session, SessionAnomalyReport, SessionHistoryFilter, SessionHistoryPage,
+    SessionType, SoftOverlayDismissAction, UserSettings, WorkSchedule,
+};
+use crate::database::DatabaseManager;
+use crate::phase_hooks;
 use crate::state::AppState;
-use chrono::Utc;
+use crate::util::{format_duration, format_time, TimeFormatStyle};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
 #[cfg(target_os = "macos")]
@@ -15,19 +24,13 @@ pub struct InitializeCycleRequest {
     pub force_reload: Option<bool>,
 }
 
-/// Helper function to format time in MM:SS format
-fn format_time(seconds: u32) -> String {
-    let minutes = seconds / 60;
-    let secs = seconds % 60;
-    format!("{:02}:{:02}", minutes, secs)
-}
-
 // Icon generation functions commented out until Tauri supports text in menu bar
 // See: https://github.com/tauri-apps/tao/issues/65
 // When support is added, we can uncomment and use these functions to generate
 // dynamic icons with text rendered on them.
 
 /// Update the tray icon with text showing the timer
+/// The timer reads MM:SS, switching to H:MM:SS for phases of an hour or more
 /// On macOS, we use native NSStatusItem to show text directly in menu bar
 /// On other platforms, we fall back to tooltip
 fn update_tray_icon_with_text(app: &AppHandle, state: &CycleState) {
@@ -105,49 +108,104 @@ fn update_tray_tooltip_fallback(app: &AppHandle, state: &CycleState) {
     }
 }
 
-/// Initialize the cycle orchestrator with current user settings
-#[tauri::command]
-pub async fn initialize_cycle_orchestrator(
-    state: State<'_, AppState>,
-    app: AppHandle,
-) -> Result<CycleState, String> {
-    println!("🔄 [Rust] initialize_cycle_orchestrator called");
+/// Keep the display awake while a focus phase runs, if the user asked for it
+fn sync_display_sleep(state: &AppState, keep_awake: bool) {
+    if let Ok(mut inhibitor) = state.display_sleep_inhibitor.lock() {
+        inhibitor.sync(keep_awake);
+    }
+}
 
-    // Get user settings
-    let user_settings = state
-        .database
-        .with_connection(|conn| {
-            let mut stmt = conn
-                .prepare(
-                    r#"
-                SELECT id, focus_duration, short_break_duration, long_break_duration,
-                       cycles_per_long_break, cycles_per_long_break_v2, pre_alert_seconds,
-                       strict_mode, pin_hash, user_name, emergency_key_combination,
-                       break_transition_seconds, created_at, updated_at
-                FROM user_settings 
-                WHERE id = 1
-                "#,
-                )
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+/// Save the orchestrator's phase so `initialize_cycle_orchestrator` can
+/// continue it after a restart
+fn save_cycle_state(state: &AppState, orchestrator: &CycleOrchestrator) {
+    if let Err(e) = state.database.save_cycle_state(&orchestrator.saved_state()) {
+        eprintln!("❌ [CycleHandler] Failed to save cycle state: {}", e);
+    }
+}
 
-            let settings = stmt
-                .query_row([], |row| UserSettings::from_row(row))
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+/// Continue the phase saved before the app quit. Shutdown closed the running
+/// session's row, so it is reopened, or completed when the phase ran out
+/// while the app was closed.
+fn restore_cycle_state(state: &AppState, orchestrator: &mut CycleOrchestrator) -> Vec<CycleEvent> {
+    let saved = match state.database.get_cycle_state() {
+        Ok(Some(saved)) => saved,
+        Ok(None) => return vec![],
+        Err(e) => {
+            eprintln!("⚠️ [CycleHandler] Failed to load saved cycle state: {}", e);
+            return vec![];
+        }
+    };
 
-            Ok(settings)
-        })
-        .map_err(|e| format!("Failed to get user settings: {}", e))?;
+    let events = orchestrator.restore(&saved, Utc::now());
+    let restored = orchestrator.get_state();
+    let ran_out = events.iter().any(|event| {
+        matches!(
+            event,
+            CycleEvent::PhaseEnded {
+                completed: true,
+                ..
+            }
+        )
+    });
+
+    if let Some(session_id) = saved.session_id.as_deref() {
+        match state.database.get_session(session_id) {
+            Ok(Some(mut session)) if restored.session_id.is_some() || ran_out => {
+                if ran_out {
+                    session.actual_duration = Some(saved.phase_seconds as i32);
+                    session.end_time = Some(
+                        session.start_time + chrono::Duration::seconds(saved.phase_seconds as i64),
+                    );
+                    session.completed = true;
+                } else {
+                    session.end_time = None;
+                    session.completed = false;
+                }
 
-    // Get work schedule
-    let work_schedule = state
+                if let Err(e) = state.database.update_session(&session) {
+                    eprintln!("❌ [CycleHandler] Failed to update restored session: {}", e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("❌ [CycleHandler] Failed to load restored session: {}", e),
+        }
+    }
+
+    if ran_out {
+        println!(
+            "⏱️ [CycleHandler] Saved {} phase ran out while closed, ended it",
+            saved.phase
+        );
+    } else if restored.phase != CyclePhase::Idle {
+        println!(
+            "🔄 [CycleHandler] Restored {} phase with {}s left",
+            restored.phase, restored.remaining_seconds
+        );
+    }
+
+    save_cycle_state(state, orchestrator);
+    events
+}
+
+/// Load the persisted user settings row
+fn load_user_settings(state: &State<'_, AppState>) -> Result<UserSettings, String> {
+    state
+        .database
+        .get_user_settings()
+        .map_err(|e| format!("Failed to get user settings: {}", e))?
+        .ok_or_else(|| "Failed to get user settings: no settings found".to_string())
+}
+
+fn load_work_schedule(state: &State<'_, AppState>) -> Result<Option<WorkSchedule>, String> {
+    state
         .database
         .with_connection(|conn| {
             let mut stmt = conn
                 .prepare(
                     r#"
-                SELECT id, user_id, use_work_schedule, work_start_time, 
+                SELECT id, user_id, use_work_schedule, work_start_time,
                        work_end_time, timezone, created_at, updated_at
-                FROM work_schedule 
+                FROM work_schedule
                 WHERE id = 1
                 "#,
                 )
@@ -157,21 +215,45 @@ pub async fn initialize_cycle_orchestrator(
 
             Ok(schedule)
         })
-        .map_err(|e| format!("Failed to get work schedule: {}", e))?;
+        .map_err(|e| format!("Failed to get work schedule: {}", e))
+}
+
+/// Initialize the cycle orchestrator with current user settings
+#[tauri::command]
+pub async fn initialize_cycle_orchestrator(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CycleState, String> {
+    println!("🔄 [Rust] initialize_cycle_orchestrator called");
+
+    // Get user settings
+    let user_settings = load_user_settings(&state)?;
+
+    // Get work schedule
+    let work_schedule = load_work_schedule(&state)?;
 
     // Create cycle config
     let config = CycleConfig::from_user_settings(user_settings.clone(), work_schedule);
 
-    // Create orchestrator
-    let orchestrator = CycleOrchestrator::new(config);
+    // Create orchestrator, continuing the phase that was running before a restart
+    let mut orchestrator = CycleOrchestrator::new(config);
+    let restored_events = restore_cycle_state(&state, &mut orchestrator);
 
     let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
+
+    for event in &restored_events {
+        if let Err(e) = app.emit("cycle-event", event) {
+            eprintln!("Failed to emit cycle event: {}", e);
+        }
+    }
 
     // Store in app state
     let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
     *cycle_orchestrator = Some(orchestrator);
 
     // Initialize notification service with user name
+    // Lock order: cycle -> notification (see `AppState`)
     let mut notification_service = state.notification_service.lock().await;
     notification_service.set_user_name(user_settings.user_name.clone());
 
@@ -192,14 +274,26 @@ pub async fn initialize_cycle_orchestrator(
             enabled: user_settings.strict_mode,
             emergency_key_combination: user_settings.emergency_key_combination.clone(),
             transition_countdown_seconds: user_settings.break_transition_seconds as u32,
+            show_focus_widget: user_settings.strict_focus_show_widget,
+            show_break_transition: user_settings.show_break_transition,
+            require_ack: user_settings.strict_require_ack,
         };
 
-        println!("🔒 [initialize_cycle_orchestrator] StrictModeConfig: enabled={}, emergency_key={:?}, transition_seconds={}", 
-            strict_config.enabled, strict_config.emergency_key_combination, strict_config.transition_countdown_seconds);
+        println!("🔒 [initialize_cycle_orchestrator] StrictModeConfig: enabled={}, emergency_key={:?}, transition_seconds={}, show_focus_widget={}", 
+            strict_config.enabled, strict_config.emergency_key_combination, strict_config.transition_countdown_seconds, strict_config.show_focus_widget);
 
         // Create window manager (will be properly initialized in future tasks)
         let window_manager = Arc::new(StdMutex::new(WindowManager::new(state.app_handle.clone())));
 
+        // Restore window positions saved on the last quit
+        if let Ok(path) = crate::config::window_states_path(&state.app_handle) {
+            if let Ok(manager) = window_manager.lock() {
+                if let Err(e) = manager.load_window_states_from_file(&path) {
+                    eprintln!("⚠️ [initialize_cycle_orchestrator] Failed to load window states: {}", e);
+                }
+            }
+        }
+
         let mut strict_orchestrator =
             StrictModeOrchestrator::new(strict_config, state.app_handle.clone(), window_manager);
 
@@ -231,6 +325,7 @@ pub async fn initialize_cycle_orchestrator(
             );
         }
 
+        // Lock order: cycle -> notification -> strict (see `AppState`)
         let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
         *strict_mode_orchestrator = Some(strict_orchestrator);
 
@@ -261,56 +356,15 @@ pub async fn start_focus_session(
     );
 
     // Reload settings from database to ensure we have the latest configuration
-    let user_settings = state
-        .database
-        .with_connection(|conn| {
-            let mut stmt = conn
-                .prepare(
-                    r#"
-                SELECT id, focus_duration, short_break_duration, long_break_duration,
-                       cycles_per_long_break, cycles_per_long_break_v2, pre_alert_seconds,
-                       strict_mode, pin_hash, user_name, emergency_key_combination,
-                       break_transition_seconds, created_at, updated_at
-                FROM user_settings 
-                WHERE id = 1
-                "#,
-                )
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
-
-            let settings = stmt
-                .query_row([], |row| UserSettings::from_row(row))
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
-
-            Ok(settings)
-        })
-        .map_err(|e| format!("Failed to get user settings: {}", e))?;
+    let user_settings = load_user_settings(&state)?;
 
     // Get work schedule
-    let work_schedule = state
-        .database
-        .with_connection(|conn| {
-            let mut stmt = conn
-                .prepare(
-                    r#"
-                SELECT id, user_id, use_work_schedule, work_start_time, 
-                       work_end_time, timezone, created_at, updated_at
-                FROM work_schedule 
-                WHERE id = 1
-                "#,
-                )
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
-
-            let schedule = stmt.query_row([], |row| WorkSchedule::from_row(row)).ok();
-
-            Ok(schedule)
-        })
-        .map_err(|e| format!("Failed to get work schedule: {}", e))?;
+    let work_schedule = load_work_schedule(&state)?;
 
     // Create updated cycle config
     let config = CycleConfig::from_user_settings(user_settings.clone(), work_schedule);
 
     // Save values we need before moving config
-    let focus_duration = config.focus_duration;
     let strict_mode = config.strict_mode;
 
     let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
@@ -322,13 +376,36 @@ pub async fn start_focus_session(
     // Update orchestrator with latest configuration
     orchestrator.update_config(config);
 
-    let events = orchestrator.start_focus_session_with_override(override_flag)?;
+    let mut events = orchestrator.start_focus_session_with_override(override_flag)?;
 
     let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
+    save_cycle_state(&state, orchestrator);
 
     // Release the cycle orchestrator lock before handling strict mode
     drop(cycle_orchestrator);
 
+    // Warn, without blocking, when this week's focus budget is already used up
+    let weekly_budget = user_settings
+        .weekly_focus_budget_minutes
+        .map(|minutes| minutes.max(0) as u32);
+    if let Some(budget_minutes) = weekly_budget {
+        match super::stats_handler::weekly_budget_progress(&state.database, weekly_budget) {
+            Ok(progress) if progress.exhausted => {
+                println!(
+                    "⚠️ [start_focus_session] Weekly focus budget reached: {}/{} minutes",
+                    progress.used_minutes, budget_minutes
+                );
+                events.push(CycleEvent::WeeklyBudgetReached {
+                    used_minutes: progress.used_minutes,
+                    budget_minutes,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to check weekly focus budget: {}", e),
+        }
+    }
+
     // Handle strict mode events if strict mode is active
     println!(
         "🔒 [start_focus_session] Checking strict mode - strict_mode flag: {}",
@@ -373,6 +450,8 @@ pub async fn start_focus_session(
     }
     drop(strict_mode_orchestrator);
 
+    phase_hooks::run_for_events(&user_settings.phase_command_hooks, &events);
+
     // Emit events to frontend
     for event in events {
         if let Err(e) = app.emit("cycle-event", &event) {
@@ -387,7 +466,8 @@ pub async fn start_focus_session(
             session_type: SessionType::Focus,
             start_time: current_state.started_at.unwrap_or_else(Utc::now),
             end_time: None,
-            planned_duration: focus_duration as i32,
+            // May differ from the configured length after a long break or a one-off override
+            planned_duration: current_state.remaining_seconds as i32,
             actual_duration: None,
             strict_mode,
             completed: false,
@@ -396,6 +476,8 @@ pub async fn start_focus_session(
             within_work_hours: current_state.within_work_hours,
             cycle_number: Some(current_state.cycle_count as i32),
             is_long_break: false,
+            tag: None,
+            is_sprint: false,
         };
 
         if let Err(e) = state.database.create_session(&session) {
@@ -404,7 +486,7 @@ pub async fn start_focus_session(
     }
 
     // Send focus start notification
-    let notification_service = state.notification_service.lock().await;
+    let mut notification_service = state.notification_service.lock().await;
     notification_service.notify_focus_start(&app);
 
     // Update tray icon with text showing timer
@@ -415,70 +497,42 @@ pub async fn start_focus_session(
     Ok(current_state)
 }
 
-/// Start a break (short or long)
+/// Make only the next focus session last `minutes`; later sessions go back
+/// to the configured length. `None` drops a pending override.
 #[tauri::command]
-pub async fn start_break_session(
-    force_long: Option<bool>,
+pub async fn set_next_focus_duration(
+    minutes: Option<u32>,
     state: State<'_, AppState>,
-    app: AppHandle,
-) -> Result<CycleState, String> {
+) -> Result<(), String> {
     println!(
-        "☕ [Rust] start_break_session called (force_long: {:?})",
-        force_long
+        "💾 [Rust] set_next_focus_duration called with {:?} minutes",
+        minutes
     );
 
-    // Reload settings from database to ensure we have the latest configuration
-    let user_settings = state
-        .database
-        .with_connection(|conn| {
-            let mut stmt = conn
-                .prepare(
-                    r#"
-                SELECT id, focus_duration, short_break_duration, long_break_duration,
-                       cycles_per_long_break, cycles_per_long_break_v2, pre_alert_seconds,
-                       strict_mode, pin_hash, user_name, emergency_key_combination,
-                       break_transition_seconds, created_at, updated_at
-                FROM user_settings 
-                WHERE id = 1
-                "#,
-                )
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
-
-            let settings = stmt
-                .query_row([], |row| UserSettings::from_row(row))
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
-
-            Ok(settings)
-        })
-        .map_err(|e| format!("Failed to get user settings: {}", e))?;
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    let orchestrator = cycle_orchestrator
+        .as_mut()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
 
-    // Get work schedule
-    let work_schedule = state
-        .database
-        .with_connection(|conn| {
-            let mut stmt = conn
-                .prepare(
-                    r#"
-                SELECT id, user_id, use_work_schedule, work_start_time, 
-                       work_end_time, timezone, created_at, updated_at
-                FROM work_schedule 
-                WHERE id = 1
-                "#,
-                )
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+    orchestrator.set_next_focus_duration(minutes)?;
 
-            let schedule = stmt.query_row([], |row| WorkSchedule::from_row(row)).ok();
+    println!("✅ [Rust] Next focus duration set");
+    Ok(())
+}
 
-            Ok(schedule)
-        })
-        .map_err(|e| format!("Failed to get work schedule: {}", e))?;
+/// Start a standalone focus sprint of the given length.
+/// Sprints don't advance the cycle count and return to idle when done.
+#[tauri::command]
+pub async fn start_focus_sprint(
+    minutes: u32,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CycleState, String> {
+    println!("🏃 [Rust] start_focus_sprint called ({} min)", minutes);
 
-    // Create updated cycle config
+    let user_settings = load_user_settings(&state)?;
+    let work_schedule = load_work_schedule(&state)?;
     let config = CycleConfig::from_user_settings(user_settings.clone(), work_schedule);
-
-    // Save values we need before moving config
-    let break_duration = config.break_duration;
-    let long_break_duration = config.long_break_duration;
     let strict_mode = config.strict_mode;
 
     let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
@@ -487,112 +541,404 @@ pub async fn start_break_session(
         .as_mut()
         .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
 
-    // Update orchestrator with latest configuration
     orchestrator.update_config(config);
 
-    let events = orchestrator.start_break(force_long.unwrap_or(false))?;
+    let events = orchestrator.start_focus_sprint(minutes)?;
 
     let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
+    save_cycle_state(&state, orchestrator);
+
+    drop(cycle_orchestrator);
+
+    // Strict mode treats a sprint like any other focus phase
+    let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+    if let Some(strict_orchestrator) = strict_mode_orchestrator.as_mut() {
+        if strict_orchestrator.is_active() {
+            for event in &events {
+                if let Err(e) = strict_orchestrator.handle_cycle_event(event) {
+                    eprintln!(
+                        "❌ [start_focus_sprint] Failed to handle strict mode event: {}",
+                        e
+                    );
+                }
+            }
+        }
+    }
+    drop(strict_mode_orchestrator);
+
+    phase_hooks::run_for_events(&user_settings.phase_command_hooks, &events);
 
-    // Emit events to frontend
     for event in events {
         if let Err(e) = app.emit("cycle-event", &event) {
             eprintln!("Failed to emit cycle event: {}", e);
         }
     }
 
-    // Save session to database
     if let Some(ref session_id) = current_state.session_id {
-        let session_type = match current_state.phase {
-            CyclePhase::LongBreak => SessionType::LongBreak,
-            CyclePhase::ShortBreak => SessionType::ShortBreak,
-            _ => SessionType::ShortBreak, // fallback
-        };
-
-        let is_long_break = current_state.phase == CyclePhase::LongBreak;
-        let duration = if is_long_break {
-            long_break_duration
-        } else {
-            break_duration
-        };
-
         let session = Session {
             id: session_id.clone(),
-            session_type,
+            session_type: SessionType::Focus,
             start_time: current_state.started_at.unwrap_or_else(Utc::now),
             end_time: None,
-            planned_duration: duration as i32,
+            planned_duration: (minutes * 60) as i32,
             actual_duration: None,
             strict_mode,
             completed: false,
             notes: None,
             created_at: Utc::now(),
             within_work_hours: current_state.within_work_hours,
-            cycle_number: Some(current_state.cycle_count as i32),
-            is_long_break,
+            cycle_number: None,
+            is_long_break: false,
+            tag: None,
+            is_sprint: true,
         };
 
         if let Err(e) = state.database.create_session(&session) {
-            eprintln!("Failed to save break session to database: {}", e);
+            eprintln!("Failed to save sprint session to database: {}", e);
         }
     }
 
-    // Send appropriate break notification based on phase
-    let notification_service = state.notification_service.lock().await;
-    match current_state.phase {
-        CyclePhase::LongBreak => notification_service.notify_long_break_start(&app),
-        _ => notification_service.notify_break_start(&app),
-    };
+    let mut notification_service = state.notification_service.lock().await;
+    notification_service.notify_focus_start(&app);
 
-    // Update tray icon with text showing timer
     update_tray_icon_with_text(&app, &current_state);
 
-    println!("✅ [Rust] Break session started");
+    println!("✅ [Rust] Focus sprint started");
 
     Ok(current_state)
 }
 
-/// Pause the current session
+/// Start a break (short or long)
 #[tauri::command]
-pub async fn pause_cycle(state: State<'_, AppState>) -> Result<CycleState, String> {
-    println!("⏸️ [Rust] pause_cycle called");
+pub async fn start_break_session(
+    force_long: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CycleState, String> {
+    println!(
+        "☕ [Rust] start_break_session called (force_long: {:?})",
+        force_long
+    );
 
-    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    let (current_state, _) = begin_break(force_long.unwrap_or(false), &state, &app).await?;
 
-    let orchestrator = cycle_orchestrator
-        .as_mut()
-        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+    Ok(current_state)
+}
 
-    orchestrator.pause()?;
+/// Take a break right away, ending the current focus session first.
+///
+/// With strict mode active the break goes through the transition, countdown
+/// and locked overlay; otherwise it starts like `start_break_session`.
+#[tauri::command]
+pub async fn break_now(
+    force_long: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CycleState, String> {
+    println!("☕ [Rust] break_now called (force_long: {:?})", force_long);
+
+    let phase = {
+        let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+        cycle_orchestrator
+            .as_ref()
+            .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?
+            .get_state()
+            .phase
+    };
 
-    let current_state = orchestrator.get_state();
+    match phase {
+        CyclePhase::Focus => {
+            end_cycle_session(false, state.clone(), app.clone()).await?;
+        }
+        CyclePhase::ShortBreak | CyclePhase::LongBreak => {
+            return Err("A break is already running".to_string());
+        }
+        CyclePhase::Idle => {}
+    }
 
-    // Update tray icon with text showing timer
-    let app_handle = state.app_handle.clone();
-    update_tray_icon_with_text(&app_handle, &current_state);
+    let (current_state, events) = begin_break(force_long.unwrap_or(false), &state, &app).await?;
 
-    println!("✅ [Rust] Cycle paused");
+    // Hand the break over to strict mode so it shows the transition and overlay
+    let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+    if let Some(orchestrator) = strict_mode_orchestrator.as_mut() {
+        if orchestrator.is_active() {
+            for event in &events {
+                if let Err(e) = orchestrator.handle_cycle_event(event) {
+                    eprintln!("❌ [break_now] Failed to handle strict mode event: {}", e);
+                }
+            }
+        }
+    }
 
     Ok(current_state)
 }
 
-/// Resume the current session
+/// Start the end-of-day wind-down offered by `WindDownSuggested`. A running
+/// break is ended first, unless strict mode is active; focus has to be ended
+/// by the user.
 #[tauri::command]
-pub async fn resume_cycle(state: State<'_, AppState>) -> Result<CycleState, String> {
-    println!("▶️ [Rust] resume_cycle called");
-
-    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+pub async fn start_wind_down(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CycleState, String> {
+    println!("🌇 [Rust] start_wind_down called");
+
+    let phase = {
+        let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+        cycle_orchestrator
+            .as_ref()
+            .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?
+            .get_state()
+            .phase
+    };
 
-    let orchestrator = cycle_orchestrator
+    match phase {
+        CyclePhase::Focus => {
+            return Err("End the focus session before winding down".to_string());
+        }
+        CyclePhase::ShortBreak | CyclePhase::LongBreak => {
+            let strict_active = state
+                .strict_mode_orchestrator
+                .lock()
+                .await
+                .as_ref()
+                .is_some_and(|orchestrator| orchestrator.is_active());
+            if strict_active {
+                return Err("Cannot replace a break while strict mode is active".to_string());
+            }
+            end_cycle_session(false, state.clone(), app.clone()).await?;
+        }
+        CyclePhase::Idle => {}
+    }
+
+    let user_settings = load_user_settings(&state)?;
+    let work_schedule = load_work_schedule(&state)?;
+    let config = CycleConfig::from_user_settings(user_settings.clone(), work_schedule);
+
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_mut()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    orchestrator.update_config(config);
+
+    let events = orchestrator.start_wind_down()?;
+
+    let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
+    save_cycle_state(&state, orchestrator);
+
+    phase_hooks::run_for_events(&user_settings.phase_command_hooks, &events);
+
+    for event in &events {
+        if let Err(e) = app.emit("cycle-event", &event) {
+            eprintln!("Failed to emit cycle event: {}", e);
+        }
+
+        if let CycleEvent::PhaseStarted {
+            phase,
+            duration,
+            cycle_count,
+        } = event
+        {
+            save_started_session(
+                &state.database,
+                phase,
+                *duration,
+                *cycle_count,
+                &current_state,
+                None,
+            );
+        }
+    }
+
+    update_tray_icon_with_text(&app, &current_state);
+
+    println!("✅ [Rust] Wind-down started");
+
+    Ok(current_state)
+}
+
+/// Start a break with the latest settings, save it and notify the user.
+/// Returns the new state along with the events already emitted to the frontend.
+async fn begin_break(
+    force_long: bool,
+    state: &State<'_, AppState>,
+    app: &AppHandle,
+) -> Result<(CycleState, Vec<CycleEvent>), String> {
+    // Reload settings from database to ensure we have the latest configuration
+    let user_settings = load_user_settings(state)?;
+
+    // Get work schedule
+    let work_schedule = load_work_schedule(state)?;
+
+    // Create updated cycle config
+    let config = CycleConfig::from_user_settings(user_settings.clone(), work_schedule);
+
+    // Save values we need before moving config
+    let break_duration = config.short_break_duration();
+    let long_break_duration = config.long_break_duration;
+    let strict_mode = config.strict_mode;
+
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
         .as_mut()
         .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
 
-    orchestrator.resume()?;
+    // Update orchestrator with latest configuration
+    orchestrator.update_config(config);
+
+    let events = orchestrator.start_break(force_long)?;
 
     let current_state = orchestrator.get_state();
+    sync_display_sleep(state, orchestrator.keeps_display_awake());
+    save_cycle_state(state, orchestrator);
+
+    phase_hooks::run_for_events(&user_settings.phase_command_hooks, &events);
+
+    // Emit events to frontend
+    for event in &events {
+        if let Err(e) = app.emit("cycle-event", &event) {
+            eprintln!("Failed to emit cycle event: {}", e);
+        }
+    }
+
+    // Save session to database
+    if let Some(ref session_id) = current_state.session_id {
+        let session_type = match current_state.phase {
+            CyclePhase::LongBreak => SessionType::LongBreak,
+            CyclePhase::ShortBreak => SessionType::ShortBreak,
+            _ => SessionType::ShortBreak, // fallback
+        };
+
+        let is_long_break = current_state.phase == CyclePhase::LongBreak;
+        let duration = if is_long_break {
+            long_break_duration
+        } else {
+            break_duration
+        };
+
+        let session = Session {
+            id: session_id.clone(),
+            session_type,
+            start_time: current_state.started_at.unwrap_or_else(Utc::now),
+            end_time: None,
+            planned_duration: duration as i32,
+            actual_duration: None,
+            strict_mode,
+            completed: false,
+            notes: None,
+            created_at: Utc::now(),
+            within_work_hours: current_state.within_work_hours,
+            cycle_number: Some(current_state.cycle_count as i32),
+            is_long_break,
+            tag: None,
+            is_sprint: false,
+        };
+
+        if let Err(e) = state.database.create_session(&session) {
+            eprintln!("Failed to save break session to database: {}", e);
+        }
+    }
+
+    // Send appropriate break notification based on phase
+    // Lock order: cycle -> notification (see `AppState`)
+    let mut notification_service = state.notification_service.lock().await;
+    match current_state.phase {
+        CyclePhase::LongBreak => notification_service.notify_long_break_start(app),
+        _ => notification_service.notify_break_start(app),
+    };
 
     // Update tray icon with text showing timer
+    update_tray_icon_with_text(app, &current_state);
+
+    println!("✅ [Rust] Break session started");
+
+    Ok((current_state, events))
+}
+
+/// Pause the current focus session or break; strict breaks can't be paused
+#[tauri::command]
+pub async fn pause_cycle(state: State<'_, AppState>) -> Result<CycleState, String> {
+    println!("⏸️ [Rust] pause_cycle called");
+
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_mut()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    // The orchestrator only knows the strict mode setting it was last given,
+    // so also ask the strict mode orchestrator whether it is running.
+    // Lock order: cycle -> strict (see `AppState`)
+    if matches!(
+        orchestrator.get_state().phase,
+        CyclePhase::ShortBreak | CyclePhase::LongBreak
+    ) {
+        let strict_active = state
+            .strict_mode_orchestrator
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|orchestrator| orchestrator.is_active());
+        if strict_active {
+            let error_msg = "Breaks can't be paused while strict mode is active".to_string();
+            println!("❌ [Rust] {}", error_msg);
+            return Err(error_msg);
+        }
+    }
+
+    let events = orchestrator.pause()?;
+
+    let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
+    save_cycle_state(&state, orchestrator);
+
+    // Emit events so other windows (focus widget, break overlay) learn about the pause
     let app_handle = state.app_handle.clone();
+    for event in events {
+        if let Err(e) = app_handle.emit("cycle-event", &event) {
+            eprintln!("Failed to emit cycle event: {}", e);
+        }
+    }
+
+    // Update tray icon with text showing timer
+    update_tray_icon_with_text(&app_handle, &current_state);
+
+    println!("✅ [Rust] Cycle paused");
+
+    Ok(current_state)
+}
+
+/// Resume the current session
+#[tauri::command]
+pub async fn resume_cycle(state: State<'_, AppState>) -> Result<CycleState, String> {
+    println!("▶️ [Rust] resume_cycle called");
+
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_mut()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    let events = orchestrator.resume()?;
+
+    let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
+    save_cycle_state(&state, orchestrator);
+
+    // Emit events so other windows (focus widget, break overlay) learn about the resume
+    let app_handle = state.app_handle.clone();
+    for event in events {
+        if let Err(e) = app_handle.emit("cycle-event", &event) {
+            eprintln!("Failed to emit cycle event: {}", e);
+        }
+    }
+
+    // Update tray icon with text showing timer
     update_tray_icon_with_text(&app_handle, &current_state);
 
     println!("✅ [Rust] Cycle resumed");
@@ -626,6 +972,7 @@ pub async fn end_cycle_session(
     let planned_duration_before_end = state_before_end.remaining_seconds;
 
     let events = orchestrator.end_session(completed)?;
+    save_cycle_state(&state, orchestrator);
 
     // Emit events to frontend
     for event in events {
@@ -635,6 +982,7 @@ pub async fn end_cycle_session(
     }
 
     let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
 
     // Update session in database if it was completed
     if completed && session_id_before_end.is_some() {
@@ -661,11 +1009,16 @@ pub async fn end_cycle_session(
 
     // Send appropriate end notification if session was completed
     if completed {
-        let notification_service = state.notification_service.lock().await;
+        // Lock order: cycle -> notification (see `AppState`)
+        let mut notification_service = state.notification_service.lock().await;
         match phase_before_end {
-            CyclePhase::Focus => notification_service.notify_focus_end(&app),
+            CyclePhase::Focus => {
+                notification_service.notify_focus_end(&app);
+                notification_service.play_focus_end_sound(&app);
+            }
             CyclePhase::ShortBreak | CyclePhase::LongBreak => {
-                notification_service.notify_break_end(&app)
+                notification_service.notify_break_end(&app);
+                notification_service.play_break_end_sound(&app);
             }
             _ => {}
         };
@@ -679,6 +1032,107 @@ pub async fn end_cycle_session(
     Ok(current_state)
 }
 
+/// Skip the current break and start the next focus session right away.
+/// Refused while strict mode is active, since strict breaks are enforced.
+#[tauri::command]
+pub async fn skip_break_session(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CycleState, String> {
+    println!("⏭️ [Rust] skip_break_session called");
+
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_mut()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    // Lock order: cycle -> strict (see `AppState`)
+    let strict_active = state
+        .strict_mode_orchestrator
+        .lock()
+        .await
+        .as_ref()
+        .is_some_and(|orchestrator| orchestrator.is_active());
+    if strict_active || orchestrator.get_config().strict_mode {
+        let error_msg = "Breaks can't be skipped while strict mode is active".to_string();
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let state_before_skip = orchestrator.get_state();
+    let events = orchestrator.skip_break()?;
+
+    let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
+    save_cycle_state(&state, orchestrator);
+
+    // Close the skipped break with the time actually spent on it
+    if let Some(session_id) = state_before_skip.session_id.as_ref() {
+        let end_time = Utc::now();
+        match state.database.get_session(session_id) {
+            Ok(Some(mut db_session)) => {
+                let elapsed = state_before_skip
+                    .started_at
+                    .map(|started_at| (end_time - started_at).num_seconds().max(0) as i32);
+
+                db_session.end_time = Some(end_time);
+                db_session.actual_duration = elapsed.or(Some(0));
+                db_session.completed = false;
+
+                if let Err(e) = state.database.update_session(&db_session) {
+                    eprintln!("❌ [CycleHandler] Failed to update skipped break: {}", e);
+                }
+            }
+            Ok(None) => eprintln!(
+                "⚠️ [CycleHandler] Session {} not found in database",
+                session_id
+            ),
+            Err(e) => eprintln!(
+                "❌ [CycleHandler] Error getting session from database: {}",
+                e
+            ),
+        }
+    }
+
+    for event in &events {
+        if let CycleEvent::PhaseStarted {
+            phase,
+            duration,
+            cycle_count,
+        } = event
+        {
+            save_started_session(
+                &state.database,
+                phase,
+                *duration,
+                *cycle_count,
+                &current_state,
+                state_before_skip.session_id.as_ref(),
+            );
+        }
+    }
+
+    match state.database.get_user_settings() {
+        Ok(settings) => {
+            phase_hooks::run_for_events(&settings.unwrap_or_default().phase_command_hooks, &events)
+        }
+        Err(e) => eprintln!("Failed to load phase hooks: {}", e),
+    }
+
+    for event in &events {
+        if let Err(e) = app.emit("cycle-event", &event) {
+            eprintln!("Failed to emit cycle event: {}", e);
+        }
+    }
+
+    update_tray_icon_with_text(&app, &current_state);
+
+    println!("✅ [Rust] Break skipped");
+
+    Ok(current_state)
+}
+
 /// Get the current cycle state
 #[tauri::command]
 pub async fn get_cycle_state(state: State<'_, AppState>) -> Result<CycleState, String> {
@@ -693,7 +1147,170 @@ pub async fn get_cycle_state(state: State<'_, AppState>) -> Result<CycleState, S
     Ok(current_state)
 }
 
-/// Get the current break session details (if a break is active)
+/// Get the remaining time of the current phase formatted for a display surface
+#[tauri::command]
+pub async fn format_remaining(
+    style: TimeFormatStyle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_ref()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    Ok(format_duration(
+        orchestrator.get_state().remaining_seconds,
+        style,
+    ))
+}
+
+/// Get the long-break progress dots (filled = completed focus session)
+#[tauri::command]
+pub async fn get_cycle_dots(state: State<'_, AppState>) -> Result<Vec<bool>, String> {
+    let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_ref()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    Ok(orchestrator.get_cycle_dots())
+}
+
+/// Get the last wall-clock change detected while a phase was running
+#[tauri::command]
+pub async fn get_clock_diagnostics(
+    state: State<'_, AppState>,
+) -> Result<crate::cycle_orchestrator::ClockDiagnostics, String> {
+    let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_ref()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    Ok(orchestrator.get_clock_diagnostics())
+}
+
+/// Get the wall-clock time the current phase ends at (null when idle)
+#[tauri::command]
+pub async fn get_phase_end_time(
+    state: State<'_, AppState>,
+) -> Result<Option<crate::cycle_orchestrator::PhaseEndTime>, String> {
+    let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_ref()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    Ok(orchestrator.get_phase_end_time())
+}
+
+/// Get the events the orchestrator produced most recently, newest first
+#[tauri::command]
+pub async fn get_recent_cycle_events(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::cycle_orchestrator::RecordedCycleEvent>, String> {
+    println!(
+        "📖 [Rust] get_recent_cycle_events called (limit: {:?})",
+        limit
+    );
+
+    let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+
+    let orchestrator = cycle_orchestrator
+        .as_ref()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    let mut events = orchestrator.get_recent_events();
+    if let Some(limit) = limit {
+        events.truncate(limit);
+    }
+
+    Ok(events)
+}
+
+/// Get the settings that differ between the database and the running cycle.
+///
+/// Returns an empty list when no orchestrator is running.
+#[tauri::command]
+pub async fn get_config_drift(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::cycle_orchestrator::ConfigDrift>, String> {
+    println!("📖 [Rust] get_config_drift called");
+
+    let user_settings = load_user_settings(&state)?;
+    let work_schedule = load_work_schedule(&state)?;
+    let saved = CycleConfig::from_user_settings(user_settings, work_schedule);
+
+    let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    let Some(orchestrator) = cycle_orchestrator.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(orchestrator.get_config().drift_from(&saved))
+}
+
+/// Apply the saved settings and work schedule to the running orchestrator
+/// without waiting for the next session to start.
+///
+/// Refused while a phase is running unless `force` is set; the running
+/// phase then keeps its remaining time and the new durations apply from
+/// the next phase.
+#[tauri::command]
+pub async fn reload_cycle_config(
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<CycleConfig, String> {
+    let force = force.unwrap_or(false);
+    println!("🔄 [Rust] reload_cycle_config called (force: {})", force);
+
+    let user_settings = load_user_settings(&state)?;
+    let work_schedule = load_work_schedule(&state)?;
+    let config = CycleConfig::from_user_settings(user_settings, work_schedule);
+
+    let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    let orchestrator = cycle_orchestrator
+        .as_mut()
+        .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+
+    let phase = orchestrator.get_state().phase;
+    if phase != CyclePhase::Idle && !force {
+        let error_msg = format!(
+            "A {} phase is running; pass force to apply the settings mid-phase",
+            phase
+        );
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    orchestrator.update_config(config);
+
+    println!("✅ [Rust] Cycle config reloaded");
+    Ok(orchestrator.get_config())
+}
+
+/// Project today's focus minutes from the saved config and work schedule,
+/// assuming `assume_compliance_percent` of the plan is actually followed
+#[tauri::command]
+pub async fn project_today(
+    assume_compliance_percent: u32,
+    state: State<'_, AppState>,
+) -> Result<crate::cycle_orchestrator::DayProjection, String> {
+    println!(
+        "📖 [Rust] project_today called (compliance: {}%)",
+        assume_compliance_percent
+    );
+
+    let user_settings = load_user_settings(&state)?;
+    let work_schedule = load_work_schedule(&state)?;
+    let config = CycleConfig::from_user_settings(user_settings, work_schedule);
+
+    Ok(config.project_day(assume_compliance_percent))
+}
+
+/// Get the current break session details (if a break is active), or the
+/// sample break while `preview_break_overlay` is showing
 #[tauri::command]
 pub async fn get_current_break(
     state: State<'_, AppState>,
@@ -711,42 +1328,33 @@ pub async fn get_current_break(
             let config = orchestrator.get_config();
 
             let (break_type, duration) = match cycle_state.phase {
+                CyclePhase::LongBreak if cycle_state.is_wind_down => (
+                    BreakType::WindDown,
+                    config.winddown_minutes.unwrap_or_default() * 60,
+                ),
                 CyclePhase::LongBreak => (BreakType::Long, config.long_break_duration),
-                _ => (BreakType::Short, config.break_duration),
+                _ => (BreakType::Short, config.short_break_duration()),
             };
 
-            // Mirror the frontend defaults so the overlay has meaningful content
-            let activity = match break_type {
-                BreakType::Long => BreakActivity {
-                    title: "Extended Break".to_string(),
-                    description: "Time for a longer break to fully recharge".to_string(),
-                    checklist: vec![
-                        "🚶‍♂️ Take a short walk".to_string(),
-                        "💧 Hydrate with water or herbal tea".to_string(),
-                        "🥗 Have a healthy snack".to_string(),
-                        "🧘‍♀️ Do some light stretching or meditation".to_string(),
-                        "🌱 Step outside for fresh air".to_string(),
-                        "📱 Check in with a friend or family member".to_string(),
-                    ],
+            let activity = state.break_content_provider.lock().await.activity_for(
+                &break_type,
+                &BreakContext {
+                    cycle_count: cycle_state.cycle_count,
+                    duration,
                 },
-                BreakType::Short => BreakActivity {
-                    title: "Quick Refresh".to_string(),
-                    description: "Take a moment to recharge with these quick activities"
-                        .to_string(),
-                    checklist: vec![
-                        "💧 Drink a glass of water".to_string(),
-                        "👀 Look away from the screen (20-20-20 rule)".to_string(),
-                        "🧘 Take 3 deep breaths".to_string(),
-                        "🚶 Stand up and stretch".to_string(),
-                    ],
-                },
-            };
+            );
 
             let id = cycle_state
                 .session_id
                 .clone()
                 .unwrap_or_else(|| Uuid::new_v4().to_string());
 
+            let message = state
+                .database
+                .get_user_settings()
+                .map_err(|e| format!("Failed to get user settings: {}", e))?
+                .and_then(|settings| settings.break_message);
+
             Ok(Some(BreakSession {
                 id,
                 break_type,
@@ -755,15 +1363,131 @@ pub async fn get_current_break(
                 activity,
                 // In strict mode we don't allow emergency overrides from the overlay UI
                 allow_emergency: false,
+                message,
             }))
         }
-        _ => Ok(None),
+        // Outside a break the overlay may be showing a preview
+        _ => Ok(state.break_preview.lock().await.clone()),
     }
 }
 
+/// Allowed length of a break overlay preview, in seconds
+const BREAK_PREVIEW_SECONDS_RANGE: std::ops::RangeInclusive<u32> = 3..=120;
+
+/// Show the break overlay with sample content for `duration_seconds`, then
+/// hide it. Nothing is saved, the cycle is untouched and the system is never
+/// locked, so it's safe for trying out themes and the break message.
+#[tauri::command]
+pub async fn preview_break_overlay(
+    duration_seconds: u32,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BreakSession, String> {
+    println!(
+        "👀 [Rust] preview_break_overlay called ({}s)",
+        duration_seconds
+    );
+
+    if !BREAK_PREVIEW_SECONDS_RANGE.contains(&duration_seconds) {
+        let error_msg = format!(
+            "Preview must last between {} and {} seconds",
+            BREAK_PREVIEW_SECONDS_RANGE.start(),
+            BREAK_PREVIEW_SECONDS_RANGE.end()
+        );
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    // The overlay window belongs to the real break while one is running
+    let phase = state
+        .cycle_orchestrator
+        .lock()
+        .await
+        .as_ref()
+        .map(|orchestrator| orchestrator.get_state().phase);
+    if matches!(phase, Some(CyclePhase::ShortBreak | CyclePhase::LongBreak)) {
+        return Err("Can't preview the overlay during a break".to_string());
+    }
+
+    let activity = state.break_content_provider.lock().await.activity_for(
+        &BreakType::Short,
+        &BreakContext {
+            cycle_count: 1,
+            duration: duration_seconds,
+        },
+    );
+
+    let message = load_user_settings(&state)?.break_message;
+
+    let preview = BreakSession {
+        id: format!("preview-{}", Uuid::new_v4()),
+        break_type: BreakType::Short,
+        duration: duration_seconds,
+        remaining: duration_seconds,
+        activity,
+        allow_emergency: false,
+        message,
+    };
+
+    *state.break_preview.lock().await = Some(preview.clone());
+
+    if let Err(e) = app.emit("break-preview", Some(&preview)) {
+        eprintln!("Failed to emit break-preview: {}", e);
+    }
+
+    crate::window_manager::WindowManager::new(app.clone())
+        .show_break_overlay()
+        .map_err(|e| format!("Failed to show break overlay: {}", e))?;
+
+    let preview_id = preview.id.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(duration_seconds as u64)).await;
+
+        let state = app.state::<AppState>();
+        let phase = state
+            .cycle_orchestrator
+            .lock()
+            .await
+            .as_ref()
+            .map(|orchestrator| orchestrator.get_state().phase);
+        let mut break_preview = state.break_preview.lock().await;
+
+        // A newer preview replaced this one and will hide itself
+        if break_preview
+            .as_ref()
+            .is_none_or(|preview| preview.id != preview_id)
+        {
+            return;
+        }
+        *break_preview = None;
+        drop(break_preview);
+
+        // A break that started meanwhile took over the overlay and its content
+        if matches!(phase, Some(CyclePhase::ShortBreak | CyclePhase::LongBreak)) {
+            println!("ℹ️ [Rust] Break started during the preview, leaving the overlay up");
+            return;
+        }
+
+        if let Err(e) = app.emit("break-preview", None::<BreakSession>) {
+            eprintln!("Failed to emit break-preview: {}", e);
+        }
+        let window_manager = crate::window_manager::WindowManager::new(app.clone());
+        if let Err(e) = window_manager.hide_break_overlay() {
+            eprintln!("❌ [Rust] Failed to hide break overlay preview: {}", e);
+        }
+
+        println!("✅ [Rust] Break overlay preview ended");
+    });
+
+    println!("✅ [Rust] Break overlay preview shown");
+
+    Ok(preview)
+}
+
 /// Handle timer tick (should be called every second by frontend)
 #[tauri::command]
 pub async fn cycle_tick(state: State<'_, AppState>, app: AppHandle) -> Result<CycleState, String> {
+    let tick_started = std::time::Instant::now();
     let mut cycle_orchestrator = state.cycle_orchestrator.lock().await;
 
     let orchestrator = cycle_orchestrator
@@ -780,6 +1504,7 @@ pub async fn cycle_tick(state: State<'_, AppState>, app: AppHandle) -> Result<Cy
 
     // Get current state after tick
     let current_state = orchestrator.get_state();
+    sync_display_sleep(&state, orchestrator.keeps_display_awake());
 
     // Handle PhaseEnded events to update sessions in database
     for event in &events {
@@ -850,71 +1575,62 @@ pub async fn cycle_tick(state: State<'_, AppState>, app: AppHandle) -> Result<Cy
                     phase, duration, cycle_count);
 
                 // Save new session when break starts automatically after focus
-                if let Some(ref session_id) = current_state.session_id {
-                    println!(
-                        "🆔 [CycleHandler] Current session_id: {}, Previous session_id: {:?}",
-                        session_id, session_id_before
-                    );
+                save_started_session(
+                    &state.database,
+                    phase,
+                    *duration,
+                    *cycle_count,
+                    &current_state,
+                    session_id_before.as_ref(),
+                );
+            }
+            crate::cycle_orchestrator::CycleEvent::BreakOverran {
+                phase,
+                overrun_seconds,
+            } => {
+                println!(
+                    "⏰ [CycleHandler] {} overran by {}s",
+                    phase, overrun_seconds
+                );
 
-                    // Check if this is a new session (not the one we had before)
-                    if session_id_before.as_ref() != Some(session_id) {
-                        println!("✨ [CycleHandler] New session detected, creating in database...");
-
-                        let session_type = match phase {
-                            CyclePhase::LongBreak => SessionType::LongBreak,
-                            CyclePhase::ShortBreak => SessionType::ShortBreak,
-                            CyclePhase::Focus => SessionType::Focus,
-                            _ => SessionType::ShortBreak,
-                        };
-
-                        let is_long_break = *phase == CyclePhase::LongBreak;
-
-                        println!("💾 [CycleHandler] Creating session: id={}, type={:?}, is_long_break={}, duration={}", 
-                            session_id, session_type, is_long_break, duration);
-
-                        let session = Session {
-                            id: session_id.clone(),
-                            session_type,
-                            start_time: current_state.started_at.unwrap_or_else(Utc::now),
-                            end_time: None,
-                            planned_duration: *duration as i32,
-                            actual_duration: None,
-                            strict_mode: false, // We don't have access to config here, but it's ok
-                            completed: false,
-                            notes: None,
-                            created_at: Utc::now(),
-                            within_work_hours: current_state.within_work_hours,
-                            cycle_number: Some(*cycle_count as i32),
-                            is_long_break,
-                        };
-
-                        match state.database.create_session(&session) {
-                            Ok(_) => {
-                                println!(
-                                    "✅ [CycleHandler] Successfully created session {} in database",
-                                    session_id
-                                );
-                            }
-                            Err(e) => {
-                                eprintln!("❌ [CycleHandler] Failed to save auto-started session to database: {}", e);
-                            }
-                        }
-                    } else {
-                        println!(
-                            "⏭️ [CycleHandler] Session {} already exists, skipping creation",
-                            session_id
-                        );
+                if let Some(session_id) = current_state.session_id.as_ref() {
+                    if let Err(e) = state
+                        .database
+                        .set_session_overrun(session_id, *overrun_seconds)
+                    {
+                        eprintln!("❌ [CycleHandler] Failed to record break overrun: {}", e);
                     }
-                } else {
-                    println!("⚠️ [CycleHandler] No session_id in current state");
                 }
             }
             _ => {}
         }
     }
 
+    // Phase changes are saved above; in between, save progress every
+    // `session_save_interval_seconds` rather than on every tick
+    let phase_changed = events.iter().any(|event| {
+        matches!(
+            event,
+            CycleEvent::PhaseEnded { .. } | CycleEvent::PhaseStarted { .. }
+        )
+    });
+    if phase_changed {
+        save_cycle_state(&state, orchestrator);
+    }
+    if orchestrator.progress_save_due() && !phase_changed {
+        if let (Some(session_id), Some(started_at)) =
+            (current_state.session_id.as_ref(), current_state.started_at)
+        {
+            let elapsed = (Utc::now() - started_at).num_seconds().max(0) as i32;
+            if let Err(e) = state.database.save_session_progress(session_id, elapsed) {
+                eprintln!("❌ [CycleHandler] Failed to save session progress: {}", e);
+            }
+        }
+    }
+
     // Check for pre-alert events and send notifications
-    let notification_service = state.notification_service.lock().await;
+    // Lock order: cycle -> notification (see `AppState`)
+    let mut notification_service = state.notification_service.lock().await;
 
     for event in &events {
         match event {
@@ -927,11 +1643,27 @@ pub async fn cycle_tick(state: State<'_, AppState>, app: AppHandle) -> Result<Cy
                 // Send cycle completed notification
                 notification_service.notify_cycle_complete(&app, *cycle_count);
             }
+            crate::cycle_orchestrator::CycleEvent::BreakOverran {
+                overrun_seconds, ..
+            } => {
+                notification_service.notify_break_overrun(&app, overrun_seconds / 60);
+            }
+            crate::cycle_orchestrator::CycleEvent::PhaseEnded {
+                phase,
+                completed: true,
+            } => match phase {
+                CyclePhase::Focus => notification_service.play_focus_end_sound(&app),
+                CyclePhase::ShortBreak | CyclePhase::LongBreak => {
+                    notification_service.play_break_end_sound(&app)
+                }
+                _ => {}
+            },
             _ => {}
         }
     }
 
     // Handle strict mode events if strict mode is active
+    // Lock order: cycle -> notification -> strict (see `AppState`)
     let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
     if let Some(orchestrator) = strict_mode_orchestrator.as_mut() {
         if orchestrator.is_active() {
@@ -944,6 +1676,20 @@ pub async fn cycle_tick(state: State<'_, AppState>, app: AppHandle) -> Result<Cy
     }
     drop(strict_mode_orchestrator); // Release lock before emitting events
 
+    // Automatic phase changes run the phase hooks too
+    if events
+        .iter()
+        .any(|event| matches!(event, CycleEvent::PhaseStarted { .. }))
+    {
+        match state.database.get_user_settings() {
+            Ok(settings) => phase_hooks::run_for_events(
+                &settings.unwrap_or_default().phase_command_hooks,
+                &events,
+            ),
+            Err(e) => eprintln!("Failed to load phase hooks: {}", e),
+        }
+    }
+
     // Emit events to frontend
     for event in events {
         if let Err(e) = app.emit("cycle-event", &event) {
@@ -954,9 +1700,93 @@ pub async fn cycle_tick(state: State<'_, AppState>, app: AppHandle) -> Result<Cy
     // Update tray icon with text showing timer
     update_tray_icon_with_text(&app, &current_state);
 
+    // Only ticks of a running phase are measured; idle ticks are slower on purpose
+    if state_before_tick.is_running {
+        state.performance_counters.record_tick(tick_started);
+    } else {
+        state.performance_counters.clear_last_tick();
+    }
+
     Ok(current_state)
 }
 
+/// Save the session of a phase the orchestrator started on its own.
+///
+/// Safe to call more than once for the same state: `create_session` ignores
+/// ids that already exist, so a repeated `PhaseStarted` never double-inserts.
+fn save_started_session(
+    database: &DatabaseManager,
+    phase: &CyclePhase,
+    duration: u32,
+    cycle_count: u32,
+    current_state: &CycleState,
+    session_id_before: Option<&String>,
+) {
+    if let Some(ref session_id) = current_state.session_id {
+        println!(
+            "🆔 [CycleHandler] Current session_id: {}, Previous session_id: {:?}",
+            session_id, session_id_before
+        );
+
+        // Check if this is a new session (not the one we had before)
+        if session_id_before != Some(session_id) {
+            println!("✨ [CycleHandler] New session detected, creating in database...");
+
+            let session_type = match phase {
+                CyclePhase::LongBreak => SessionType::LongBreak,
+                CyclePhase::ShortBreak => SessionType::ShortBreak,
+                CyclePhase::Focus => SessionType::Focus,
+                _ => SessionType::ShortBreak,
+            };
+
+            let is_long_break = *phase == CyclePhase::LongBreak;
+
+            println!("💾 [CycleHandler] Creating session: id={}, type={:?}, is_long_break={}, duration={}", 
+                session_id, session_type, is_long_break, duration);
+
+            let session = Session {
+                id: session_id.clone(),
+                session_type,
+                start_time: current_state.started_at.unwrap_or_else(Utc::now),
+                end_time: None,
+                planned_duration: duration as i32,
+                actual_duration: None,
+                strict_mode: false, // We don't have access to config here, but it's ok
+                completed: false,
+                notes: None,
+                created_at: Utc::now(),
+                within_work_hours: current_state.within_work_hours,
+                cycle_number: Some(cycle_count as i32),
+                is_long_break,
+                tag: None,
+                is_sprint: false,
+            };
+
+            match database.create_session(&session) {
+                Ok(_) => {
+                    println!(
+                        "✅ [CycleHandler] Successfully created session {} in database",
+                        session_id
+                    );
+                }
+                Err(e) => {
+                    eprintln!(
+                        "❌ [CycleHandler] Failed to save auto-started session to database: {}",
+                        e
+                    );
+                }
+            }
+        } else {
+            println!(
+                "⏭️ [CycleHandler] Session {} already exists, skipping creation",
+                session_id
+            );
+        }
+    } else {
+        println!("⚠️ [CycleHandler] No session_id in current state");
+    }
+}
+
 /// Reset the cycle counter
 #[tauri::command]
 pub async fn reset_cycle_count(state: State<'_, AppState>) -> Result<CycleState, String> {
@@ -969,6 +1799,7 @@ pub async fn reset_cycle_count(state: State<'_, AppState>) -> Result<CycleState,
         .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
 
     orchestrator.reset_cycle_count();
+    save_cycle_state(&state, orchestrator);
 
     let current_state = orchestrator.get_state();
 
@@ -1019,6 +1850,373 @@ pub async fn log_bypass_attempt(
     Ok(())
 }
 
+/// What the overlay's exits (Escape and dismiss) go on
+struct OverlayExitContext {
+    user_settings: UserSettings,
+    strict_mode_active: bool,
+    session_id: String,
+}
+
+/// Read the settings and strict mode state for an overlay exit, failing
+/// when no break is running
+async fn overlay_exit_context(state: &State<'_, AppState>) -> Result<OverlayExitContext, String> {
+    let user_settings = load_user_settings(state)?;
+
+    let strict_mode_active = {
+        let strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+        strict_mode_orchestrator
+            .as_ref()
+            .map(|orchestrator| orchestrator.is_active())
+            .unwrap_or(false)
+    };
+
+    let (phase, session_id) = {
+        let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+        let orchestrator = cycle_orchestrator
+            .as_ref()
+            .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?;
+        let current_state = orchestrator.get_state();
+        (current_state.phase, current_state.session_id)
+    };
+
+    if !matches!(phase, CyclePhase::ShortBreak | CyclePhase::LongBreak) {
+        return Err("No break is running".to_string());
+    }
+
+    Ok(OverlayExitContext {
+        user_settings,
+        strict_mode_active,
+        session_id: session_id.unwrap_or_default(),
+    })
+}
+
+/// Result of pressing Escape on the break overlay
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayEscapeOutcome {
+    Ignored,
+    BypassLogged,
+    BreakEnded,
+    PinRequired,
+    PinRejected,
+}
+
+/// Apply the configured Escape action on the break overlay.
+///
+/// The action is enforced here rather than in the overlay: while strict mode
+/// is active Escape can at most log a bypass attempt.
+#[tauri::command]
+pub async fn handle_overlay_escape(
+    pin: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<OverlayEscapeOutcome, String> {
+    println!("⌨️ [Rust] handle_overlay_escape called");
+
+    let OverlayExitContext {
+        user_settings,
+        strict_mode_active,
+        session_id,
+    } = overlay_exit_context(&state).await?;
+
+    let action = user_settings
+        .overlay_escape_action
+        .effective(strict_mode_active);
+
+    let outcome = match action {
+        OverlayEscapeAction::Ignore => OverlayEscapeOutcome::Ignored,
+        OverlayEscapeAction::LogBypass => {
+            log_bypass_attempt(
+                session_id,
+                "escape_blocked".to_string(),
+                Utc::now().to_rfc3339(),
+                state.clone(),
+            )
+            .await?;
+            OverlayEscapeOutcome::BypassLogged
+        }
+        OverlayEscapeAction::EndBreak => {
+            end_cycle_session(false, state.clone(), app.clone()).await?;
+            OverlayEscapeOutcome::BreakEnded
+        }
+        OverlayEscapeAction::RequirePin => {
+            match check_escape_pin(pin.as_deref(), user_settings.pin_hash.as_deref()) {
+                OverlayEscapeOutcome::BreakEnded => {
+                    end_cycle_session(false, state.clone(), app.clone()).await?;
+                    OverlayEscapeOutcome::BreakEnded
+                }
+                OverlayEscapeOutcome::PinRejected => {
+                    log_bypass_attempt(
+                        session_id,
+                        "escape_pin_rejected".to_string(),
+                        Utc::now().to_rfc3339(),
+                        state.clone(),
+                    )
+                    .await?;
+                    OverlayEscapeOutcome::PinRejected
+                }
+                outcome => outcome,
+            }
+        }
+    };
+
+    println!(
+        "✅ [Rust] Overlay escape handled with {} ({:?})",
+        action, outcome
+    );
+
+    Ok(outcome)
+}
+
+/// Check the PIN sent with Escape under `RequirePin`: `PinRequired` until one
+/// is sent, `BreakEnded` when it matches the stored hash, `PinRejected`
+/// otherwise (including when no PIN was ever set)
+fn check_escape_pin(pin: Option<&str>, pin_hash: Option<&str>) -> OverlayEscapeOutcome {
+    match (pin, pin_hash) {
+        (None, _) => OverlayEscapeOutcome::PinRequired,
+        (Some(pin), Some(pin_hash)) if bcrypt::verify(pin, pin_hash).unwrap_or(false) => {
+            OverlayEscapeOutcome::BreakEnded
+        }
+        _ => OverlayEscapeOutcome::PinRejected,
+    }
+}
+
+/// Result of dismissing the break overlay
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OverlayDismissOutcome {
+    /// Strict mode is on, so the overlay stays
+    Blocked,
+    BreakEnded,
+    /// The break is paused and resumes, overlay included, after `minutes`
+    Snoozed {
+        minutes: u32,
+    },
+    /// The overlay should hide while the break keeps running
+    Hidden,
+}
+
+/// Apply the configured soft-mode dismiss action on the break overlay.
+///
+/// Every dismissal is logged as a bypass attempt so leaving the break still
+/// shows up in the stats. While strict mode is active nothing else happens.
+#[tauri::command]
+pub async fn handle_overlay_dismiss(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<OverlayDismissOutcome, String> {
+    println!("🙈 [Rust] handle_overlay_dismiss called");
+
+    let OverlayExitContext {
+        user_settings,
+        strict_mode_active,
+        session_id,
+    } = overlay_exit_context(&state).await?;
+
+    let action = user_settings.soft_overlay_dismiss_action;
+    let method = if strict_mode_active {
+        "overlay_dismiss_blocked".to_string()
+    } else {
+        format!("overlay_dismiss_{}", action)
+    };
+    log_bypass_attempt(
+        session_id.clone(),
+        method,
+        Utc::now().to_rfc3339(),
+        state.clone(),
+    )
+    .await?;
+
+    if strict_mode_active {
+        return Ok(OverlayDismissOutcome::Blocked);
+    }
+
+    let outcome = match action {
+        SoftOverlayDismissAction::EndBreak => {
+            end_cycle_session(false, state.clone(), app.clone()).await?;
+            OverlayDismissOutcome::BreakEnded
+        }
+        SoftOverlayDismissAction::Snooze => {
+            let minutes = user_settings.break_snooze_minutes.max(1) as u32;
+            pause_cycle(state.clone()).await?;
+            if let Some(orchestrator) = state.cycle_orchestrator.lock().await.as_mut() {
+                orchestrator.mark_paused_by_snooze();
+            }
+            schedule_snooze_end(app.clone(), session_id, minutes);
+            OverlayDismissOutcome::Snoozed { minutes }
+        }
+        SoftOverlayDismissAction::HideOverlay => OverlayDismissOutcome::Hidden,
+    };
+
+    println!(
+        "✅ [Rust] Overlay dismiss handled with {} ({:?})",
+        action, outcome
+    );
+
+    Ok(outcome)
+}
+
+/// Resume a snoozed break after `minutes`, unless it was resumed, ended or
+/// replaced in the meantime. A pause the user made after resuming it is
+/// theirs and is left alone.
+fn schedule_snooze_end(app: AppHandle, session_id: String, minutes: u32) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(minutes as u64 * 60)).await;
+
+        let state = app.state::<AppState>();
+        let still_snoozed = state
+            .cycle_orchestrator
+            .lock()
+            .await
+            .as_ref()
+            .map(|orchestrator| orchestrator.is_snoozed(&session_id))
+            .unwrap_or(false);
+
+        if still_snoozed {
+            println!("⏰ [Rust] Break snooze ended, resuming break");
+            if let Err(e) = resume_cycle(state).await {
+                eprintln!("❌ [Rust] Failed to resume snoozed break: {}", e);
+            }
+        }
+    });
+}
+
+/// A scheduled start missed by more than this (the app was closed at the
+/// time) is dropped instead of fired late
+const SCHEDULED_FOCUS_GRACE_MINUTES: i64 = 10;
+
+/// How often a pending schedule re-reads the wall clock, so a start isn't
+/// held up by time the machine spent asleep
+const SCHEDULED_FOCUS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Save the pending scheduled focus start so it survives a restart
+fn save_scheduled_focus(
+    database: &DatabaseManager,
+    at: Option<DateTime<Utc>>,
+) -> Result<(), String> {
+    database
+        .with_connection(|conn| {
+            conn.execute(
+                "UPDATE user_settings SET scheduled_focus_at = ?1, updated_at = ?2 WHERE id = 1",
+                rusqlite::params![at, Utc::now()],
+            )
+            .map_err(crate::database::DatabaseError::Sqlite)
+        })
+        .map(|_| ())
+        .map_err(|e| format!("Failed to save scheduled focus: {}", e))
+}
+
+fn load_scheduled_focus(database: &DatabaseManager) -> Option<DateTime<Utc>> {
+    database
+        .get_user_settings()
+        .ok()
+        .flatten()
+        .and_then(|settings| settings.scheduled_focus_at)
+}
+
+/// Start a focus session automatically at `at`, replacing any earlier schedule.
+/// Work hours enforcement applies as if the session were started by hand.
+#[tauri::command]
+pub async fn schedule_focus(
+    at: DateTime<Utc>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<DateTime<Utc>, String> {
+    println!("⏰ [Rust] schedule_focus called for {}", at);
+
+    if at <= Utc::now() {
+        let error_msg = "Scheduled focus time must be in the future".to_string();
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    save_scheduled_focus(&state.database, Some(at))?;
+    spawn_scheduled_focus(app, at);
+
+    println!("✅ [Rust] Focus scheduled for {}", at);
+    Ok(at)
+}
+
+/// Drop the pending scheduled focus start, if any
+#[tauri::command]
+pub async fn cancel_scheduled_focus(state: State<'_, AppState>) -> Result<(), String> {
+    println!("🗑️ [Rust] cancel_scheduled_focus called");
+
+    save_scheduled_focus(&state.database, None)?;
+
+    println!("✅ [Rust] Scheduled focus cancelled");
+    Ok(())
+}
+
+/// When the pending scheduled focus start fires, or `None` if nothing is scheduled
+#[tauri::command]
+pub async fn get_scheduled_focus(
+    state: State<'_, AppState>,
+) -> Result<Option<DateTime<Utc>>, String> {
+    Ok(load_scheduled_focus(&state.database))
+}
+
+/// Re-arm a focus start scheduled before the last restart
+pub fn restore_scheduled_focus(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if let Some(at) = load_scheduled_focus(&state.database) {
+        println!("⏰ [Rust] Restoring focus scheduled for {}", at);
+        spawn_scheduled_focus(app.clone(), at);
+    }
+}
+
+/// Wait until `at`, then start focus unless the schedule was cancelled or
+/// replaced in the meantime. The schedule is cleared whether or not the
+/// start succeeds, so it fires at most once.
+fn spawn_scheduled_focus(app: AppHandle, at: DateTime<Utc>) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+
+        loop {
+            let remaining = (at - Utc::now()).to_std().unwrap_or_default();
+            if remaining.is_zero() {
+                break;
+            }
+            tokio::time::sleep(remaining.min(SCHEDULED_FOCUS_POLL_INTERVAL)).await;
+
+            if load_scheduled_focus(&state.database) != Some(at) {
+                return;
+            }
+        }
+
+        if let Err(e) = save_scheduled_focus(&state.database, None) {
+            eprintln!("❌ [Rust] {}", e);
+        }
+
+        if Utc::now() - at > chrono::Duration::minutes(SCHEDULED_FOCUS_GRACE_MINUTES) {
+            println!("⏭️ [Rust] Scheduled focus for {} was missed, skipping", at);
+            return;
+        }
+
+        let initialized = state.cycle_orchestrator.lock().await.is_some();
+        if !initialized {
+            if let Err(e) = initialize_cycle_orchestrator(state.clone(), app.clone()).await {
+                eprintln!("❌ [Rust] Scheduled focus skipped: {}", e);
+                return;
+            }
+        }
+
+        match start_focus_session(None, state.clone(), app.clone()).await {
+            Ok(_) => {
+                println!("✅ [Rust] Scheduled focus started");
+                let mut notification_service = state.notification_service.lock().await;
+                notification_service.notify_scheduled_focus_start(&app);
+                drop(notification_service);
+
+                if let Err(e) = app.emit("scheduled-focus-started", at) {
+                    eprintln!("Failed to emit scheduled-focus-started: {}", e);
+                }
+            }
+            Err(e) => eprintln!("⚠️ [Rust] Scheduled focus skipped: {}", e),
+        }
+    });
+}
+
 /// Get work schedule information for UI display
 #[tauri::command]
 pub async fn get_work_schedule_info(
@@ -1039,73 +2237,376 @@ pub async fn get_work_schedule_info(
     Ok(info)
 }
 
+/// Work hours compliance over the last `days` days of focus sessions.
+/// With `completed_only` set, abandoned focus sessions don't count toward the
+/// totals or the compliance percentage.
+fn query_work_hours_stats(
+    database: &DatabaseManager,
+    days: u32,
+    completed_only: bool,
+) -> Result<crate::database::models::WorkHoursStats, crate::database::DatabaseError> {
+    database.with_connection(|conn| {
+        // Calculate date range
+        let now = chrono::Utc::now();
+        let start_date = now - chrono::Duration::days(days as i64);
+
+        // Query sessions within date range
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT 
+                    COUNT(*) as total_sessions,
+                    SUM(CASE WHEN within_work_hours = 1 THEN 1 ELSE 0 END) as within_hours,
+                    SUM(CASE WHEN within_work_hours = 0 THEN 1 ELSE 0 END) as outside_hours,
+                    SUM(CASE WHEN within_work_hours = 1 AND session_type = 'focus' AND completed = 1 
+                        THEN actual_duration ELSE 0 END) as focus_minutes_within,
+                    SUM(CASE WHEN within_work_hours = 0 AND session_type = 'focus' AND completed = 1 
+                        THEN actual_duration ELSE 0 END) as focus_minutes_outside
+                FROM sessions
+                WHERE start_time >= ?1 AND session_type = 'focus'
+                    AND (?2 = 0 OR completed = 1)
+                "#,
+            )
+            .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+
+        let result = stmt.query_row(rusqlite::params![start_date, completed_only], |row| {
+            let total: u32 = row.get(0).unwrap_or(0);
+            let within: u32 = row.get(1).unwrap_or(0);
+            let outside: u32 = row.get(2).unwrap_or(0);
+            let focus_within_seconds: i32 = row.get(3).unwrap_or(0);
+            let focus_outside_seconds: i32 = row.get(4).unwrap_or(0);
+
+            let compliance_percentage = if total > 0 {
+                (within as f64 / total as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            Ok(crate::database::models::WorkHoursStats {
+                total_sessions: total,
+                within_work_hours: within,
+                outside_work_hours: outside,
+                compliance_percentage,
+                total_focus_minutes_within: (focus_within_seconds / 60) as u32,
+                total_focus_minutes_outside: (focus_outside_seconds / 60) as u32,
+                period_start: start_date.format("%Y-%m-%d").to_string(),
+                period_end: now.format("%Y-%m-%d").to_string(),
+            })
+        });
+
+        result.map_err(|e| crate::database::DatabaseError::Sqlite(e))
+    })
+}
+
 /// Get work hours compliance statistics
 #[tauri::command]
 pub async fn get_work_hours_stats(
     days: Option<u32>,
+    completed_only: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<crate::database::models::WorkHoursStats, String> {
     let days = days.unwrap_or(30); // Default to last 30 days
+    let completed_only = completed_only.unwrap_or(true);
+    println!(
+        "📊 [Rust] get_work_hours_stats called for last {} days (completed only: {})",
+        days, completed_only
+    );
+
+    let stats = query_work_hours_stats(&state.database, days, completed_only)
+        .map_err(|e| format!("Failed to get work hours stats: {}", e))?;
+
+    println!("✅ [Rust] Work hours stats retrieved: {:?}", stats);
+
+    Ok(stats)
+}
+
+/// Get the session id the cycle orchestrator is currently tracking, if any
+async fn current_session_id(state: &State<'_, AppState>) -> Option<String> {
+    let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+    cycle_orchestrator
+        .as_ref()
+        .and_then(|orchestrator| orchestrator.get_state().session_id)
+}
+
+/// List sessions left open (no end_time) that are not the active cycle session
+#[tauri::command]
+pub async fn get_incomplete_sessions(state: State<'_, AppState>) -> Result<Vec<Session>, String> {
+    println!("📖 [Rust] get_incomplete_sessions called");
+
+    let active_session_id = current_session_id(&state).await;
+
+    let sessions = state
+        .database
+        .get_incomplete_sessions(active_session_id.as_deref())
+        .map_err(|e| format!("Failed to get incomplete sessions: {}", e))?;
+
+    println!("✅ [Rust] Found {} incomplete sessions", sessions.len());
+
+    Ok(sessions)
+}
+
+/// Most sessions returned by one `get_session_history` call
+const MAX_SESSION_HISTORY_PAGE: u32 = 200;
+
+/// One page of session history, newest first. `filter` limits it to focus
+/// sessions or breaks; `total` counts every matching session for paging.
+#[tauri::command]
+pub async fn get_session_history(
+    offset: u32,
+    limit: u32,
+    filter: Option<SessionHistoryFilter>,
+    state: State<'_, AppState>,
+) -> Result<SessionHistoryPage, String> {
     println!(
-        "📊 [Rust] get_work_hours_stats called for last {} days",
-        days
+        "📖 [Rust] get_session_history called (offset: {}, limit: {}, filter: {:?})",
+        offset, limit, filter
     );
 
-    let stats = state
+    if limit == 0 || limit > MAX_SESSION_HISTORY_PAGE {
+        return Err(format!(
+            "Page size must be between 1 and {}",
+            MAX_SESSION_HISTORY_PAGE
+        ));
+    }
+
+    let (sessions, total) = state
         .database
-        .with_connection(|conn| {
-            // Calculate date range
-            let now = chrono::Utc::now();
-            let start_date = now - chrono::Duration::days(days as i64);
+        .get_sessions_paginated(offset, limit, filter)
+        .map_err(|e| format!("Failed to get session history: {}", e))?;
 
-            // Query sessions within date range
-            let mut stmt = conn
-                .prepare(
-                    r#"
-                    SELECT 
-                        COUNT(*) as total_sessions,
-                        SUM(CASE WHEN within_work_hours = 1 THEN 1 ELSE 0 END) as within_hours,
-                        SUM(CASE WHEN within_work_hours = 0 THEN 1 ELSE 0 END) as outside_hours,
-                        SUM(CASE WHEN within_work_hours = 1 AND session_type = 'focus' AND completed = 1 
-                            THEN actual_duration ELSE 0 END) as focus_minutes_within,
-                        SUM(CASE WHEN within_work_hours = 0 AND session_type = 'focus' AND completed = 1 
-                            THEN actual_duration ELSE 0 END) as focus_minutes_outside
-                    FROM sessions
-                    WHERE start_time >= ?1 AND session_type = 'focus'
-                    "#,
+    Ok(SessionHistoryPage { sessions, total })
+}
+
+/// Close dangling sessions left open (e.g. after a crash), returning how many were closed
+#[tauri::command]
+pub async fn close_incomplete_sessions(
+    confirm: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    println!("🧹 [Rust] close_incomplete_sessions called");
+
+    super::require_confirmation(&confirm, "close_incomplete_sessions")?;
+
+    let active_session_id = current_session_id(&state).await;
+
+    let closed = state
+        .database
+        .close_incomplete_sessions(active_session_id.as_deref())
+        .map_err(|e| format!("Failed to close incomplete sessions: {}", e))?;
+
+    println!("✅ [Rust] Closed {} incomplete sessions", closed);
+
+    Ok(closed)
+}
+
+/// Permanently delete the sessions that started in `[start, end)`, with
+/// their evasion/bypass attempts and notifications, returning how many
+/// sessions were deleted. The running session is never deleted.
+#[tauri::command]
+pub async fn delete_sessions_in_range(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    confirm: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    println!(
+        "🗑️ [Rust] delete_sessions_in_range called: {} to {}",
+        start, end
+    );
+
+    super::require_confirmation(&confirm, "delete_sessions_in_range")?;
+
+    if start >= end {
+        let error_msg = "Range start must be before its end".to_string();
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let active_session_id = current_session_id(&state).await;
+
+    let deleted = state
+        .database
+        .delete_sessions_in_range(start, end, active_session_id.as_deref())
+        .map_err(|e| {
+            let error_msg = format!("Failed to delete sessions: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            error_msg
+        })?;
+
+    println!("✅ [Rust] Deleted {} sessions", deleted);
+
+    Ok(deleted)
+}
+
+/// Report sessions with impossible or inconsistent data (end before start,
+/// overlaps, far overrun durations, missing fields). Passing `fix` also
+/// repairs the clearly invalid ones and needs the confirmation token.
+#[tauri::command]
+pub async fn find_session_anomalies(
+    fix: Option<bool>,
+    confirm: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SessionAnomalyReport, String> {
+    let fix = fix.unwrap_or(false);
+    println!("🔍 [Rust] find_session_anomalies called (fix: {})", fix);
+
+    if fix {
+        super::require_confirmation(confirm.as_deref().unwrap_or(""), "find_session_anomalies")?;
+    }
+
+    let report = state
+        .database
+        .find_session_anomalies(fix)
+        .map_err(|e| format!("Failed to check sessions: {}", e))?;
+
+    println!(
+        "✅ [Rust] Found {} session anomalies, fixed {}",
+        report.anomalies.len(),
+        report.fixed
+    );
+
+    Ok(report)
+}
+
+/// Emit a synthetic cycle event for frontend testing (debug builds only).
+///
+/// The event is only broadcast on `cycle-event`; orchestrator state, strict mode
+/// and notifications are left untouched.
+#[tauri::command]
+pub async fn emit_test_cycle_event(event_json: String, app: AppHandle) -> Result<(), String> {
+    if !cfg!(debug_assertions) {
+        return Err("emit_test_cycle_event is only available in debug builds".to_string());
+    }
+
+    println!("🧪 [Rust] emit_test_cycle_event called: {}", event_json);
+
+    let event: CycleEvent = serde_json::from_str(&event_json)
+        .map_err(|e| format!("Invalid cycle event: {}", e))?;
+
+    app.emit("cycle-event", &event)
+        .map_err(|e| format!("Failed to emit cycle event: {}", e))?;
+
+    println!("✅ [Rust] Test cycle event emitted: {:?}", event);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_started_session_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        let current_state = CycleState {
+            phase: CyclePhase::ShortBreak,
+            remaining_seconds: 300,
+            cycle_count: 1,
+            is_running: true,
+            can_start: true,
+            session_id: Some("break-session".to_string()),
+            started_at: Some(Utc::now()),
+            within_work_hours: true,
+            is_sprint: false,
+            is_wind_down: false,
+            phase_color: None,
+        };
+
+        // Two ticks handling the same PhaseStarted event
+        for _ in 0..2 {
+            save_started_session(
+                &database,
+                &CyclePhase::ShortBreak,
+                300,
+                1,
+                &current_state,
+                None,
+            );
+        }
+
+        let rows: i64 = database
+            .with_connection(|conn| {
+                conn.query_row(
+                    "SELECT COUNT(*) FROM sessions WHERE id = 'break-session'",
+                    [],
+                    |row| row.get(0),
                 )
-                .map_err(|e| crate::database::DatabaseError::Sqlite(e))?;
+                .map_err(crate::database::DatabaseError::Sqlite)
+            })
+            .unwrap();
+        assert_eq!(rows, 1);
+    }
+
+    fn focus_session(id: &str, completed: bool, within_work_hours: bool) -> Session {
+        let start = Utc::now() - chrono::Duration::hours(1);
+        Session {
+            id: id.to_string(),
+            session_type: SessionType::Focus,
+            start_time: start,
+            end_time: Some(start + chrono::Duration::minutes(25)),
+            planned_duration: 1500,
+            actual_duration: Some(if completed { 1500 } else { 600 }),
+            strict_mode: false,
+            completed,
+            notes: None,
+            created_at: start,
+            within_work_hours,
+            cycle_number: Some(1),
+            is_long_break: false,
+            tag: None,
+            is_sprint: false,
+        }
+    }
 
-            let result = stmt.query_row([start_date], |row| {
-                let total: u32 = row.get(0).unwrap_or(0);
-                let within: u32 = row.get(1).unwrap_or(0);
-                let outside: u32 = row.get(2).unwrap_or(0);
-                let focus_within_seconds: i32 = row.get(3).unwrap_or(0);
-                let focus_outside_seconds: i32 = row.get(4).unwrap_or(0);
+    #[test]
+    fn test_work_hours_stats_can_skip_incomplete_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
+
+        for session in [
+            focus_session("done-inside", true, true),
+            focus_session("done-outside", true, false),
+            focus_session("abandoned-outside-1", false, false),
+            focus_session("abandoned-outside-2", false, false),
+        ] {
+            database.create_session(&session).unwrap();
+        }
 
-                let compliance_percentage = if total > 0 {
-                    (within as f64 / total as f64) * 100.0
-                } else {
-                    0.0
-                };
-
-                Ok(crate::database::models::WorkHoursStats {
-                    total_sessions: total,
-                    within_work_hours: within,
-                    outside_work_hours: outside,
-                    compliance_percentage,
-                    total_focus_minutes_within: (focus_within_seconds / 60) as u32,
-                    total_focus_minutes_outside: (focus_outside_seconds / 60) as u32,
-                    period_start: start_date.format("%Y-%m-%d").to_string(),
-                    period_end: now.format("%Y-%m-%d").to_string(),
-                })
-            });
-
-            result.map_err(|e| crate::database::DatabaseError::Sqlite(e))
-        })
-        .map_err(|e| format!("Failed to get work hours stats: {}", e))?;
+        let completed = query_work_hours_stats(&database, 30, true).unwrap();
+        assert_eq!(completed.total_sessions, 2);
+        assert_eq!(completed.outside_work_hours, 1);
+        assert_eq!(completed.compliance_percentage, 50.0);
+        assert_eq!(completed.total_focus_minutes_within, 25);
+        assert_eq!(completed.total_focus_minutes_outside, 25);
+
+        let all = query_work_hours_stats(&database, 30, false).unwrap();
+        assert_eq!(all.total_sessions, 4);
+        assert_eq!(all.outside_work_hours, 3);
+        assert_eq!(all.compliance_percentage, 25.0);
+        assert_eq!(all.total_focus_minutes_outside, 25);
+    }
 
-    println!("✅ [Rust] Work hours stats retrieved: {:?}", stats);
+    #[test]
+    fn test_escape_pin_accepts_matching_pin_and_rejects_others() {
+        let pin_hash = bcrypt::hash("2468", 4).unwrap();
 
-    Ok(stats)
+        assert_eq!(
+            check_escape_pin(None, Some(&pin_hash)),
+            OverlayEscapeOutcome::PinRequired
+        );
+        assert_eq!(
+            check_escape_pin(Some("2468"), Some(&pin_hash)),
+            OverlayEscapeOutcome::BreakEnded
+        );
+        assert_eq!(
+            check_escape_pin(Some("1357"), Some(&pin_hash)),
+            OverlayEscapeOutcome::PinRejected
+        );
+        assert_eq!(
+            check_escape_pin(Some("2468"), None),
+            OverlayEscapeOutcome::PinRejected
+        );
+    }
 }
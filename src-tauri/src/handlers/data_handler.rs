@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use tauri::State;
+
+use crate::config::save_data_directory;
+use crate::database::DatabaseError;
+use crate::state::AppState;
+
+/// Directory currently holding the database and configuration backups
+#[tauri::command]
+pub async fn get_data_directory(state: State<'_, AppState>) -> Result<String, String> {
+    println!("📖 [Rust] get_data_directory called");
+
+    Ok(state
+        .database
+        .data_directory()
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Move the database and backups to `new_path` and use it from now on.
+/// On failure the data stays where it was and the error is returned.
+#[tauri::command]
+pub async fn move_data_directory(
+    new_path: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    println!("💾 [Rust] move_data_directory called: {}", new_path);
+
+    let new_dir = PathBuf::from(new_path.trim());
+    let app_handle = state.app_handle.clone();
+
+    state
+        .database
+        .relocate(&new_dir, |dir| {
+            save_data_directory(&app_handle, dir).map_err(DatabaseError::Validation)
+        })
+        .map_err(|e| {
+            let error_msg = format!("Failed to move data directory: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            error_msg
+        })?;
+
+    println!("✅ [Rust] Data directory moved to {}", new_dir.display());
+    Ok(new_dir.to_string_lossy().into_owned())
+}
+
+/// Create any database table or column that is missing, e.g. after an
+/// interrupted migration. Returns the tables created and the columns added,
+/// as `table.column`.
+#[tauri::command]
+pub async fn ensure_schema(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    println!("💾 [Rust] ensure_schema called");
+
+    let repaired = state.database.ensure_schema().map_err(|e| {
+        let error_msg = format!("Failed to ensure database schema: {}", e);
+        println!("❌ [Rust] {}", error_msg);
+        error_msg
+    })?;
+
+    if repaired.is_empty() {
+        println!("✅ [Rust] All database tables and columns present");
+    } else {
+        println!(
+            "✅ [Rust] Repaired database schema: {}",
+            repaired.join(", ")
+        );
+    }
+    Ok(repaired)
+}
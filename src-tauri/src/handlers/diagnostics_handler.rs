@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::performance::PerformanceSnapshot;
+use crate::state::AppState;
+
+/// Process and in-memory buffer sizes, to attach to memory growth reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeStats {
+    /// Resident memory of the app process; `None` where it can't be read
+    pub rss_bytes: Option<u64>,
+    /// Webview windows currently open
+    pub open_windows: usize,
+    pub window_labels: Vec<String>,
+    /// Entries in the cycle event log, 0 before the orchestrator exists
+    pub cycle_events: usize,
+    pub cycle_events_capacity: usize,
+    pub notification_history: usize,
+    pub notification_history_capacity: usize,
+    /// Telemetry events waiting to be flushed
+    pub telemetry_queue: usize,
+}
+
+/// Current memory use, open windows and in-memory buffer sizes, for
+/// diagnosing growth over long sessions
+#[tauri::command]
+pub async fn get_runtime_stats(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<RuntimeStats, String> {
+    println!("📖 [Rust] get_runtime_stats called");
+
+    let mut window_labels: Vec<String> = app.webview_windows().into_keys().collect();
+    window_labels.sort();
+
+    let cycle_events = state
+        .cycle_orchestrator
+        .lock()
+        .await
+        .as_ref()
+        .map_or(0, |orchestrator| orchestrator.event_log_len());
+
+    let (notification_history, notification_history_capacity) = {
+        let notification_service = state.notification_service.lock().await;
+        (
+            notification_service.history_len(),
+            notification_service.get_history_capacity(),
+        )
+    };
+
+    Ok(RuntimeStats {
+        rss_bytes: process_rss_bytes(),
+        open_windows: window_labels.len(),
+        window_labels,
+        cycle_events,
+        cycle_events_capacity: crate::cycle_orchestrator::EVENT_LOG_CAPACITY,
+        notification_history,
+        notification_history_capacity,
+        telemetry_queue: state.telemetry_service.queued_events().await,
+    })
+}
+
+/// Tick and database write counters since they were last reset
+#[tauri::command]
+pub async fn get_performance_counters(
+    state: State<'_, AppState>,
+) -> Result<PerformanceSnapshot, String> {
+    println!("📖 [Rust] get_performance_counters called");
+
+    Ok(state.performance_counters.snapshot())
+}
+
+/// Zero the performance counters, e.g. before measuring a session
+#[tauri::command]
+pub async fn reset_performance_counters(state: State<'_, AppState>) -> Result<(), String> {
+    println!("🧹 [Rust] reset_performance_counters called");
+
+    state.performance_counters.reset();
+    Ok(())
+}
+
+/// Resident set size of this process, read from `/proc` on Linux and `ps`
+/// on macOS
+fn process_rss_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let kilobytes = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))?
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(kilobytes * 1024)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "rss=", "-p", &std::process::id().to_string()])
+            .output()
+            .ok()?;
+        let kilobytes = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(kilobytes * 1024)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        None
+    }
+}
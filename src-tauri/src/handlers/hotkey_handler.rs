@@ -0,0 +1,143 @@
+use crate::hotkey_manager::{HotkeyAction, HotkeyConfig, HotkeyEventResult, HotkeyManager};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+/// Save the hotkeys after a change, so it is kept across restarts
+fn save_hotkeys(hotkey_manager: &HotkeyManager) -> Result<(), String> {
+    hotkey_manager
+        .save_hotkey_configurations()
+        .map_err(|e| format!("Failed to save hotkey configurations: {}", e))
+}
+
+/// Get every hotkey configuration, keyed by action
+#[tauri::command]
+pub async fn get_hotkey_configs(
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<HashMap<HotkeyAction, HotkeyConfig>, String> {
+    Ok(hotkey_manager.get_hotkey_configs())
+}
+
+/// Bind an action to a new shortcut
+#[tauri::command]
+pub async fn update_hotkey_config(
+    config: HotkeyConfig,
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<(), String> {
+    println!("⌨️ [Rust] update_hotkey_config called: {:?}", config);
+
+    hotkey_manager
+        .update_hotkey(config)
+        .map_err(|e| format!("Failed to update hotkey: {}", e))?;
+    save_hotkeys(&hotkey_manager)
+}
+
+/// Turn an action's hotkey on or off, keeping its shortcut
+#[tauri::command]
+pub async fn set_hotkey_enabled(
+    action: HotkeyAction,
+    enabled: bool,
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<(), String> {
+    println!(
+        "⌨️ [Rust] set_hotkey_enabled called: {:?} = {}",
+        action, enabled
+    );
+
+    hotkey_manager
+        .set_hotkey_enabled(&action, enabled)
+        .map_err(|e| format!("Failed to set hotkey enabled state: {}", e))?;
+    save_hotkeys(&hotkey_manager)
+}
+
+/// Run an action as if its hotkey had been pressed
+#[tauri::command]
+pub async fn route_hotkey_event(
+    action: HotkeyAction,
+    app: AppHandle,
+) -> Result<HotkeyEventResult, String> {
+    println!("⌨️ [Rust] route_hotkey_event called: {:?}", action);
+
+    Ok(HotkeyManager::run_action(&app, action).await)
+}
+
+/// Save the current hotkey configurations to the database
+#[tauri::command]
+pub async fn save_hotkey_configurations(
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<(), String> {
+    hotkey_manager
+        .save_hotkey_configurations()
+        .map_err(|e| format!("Failed to save hotkey configurations: {}", e))
+}
+
+/// Re-register the hotkey configurations saved in the database
+#[tauri::command]
+pub async fn load_custom_hotkeys(hotkey_manager: State<'_, HotkeyManager>) -> Result<(), String> {
+    hotkey_manager
+        .load_custom_hotkeys()
+        .map_err(|e| format!("Failed to load custom hotkeys: {}", e))
+}
+
+/// Restore and save the default shortcut of every action
+#[tauri::command]
+pub async fn reset_hotkeys_to_defaults(
+    confirm: String,
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<(), String> {
+    println!("🔄 [Rust] reset_hotkeys_to_defaults called");
+
+    super::require_confirmation(&confirm, "reset_hotkeys_to_defaults")?;
+
+    hotkey_manager
+        .reset_to_defaults()
+        .map_err(|e| format!("Failed to reset hotkeys to defaults: {}", e))
+}
+
+/// Actions whose hotkey isn't currently bound
+#[tauri::command]
+pub async fn check_hotkey_conflicts(
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<Vec<HotkeyAction>, String> {
+    Ok(hotkey_manager.check_for_conflicts())
+}
+
+/// Export every hotkey configuration as JSON, keyed by action name
+#[tauri::command]
+pub async fn export_hotkeys_json(
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<String, String> {
+    println!("📤 [Rust] export_hotkeys_json called");
+
+    hotkey_manager
+        .export_hotkeys()
+        .map_err(|e| format!("Failed to export hotkeys: {}", e))
+}
+
+/// Apply hotkey configurations exported by `export_hotkeys_json`, returning
+/// how many were imported. Nothing changes if the file names an unknown
+/// action, holds an invalid shortcut or would bind a shortcut twice.
+#[tauri::command]
+pub async fn import_hotkeys_json(
+    json: String,
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<usize, String> {
+    println!("📥 [Rust] import_hotkeys_json called");
+
+    let imported = hotkey_manager
+        .import_hotkeys(&json)
+        .map_err(|e| format!("Failed to import hotkeys: {}", e))?;
+    save_hotkeys(&hotkey_manager)?;
+    Ok(imported)
+}
+
+/// Modifier combinations offered when customizing a hotkey
+#[tauri::command]
+pub async fn get_available_modifiers() -> Result<Vec<(String, String)>, String> {
+    // Convert Modifiers to string representation for frontend
+    let modifiers = HotkeyManager::get_available_modifiers();
+    let string_modifiers: Vec<(String, String)> = modifiers
+        .into_iter()
+        .map(|(name, _)| (name.clone(), name)) // In a real implementation, convert Modifiers to string
+        .collect();
+    Ok(string_modifiers)
+}
@@ -1,9 +1,32 @@
 pub mod auth_handler;
+pub mod command_handler;
 pub mod cycle_config_handler;
 pub mod cycle_handler;
+pub mod data_handler;
+pub mod diagnostics_handler;
+pub mod hotkey_handler;
 pub mod notification_handler;
 pub mod onboarding_handler;
 pub mod stats_handler;
 pub mod strict_mode_handler;
 pub mod telemetry_handler;
 pub mod work_schedule_handler;
+
+use crate::errors::AppError;
+
+/// Token destructive commands expect in their `confirm` argument.
+pub const DESTRUCTIVE_ACTION_CONFIRMATION: &str = "CONFIRM";
+
+/// Reject a destructive command unless the caller passed the confirmation token.
+pub fn require_confirmation(confirm: &str, action: &str) -> Result<(), String> {
+    if confirm == DESTRUCTIVE_ACTION_CONFIRMATION {
+        Ok(())
+    } else {
+        let error = AppError::ConfirmationRequired(format!(
+            "{} requires confirm = \"{}\"",
+            action, DESTRUCTIVE_ACTION_CONFIRMATION
+        ));
+        println!("❌ [Rust] {}", error);
+        Err(error.to_string())
+    }
+}
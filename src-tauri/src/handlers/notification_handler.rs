@@ -1,4 +1,7 @@
+use crate::database::models::NotificationHistory;
 use crate::state::AppState;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::params;
 use tauri::State;
 
 /// Update the user name for personalized notifications
@@ -28,3 +31,140 @@ pub async fn get_notification_user_name(
     let notification_service = state.notification_service.lock().await;
     Ok(notification_service.get_user_name().map(|s| s.to_string()))
 }
+
+/// Get the notifications fired during this run, newest first
+#[tauri::command]
+pub async fn get_notification_history(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<NotificationHistory>, String> {
+    println!(
+        "📖 [Rust] get_notification_history called (limit: {:?})",
+        limit
+    );
+
+    let notification_service = state.notification_service.lock().await;
+    let mut history = notification_service.get_history();
+    if let Some(limit) = limit {
+        history.truncate(limit);
+    }
+
+    Ok(history)
+}
+
+/// Clear the in-memory notification history
+#[tauri::command]
+pub async fn clear_notification_history(
+    confirm: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!("🗑️ [Rust] clear_notification_history called");
+
+    super::require_confirmation(&confirm, "clear_notification_history")?;
+
+    let mut notification_service = state.notification_service.lock().await;
+    notification_service.clear_history();
+
+    println!("✅ [Rust] Notification history cleared");
+
+    Ok(())
+}
+
+/// Set how many notifications the history keeps
+#[tauri::command]
+pub async fn set_notification_history_capacity(
+    capacity: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "💾 [Rust] set_notification_history_capacity called: {}",
+        capacity
+    );
+
+    let mut notification_service = state.notification_service.lock().await;
+    notification_service.set_history_capacity(capacity)?;
+
+    println!("✅ [Rust] Notification history capacity updated");
+
+    Ok(())
+}
+
+/// Get how many notifications the history keeps
+#[tauri::command]
+pub async fn get_notification_history_capacity(
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let notification_service = state.notification_service.lock().await;
+    Ok(notification_service.get_history_capacity())
+}
+
+/// Longest allowed ad-hoc mute, in minutes
+const MAX_MUTE_MINUTES: u32 = 24 * 60;
+
+/// Save the mute end so it survives a restart
+fn save_muted_until(
+    state: &State<'_, AppState>,
+    until: Option<DateTime<Utc>>,
+) -> Result<(), String> {
+    state
+        .database
+        .with_connection(|conn| {
+            conn.execute(
+                "UPDATE user_settings SET notifications_muted_until = ?1, updated_at = ?2 WHERE id = 1",
+                params![until, Utc::now()],
+            )
+            .map_err(crate::database::DatabaseError::Sqlite)
+        })
+        .map(|_| ())
+        .map_err(|e| format!("Failed to save notification mute: {}", e))
+}
+
+/// Mute all non-critical notifications for the next `minutes`.
+/// Unlike quiet hours this is a one-off; it returns when the mute ends.
+#[tauri::command]
+pub async fn mute_notifications(
+    minutes: u32,
+    state: State<'_, AppState>,
+) -> Result<DateTime<Utc>, String> {
+    println!("🔕 [Rust] mute_notifications called: {} minutes", minutes);
+
+    if minutes == 0 || minutes > MAX_MUTE_MINUTES {
+        let error_msg = format!("Mute must be between 1 and {} minutes", MAX_MUTE_MINUTES);
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let until = Utc::now() + Duration::minutes(minutes as i64);
+    save_muted_until(&state, Some(until))?;
+
+    let mut notification_service = state.notification_service.lock().await;
+    notification_service.set_muted_until(Some(until));
+
+    println!("✅ [Rust] Notifications muted until {}", until);
+
+    Ok(until)
+}
+
+/// End an ad-hoc notification mute early
+#[tauri::command]
+pub async fn unmute_notifications(state: State<'_, AppState>) -> Result<(), String> {
+    println!("🔔 [Rust] unmute_notifications called");
+
+    save_muted_until(&state, None)?;
+
+    let mut notification_service = state.notification_service.lock().await;
+    notification_service.set_muted_until(None);
+
+    println!("✅ [Rust] Notifications unmuted");
+
+    Ok(())
+}
+
+/// When the current notification mute ends, or `None` if not muted
+#[tauri::command]
+pub async fn get_notification_mute(
+    state: State<'_, AppState>,
+) -> Result<Option<DateTime<Utc>>, String> {
+    let notification_service = state.notification_service.lock().await;
+    Ok(notification_service.get_muted_until())
+}
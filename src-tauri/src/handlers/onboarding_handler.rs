@@ -1,9 +1,16 @@
+use crate::api_models::UserSettings as ApiUserSettings;
+use crate::database::models::UserSettings;
+use crate::handlers::strict_mode_handler::{
+    resolve_strict_mode_request, spawn_pending_strict_disable,
+};
 use crate::onboarding::{
     create_post_onboarding_backup, create_pre_onboarding_backup, validate_step_data,
     OnboardingManager, OnboardingStep, OnboardingValidator,
 };
+use crate::strict_mode::Shortcut;
+use chrono::{DateTime, Utc};
 use std::sync::Mutex;
-use tauri::{Manager, State};
+use tauri::State;
 
 #[tauri::command]
 pub async fn start_onboarding(
@@ -129,9 +136,28 @@ pub async fn complete_onboarding(
         final_config
     );
 
+    finish_onboarding(&final_config, &onboarding_state, &app_state)?;
+
+    println!("✅ [Rust] Onboarding completed and saved to database successfully");
+
+    // The focus session itself starts once the configuration is applied to
+    // the settings, so it doesn't run with the pre-onboarding durations
+    if start_focus_after_onboarding(&final_config) {
+        println!("🎯 [Rust] Focus will start when the onboarding settings are applied");
+    }
+
+    Ok(())
+}
+
+/// Validate, back up and persist a finished onboarding configuration
+fn finish_onboarding(
+    final_config: &serde_json::Value,
+    onboarding_state: &Mutex<OnboardingManager>,
+    app_state: &crate::state::AppState,
+) -> Result<(), String> {
     // Comprehensive validation of final configuration
     let mut validator = OnboardingValidator::new();
-    if let Err(validation_errors) = validator.validate_configuration(&final_config) {
+    if let Err(validation_errors) = validator.validate_configuration(final_config) {
         let error_messages: Vec<String> = validation_errors.iter().map(|e| e.to_string()).collect();
         let error_msg = format!(
             "Configuration validation failed: {}",
@@ -142,15 +168,14 @@ pub async fn complete_onboarding(
     }
 
     // Create backup before completing onboarding
-    if let Ok(app_data_dir) = app_state.app_handle.path().app_data_dir() {
-        match create_pre_onboarding_backup(&app_state.database, &app_data_dir) {
-            Ok(backup_id) => {
-                println!("✅ [Rust] Pre-onboarding backup created: {}", backup_id);
-            }
-            Err(e) => {
-                println!("⚠️ [Rust] Failed to create pre-onboarding backup: {}", e);
-                // Continue anyway - backup failure shouldn't block onboarding
-            }
+    let app_data_dir = app_state.database.data_directory();
+    match create_pre_onboarding_backup(&app_state.database, &app_data_dir) {
+        Ok(backup_id) => {
+            println!("✅ [Rust] Pre-onboarding backup created: {}", backup_id);
+        }
+        Err(e) => {
+            println!("⚠️ [Rust] Failed to create pre-onboarding backup: {}", e);
+            // Continue anyway - backup failure shouldn't block onboarding
         }
     }
 
@@ -162,13 +187,13 @@ pub async fn complete_onboarding(
     // Store the final configuration
     manager.set_step_data(OnboardingStep::Complete, final_config.clone())?;
 
-    // Mark as complete
-    if !manager.is_complete() {
-        manager.next_step()?; // This should set is_complete to true
+    // Mark as complete (quick start may skip straight here from any step)
+    while !manager.is_complete() {
+        manager.next_step()?;
     }
 
     // Save onboarding completion to database
-    let config_json = serde_json::to_string(&final_config).map_err(|e| {
+    let config_json = serde_json::to_string(final_config).map_err(|e| {
         let error_msg = format!("Failed to serialize config: {}", e);
         println!("❌ [Rust] {}", error_msg);
         error_msg
@@ -184,20 +209,17 @@ pub async fn complete_onboarding(
         })?;
 
     // Create backup after completing onboarding
-    if let Ok(app_data_dir) = app_state.app_handle.path().app_data_dir() {
-        match create_post_onboarding_backup(&app_state.database, &app_data_dir) {
-            Ok(backup_id) => {
-                println!("✅ [Rust] Post-onboarding backup created: {}", backup_id);
-            }
-            Err(e) => {
-                println!("⚠️ [Rust] Failed to create post-onboarding backup: {}", e);
-                // Continue anyway - backup failure shouldn't block onboarding
-            }
+    let app_data_dir = app_state.database.data_directory();
+    match create_post_onboarding_backup(&app_state.database, &app_data_dir) {
+        Ok(backup_id) => {
+            println!("✅ [Rust] Post-onboarding backup created: {}", backup_id);
+        }
+        Err(e) => {
+            println!("⚠️ [Rust] Failed to create post-onboarding backup: {}", e);
+            // Continue anyway - backup failure shouldn't block onboarding
         }
     }
 
-    println!("✅ [Rust] Onboarding completed and saved to database successfully");
-
     Ok(())
 }
 
@@ -263,11 +285,14 @@ pub async fn is_first_launch(app_state: State<'_, crate::state::AppState>) -> Re
 
 #[tauri::command]
 pub async fn reset_onboarding_for_testing(
+    confirm: String,
     onboarding_state: State<'_, Mutex<OnboardingManager>>,
     app_state: State<'_, crate::state::AppState>,
 ) -> Result<(), String> {
     println!("🔄 [Rust] reset_onboarding_for_testing called");
 
+    super::require_confirmation(&confirm, "reset_onboarding_for_testing")?;
+
     // Reset the in-memory onboarding manager
     let mut manager = onboarding_state.lock().map_err(|e| {
         println!("❌ [Rust] Error acquiring onboarding manager lock: {}", e);
@@ -304,45 +329,6 @@ pub async fn apply_onboarding_config_to_settings(
         config
     );
 
-    // Parse the onboarding configuration
-    let focus_duration = config
-        .get("focusDuration")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(25) as i32
-        * 60; // Convert minutes to seconds
-
-    let break_duration = config
-        .get("breakDuration")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(5) as i32
-        * 60; // Convert minutes to seconds
-
-    let long_break_duration = config
-        .get("longBreakDuration")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(15) as i32
-        * 60; // Convert minutes to seconds
-
-    let cycles_per_long_break = config
-        .get("cyclesPerLongBreak")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(4) as i32;
-
-    let strict_mode = config
-        .get("strictMode")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-
-    let user_name = config
-        .get("userName")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    let emergency_key = config
-        .get("emergencyKey")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
     // Get existing user settings or create default
     let mut user_settings = app_state
         .database
@@ -353,16 +339,9 @@ pub async fn apply_onboarding_config_to_settings(
             error_msg
         })?
         .unwrap_or_default();
+    let pending_disable = user_settings.strict_disable_at;
 
-    // Update settings with onboarding configuration
-    user_settings.focus_duration = focus_duration;
-    user_settings.short_break_duration = break_duration;
-    user_settings.long_break_duration = long_break_duration;
-    user_settings.cycles_per_long_break_v2 = cycles_per_long_break;
-    user_settings.strict_mode = strict_mode;
-    user_settings.user_name = user_name;
-    user_settings.emergency_key_combination = emergency_key;
-    user_settings.updated_at = chrono::Utc::now();
+    apply_onboarding_config(&mut user_settings, &config, Utc::now())?;
 
     // Save updated settings
     app_state
@@ -374,8 +353,18 @@ pub async fn apply_onboarding_config_to_settings(
             error_msg
         })?;
 
+    if let Some(at) = user_settings
+        .strict_disable_at
+        .filter(|_| pending_disable.is_none())
+    {
+        println!("⏳ [Rust] Strict mode will turn off at {}", at);
+        spawn_pending_strict_disable(app_state.app_handle.clone(), at);
+    }
+
     println!("✅ [Rust] Onboarding configuration applied to user settings successfully");
 
+    let start_focus = start_focus_after_onboarding(&config);
+
     // Also save work schedule if provided
     if let Some(work_schedule_config) = config.get("workSchedule") {
         let use_work_schedule = work_schedule_config
@@ -416,9 +405,153 @@ pub async fn apply_onboarding_config_to_settings(
         println!("✅ [Rust] Work schedule configuration applied successfully");
     }
 
+    // Settings and schedule are saved, so the session runs with the new configuration
+    if start_focus {
+        start_first_focus_session(&app_state).await?;
+    }
+
     Ok(())
 }
 
+/// Apply an onboarding configuration to `user_settings` and validate the
+/// result. Nothing is saved here.
+fn apply_onboarding_config(
+    user_settings: &mut UserSettings,
+    config: &serde_json::Value,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    // Anything the configuration leaves out falls back to the settings defaults
+    let defaults = UserSettings::default();
+
+    // Parse the onboarding configuration
+    let minutes_to_seconds = |key: &str, default: i32| {
+        config
+            .get(key)
+            .and_then(|v| v.as_u64())
+            .map(|minutes| minutes as i32 * 60)
+            .unwrap_or(default)
+    };
+
+    let focus_duration = minutes_to_seconds("focusDuration", defaults.focus_duration);
+    let break_duration = minutes_to_seconds("breakDuration", defaults.short_break_duration);
+    let long_break_duration = minutes_to_seconds("longBreakDuration", defaults.long_break_duration);
+
+    let cycles_per_long_break = config
+        .get("cyclesPerLongBreak")
+        .and_then(|v| v.as_u64())
+        .map(|cycles| cycles as i32)
+        .unwrap_or(defaults.cycles_per_long_break_v2);
+
+    let strict_mode = config
+        .get("strictMode")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(defaults.strict_mode);
+
+    let user_name = config
+        .get("userName")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let emergency_key = config
+        .get("emergencyKey")
+        .and_then(|v| v.as_str())
+        .map(|s| Shortcut::parse_emergency_key(s).map(|shortcut| shortcut.to_string()))
+        .transpose()?;
+
+    // Update settings with onboarding configuration
+    user_settings.focus_duration = focus_duration;
+    user_settings.short_break_duration = break_duration;
+    user_settings.long_break_duration = long_break_duration;
+    user_settings.cycles_per_long_break_v2 = cycles_per_long_break;
+    // Turning strict mode off goes through the disable delay like any other
+    // settings save; resubmitting it on leaves a pending disable alone
+    if !strict_mode || user_settings.strict_disable_at.is_none() {
+        (user_settings.strict_mode, user_settings.strict_disable_at) =
+            resolve_strict_mode_request(user_settings, strict_mode, now);
+    }
+    user_settings.user_name = user_name;
+    user_settings.emergency_key_combination = emergency_key;
+    user_settings.updated_at = now;
+
+    user_settings.validate_durations().map_err(|e| {
+        println!("❌ [Rust] {}", e);
+        String::from(e)
+    })?;
+
+    Ok(())
+}
+
+/// Whether the onboarding configuration asks to start focusing right away
+/// (`startFocusAfterOnboarding`, false when missing)
+fn start_focus_after_onboarding(config: &serde_json::Value) -> bool {
+    config
+        .get("startFocusAfterOnboarding")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Initialize the cycle orchestrator from the freshly applied settings and
+/// start a focus session
+async fn start_first_focus_session(
+    app_state: &State<'_, crate::state::AppState>,
+) -> Result<(), String> {
+    println!("🎯 [Rust] Starting focus session after onboarding");
+
+    crate::handlers::cycle_handler::initialize_cycle_orchestrator(
+        app_state.clone(),
+        app_state.app_handle.clone(),
+    )
+    .await?;
+
+    crate::handlers::cycle_handler::start_focus_session(
+        None,
+        app_state.clone(),
+        app_state.app_handle.clone(),
+    )
+    .await?;
+
+    println!("✅ [Rust] Focus session started after onboarding");
+    Ok(())
+}
+
+/// Skip the step-by-step onboarding: apply default settings (25/5/15, 4 cycles,
+/// no strict mode), mark onboarding complete and initialize the cycle orchestrator
+#[tauri::command]
+pub async fn quick_start(
+    user_name: Option<String>,
+    onboarding_state: State<'_, Mutex<OnboardingManager>>,
+    app_state: State<'_, crate::state::AppState>,
+) -> Result<crate::cycle_orchestrator::CycleState, String> {
+    println!("🚀 [Rust] quick_start called");
+
+    let defaults = ApiUserSettings::default();
+    let mut config = serde_json::json!({
+        "focusDuration": defaults.focus_duration,
+        "breakDuration": defaults.short_break_duration,
+        "longBreakDuration": defaults.long_break_duration,
+        "cyclesPerLongBreak": defaults.cycles_per_long_break,
+        "strictMode": defaults.strict_mode,
+        "quickStart": true,
+    });
+    if let Some(name) = user_name.filter(|name| !name.trim().is_empty()) {
+        config["userName"] = serde_json::Value::String(name);
+    }
+
+    finish_onboarding(&config, &onboarding_state, &app_state)?;
+
+    apply_onboarding_config_to_settings(config, app_state.clone()).await?;
+
+    let cycle_state = crate::handlers::cycle_handler::initialize_cycle_orchestrator(
+        app_state.clone(),
+        app_state.app_handle.clone(),
+    )
+    .await?;
+
+    println!("✅ [Rust] Quick start completed");
+
+    Ok(cycle_state)
+}
+
 #[tauri::command]
 pub async fn validate_onboarding_config(config: serde_json::Value) -> Result<(), String> {
     println!("🔍 [Rust] validate_onboarding_config called");
@@ -480,11 +613,7 @@ pub async fn create_configuration_backup(
         }
     };
 
-    let app_data_dir = app_state
-        .app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let app_data_dir = app_state.database.data_directory();
 
     let backup_manager = crate::onboarding::BackupManager::new(&app_data_dir)
         .map_err(|e| format!("Failed to create backup manager: {}", e))?;
@@ -508,11 +637,7 @@ pub async fn list_configuration_backups(
 ) -> Result<Vec<(String, serde_json::Value)>, String> {
     println!("📋 [Rust] list_configuration_backups called");
 
-    let app_data_dir = app_state
-        .app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let app_data_dir = app_state.database.data_directory();
 
     let backup_manager = crate::onboarding::BackupManager::new(&app_data_dir)
         .map_err(|e| format!("Failed to create backup manager: {}", e))?;
@@ -557,21 +682,37 @@ pub async fn restore_configuration_backup(
         backup_id
     );
 
-    let app_data_dir = app_state
-        .app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let app_data_dir = app_state.database.data_directory();
 
     let backup_manager = crate::onboarding::BackupManager::new(&app_data_dir)
         .map_err(|e| format!("Failed to create backup manager: {}", e))?;
 
+    let pending_disable = app_state
+        .database
+        .get_user_settings()
+        .ok()
+        .flatten()
+        .and_then(|settings| settings.strict_disable_at);
+
     match backup_manager.restore_backup(&backup_id, &app_state.database) {
         Ok(()) => {
             println!(
                 "✅ [Rust] Configuration restored from backup: {}",
                 backup_id
             );
+
+            // Restoring strict mode as off may have scheduled its disable
+            let restored_disable = app_state
+                .database
+                .get_user_settings()
+                .ok()
+                .flatten()
+                .and_then(|settings| settings.strict_disable_at);
+            if let Some(at) = restored_disable.filter(|_| pending_disable.is_none()) {
+                println!("⏳ [Rust] Strict mode will turn off at {}", at);
+                spawn_pending_strict_disable(app_state.app_handle.clone(), at);
+            }
+
             Ok(())
         }
         Err(e) => {
@@ -694,3 +835,31 @@ pub async fn force_database_migration(
     println!("✅ [Rust] Database migration completed successfully");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_onboarding_strict_mode_disable_waits_for_the_delay() {
+        let now = Utc::now();
+        let mut settings = UserSettings {
+            strict_mode: true,
+            strict_disable_delay_seconds: 300,
+            ..UserSettings::default()
+        };
+
+        apply_onboarding_config(
+            &mut settings,
+            &serde_json::json!({ "strictMode": false }),
+            now,
+        )
+        .unwrap();
+
+        assert!(settings.strict_mode);
+        assert_eq!(
+            settings.strict_disable_at,
+            Some(now + chrono::Duration::seconds(300))
+        );
+    }
+}
@@ -1,6 +1,15 @@
+use chrono::{DateTime, Local, Utc};
+use std::path::PathBuf;
 use tauri::State;
 
-use crate::api_models::SessionStats;
+use crate::api_models::{
+    AnalyticsExport, AverageDurations, BreakFocusRatio, BypassAttemptsByHour, ConsistencyStats,
+    DailyCycleCount, DailyProgress, Dashboard, FocusByWeekday, MoodTrends, PeriodComparison,
+    PeriodStats, SessionStats, SessionsToGoal, TagStats, TodaySessionCount, WeekGridDay,
+    WeeklyBudgetProgress, WorkHoursDay, ANALYTICS_EXPORT_VERSION,
+};
+use crate::database::models::{DailyGoal, PersonalRecords, StatsPeriod};
+use crate::database::{DatabaseError, DatabaseManager, DatabaseResult};
 use crate::state::AppState;
 
 /// Fetch focus session statistics for the given horizon (in days).
@@ -19,3 +28,569 @@ pub async fn get_session_stats(
 
     Ok(stats.into_iter().map(SessionStats::from).collect())
 }
+
+/// Mean and spread of daily focus minutes over the last `days` days.
+#[tauri::command]
+pub async fn get_consistency_stats(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<ConsistencyStats, String> {
+    let stats = state
+        .database
+        .get_session_stats(days)
+        .map_err(|error| format!("Failed to get session stats: {}", error))?;
+
+    let daily_minutes: Vec<u32> = stats.iter().map(|day| day.focus_minutes).collect();
+
+    Ok(ConsistencyStats::from_daily_minutes(days, &daily_minutes))
+}
+
+/// Fetch focus statistics for sessions carrying `tag` (or untagged sessions when `None`).
+#[tauri::command]
+pub async fn get_stats_by_tag(
+    tag: Option<String>,
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<TagStats, String> {
+    let tag = tag.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+
+    let stats = state
+        .database
+        .get_stats_by_tag(tag.as_deref(), days)
+        .map_err(|error| format!("Failed to get stats by tag: {}", error))?;
+
+    Ok(TagStats::from(stats))
+}
+
+/// Set or clear the tag on a recorded session.
+#[tauri::command]
+pub async fn set_session_tag(
+    session_id: String,
+    tag: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let tag = tag.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+
+    let updated = state
+        .database
+        .set_session_tag(&session_id, tag.as_deref())
+        .map_err(|error| format!("Failed to set session tag: {}", error))?;
+
+    if !updated {
+        return Err(format!("Session {} not found", session_id));
+    }
+
+    Ok(())
+}
+
+/// Valid session mood ratings
+const MOOD_RANGE: std::ops::RangeInclusive<u8> = 1..=5;
+
+/// Rate how a recorded session felt, from 1 to 5, or clear the rating with `None`.
+#[tauri::command]
+pub async fn set_session_mood(
+    session_id: String,
+    rating: Option<u8>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(rating) = rating {
+        if !MOOD_RANGE.contains(&rating) {
+            return Err(format!(
+                "Mood rating must be between {} and {}",
+                MOOD_RANGE.start(),
+                MOOD_RANGE.end()
+            ));
+        }
+    }
+
+    let updated = state
+        .database
+        .set_session_mood(&session_id, rating)
+        .map_err(|error| format!("Failed to set session mood: {}", error))?;
+
+    if !updated {
+        return Err(format!("Session {} not found", session_id));
+    }
+
+    Ok(())
+}
+
+/// Average session mood per local day over the last `days` days, and how
+/// mood relates to finishing the session
+#[tauri::command]
+pub async fn get_mood_trends(days: u32, state: State<'_, AppState>) -> Result<MoodTrends, String> {
+    let days = days.max(1);
+    let (since, _) = StatsPeriod::Day.bounds(days - 1, chrono::Local::now());
+
+    let rows = state
+        .database
+        .get_session_moods(since)
+        .map_err(|error| format!("Failed to get session moods: {}", error))?;
+
+    Ok(MoodTrends::from_rows(days, &rows))
+}
+
+/// Compare focus statistics for a period with the one before it.
+/// `offset` selects the period (0 = this day/week/month, 1 = the previous one, ...).
+#[tauri::command]
+pub async fn compare_periods(
+    period: StatsPeriod,
+    offset: u32,
+    state: State<'_, AppState>,
+) -> Result<PeriodComparison, String> {
+    let now = chrono::Local::now();
+    let fetch = |offset: u32| {
+        let (start, end) = period.bounds(offset, now);
+        state
+            .database
+            .get_period_stats(start, end)
+            .map(PeriodStats::from)
+            .map_err(|error| format!("Failed to get period stats: {}", error))
+    };
+
+    let current = fetch(offset)?;
+    let previous = fetch(offset + 1)?;
+
+    Ok(PeriodComparison::new(current, previous))
+}
+
+/// How many full focus sessions are still needed today to reach `goal_minutes`,
+/// at the configured focus duration, plus the minutes left over.
+#[tauri::command]
+pub async fn get_sessions_to_goal(
+    goal_minutes: u32,
+    state: State<'_, AppState>,
+) -> Result<SessionsToGoal, String> {
+    let (start, end) = StatsPeriod::Day.bounds(0, chrono::Local::now());
+    let today = state
+        .database
+        .get_period_stats(start, end)
+        .map_err(|error| format!("Failed to get today's stats: {}", error))?;
+
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|error| format!("Failed to get user settings: {}", error))?
+        .unwrap_or_default();
+    let focus_minutes = (settings.focus_duration / 60).max(0) as u32;
+
+    Ok(SessionsToGoal::new(
+        today.focus_minutes,
+        goal_minutes,
+        focus_minutes,
+    ))
+}
+
+/// Largest daily targets accepted by `set_daily_goal`
+const MAX_DAILY_GOAL_MINUTES: u32 = 24 * 60;
+const MAX_DAILY_GOAL_SESSIONS: u32 = 50;
+
+/// Set the daily focus goal from today on. Either target may be `None`,
+/// but not both.
+#[tauri::command]
+pub async fn set_daily_goal(
+    target_minutes: Option<u32>,
+    target_sessions: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<DailyGoal, String> {
+    println!(
+        "💾 [Rust] set_daily_goal called: {:?} minutes, {:?} sessions",
+        target_minutes, target_sessions
+    );
+
+    let invalid = match (target_minutes, target_sessions) {
+        (None, None) => Some("Set a minutes or sessions target".to_string()),
+        (Some(minutes), _) if minutes == 0 || minutes > MAX_DAILY_GOAL_MINUTES => Some(format!(
+            "Daily minutes target must be between 1 and {}",
+            MAX_DAILY_GOAL_MINUTES
+        )),
+        (_, Some(sessions)) if sessions == 0 || sessions > MAX_DAILY_GOAL_SESSIONS => {
+            Some(format!(
+                "Daily sessions target must be between 1 and {}",
+                MAX_DAILY_GOAL_SESSIONS
+            ))
+        }
+        _ => None,
+    };
+    if let Some(error_msg) = invalid {
+        println!("❌ [Rust] {}", error_msg);
+        return Err(error_msg);
+    }
+
+    let goal = state
+        .database
+        .set_daily_goal(Local::now().date_naive(), target_minutes, target_sessions)
+        .map_err(|error| format!("Failed to save daily goal: {}", error))?;
+
+    println!("✅ [Rust] Daily goal saved");
+    Ok(goal)
+}
+
+/// Completed focus minutes and sessions today, and how far along the daily
+/// goal they are
+#[tauri::command]
+pub async fn get_daily_progress(state: State<'_, AppState>) -> Result<DailyProgress, String> {
+    let now = Local::now();
+    let (start, end) = StatsPeriod::Day.bounds(0, now);
+
+    // Only completed focus sessions count
+    let today = state
+        .database
+        .get_period_stats(start, end)
+        .map_err(|error| format!("Failed to get today's stats: {}", error))?;
+    let goal = state
+        .database
+        .get_daily_goal(now.date_naive())
+        .map_err(|error| format!("Failed to get daily goal: {}", error))?;
+
+    Ok(DailyProgress::new(
+        now.date_naive(),
+        goal,
+        today.focus_minutes,
+        today.sessions_completed,
+    ))
+}
+
+/// Focus sessions started since local midnight, and how many completed
+#[tauri::command]
+pub async fn get_today_session_count(
+    state: State<'_, AppState>,
+) -> Result<TodaySessionCount, String> {
+    let (start, end) = StatsPeriod::Day.bounds(0, chrono::Local::now());
+    let (started, completed) = state
+        .database
+        .count_focus_sessions(start, end)
+        .map_err(|error| format!("Failed to count today's sessions: {}", error))?;
+
+    Ok(TodaySessionCount { started, completed })
+}
+
+/// Completed focus minutes this week against `budget_minutes`
+pub fn weekly_budget_progress(
+    database: &DatabaseManager,
+    budget_minutes: Option<u32>,
+) -> DatabaseResult<WeeklyBudgetProgress> {
+    let (start, end) = StatsPeriod::Week.bounds(0, chrono::Local::now());
+    let week = database.get_period_stats(start, end)?;
+
+    Ok(WeeklyBudgetProgress::new(
+        week.focus_minutes,
+        budget_minutes,
+        start,
+        end,
+    ))
+}
+
+/// Focus minutes used this week against the weekly focus budget
+#[tauri::command]
+pub async fn get_weekly_budget_progress(
+    state: State<'_, AppState>,
+) -> Result<WeeklyBudgetProgress, String> {
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|error| format!("Failed to get user settings: {}", error))?
+        .unwrap_or_default();
+    let budget_minutes = settings
+        .weekly_focus_budget_minutes
+        .map(|minutes| minutes.max(0) as u32);
+
+    weekly_budget_progress(&state.database, budget_minutes)
+        .map_err(|error| format!("Failed to get weekly budget progress: {}", error))
+}
+
+/// Completed cycles and focus sessions per local day over the last `days` days,
+/// oldest first. See `DailyCycleCount` for how a cycle is counted.
+#[tauri::command]
+pub async fn get_daily_cycle_counts(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<DailyCycleCount>, String> {
+    let days = days.max(1);
+    let now = chrono::Local::now();
+    let (start, _) = StatsPeriod::Day.bounds(days - 1, now);
+    let (_, end) = StatsPeriod::Day.bounds(0, now);
+
+    let sessions = state
+        .database
+        .get_sessions_in_range(start, end)
+        .map_err(|error| format!("Failed to get sessions: {}", error))?;
+
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|error| format!("Failed to get user settings: {}", error))?
+        .unwrap_or_default();
+
+    Ok(DailyCycleCount::from_sessions(
+        start.with_timezone(&chrono::Local).date_naive(),
+        days,
+        &sessions,
+        settings.cycles_per_long_break_v2.max(1) as u32,
+    ))
+}
+
+/// Completed focus minutes inside and outside work hours per local day over
+/// the last `days` days, oldest first
+#[tauri::command]
+pub async fn get_work_hours_timeseries(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorkHoursDay>, String> {
+    let days = days.max(1);
+    let now = chrono::Local::now();
+    let (start, _) = StatsPeriod::Day.bounds(days - 1, now);
+    let (_, end) = StatsPeriod::Day.bounds(0, now);
+
+    let sessions = state
+        .database
+        .get_sessions_in_range(start, end)
+        .map_err(|error| format!("Failed to get sessions: {}", error))?;
+
+    Ok(WorkHoursDay::from_sessions(
+        start.with_timezone(&chrono::Local).date_naive(),
+        days,
+        &sessions,
+    ))
+}
+
+/// Monday-to-Sunday grid of focus minutes, completed sessions and whether
+/// `goal_minutes` was reached, for the week `week_offset` weeks back
+/// (0 = this week)
+#[tauri::command]
+pub async fn get_week_grid(
+    week_offset: u32,
+    goal_minutes: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<WeekGridDay>, String> {
+    let (start, end) = StatsPeriod::Week.bounds(week_offset, Local::now());
+
+    let sessions = state
+        .database
+        .get_sessions_in_range(start, end)
+        .map_err(|error| format!("Failed to get sessions: {}", error))?;
+
+    Ok(WeekGridDay::week_from_sessions(
+        start.with_timezone(&Local).date_naive(),
+        &sessions,
+        goal_minutes,
+    ))
+}
+
+/// Completed focus minutes per local weekday over the last `weeks` weeks,
+/// Monday first.
+#[tauri::command]
+pub async fn get_focus_by_weekday(
+    weeks: u32,
+    state: State<'_, AppState>,
+) -> Result<FocusByWeekday, String> {
+    let weeks = weeks.max(1);
+    let now = chrono::Local::now();
+    let (start, _) = StatsPeriod::Day.bounds(weeks * 7 - 1, now);
+    let (_, end) = StatsPeriod::Day.bounds(0, now);
+
+    let sessions = state
+        .database
+        .get_sessions_in_range(start, end)
+        .map_err(|error| format!("Failed to get sessions: {}", error))?;
+
+    Ok(FocusByWeekday::from_sessions(weeks, &sessions))
+}
+
+/// Bypass attempts per local hour of the day over the last `days` days,
+/// to show when leaving a session is most tempting
+#[tauri::command]
+pub async fn get_bypass_attempts_by_hour(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<BypassAttemptsByHour, String> {
+    let days = days.max(1);
+    let (since, _) = StatsPeriod::Day.bounds(days - 1, chrono::Local::now());
+
+    let timestamps = state
+        .database
+        .get_bypass_attempt_timestamps(since)
+        .map_err(|error| format!("Failed to get bypass attempts: {}", error))?;
+
+    Ok(BypassAttemptsByHour::from_timestamps(
+        days,
+        since,
+        &timestamps,
+    ))
+}
+
+/// Average length of completed focus, short break and long break sessions
+/// over the last `days` days
+#[tauri::command]
+pub async fn get_average_durations(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<AverageDurations, String> {
+    let days = days.max(1);
+    let (since, _) = StatsPeriod::Day.bounds(days - 1, chrono::Local::now());
+
+    let rows = state
+        .database
+        .get_average_durations(since)
+        .map_err(|error| format!("Failed to get average durations: {}", error))?;
+
+    Ok(AverageDurations::from_rows(days, &rows))
+}
+
+/// Completed break minutes against completed focus minutes over the last
+/// `days` days, with short and long breaks kept apart
+#[tauri::command]
+pub async fn get_break_focus_ratio(
+    days: u32,
+    state: State<'_, AppState>,
+) -> Result<BreakFocusRatio, String> {
+    let days = days.max(1);
+    let (since, _) = StatsPeriod::Day.bounds(days - 1, chrono::Local::now());
+
+    let rows = state
+        .database
+        .get_average_durations(since)
+        .map_err(|error| format!("Failed to get session durations: {}", error))?;
+
+    Ok(BreakFocusRatio::from_rows(days, &rows))
+}
+
+/// Sessions listed under "recent" on the dashboard
+const DASHBOARD_RECENT_SESSIONS: u32 = 10;
+
+/// Streak, today's goal progress (when `goal_minutes` is given), completion
+/// rate, focus by hour, recent sessions and totals over the last `days` days,
+/// all read from the same database snapshot
+#[tauri::command]
+pub async fn get_dashboard(
+    days: u32,
+    goal_minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Dashboard, String> {
+    let days = days.max(1);
+    let now = chrono::Local::now();
+    let (start, _) = StatsPeriod::Day.bounds(days - 1, now);
+    let (_, end) = StatsPeriod::Day.bounds(0, now);
+
+    let snapshot = state
+        .database
+        .get_dashboard_snapshot(start, end, DASHBOARD_RECENT_SESSIONS)
+        .map_err(|error| format!("Failed to get dashboard: {}", error))?;
+
+    let settings = state
+        .database
+        .get_user_settings()
+        .map_err(|error| format!("Failed to get user settings: {}", error))?
+        .unwrap_or_default();
+    let focus_minutes = (settings.focus_duration / 60).max(0) as u32;
+
+    Ok(Dashboard::new(
+        days,
+        now.date_naive(),
+        goal_minutes,
+        focus_minutes,
+        snapshot,
+    ))
+}
+
+/// Personal bests: longest session, best day by minutes and by sessions,
+/// and the longest daily streak, each with the local date it happened
+#[tauri::command]
+pub async fn get_personal_records(state: State<'_, AppState>) -> Result<PersonalRecords, String> {
+    state
+        .database
+        .get_personal_records()
+        .map_err(|error| format!("Failed to get personal records: {}", error))
+}
+
+/// Write every session, daily stat, work hours split, personal record,
+/// bypass attempt and strict mode stretch between `start` and `end` to
+/// `path` as one JSON document. The file is replaced in one step, so a
+/// failed export never leaves a partial file behind.
+#[tauri::command]
+pub async fn export_analytics_json(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    println!(
+        "📤 [Rust] export_analytics_json called for {} - {} to {}",
+        start, end, path
+    );
+
+    let path = PathBuf::from(path);
+    if !path.is_absolute() {
+        return Err("Export path must be absolute".to_string());
+    }
+    if start >= end {
+        return Err("Export range start must be before its end".to_string());
+    }
+
+    let database = &state.database;
+    let to_error = |what: &str, error: DatabaseError| {
+        let error_msg = format!("Failed to export analytics: {}: {}", what, error);
+        println!("❌ [Rust] {}", error_msg);
+        error_msg
+    };
+
+    let sessions = database
+        .get_sessions_in_range(start, end)
+        .map_err(|e| to_error("sessions", e))?;
+    let settings = database
+        .get_user_settings()
+        .map_err(|e| to_error("settings", e))?
+        .unwrap_or_default();
+
+    let first_day = start.with_timezone(&Local).date_naive();
+    let last_day = (end - chrono::Duration::seconds(1))
+        .with_timezone(&Local)
+        .date_naive();
+    let days = ((last_day - first_day).num_days() + 1).max(1) as u32;
+
+    let export = AnalyticsExport {
+        format_version: ANALYTICS_EXPORT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        range_start: start,
+        range_end: end,
+        daily_cycles: DailyCycleCount::from_sessions(
+            first_day,
+            days,
+            &sessions,
+            settings.cycles_per_long_break_v2.max(1) as u32,
+        ),
+        work_hours: WorkHoursDay::from_sessions(first_day, days, &sessions),
+        personal_records: database
+            .get_personal_records()
+            .map_err(|e| to_error("personal records", e))?,
+        bypass_attempts: database
+            .get_bypass_attempts_in_range(start, end)
+            .map_err(|e| to_error("bypass attempts", e))?,
+        strict_mode_history: database
+            .get_strict_mode_history(start, end, Utc::now())
+            .map_err(|e| to_error("strict mode history", e))?,
+        sessions,
+    };
+
+    let raw = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize analytics export: {}", e))?;
+
+    // Write then rename so a crash never leaves a half-written export
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, raw)
+        .and_then(|_| std::fs::rename(&temp, &path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            let error_msg = format!("Failed to write analytics export: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            error_msg
+        })?;
+
+    println!(
+        "✅ [Rust] Exported {} sessions to {}",
+        export.sessions.len(),
+        path.display()
+    );
+    Ok(())
+}
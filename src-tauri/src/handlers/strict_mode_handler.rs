@@ -1,7 +1,15 @@
-use tauri::State;
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use uuid::Uuid;
 
+use crate::database::models::{StatsPeriod, StrictModeInterval, StrictModeUptime, UserSettings};
+use crate::hotkey_manager::HotkeyManager;
 use crate::state::AppState;
-use crate::strict_mode::StrictModeState;
+use crate::strict_mode::models::EmergencyHotkeyTest;
+use crate::strict_mode::orchestrator::StrictModeLockStatus;
+use crate::strict_mode::shortcut::{check_availability, ShortcutAvailability};
+use crate::strict_mode::{Shortcut, StrictModeOrchestrator, StrictModeState};
 
 /// Activate strict mode
 #[tauri::command]
@@ -29,18 +37,52 @@ pub async fn deactivate_strict_mode(app_state: State<'_, AppState>) -> Result<()
     }
 }
 
-/// Get the current strict mode state
+/// Get the current strict mode state, or an inactive state before the
+/// orchestrator is initialized
 #[tauri::command]
 pub async fn get_strict_mode_state(
     app_state: State<'_, AppState>,
 ) -> Result<StrictModeState, String> {
     let orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
 
-    if let Some(orchestrator) = orchestrator_guard.as_ref() {
-        Ok(orchestrator.get_state())
-    } else {
-        Err("StrictModeOrchestrator not initialized".to_string())
-    }
+    Ok(orchestrator_guard
+        .as_ref()
+        .map(|orchestrator| orchestrator.get_state())
+        .unwrap_or_default())
+}
+
+/// Whether the system lock manager has the system locked and which emergency
+/// hotkey it has registered. Reports unlocked before the orchestrator is
+/// initialized.
+#[tauri::command]
+pub async fn get_system_lock_status(
+    app_state: State<'_, AppState>,
+) -> Result<StrictModeLockStatus, String> {
+    let orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
+
+    let Some(orchestrator) = orchestrator_guard.as_ref() else {
+        return Ok(StrictModeLockStatus {
+            is_locked: false,
+            emergency_hotkey: None,
+        });
+    };
+
+    let lock_manager = orchestrator.get_system_lock_manager();
+    let lock_manager = lock_manager
+        .lock()
+        .map_err(|e| format!("Failed to lock system lock manager: {}", e))?;
+
+    Ok(StrictModeLockStatus {
+        is_locked: lock_manager.is_locked(),
+        emergency_hotkey: lock_manager.get_emergency_hotkey(),
+    })
+}
+
+/// Check the strict mode window the state expects and recreate it if it
+/// went missing or stopped responding. Returns whether anything was repaired.
+#[tauri::command]
+pub async fn repair_strict_mode_windows(app: AppHandle) -> Result<bool, String> {
+    crate::strict_mode::orchestrator::repair_windows(&app).await
 }
 
 /// Show menu bar popover
@@ -72,11 +114,10 @@ pub async fn hide_menu_bar_popover(app_state: State<'_, AppState>) -> Result<(),
 /// Stop break transition countdown
 #[tauri::command]
 pub async fn stop_break_transition_countdown(app_state: State<'_, AppState>) -> Result<(), String> {
-    let orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
+    let mut orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
 
-    if orchestrator_guard.is_some() {
-        // This will be handled by the frontend countdown logic
-        // Backend just needs to acknowledge the request
+    if let Some(orchestrator) = orchestrator_guard.as_mut() {
+        orchestrator.stop_transition_countdown();
         println!("⏸️ [StrictModeHandler] Break transition countdown stopped");
         Ok(())
     } else {
@@ -84,6 +125,18 @@ pub async fn stop_break_transition_countdown(app_state: State<'_, AppState>) ->
     }
 }
 
+/// Acknowledge a break transition that is waiting for the user
+#[tauri::command]
+pub async fn acknowledge_transition(app_state: State<'_, AppState>) -> Result<(), String> {
+    let mut orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
+
+    if let Some(orchestrator) = orchestrator_guard.as_mut() {
+        orchestrator.acknowledge_transition()
+    } else {
+        Err("StrictModeOrchestrator not initialized".to_string())
+    }
+}
+
 /// Start break from transition window
 #[tauri::command]
 pub async fn start_break_from_transition(app_state: State<'_, AppState>) -> Result<(), String> {
@@ -103,6 +156,11 @@ pub async fn emergency_exit_strict_mode(app_state: State<'_, AppState>) -> Resul
     let mut orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
 
     if let Some(orchestrator) = orchestrator_guard.as_mut() {
+        // Let the display sleep even if the focus phase keeps running
+        if let Ok(mut inhibitor) = app_state.display_sleep_inhibitor.lock() {
+            inhibitor.suspend();
+        }
+
         orchestrator.emergency_exit()?;
         Ok(())
     } else {
@@ -123,7 +181,8 @@ pub async fn hide_fullscreen_break_overlay(app_state: State<'_, AppState>) -> Re
     }
 }
 
-/// Register emergency hotkey combination
+/// Register emergency hotkey combination; weak or system shortcuts are
+/// rejected and the current hotkey stays registered
 #[tauri::command]
 pub async fn register_emergency_hotkey(
     combination: String,
@@ -132,7 +191,9 @@ pub async fn register_emergency_hotkey(
     let mut orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
 
     if let Some(orchestrator) = orchestrator_guard.as_mut() {
-        orchestrator.register_emergency_hotkey(combination)?;
+        orchestrator
+            .validate_and_register_emergency_hotkey(&combination)
+            .map_err(|e| e.to_string())?;
         Ok(())
     } else {
         Err("StrictModeOrchestrator not initialized".to_string())
@@ -151,3 +212,373 @@ pub async fn unregister_emergency_hotkey(app_state: State<'_, AppState>) -> Resu
         Err("StrictModeOrchestrator not initialized".to_string())
     }
 }
+
+/// How long `test_emergency_hotkey` listens for the combination
+const EMERGENCY_HOTKEY_TEST_SECONDS: u64 = 10;
+
+/// Register `combination` as a global shortcut for a few seconds, so it can
+/// be tried on the current keyboard layout before strict mode relies on it.
+/// Fails if the operating system or another app holds the combination.
+/// `emergency-hotkey-test` carries the running test, or null once it ends;
+/// a press emits `emergency-hotkey-pressed` and ends the test.
+#[tauri::command]
+pub async fn test_emergency_hotkey(
+    combination: String,
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+) -> Result<EmergencyHotkeyTest, String> {
+    println!("🔑 [Rust] test_emergency_hotkey called: {}", combination);
+
+    let combination = StrictModeOrchestrator::validate_emergency_hotkey(&combination)
+        .map_err(|e| e.to_string())?;
+    let global = combination.parse::<Shortcut>()?.to_global_shortcut()?;
+
+    // Free the shortcut held by a test still running
+    end_emergency_hotkey_test(&app, None, false).await;
+
+    if app.global_shortcut().is_registered(global) {
+        return Err(format!(
+            "'{}' is already used by another hotkey",
+            combination
+        ));
+    }
+
+    let test = EmergencyHotkeyTest {
+        id: Uuid::new_v4().to_string(),
+        combination,
+        expires_at: Utc::now() + chrono::Duration::seconds(EMERGENCY_HOTKEY_TEST_SECONDS as i64),
+    };
+
+    // Hold the state lock until the shortcut is registered, so a press
+    // always finds its test
+    let mut running = app_state.emergency_hotkey_test.lock().await;
+
+    let test_id = test.id.clone();
+    app.global_shortcut()
+        .on_shortcut(global, move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                let app = app.clone();
+                let test_id = test_id.clone();
+                tauri::async_runtime::spawn(async move {
+                    end_emergency_hotkey_test(&app, Some(&test_id), true).await;
+                });
+            }
+        })
+        .map_err(|e| {
+            format!(
+                "'{}' is taken by the system or another app: {}",
+                test.combination, e
+            )
+        })?;
+
+    *running = Some(test.clone());
+    drop(running);
+
+    if let Err(e) = app.emit("emergency-hotkey-test", Some(&test)) {
+        eprintln!("Failed to emit emergency-hotkey-test: {}", e);
+    }
+
+    let test_id = test.id.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(
+            EMERGENCY_HOTKEY_TEST_SECONDS,
+        ))
+        .await;
+
+        // Already pressed, or replaced by a newer test, if this ends nothing
+        if end_emergency_hotkey_test(&app, Some(&test_id), false).await {
+            println!("⏱️ [Rust] Emergency hotkey test ended without a press");
+        }
+    });
+
+    println!(
+        "✅ [Rust] Emergency hotkey test started: {}",
+        test.combination
+    );
+
+    Ok(test)
+}
+
+/// Report a key combination the frontend saw pressed while
+/// `test_emergency_hotkey` runs, for platforms that hand the press to the
+/// focused window instead of the global shortcut. Returns whether it
+/// matched; a match ends the test like a global press.
+#[tauri::command]
+pub async fn report_emergency_hotkey_press(
+    combination: String,
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+) -> Result<bool, String> {
+    // Partial presses (modifiers only) can't match anything
+    let Ok(pressed) = combination.parse::<Shortcut>() else {
+        return Ok(false);
+    };
+
+    let test_id = app_state
+        .emergency_hotkey_test
+        .lock()
+        .await
+        .as_ref()
+        .filter(|test| test.expires_at > Utc::now() && test.combination == pressed.to_string())
+        .map(|test| test.id.clone());
+
+    Ok(match test_id {
+        Some(test_id) => end_emergency_hotkey_test(&app, Some(&test_id), true).await,
+        None => false,
+    })
+}
+
+/// End the running emergency hotkey test, or only the one with `test_id`,
+/// unregistering its shortcut. Returns whether a test was ended.
+async fn end_emergency_hotkey_test(app: &AppHandle, test_id: Option<&str>, pressed: bool) -> bool {
+    let app_state = app.state::<AppState>();
+    let mut running = app_state.emergency_hotkey_test.lock().await;
+
+    if test_id.is_some_and(|id| running.as_ref().map(|test| test.id.as_str()) != Some(id)) {
+        return false;
+    }
+    let Some(test) = running.take() else {
+        return false;
+    };
+    drop(running);
+
+    match test
+        .combination
+        .parse::<Shortcut>()
+        .and_then(|shortcut| shortcut.to_global_shortcut())
+    {
+        Ok(global) => {
+            if let Err(e) = app.global_shortcut().unregister(global) {
+                eprintln!("Failed to unregister emergency hotkey test: {}", e);
+            }
+        }
+        Err(e) => eprintln!("Failed to unregister emergency hotkey test: {}", e),
+    }
+
+    if pressed {
+        if let Err(e) = app.emit("emergency-hotkey-pressed", &test.combination) {
+            eprintln!("Failed to emit emergency-hotkey-pressed: {}", e);
+        }
+        println!(
+            "✅ [Rust] Emergency hotkey test passed: {}",
+            test.combination
+        );
+    }
+    if let Err(e) = app.emit("emergency-hotkey-test", None::<EmergencyHotkeyTest>) {
+        eprintln!("Failed to emit emergency-hotkey-test: {}", e);
+    }
+
+    true
+}
+
+/// Check a candidate shortcut before saving it: whether it parses, clashes
+/// with the emergency key or one of Pausa's hotkeys, or is taken by the
+/// operating system or another app
+#[tauri::command]
+pub async fn is_shortcut_available(
+    shortcut: String,
+    app: AppHandle,
+    app_state: State<'_, AppState>,
+    hotkey_manager: State<'_, HotkeyManager>,
+) -> Result<ShortcutAvailability, String> {
+    let emergency_key = match app_state.strict_mode_orchestrator.lock().await.as_ref() {
+        Some(orchestrator) => orchestrator.get_config().emergency_key_combination,
+        None => app_state
+            .database
+            .get_user_settings()
+            .map_err(|e| format!("Failed to get user settings: {}", e))?
+            .and_then(|settings| settings.emergency_key_combination),
+    };
+
+    let registered: Vec<(&str, Shortcut)> = emergency_key
+        .and_then(|combination| combination.parse().ok())
+        .map(|shortcut| ("emergency_exit", shortcut))
+        .into_iter()
+        .collect();
+
+    let availability = check_availability(&shortcut, &registered);
+    let ShortcutAvailability::Available {
+        shortcut: normalized,
+    } = &availability
+    else {
+        return Ok(availability);
+    };
+
+    let global = match shortcut.parse::<Shortcut>()?.to_global_shortcut() {
+        Ok(global) => global,
+        Err(reason) => return Ok(ShortcutAvailability::Invalid { reason }),
+    };
+
+    let hotkeys = hotkey_manager.get_hotkey_configs();
+    if let Some(config) = hotkeys
+        .values()
+        .find(|config| config.enabled && config.shortcut == global)
+    {
+        return Ok(ShortcutAvailability::InternalConflict {
+            shortcut: normalized.clone(),
+            action: config.action.name(),
+        });
+    }
+
+    // Only the OS knows what other apps hold: try to take the shortcut and
+    // give it straight back
+    let global_shortcut = app.global_shortcut();
+    if let Err(e) = global_shortcut.register(global) {
+        println!(
+            "⚠️ [Rust] Shortcut {} couldn't be registered: {}",
+            normalized, e
+        );
+        return Ok(ShortcutAvailability::SystemConflict {
+            shortcut: normalized.clone(),
+        });
+    }
+    if let Err(e) = global_shortcut.unregister(global) {
+        eprintln!(
+            "⚠️ [Rust] Failed to release probed shortcut {}: {}",
+            normalized, e
+        );
+    }
+
+    Ok(availability)
+}
+
+/// Time strict mode was on over the last `days` local days, with the
+/// number of emergency exits in that window
+#[tauri::command]
+pub async fn get_strict_mode_uptime(
+    days: u32,
+    app_state: State<'_, AppState>,
+) -> Result<StrictModeUptime, String> {
+    println!("📖 [Rust] get_strict_mode_uptime called for {} days", days);
+
+    let now = chrono::Local::now();
+    let (start, _) = StatsPeriod::Day.bounds(days.max(1) - 1, now);
+    let (_, end) = StatsPeriod::Day.bounds(0, now);
+
+    app_state
+        .database
+        .get_strict_mode_uptime(start, end, now.with_timezone(&chrono::Utc))
+        .map_err(|e| {
+            let error_msg = format!("Failed to get strict mode uptime: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            error_msg
+        })
+}
+
+/// Each strict mode stretch over the last `days` local days, oldest first,
+/// with how it ended and the bypass attempts made during it
+#[tauri::command]
+pub async fn get_strict_mode_history(
+    days: u32,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<StrictModeInterval>, String> {
+    println!("📖 [Rust] get_strict_mode_history called for {} days", days);
+
+    let now = chrono::Local::now();
+    let (start, _) = StatsPeriod::Day.bounds(days.max(1) - 1, now);
+    let (_, end) = StatsPeriod::Day.bounds(0, now);
+
+    app_state
+        .database
+        .get_strict_mode_history(start, end, now.with_timezone(&chrono::Utc))
+        .map_err(|e| {
+            let error_msg = format!("Failed to get strict mode history: {}", e);
+            println!("❌ [Rust] {}", error_msg);
+            error_msg
+        })
+}
+
+/// How often a pending strict mode disable re-reads the wall clock, so
+/// time the machine spent asleep still counts towards the delay
+const STRICT_DISABLE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// What turning strict mode on or off in settings stores right now: the
+/// `strict_mode` value and when a pending disable takes effect. Turning it
+/// off with a delay keeps it on and schedules the disable (keeping an
+/// earlier schedule); turning it on cancels any pending disable.
+pub fn resolve_strict_mode_request(
+    settings: &UserSettings,
+    enabled: bool,
+    now: DateTime<Utc>,
+) -> (bool, Option<DateTime<Utc>>) {
+    if enabled || !settings.strict_mode || settings.strict_disable_delay_seconds <= 0 {
+        return (enabled, None);
+    }
+
+    let at = settings.strict_disable_at.unwrap_or_else(|| {
+        now + chrono::Duration::seconds(settings.strict_disable_delay_seconds as i64)
+    });
+    (true, Some(at))
+}
+
+/// Re-arm a strict mode disable that was pending at the last quit
+pub fn restore_pending_strict_disable(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let pending = state
+        .database
+        .get_user_settings()
+        .ok()
+        .flatten()
+        .and_then(|settings| settings.strict_disable_at);
+
+    if let Some(at) = pending {
+        println!(
+            "⏳ [Rust] Restoring strict mode disable pending until {}",
+            at
+        );
+        spawn_pending_strict_disable(app.clone(), at);
+    }
+}
+
+/// Wait until `at`, then turn strict mode off in settings and deactivate it,
+/// unless the disable was cancelled by turning strict mode back on
+pub fn spawn_pending_strict_disable(app: AppHandle, at: DateTime<Utc>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let remaining = (at - Utc::now()).to_std().unwrap_or_default();
+            if remaining.is_zero() {
+                break;
+            }
+            tokio::time::sleep(remaining.min(STRICT_DISABLE_POLL_INTERVAL)).await;
+        }
+
+        let state = app.state::<AppState>();
+
+        // Only applies if this exact disable is still pending
+        let applied = state.database.with_connection(|conn| {
+            conn.execute(
+                "UPDATE user_settings SET strict_mode = 0, strict_disable_at = NULL, updated_at = ?1
+                 WHERE id = 1 AND strict_disable_at = ?2",
+                rusqlite::params![Utc::now(), at],
+            )
+            .map_err(crate::database::DatabaseError::Sqlite)
+        });
+
+        match applied {
+            Ok(0) => return,
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!(
+                    "❌ [Rust] Failed to apply pending strict mode disable: {}",
+                    e
+                );
+                return;
+            }
+        }
+
+        let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+        if let Some(orchestrator) = strict_mode_orchestrator.as_mut() {
+            if orchestrator.is_active() {
+                if let Err(e) = orchestrator.deactivate() {
+                    eprintln!("⚠️ [Rust] Failed to deactivate strict mode: {}", e);
+                }
+            }
+        }
+        drop(strict_mode_orchestrator);
+
+        println!("✅ [Rust] Pending strict mode disable applied");
+        if let Err(e) = app.emit("strict-mode-disabled", at) {
+            eprintln!("Failed to emit strict-mode-disabled: {}", e);
+        }
+    });
+}
@@ -1,4 +1,4 @@
-use crate::database::models::WorkSchedule;
+use crate::database::models::{WorkDayStatus, WorkHoursStatus, WorkSchedule};
 use crate::state::AppState;
 use chrono::{Local, NaiveTime, Utc};
 use rusqlite::params;
@@ -195,6 +195,35 @@ pub async fn is_within_work_hours(state: State<'_, AppState>) -> Result<bool, St
     Ok(within_hours)
 }
 
+/// Get the current work hours status plus when it next changes
+#[tauri::command]
+pub async fn is_within_work_hours_now(
+    state: State<'_, AppState>,
+) -> Result<WorkHoursStatus, String> {
+    println!("🕐 [Rust] is_within_work_hours_now called");
+
+    let work_schedule = get_work_schedule(state).await?;
+    let status = work_schedule.status_at(Local::now());
+
+    println!("✅ [Rust] Work hours status: {:?}", status);
+    Ok(status)
+}
+
+/// Whether today is a scheduled work day, plus its hours if it is; `None`
+/// while the schedule has no per-weekday settings to answer from
+#[tauri::command]
+pub async fn is_work_day_today(
+    state: State<'_, AppState>,
+) -> Result<Option<WorkDayStatus>, String> {
+    println!("📅 [Rust] is_work_day_today called");
+
+    let work_schedule = get_work_schedule(state).await?;
+    let status = work_schedule.work_day();
+
+    println!("✅ [Rust] Work day status: {:?}", status);
+    Ok(status)
+}
+
 #[tauri::command]
 pub async fn get_system_timezone_info() -> Result<String, String> {
     println!("🌍 [Rust] get_system_timezone_info called");
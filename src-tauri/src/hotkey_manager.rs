@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use tauri::AppHandle;
-// use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
 
-use crate::api_models::BreakType;
-use crate::state_manager::{AppState, StateEvent, StateManager};
-use crate::window_manager::WindowManager;
+use crate::cycle_orchestrator::{CyclePhase, CycleState};
+use crate::database::models::StoredHotkeyConfig;
+use crate::handlers::{cycle_handler, strict_mode_handler};
+use crate::state::AppState;
+use crate::window_manager::{WindowManager, WindowType};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum HotkeyAction {
@@ -14,9 +16,19 @@ pub enum HotkeyAction {
     ToggleFocusSession,
     ImmediateLock,
     EmergencyExit,
+    PauseEverything,
 }
 
 impl HotkeyAction {
+    /// Every action, in the order their defaults are registered
+    pub const ALL: [HotkeyAction; 5] = [
+        HotkeyAction::ToggleCommandPalette,
+        HotkeyAction::ToggleFocusSession,
+        HotkeyAction::ImmediateLock,
+        HotkeyAction::EmergencyExit,
+        HotkeyAction::PauseEverything,
+    ];
+
     pub fn default_shortcut(&self) -> Shortcut {
         match self {
             HotkeyAction::ToggleCommandPalette => {
@@ -43,6 +55,12 @@ impl HotkeyAction {
                 #[cfg(not(target_os = "macos"))]
                 return Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyE);
             }
+            HotkeyAction::PauseEverything => {
+                #[cfg(target_os = "macos")]
+                return Shortcut::new(Some(Modifiers::META | Modifiers::SHIFT), Code::KeyH);
+                #[cfg(not(target_os = "macos"))]
+                return Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyH);
+            }
         }
     }
 
@@ -52,32 +70,88 @@ impl HotkeyAction {
             HotkeyAction::ToggleFocusSession => "Toggle Focus Session",
             HotkeyAction::ImmediateLock => "Immediate Lock/Break",
             HotkeyAction::EmergencyExit => "Emergency Exit from Strict Mode",
+            HotkeyAction::PauseEverything => "Pause Everything and Hide Windows",
         }
     }
+
+    /// Whether the action's hotkey is on before the user changes it. The
+    /// emergency exit is off: strict mode registers its own emergency key,
+    /// and the default shortcut is the in-window "end session" key.
+    pub fn enabled_by_default(&self) -> bool {
+        !matches!(self, HotkeyAction::EmergencyExit)
+    }
+
+    /// Name the action is saved and exported under, e.g. "ToggleFocusSession"
+    pub fn name(&self) -> String {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::String(name)) => name,
+            _ => format!("{:?}", self),
+        }
+    }
+
+    /// Action saved or exported under `name`, if this version knows it
+    pub fn from_name(name: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HotkeyConfig {
     pub action: HotkeyAction,
     pub shortcut: Shortcut,
     pub enabled: bool,
 }
 
+/// One hotkey as written to an exported configuration file, with the
+/// shortcut in its normalized string form (e.g. "shift+super+KeyF")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotkeyExportEntry {
+    pub shortcut: String,
+    pub enabled: bool,
+}
+
+/// Outcome of a hotkey action, also emitted to the frontend as `hotkey-triggered`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotkeyEventResult {
     pub action: HotkeyAction,
     pub success: bool,
     pub message: String,
-    pub state_changes: Vec<StateEvent>,
+    /// Cycle state after the action, when it changed the cycle
+    pub cycle_state: Option<CycleState>,
+}
+
+impl HotkeyEventResult {
+    fn succeeded(
+        action: HotkeyAction,
+        message: impl Into<String>,
+        cycle_state: Option<CycleState>,
+    ) -> Self {
+        Self {
+            action,
+            success: true,
+            message: message.into(),
+            cycle_state,
+        }
+    }
+
+    fn failed(action: HotkeyAction, message: impl Into<String>) -> Self {
+        Self {
+            action,
+            success: false,
+            message: message.into(),
+            cycle_state: None,
+        }
+    }
 }
 
 impl HotkeyConfig {
     pub fn new(action: HotkeyAction) -> Self {
         let shortcut = action.default_shortcut();
+        let enabled = action.enabled_by_default();
         Self {
             action,
             shortcut,
-            enabled: true,
+            enabled,
         }
     }
 
@@ -88,121 +162,163 @@ impl HotkeyConfig {
             enabled: true,
         }
     }
+
+    /// Row saved to the `hotkey_configs` table for this hotkey
+    pub fn to_stored(&self) -> StoredHotkeyConfig {
+        StoredHotkeyConfig {
+            action: self.action.name(),
+            shortcut: self.shortcut.into_string(),
+            enabled: self.enabled,
+        }
+    }
+
+    /// Hotkey saved as `stored`; fails if this version doesn't know the
+    /// action or the shortcut no longer parses
+    pub fn from_stored(stored: &StoredHotkeyConfig) -> Result<Self, String> {
+        let action = HotkeyAction::from_name(&stored.action)
+            .ok_or_else(|| format!("Unknown hotkey action {}", stored.action))?;
+        let shortcut = stored
+            .shortcut
+            .parse()
+            .map_err(|e| format!("Invalid shortcut '{}': {}", stored.shortcut, e))?;
+
+        Ok(Self {
+            action,
+            shortcut,
+            enabled: stored.enabled,
+        })
+    }
 }
 
 pub struct HotkeyManager {
     app_handle: AppHandle,
     registered_hotkeys: Arc<Mutex<HashMap<HotkeyAction, HotkeyConfig>>>,
-    state_manager: Arc<Mutex<StateManager>>,
     window_manager: Arc<Mutex<WindowManager>>,
 }
 
 impl HotkeyManager {
-    pub fn new(
-        app_handle: AppHandle,
-        state_manager: Arc<Mutex<StateManager>>,
-        window_manager: Arc<Mutex<WindowManager>>,
-    ) -> Self {
+    pub fn new(app_handle: AppHandle) -> Self {
+        let window_manager = WindowManager::new(app_handle.clone());
         Self {
             app_handle,
             registered_hotkeys: Arc::new(Mutex::new(HashMap::new())),
-            state_manager,
-            window_manager,
+            window_manager: Arc::new(Mutex::new(window_manager)),
         }
     }
 
-    /// Initialize the hotkey manager with default hotkeys
-    pub fn initialize(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let default_actions = vec![
-            HotkeyAction::ToggleCommandPalette,
-            HotkeyAction::ToggleFocusSession,
-            HotkeyAction::ImmediateLock,
-            HotkeyAction::EmergencyExit,
-        ];
+    /// Initialize the hotkey manager with default hotkeys, then apply the
+    /// custom ones saved in the database over them
+    pub fn initialize(&self) {
+        self.register_defaults();
+
+        // Keep the defaults if the saved hotkeys can't be read
+        if let Err(e) = self.load_custom_hotkeys() {
+            eprintln!("Warning: Failed to load custom hotkeys: {}", e);
+        }
+    }
 
-        for action in default_actions {
+    /// Register every action's default shortcut. One the OS refuses (e.g.
+    /// already bound system-wide) is kept switched off, so it can be rebound.
+    fn register_defaults(&self) {
+        for action in HotkeyAction::ALL {
             let config = HotkeyConfig::new(action.clone());
-            self.register_hotkey(config)?;
+            if let Err(e) = self.register_hotkey(config.clone()) {
+                eprintln!(
+                    "Warning: Failed to register default hotkey for {:?}: {}",
+                    action, e
+                );
+                self.store_config(HotkeyConfig {
+                    enabled: false,
+                    ..config
+                });
+            }
         }
 
         println!("Hotkey manager initialized with default hotkeys");
-        Ok(())
     }
 
-    /// Register a hotkey with the system
+    /// Register a hotkey with the system, replacing the action's current
+    /// shortcut. A disabled hotkey is only recorded, so it can be turned on later.
     pub fn register_hotkey(&self, config: HotkeyConfig) -> Result<(), Box<dyn std::error::Error>> {
-        if !config.enabled {
-            return Ok(());
-        }
-
         // Unregister existing hotkey if it exists
-        if let Ok(hotkeys) = self.registered_hotkeys.lock() {
-            if let Some(existing_config) = hotkeys.get(&config.action) {
-                if let Err(e) = self
-                    .app_handle
-                    .global_shortcut()
-                    .unregister(existing_config.shortcut.clone())
-                {
-                    eprintln!("Warning: Failed to unregister existing hotkey: {}", e);
-                }
+        let existing = self.config_for(&config.action);
+        if let Some(existing_config) = existing.as_ref().filter(|existing| existing.enabled) {
+            if let Err(e) = self
+                .app_handle
+                .global_shortcut()
+                .unregister(existing_config.shortcut)
+            {
+                eprintln!("Warning: Failed to unregister existing hotkey: {}", e);
             }
         }
 
-        // Create closure for hotkey handler
-        let action = config.action.clone();
-        let state_manager = Arc::clone(&self.state_manager);
-        let window_manager = Arc::clone(&self.window_manager);
-
-        // Register the new hotkey
-        self.app_handle.global_shortcut().on_shortcut(
-            config.shortcut.clone(),
-            move |_app, _shortcut, event| {
-                if event.state == ShortcutState::Pressed {
-                    if let Err(e) = Self::handle_hotkey_event(
-                        action.clone(),
-                        Arc::clone(&state_manager),
-                        Arc::clone(&window_manager),
-                    ) {
-                        eprintln!("Error handling hotkey event: {}", e);
+        if config.enabled {
+            let action = config.action.clone();
+            let registered = self.app_handle.global_shortcut().on_shortcut(
+                config.shortcut,
+                move |app, _shortcut, event| {
+                    if event.state == ShortcutState::Pressed {
+                        spawn_hotkey_action(app.clone(), action.clone());
                     }
+                },
+            );
+
+            if let Err(e) = registered {
+                // The old shortcut is already gone, so the action is unbound now
+                if let Some(existing_config) = existing {
+                    self.store_config(HotkeyConfig {
+                        enabled: false,
+                        ..existing_config
+                    });
                 }
-            },
-        )?;
-
-        // Store the configuration
-        if let Ok(mut hotkeys) = self.registered_hotkeys.lock() {
-            hotkeys.insert(config.action.clone(), config.clone());
+                return Err(e.into());
+            }
         }
 
+        self.store_config(config.clone());
+
         println!("Registered hotkey for action: {:?}", config.action);
         Ok(())
     }
 
+    fn config_for(&self, action: &HotkeyAction) -> Option<HotkeyConfig> {
+        self.registered_hotkeys
+            .lock()
+            .ok()
+            .and_then(|hotkeys| hotkeys.get(action).cloned())
+    }
+
+    fn store_config(&self, config: HotkeyConfig) {
+        if let Ok(mut hotkeys) = self.registered_hotkeys.lock() {
+            hotkeys.insert(config.action.clone(), config);
+        }
+    }
+
     /// Unregister a hotkey
     pub fn unregister_hotkey(
         &self,
         action: &HotkeyAction,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        if let Ok(mut hotkeys) = self.registered_hotkeys.lock() {
-            if let Some(config) = hotkeys.remove(action) {
+        let removed = self
+            .registered_hotkeys
+            .lock()
+            .ok()
+            .and_then(|mut hotkeys| hotkeys.remove(action));
+
+        if let Some(config) = removed {
+            if config.enabled {
                 self.app_handle
                     .global_shortcut()
-                    .unregister(config.shortcut.clone())?;
-                println!("Unregistered hotkey for action: {:?}", action);
+                    .unregister(config.shortcut)?;
             }
+            println!("Unregistered hotkey for action: {:?}", action);
         }
         Ok(())
     }
 
     /// Update a hotkey configuration
     pub fn update_hotkey(&self, config: HotkeyConfig) -> Result<(), Box<dyn std::error::Error>> {
-        // Unregister the old hotkey
-        self.unregister_hotkey(&config.action)?;
-
-        // Register the new hotkey
-        self.register_hotkey(config)?;
-
-        Ok(())
+        self.register_hotkey(config)
     }
 
     /// Enable or disable a hotkey
@@ -211,28 +327,13 @@ impl HotkeyManager {
         action: &HotkeyAction,
         enabled: bool,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        if let Ok(mut hotkeys) = self.registered_hotkeys.lock() {
-            if let Some(config) = hotkeys.get_mut(action) {
-                if config.enabled == enabled {
-                    return Ok(()); // No change needed
-                }
-
-                config.enabled = enabled;
-
-                if enabled {
-                    // Re-register the hotkey
-                    let config_clone = config.clone();
-                    drop(hotkeys); // Release the lock before calling register_hotkey
-                    self.register_hotkey(config_clone)?;
-                } else {
-                    // Unregister the hotkey
-                    let shortcut = config.shortcut.clone();
-                    drop(hotkeys); // Release the lock before calling unregister
-                    self.app_handle.global_shortcut().unregister(shortcut)?;
-                }
+        match self.config_for(action) {
+            Some(config) if config.enabled != enabled => {
+                self.register_hotkey(HotkeyConfig { enabled, ..config })
             }
+            // No change needed
+            _ => Ok(()),
         }
-        Ok(())
     }
 
     /// Get all registered hotkey configurations
@@ -244,308 +345,324 @@ impl HotkeyManager {
         }
     }
 
-    /// Check if hotkeys should be enabled based on app state
-    pub fn update_hotkey_state_based_on_app_state(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let app_state = if let Ok(state_manager) = self.state_manager.lock() {
-            state_manager.get_state()
-        } else {
-            return Err("Failed to lock state manager".into());
+    /// Run the action bound to a hotkey. Failures come back as an
+    /// unsuccessful result, since a pressed shortcut has no caller to
+    /// return an error to.
+    pub async fn run_action(app: &AppHandle, action: HotkeyAction) -> HotkeyEventResult {
+        let result = match action {
+            HotkeyAction::ToggleCommandPalette => Self::handle_command_palette_toggle(app),
+            HotkeyAction::ToggleFocusSession => Self::handle_focus_session_toggle(app).await,
+            HotkeyAction::ImmediateLock => Self::handle_immediate_lock(app).await,
+            HotkeyAction::EmergencyExit => Self::handle_emergency_exit(app).await,
+            HotkeyAction::PauseEverything => Self::handle_pause_everything(app).await,
         };
 
-        // Determine which hotkeys should be enabled based on app state
-        let command_palette_enabled = true; // Always enabled
-        let focus_session_enabled = matches!(
-            app_state,
-            AppState::Idle | AppState::FocusRunning | AppState::FocusPaused
-        );
-        let immediate_lock_enabled = !matches!(
-            app_state,
-            AppState::BreakRunning | AppState::LongBreakRunning
-        );
+        result.unwrap_or_else(|e| HotkeyEventResult::failed(action, e))
+    }
 
-        // Update hotkey states
-        self.set_hotkey_enabled(&HotkeyAction::ToggleCommandPalette, command_palette_enabled)?;
-        self.set_hotkey_enabled(&HotkeyAction::ToggleFocusSession, focus_session_enabled)?;
-        self.set_hotkey_enabled(&HotkeyAction::ImmediateLock, immediate_lock_enabled)?;
+    /// Handle command palette toggle with context awareness
+    fn handle_command_palette_toggle(app: &AppHandle) -> Result<HotkeyEventResult, String> {
+        let was_visible = Self::with_window_manager(app, |window_manager| {
+            let was_visible = window_manager.is_window_visible(WindowType::CommandPalette);
+            window_manager.toggle_command_palette()?;
+            Ok(was_visible)
+        })?;
 
-        println!("Updated hotkey states based on app state: {:?}", app_state);
-        Ok(())
+        Ok(HotkeyEventResult::succeeded(
+            HotkeyAction::ToggleCommandPalette,
+            if was_visible {
+                "Command palette hidden"
+            } else {
+                "Command palette shown"
+            },
+            None,
+        ))
     }
 
-    /// Route hotkey events to appropriate handlers based on current context
-    pub fn route_hotkey_event(
-        &self,
-        action: HotkeyAction,
-    ) -> Result<Vec<HotkeyEventResult>, Box<dyn std::error::Error>> {
-        let mut results = Vec::new();
-
-        match action {
-            HotkeyAction::ToggleCommandPalette => {
-                results.push(self.handle_command_palette_toggle()?);
+    /// Start a focus session when idle, otherwise pause or resume the running one
+    async fn handle_focus_session_toggle(app: &AppHandle) -> Result<HotkeyEventResult, String> {
+        let action = HotkeyAction::ToggleFocusSession;
+        let state = app.state::<AppState>();
+        let current_state = Self::current_cycle_state(app).await?;
+
+        match (&current_state.phase, current_state.is_running) {
+            (CyclePhase::Idle, _) => {
+                let cycle_state =
+                    cycle_handler::start_focus_session(None, state.clone(), app.clone()).await?;
+                Ok(HotkeyEventResult::succeeded(
+                    action,
+                    "Focus session started",
+                    Some(cycle_state),
+                ))
             }
-            HotkeyAction::ToggleFocusSession => {
-                results.extend(self.handle_focus_session_toggle()?);
+            (CyclePhase::Focus, true) => {
+                let cycle_state = cycle_handler::pause_cycle(state.clone()).await?;
+                Ok(HotkeyEventResult::succeeded(
+                    action,
+                    "Focus session paused",
+                    Some(cycle_state),
+                ))
             }
-            HotkeyAction::ImmediateLock => {
-                results.extend(self.handle_immediate_lock()?);
-            }
-            HotkeyAction::EmergencyExit => {
-                results.push(self.handle_emergency_exit()?);
+            (CyclePhase::Focus, false) => {
+                let cycle_state = cycle_handler::resume_cycle(state.clone()).await?;
+                Ok(HotkeyEventResult::succeeded(
+                    action,
+                    "Focus session resumed",
+                    Some(cycle_state),
+                ))
             }
+            (phase, _) => Ok(HotkeyEventResult::failed(
+                action,
+                format!("Cannot toggle focus session in phase: {}", phase),
+            )),
         }
-
-        Ok(results)
     }
 
-    /// Handle command palette toggle with context awareness
-    fn handle_command_palette_toggle(
-        &self,
-    ) -> Result<HotkeyEventResult, Box<dyn std::error::Error>> {
-        if let Ok(window_manager) = self.window_manager.lock() {
-            let was_visible =
-                window_manager.is_window_visible(crate::window_manager::WindowType::CommandPalette);
+    /// Take a break right away, ending a running focus session first
+    async fn handle_immediate_lock(app: &AppHandle) -> Result<HotkeyEventResult, String> {
+        let action = HotkeyAction::ImmediateLock;
+        let state = app.state::<AppState>();
+        let current_state = Self::current_cycle_state(app).await?;
+
+        let message = match current_state.phase {
+            CyclePhase::Focus => "Focus session ended, break started",
+            CyclePhase::Idle => "Immediate break started",
+            phase => {
+                return Ok(HotkeyEventResult::failed(
+                    action,
+                    format!("Cannot start immediate lock in phase: {}", phase),
+                ));
+            }
+        };
 
-            window_manager.toggle_command_palette()?;
+        let cycle_state = cycle_handler::break_now(None, state.clone(), app.clone()).await?;
+        Ok(HotkeyEventResult::succeeded(
+            action,
+            message,
+            Some(cycle_state),
+        ))
+    }
 
-            Ok(HotkeyEventResult {
-                action: HotkeyAction::ToggleCommandPalette,
-                success: true,
-                message: if was_visible {
-                    "Command palette hidden".to_string()
-                } else {
-                    "Command palette shown".to_string()
-                },
-                state_changes: vec![],
-            })
-        } else {
-            Err("Failed to access window manager".into())
+    /// Leave strict mode the way its own emergency key does
+    async fn handle_emergency_exit(app: &AppHandle) -> Result<HotkeyEventResult, String> {
+        let action = HotkeyAction::EmergencyExit;
+        let state = app.state::<AppState>();
+
+        let strict_active = state
+            .strict_mode_orchestrator
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|orchestrator| orchestrator.is_active());
+        if !strict_active {
+            return Ok(HotkeyEventResult::failed(
+                action,
+                "Strict mode is not active",
+            ));
         }
-    }
 
-    /// Handle focus session toggle with state-aware logic
-    fn handle_focus_session_toggle(
-        &self,
-    ) -> Result<Vec<HotkeyEventResult>, Box<dyn std::error::Error>> {
-        let mut results = Vec::new();
+        strict_mode_handler::emergency_exit_strict_mode(state.clone()).await?;
+        Ok(HotkeyEventResult::succeeded(
+            action,
+            "Emergency exit triggered",
+            None,
+        ))
+    }
 
-        if let Ok(mut state_manager) = self.state_manager.lock() {
-            let current_state = state_manager.get_state();
+    /// Pause the running focus session or break, drop a pending scheduled
+    /// focus start, stop any sound still playing and hide every window, e.g.
+    /// when pulled into a meeting. Does nothing while idle with nothing
+    /// scheduled. A locked strict mode break is neither paused nor hidden.
+    async fn handle_pause_everything(app: &AppHandle) -> Result<HotkeyEventResult, String> {
+        let action = HotkeyAction::PauseEverything;
+        let state = app.state::<AppState>();
+        let current_state = Self::current_cycle_state(app).await?;
+        let in_break = matches!(
+            current_state.phase,
+            CyclePhase::ShortBreak | CyclePhase::LongBreak
+        );
 
-            match current_state {
-                AppState::Idle => {
-                    // Start a new focus session
-                    let events = state_manager.start_focus_session(false)?;
+        if current_state.phase == CyclePhase::Idle {
+            if !Self::cancel_scheduled_focus(app).await? {
+                return Ok(HotkeyEventResult::failed(
+                    action,
+                    "Nothing to pause: no focus session or break is running",
+                ));
+            }
 
-                    // Show focus widget
-                    if let Ok(window_manager) = self.window_manager.lock() {
-                        window_manager.show_focus_widget()?;
-                    }
+            Self::silence_and_hide(app).await?;
+            return Ok(HotkeyEventResult::succeeded(
+                action,
+                "Scheduled focus start cancelled, windows hidden",
+                Some(current_state),
+            ));
+        }
 
-                    results.push(HotkeyEventResult {
-                        action: HotkeyAction::ToggleFocusSession,
-                        success: true,
-                        message: "Focus session started".to_string(),
-                        state_changes: events,
-                    });
+        if in_break && Self::is_strict_break_locked(app).await {
+            // Strict mode owns the break: keep it running and its overlay up
+            Self::with_window_manager(app, |window_manager| {
+                for window_type in [
+                    WindowType::CommandPalette,
+                    WindowType::FocusWidget,
+                    WindowType::Settings,
+                    WindowType::MenuBarPopover,
+                ] {
+                    window_manager.hide_window(window_type)?;
                 }
-                AppState::FocusRunning => {
-                    // Pause the current session
-                    let events = state_manager.pause_session()?;
-
-                    results.push(HotkeyEventResult {
-                        action: HotkeyAction::ToggleFocusSession,
-                        success: true,
-                        message: "Focus session paused".to_string(),
-                        state_changes: events,
-                    });
-                }
-                AppState::FocusPaused => {
-                    // Resume the current session
-                    let events = state_manager.resume_session()?;
-
-                    results.push(HotkeyEventResult {
-                        action: HotkeyAction::ToggleFocusSession,
-                        success: true,
-                        message: "Focus session resumed".to_string(),
-                        state_changes: events,
-                    });
-                }
-                _ => {
-                    results.push(HotkeyEventResult {
-                        action: HotkeyAction::ToggleFocusSession,
-                        success: false,
-                        message: format!(
-                            "Cannot toggle focus session in state: {:?}",
-                            current_state
-                        ),
-                        state_changes: vec![],
-                    });
-                }
-            }
-        } else {
-            return Err("Failed to access state manager".into());
+                Ok(())
+            })?;
+
+            return Ok(HotkeyEventResult::failed(
+                action,
+                "Strict mode break is locked; the break overlay stays up",
+            ));
         }
 
-        Ok(results)
+        let (cycle_state, message) = if !current_state.is_running {
+            (
+                current_state,
+                "Windows hidden; the session was already paused",
+            )
+        } else if in_break {
+            let cycle_state = cycle_handler::pause_cycle(state.clone()).await?;
+            (cycle_state, "Break paused, windows hidden")
+        } else {
+            let cycle_state = cycle_handler::pause_cycle(state.clone()).await?;
+            (cycle_state, "Focus session paused, windows hidden")
+        };
+
+        Self::cancel_scheduled_focus(app).await?;
+        Self::silence_and_hide(app).await?;
+
+        Ok(HotkeyEventResult::succeeded(
+            action,
+            message,
+            Some(cycle_state),
+        ))
     }
 
-    /// Handle immediate lock with context-aware break type selection
-    fn handle_immediate_lock(&self) -> Result<Vec<HotkeyEventResult>, Box<dyn std::error::Error>> {
-        let mut results = Vec::new();
+    /// Drop a pending scheduled focus start, returning whether there was one
+    async fn cancel_scheduled_focus(app: &AppHandle) -> Result<bool, String> {
+        let state = app.state::<AppState>();
+        if cycle_handler::get_scheduled_focus(state.clone())
+            .await?
+            .is_none()
+        {
+            return Ok(false);
+        }
 
-        if let Ok(mut state_manager) = self.state_manager.lock() {
-            let current_state = state_manager.get_state();
+        cycle_handler::cancel_scheduled_focus(state.clone()).await?;
+        Ok(true)
+    }
 
-            match current_state {
-                AppState::FocusRunning | AppState::FocusPaused => {
-                    // End current focus session and start break
-                    let end_events = state_manager.end_session()?;
+    /// Cut off any sound still playing and hide every window
+    async fn silence_and_hide(app: &AppHandle) -> Result<(), String> {
+        let state = app.state::<AppState>();
+        let stopped = state.notification_service.lock().await.stop_sounds();
+        if stopped > 0 {
+            println!("Stopped {} playing sounds", stopped);
+        }
 
-                    // Show break overlay
-                    if let Ok(window_manager) = self.window_manager.lock() {
-                        window_manager.show_break_overlay()?;
-                    }
+        Self::with_window_manager(app, |window_manager| window_manager.close_all_windows())
+    }
 
-                    results.push(HotkeyEventResult {
-                        action: HotkeyAction::ImmediateLock,
-                        success: true,
-                        message: "Focus session ended, break started".to_string(),
-                        state_changes: end_events,
-                    });
-                }
-                AppState::Idle => {
-                    // Start immediate short break
-                    let events = state_manager.start_break(BreakType::Short)?;
+    /// Whether strict mode has locked the current break
+    async fn is_strict_break_locked(app: &AppHandle) -> bool {
+        let state = app.state::<AppState>();
+        let strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
 
-                    // Show break overlay
-                    if let Ok(window_manager) = self.window_manager.lock() {
-                        window_manager.show_break_overlay()?;
-                    }
+        strict_mode_orchestrator
+            .as_ref()
+            .is_some_and(|orchestrator| {
+                let strict_state = orchestrator.get_state();
+                strict_state.is_active && strict_state.is_locked
+            })
+    }
 
-                    results.push(HotkeyEventResult {
-                        action: HotkeyAction::ImmediateLock,
-                        success: true,
-                        message: "Immediate break started".to_string(),
-                        state_changes: events,
-                    });
-                }
-                _ => {
-                    results.push(HotkeyEventResult {
-                        action: HotkeyAction::ImmediateLock,
-                        success: false,
-                        message: format!(
-                            "Cannot start immediate lock in state: {:?}",
-                            current_state
-                        ),
-                        state_changes: vec![],
-                    });
-                }
-            }
-        } else {
-            return Err("Failed to access state manager".into());
+    /// Current cycle state, setting up the orchestrator first if the
+    /// frontend hasn't yet
+    async fn current_cycle_state(app: &AppHandle) -> Result<CycleState, String> {
+        let state = app.state::<AppState>();
+        let current_state = state
+            .cycle_orchestrator
+            .lock()
+            .await
+            .as_ref()
+            .map(|orchestrator| orchestrator.get_state());
+
+        match current_state {
+            Some(current_state) => Ok(current_state),
+            None => cycle_handler::initialize_cycle_orchestrator(state.clone(), app.clone()).await,
         }
+    }
 
-        Ok(results)
+    /// Run `f` against the window manager the hotkey handlers share
+    fn with_window_manager<T>(
+        app: &AppHandle,
+        f: impl FnOnce(&WindowManager) -> Result<T, Box<dyn std::error::Error>>,
+    ) -> Result<T, String> {
+        let manager = app.state::<HotkeyManager>();
+        let window_manager = manager
+            .window_manager
+            .lock()
+            .map_err(|_| "Failed to access window manager".to_string())?;
+        f(&window_manager).map_err(|e| e.to_string())
     }
 
-    /// Handle emergency exit from strict mode
-    fn handle_emergency_exit(&self) -> Result<HotkeyEventResult, Box<dyn std::error::Error>> {
-        // Note: The actual emergency exit is handled by the StrictModeOrchestrator
-        // This method is called from the frontend when the emergency key is detected
-        // The frontend will call the emergency_exit_strict_mode command directly
+    /// Load custom hotkey configurations from the database and register them
+    /// over the defaults. A saved shortcut that no longer parses or registers
+    /// (e.g. the OS has since claimed it) reverts to the action's default.
+    pub fn load_custom_hotkeys(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let stored = self
+            .app_handle
+            .state::<AppState>()
+            .database
+            .get_hotkey_configs()?;
+
+        let mut loaded = 0;
+        for entry in stored {
+            let Some(action) = HotkeyAction::from_name(&entry.action) else {
+                eprintln!(
+                    "Warning: Skipping saved hotkey for unknown action {}",
+                    entry.action
+                );
+                continue;
+            };
 
-        Ok(HotkeyEventResult {
-            action: HotkeyAction::EmergencyExit,
-            success: true,
-            message: "Emergency exit triggered".to_string(),
-            state_changes: vec![],
-        })
-    }
+            let registered = HotkeyConfig::from_stored(&entry)
+                .and_then(|config| self.update_hotkey(config).map_err(|e| e.to_string()));
 
-    /// Handle hotkey events (simplified version for callback)
-    fn handle_hotkey_event(
-        action: HotkeyAction,
-        state_manager: Arc<Mutex<StateManager>>,
-        window_manager: Arc<Mutex<WindowManager>>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        match action {
-            HotkeyAction::ToggleCommandPalette => {
-                if let Ok(manager) = window_manager.lock() {
-                    manager.toggle_command_palette()?;
-                }
-            }
-            HotkeyAction::ToggleFocusSession => {
-                if let Ok(mut state_mgr) = state_manager.lock() {
-                    let current_state = state_mgr.get_state();
-                    match current_state {
-                        AppState::Idle => {
-                            let _events = state_mgr.start_focus_session(false)?;
-                            if let Ok(window_mgr) = window_manager.lock() {
-                                window_mgr.show_focus_widget()?;
-                            }
-                        }
-                        AppState::FocusRunning => {
-                            let _events = state_mgr.pause_session()?;
-                        }
-                        AppState::FocusPaused => {
-                            let _events = state_mgr.resume_session()?;
-                        }
-                        _ => {
-                            println!("Cannot toggle focus session in state: {:?}", current_state);
-                        }
-                    }
-                }
-            }
-            HotkeyAction::ImmediateLock => {
-                if let Ok(mut state_mgr) = state_manager.lock() {
-                    let current_state = state_mgr.get_state();
-                    match current_state {
-                        AppState::FocusRunning | AppState::FocusPaused => {
-                            let _events = state_mgr.end_session()?;
-                            if let Ok(window_mgr) = window_manager.lock() {
-                                window_mgr.show_break_overlay()?;
-                            }
-                        }
-                        AppState::Idle => {
-                            let _events = state_mgr.start_break(BreakType::Short)?;
-                            if let Ok(window_mgr) = window_manager.lock() {
-                                window_mgr.show_break_overlay()?;
-                            }
-                        }
-                        _ => {
-                            println!("Cannot start immediate lock in state: {:?}", current_state);
-                        }
-                    }
+            if let Err(e) = registered {
+                eprintln!(
+                    "Warning: Saved hotkey '{}' for {:?} could not be registered ({}); reverted to the default",
+                    entry.shortcut, action, e
+                );
+                // Left switched off if even the default is taken
+                if let Err(e) = self.update_hotkey(HotkeyConfig::new(action.clone())) {
+                    eprintln!(
+                        "Warning: Default hotkey for {:?} could not be registered either: {}",
+                        action, e
+                    );
                 }
             }
-            HotkeyAction::EmergencyExit => {
-                // Emergency exit is handled by the frontend calling emergency_exit_strict_mode command
-                println!("Emergency exit hotkey detected");
-            }
+
+            loaded += 1;
         }
-        Ok(())
-    }
 
-    /// Load custom hotkey configurations from settings
-    pub fn load_custom_hotkeys(&self) -> Result<(), Box<dyn std::error::Error>> {
-        // In a real implementation, this would load from a settings file or database
-        // For now, we'll use default configurations
-        println!("Loading custom hotkey configurations (using defaults for now)");
+        println!("Loaded {} custom hotkey configurations", loaded);
         Ok(())
     }
 
-    /// Save current hotkey configurations to settings
+    /// Save current hotkey configurations to the database
     pub fn save_hotkey_configurations(&self) -> Result<(), Box<dyn std::error::Error>> {
-        if let Ok(hotkeys) = self.registered_hotkeys.lock() {
-            // In a real implementation, this would save to a settings file or database
-            println!("Saving {} hotkey configurations", hotkeys.len());
-            for (action, config) in hotkeys.iter() {
-                println!(
-                    "  {:?}: enabled={}, shortcut={:?}",
-                    action, config.enabled, config.shortcut
-                );
-            }
+        let state = self.app_handle.state::<AppState>();
+        let configs = self.get_hotkey_configs();
+
+        for config in configs.values() {
+            state.database.save_hotkey_config(&config.to_stored())?;
         }
+
+        println!("Saved {} hotkey configurations", configs.len());
         Ok(())
     }
 
@@ -559,8 +676,9 @@ impl HotkeyManager {
             hotkeys.clear();
         }
 
-        // Re-initialize with defaults
-        self.initialize()?;
+        // Re-initialize with defaults and make them the saved configuration
+        self.register_defaults();
+        self.save_hotkey_configurations()?;
 
         println!("Reset all hotkeys to default configurations");
         Ok(())
@@ -583,6 +701,120 @@ impl HotkeyManager {
         conflicts
     }
 
+    /// Serialize all hotkey configurations, keyed by action
+    pub fn export_hotkeys(&self) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(Self::export_configs(&self.get_hotkey_configs())?)
+    }
+
+    fn export_configs(
+        configs: &HashMap<HotkeyAction, HotkeyConfig>,
+    ) -> Result<String, serde_json::Error> {
+        let entries: HashMap<String, HotkeyExportEntry> = configs
+            .iter()
+            .map(|(action, config)| {
+                let entry = HotkeyExportEntry {
+                    shortcut: config.shortcut.into_string(),
+                    enabled: config.enabled,
+                };
+                (action.name(), entry)
+            })
+            .collect();
+
+        serde_json::to_string_pretty(&entries)
+    }
+
+    /// Parse an exported configuration. Every action must be known to this
+    /// version and every shortcut must parse; nothing is applied here.
+    pub fn parse_hotkey_export(json: &str) -> Result<Vec<HotkeyConfig>, String> {
+        let entries: HashMap<String, HotkeyExportEntry> =
+            serde_json::from_str(json).map_err(|e| format!("Invalid hotkey file: {}", e))?;
+
+        let mut unknown = Vec::new();
+        let mut configs = Vec::new();
+
+        for (name, entry) in entries {
+            let Some(action) = HotkeyAction::from_name(&name) else {
+                unknown.push(name);
+                continue;
+            };
+
+            let shortcut: Shortcut = entry.shortcut.parse().map_err(|e| {
+                format!("Invalid shortcut '{}' for {}: {}", entry.shortcut, name, e)
+            })?;
+
+            configs.push(HotkeyConfig {
+                action,
+                shortcut,
+                enabled: entry.enabled,
+            });
+        }
+
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(format!("Unknown hotkey actions: {}", unknown.join(", ")));
+        }
+
+        Ok(configs)
+    }
+
+    /// Shortcuts that would be bound to more than one enabled action once
+    /// `imported` replaces the current configurations for its actions
+    pub fn find_import_conflicts(&self, imported: &[HotkeyConfig]) -> Vec<String> {
+        Self::conflicts_after_import(self.get_hotkey_configs(), imported)
+    }
+
+    fn conflicts_after_import(
+        mut merged: HashMap<HotkeyAction, HotkeyConfig>,
+        imported: &[HotkeyConfig],
+    ) -> Vec<String> {
+        for config in imported {
+            merged.insert(config.action.clone(), config.clone());
+        }
+
+        let mut by_shortcut: HashMap<String, Vec<String>> = HashMap::new();
+        for config in merged.values().filter(|config| config.enabled) {
+            by_shortcut
+                .entry(config.shortcut.into_string())
+                .or_default()
+                .push(config.action.description().to_string());
+        }
+
+        let mut conflicts: Vec<String> = by_shortcut
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(shortcut, mut actions)| {
+                actions.sort();
+                format!("{} is used by {}", shortcut, actions.join(" and "))
+            })
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Apply an exported configuration after checking it for unknown actions
+    /// and shortcut conflicts. Actions missing from the file are left as they are.
+    pub fn import_hotkeys(&self, json: &str) -> Result<usize, Box<dyn std::error::Error>> {
+        let configs = Self::parse_hotkey_export(json)?;
+
+        let conflicts = self.find_import_conflicts(&configs);
+        if !conflicts.is_empty() {
+            return Err(format!("Hotkey conflicts: {}", conflicts.join("; ")).into());
+        }
+
+        // Free every replaced shortcut first, so two actions can swap theirs
+        for config in &configs {
+            self.set_hotkey_enabled(&config.action, false)?;
+        }
+
+        let count = configs.len();
+        for config in configs {
+            self.update_hotkey(config)?;
+        }
+
+        println!("Imported {} hotkey configurations", count);
+        Ok(count)
+    }
+
     /// Get available modifier combinations for hotkey customization
     pub fn get_available_modifiers() -> Vec<(String, Modifiers)> {
         vec![
@@ -609,11 +841,11 @@ impl HotkeyManager {
     /// Unregister all hotkeys (cleanup)
     pub fn unregister_all(&self) -> Result<(), Box<dyn std::error::Error>> {
         if let Ok(hotkeys) = self.registered_hotkeys.lock() {
-            for config in hotkeys.values() {
+            for config in hotkeys.values().filter(|config| config.enabled) {
                 if let Err(e) = self
                     .app_handle
                     .global_shortcut()
-                    .unregister(config.shortcut.clone())
+                    .unregister(config.shortcut)
                 {
                     eprintln!("Warning: Failed to unregister hotkey: {}", e);
                 }
@@ -623,123 +855,110 @@ impl HotkeyManager {
     }
 }
 
-// Tauri commands for hotkey management
+/// Run a pressed hotkey's action in the background and report the outcome
+/// to the frontend
+fn spawn_hotkey_action(app: AppHandle, action: HotkeyAction) {
+    tauri::async_runtime::spawn(async move {
+        let result = HotkeyManager::run_action(&app, action).await;
+        println!("Hotkey {:?}: {}", result.action, result.message);
 
-#[tauri::command]
-pub async fn get_hotkey_configs(
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<HashMap<HotkeyAction, HotkeyConfig>, String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    Ok(manager.get_hotkey_configs())
+        if let Err(e) = app.emit("hotkey-triggered", &result) {
+            eprintln!("Failed to emit hotkey-triggered: {}", e);
+        }
+    });
 }
 
-#[tauri::command]
-pub async fn update_hotkey_config(
-    config: HotkeyConfig,
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<(), String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    manager
-        .update_hotkey(config)
-        .map_err(|e| format!("Failed to update hotkey: {}", e))
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::DatabaseManager;
 
-#[tauri::command]
-pub async fn set_hotkey_enabled(
-    action: HotkeyAction,
-    enabled: bool,
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<(), String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    manager
-        .set_hotkey_enabled(&action, enabled)
-        .map_err(|e| format!("Failed to set hotkey enabled state: {}", e))
-}
+    fn default_configs() -> HashMap<HotkeyAction, HotkeyConfig> {
+        HotkeyAction::ALL
+            .into_iter()
+            .map(|action| (action.clone(), HotkeyConfig::new(action)))
+            .collect()
+    }
 
-#[tauri::command]
-pub async fn refresh_hotkey_state(
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<(), String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    manager
-        .update_hotkey_state_based_on_app_state()
-        .map_err(|e| format!("Failed to refresh hotkey state: {}", e))
-}
+    #[test]
+    fn test_hotkey_export_round_trips_through_import() {
+        let mut configs = default_configs();
+        let custom = HotkeyConfig::with_shortcut(
+            HotkeyAction::ImmediateLock,
+            Shortcut::new(Some(Modifiers::ALT | Modifiers::SHIFT), Code::KeyP),
+        );
+        configs.insert(HotkeyAction::ImmediateLock, custom);
 
-#[tauri::command]
-pub async fn route_hotkey_event(
-    action: HotkeyAction,
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<Vec<HotkeyEventResult>, String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    manager
-        .route_hotkey_event(action)
-        .map_err(|e| format!("Failed to route hotkey event: {}", e))
-}
+        let json = HotkeyManager::export_configs(&configs).unwrap();
+        let imported = HotkeyManager::parse_hotkey_export(&json).unwrap();
 
-#[tauri::command]
-pub async fn save_hotkey_configurations(
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<(), String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    manager
-        .save_hotkey_configurations()
-        .map_err(|e| format!("Failed to save hotkey configurations: {}", e))
-}
+        assert!(HotkeyManager::conflicts_after_import(default_configs(), &imported).is_empty());
+        let imported: HashMap<HotkeyAction, HotkeyConfig> = imported
+            .into_iter()
+            .map(|config| (config.action.clone(), config))
+            .collect();
+        assert_eq!(imported, configs);
+    }
 
-#[tauri::command]
-pub async fn load_custom_hotkeys(
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<(), String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    manager
-        .load_custom_hotkeys()
-        .map_err(|e| format!("Failed to load custom hotkeys: {}", e))
-}
+    #[test]
+    fn test_saved_hotkeys_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = DatabaseManager::new(dir.path().join("pausa.db")).unwrap();
 
-#[tauri::command]
-pub async fn reset_hotkeys_to_defaults(
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<(), String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    manager
-        .reset_to_defaults()
-        .map_err(|e| format!("Failed to reset hotkeys to defaults: {}", e))
-}
+        let mut configs = default_configs();
+        let custom = HotkeyConfig {
+            action: HotkeyAction::ToggleFocusSession,
+            shortcut: Shortcut::new(Some(Modifiers::ALT | Modifiers::SHIFT), Code::KeyF),
+            enabled: false,
+        };
+        configs.insert(HotkeyAction::ToggleFocusSession, custom);
 
-#[tauri::command]
-pub async fn check_hotkey_conflicts(
-    hotkey_manager: tauri::State<'_, Arc<Mutex<HotkeyManager>>>,
-) -> Result<Vec<HotkeyAction>, String> {
-    let manager = hotkey_manager
-        .lock()
-        .map_err(|e| format!("Failed to lock hotkey manager: {}", e))?;
-    Ok(manager.check_for_conflicts())
-}
+        for config in configs.values() {
+            database.save_hotkey_config(&config.to_stored()).unwrap();
+        }
+
+        let read_back: HashMap<HotkeyAction, HotkeyConfig> = database
+            .get_hotkey_configs()
+            .unwrap()
+            .iter()
+            .map(|stored| {
+                let config = HotkeyConfig::from_stored(stored).unwrap();
+                (config.action.clone(), config)
+            })
+            .collect();
+        assert_eq!(read_back, configs);
 
-#[tauri::command]
-pub async fn get_available_modifiers() -> Result<Vec<(String, String)>, String> {
-    // Convert Modifiers to string representation for frontend
-    let modifiers = HotkeyManager::get_available_modifiers();
-    let string_modifiers: Vec<(String, String)> = modifiers
-        .into_iter()
-        .map(|(name, _)| (name.clone(), name)) // In a real implementation, convert Modifiers to string
-        .collect();
-    Ok(string_modifiers)
+        let unknown = StoredHotkeyConfig {
+            action: "Teleport".to_string(),
+            shortcut: "shift+super+KeyT".to_string(),
+            enabled: true,
+        };
+        assert!(HotkeyConfig::from_stored(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_hotkey_import_rejects_malformed_files() {
+        assert!(HotkeyManager::parse_hotkey_export("not json").is_err());
+        assert!(HotkeyManager::parse_hotkey_export(r#"["ImmediateLock"]"#).is_err());
+
+        let unknown = HotkeyManager::parse_hotkey_export(
+            r#"{"Teleport": {"shortcut": "shift+super+KeyT", "enabled": true}}"#,
+        )
+        .unwrap_err();
+        assert!(unknown.contains("Teleport"));
+
+        let bad_shortcut = HotkeyManager::parse_hotkey_export(
+            r#"{"ImmediateLock": {"shortcut": "shift+NotAKey", "enabled": true}}"#,
+        )
+        .unwrap_err();
+        assert!(bad_shortcut.contains("ImmediateLock"));
+
+        // Moving focus onto the lock shortcut would bind it twice
+        let lock = HotkeyAction::ImmediateLock.default_shortcut();
+        let clash = HotkeyConfig::with_shortcut(HotkeyAction::ToggleFocusSession, lock);
+        assert_eq!(
+            HotkeyManager::conflicts_after_import(default_configs(), &[clash]).len(),
+            1
+        );
+    }
 }
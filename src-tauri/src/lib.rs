@@ -3,21 +3,30 @@
 
 mod api_models;
 mod app;
+mod break_content;
 mod config;
 mod cycle_orchestrator;
 mod database;
+mod display_sleep;
 mod domain;
 mod errors;
 mod handlers;
+mod hotkey_manager;
 mod infra;
 pub mod notification_service;
 #[cfg(target_os = "macos")]
 mod menu_bar_text;
 mod onboarding;
+mod performance;
+mod phase_hooks;
 mod pkce;
 mod services;
+mod shutdown;
 mod state;
 pub mod strict_mode;
+mod theme;
+mod tray_icon;
+pub mod util;
 pub mod window_manager;
 
 use app::run;
@@ -2,21 +2,30 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 mod api_models;
 mod app;
+mod break_content;
 mod config;
 mod cycle_orchestrator;
 mod database;
+mod display_sleep;
 mod domain;
 mod errors;
 mod handlers;
+mod hotkey_manager;
 mod infra;
 mod notification_service;
 #[cfg(target_os = "macos")]
 mod menu_bar_text;
 mod onboarding;
+mod performance;
+mod phase_hooks;
 mod pkce;
 mod services;
+mod shutdown;
 mod state;
 mod strict_mode;
+mod theme;
+mod tray_icon;
+mod util;
 mod window_manager;
 
 fn main() {
@@ -1,14 +1,52 @@
-use tauri::AppHandle;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::io;
+use std::process::{Child, Command, ExitStatus};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager};
 use tauri_plugin_notification::NotificationExt;
 
+use crate::database::models::{NotificationHistory, SoundConfig, SoundName};
+
+/// Default number of notifications kept in the in-memory history
+pub const DEFAULT_HISTORY_CAPACITY: usize = 50;
+
+/// Largest allowed history capacity
+pub const MAX_HISTORY_CAPACITY: usize = 1000;
+
+/// How often a waiting sound thread checks whether its player has exited
+const PLAYER_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Sound players still running, shared with the threads waiting on them so
+/// `stop_sounds` can cut them off
+type PlayingSounds = Arc<Mutex<Vec<Child>>>;
+
 /// Simple notification service that uses native OS notifications
 pub struct NotificationService {
     user_name: Option<String>,
+    history: VecDeque<NotificationHistory>,
+    history_capacity: usize,
+    muted_until: Option<DateTime<Utc>>,
+    sound_config: SoundConfig,
+    playing_sounds: PlayingSounds,
 }
 
+/// Notifications still shown while muted: the timer ran out and the user
+/// has to act on it
+const CRITICAL_NOTIFICATIONS: &[&str] = &["focus_end", "break_end"];
+
 impl NotificationService {
     pub fn new() -> Self {
-        Self { user_name: None }
+        Self {
+            user_name: None,
+            history: VecDeque::with_capacity(DEFAULT_HISTORY_CAPACITY),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+            muted_until: None,
+            sound_config: SoundConfig::default(),
+            playing_sounds: Arc::new(Mutex::new(Vec::new())),
+        }
     }
 
     pub fn set_user_name(&mut self, name: Option<String>) {
@@ -19,8 +57,140 @@ impl NotificationService {
         self.user_name.as_deref()
     }
 
+    /// Get fired notifications, newest first
+    pub fn get_history(&self) -> Vec<NotificationHistory> {
+        self.history.iter().rev().cloned().collect()
+    }
+
+    /// Forget all recorded notifications
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Number of notifications currently kept in the history
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn get_history_capacity(&self) -> usize {
+        self.history_capacity
+    }
+
+    /// Change how many notifications are kept, dropping the oldest if needed
+    pub fn set_history_capacity(&mut self, capacity: usize) -> Result<(), String> {
+        if capacity == 0 || capacity > MAX_HISTORY_CAPACITY {
+            return Err(format!(
+                "History capacity must be between 1 and {}",
+                MAX_HISTORY_CAPACITY
+            ));
+        }
+
+        self.history_capacity = capacity;
+        while self.history.len() > capacity {
+            self.history.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Mute non-critical notifications until `until`, or unmute with `None`
+    pub fn set_muted_until(&mut self, until: Option<DateTime<Utc>>) {
+        self.muted_until = until;
+    }
+
+    /// When the current mute ends, or `None` if notifications aren't muted
+    pub fn get_muted_until(&self) -> Option<DateTime<Utc>> {
+        self.muted_until.filter(|until| *until > Utc::now())
+    }
+
+    pub fn set_sound_config(&mut self, config: SoundConfig) {
+        self.sound_config = config;
+    }
+
+    /// Play the sound for the end of a focus session, if sounds are on
+    pub fn play_focus_end_sound(&self, app: &AppHandle) {
+        self.play_sound(app, self.sound_config.focus_end);
+    }
+
+    /// Play the sound for the end of a break, if sounds are on
+    pub fn play_break_end_sound(&self, app: &AppHandle) {
+        self.play_sound(app, self.sound_config.break_end);
+    }
+
+    /// Play a bundled sound in the background. Phase end sounds go with
+    /// critical notifications, so a mute doesn't silence them; only
+    /// `SoundConfig::enabled` does.
+    fn play_sound(&self, app: &AppHandle, sound: SoundName) {
+        if !self.sound_config.enabled {
+            return;
+        }
+        let Some(resource) = sound.resource_path() else {
+            return;
+        };
+
+        let path = match app.path().resolve(resource, BaseDirectory::Resource) {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!(
+                    "⚠️ [NotificationService] Could not find sound {}: {}",
+                    resource, e
+                );
+                return;
+            }
+        };
+
+        println!("🔔 [NotificationService] Playing {:?} sound", sound);
+
+        // Players block until the sound ends
+        let playing_sounds = Arc::clone(&self.playing_sounds);
+        std::thread::spawn(move || {
+            if let Err(e) = player::play(&path, &playing_sounds) {
+                eprintln!("⚠️ [NotificationService] Could not play sound: {}", e);
+            }
+        });
+    }
+
+    /// Stop every sound still playing, returning how many were cut off
+    pub fn stop_sounds(&self) -> usize {
+        let Ok(mut players) = self.playing_sounds.lock() else {
+            return 0;
+        };
+
+        let stopped = players.len();
+        for mut player in players.drain(..) {
+            let _ = player.kill();
+            let _ = player.wait();
+        }
+        stopped
+    }
+
+    /// Show a notification and record it in the history
+    fn send(&mut self, app: &AppHandle, notification_type: &str, title: &str, body: &str) {
+        if self.get_muted_until().is_some() && !CRITICAL_NOTIFICATIONS.contains(&notification_type)
+        {
+            println!(
+                "🔕 [NotificationService] Muted, skipping {} notification",
+                notification_type
+            );
+            return;
+        }
+
+        let _ = app.notification().builder().title(title).body(body).show();
+
+        if self.history.len() >= self.history_capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(NotificationHistory {
+            id: None,
+            session_id: None,
+            notification_type: notification_type.to_string(),
+            title: title.to_string(),
+            message: body.to_string(),
+            sent_at: Utc::now(),
+        });
+    }
+
     /// Send a focus start notification
-    pub fn notify_focus_start(&self, app: &AppHandle) {
+    pub fn notify_focus_start(&mut self, app: &AppHandle) {
         let title = "Focus mode started";
         let body = if let Some(name) = &self.user_name {
             format!("{}, time to concentrate and do great work 🧠", name)
@@ -28,19 +198,27 @@ impl NotificationService {
             "Time to concentrate and do great work 🧠".to_string()
         };
 
-        let _ = app.notification().builder().title(title).body(&body).show();
+        self.send(app, "focus_start", title, &body);
+    }
+
+    /// Tell the user a focus session they scheduled has started on its own
+    pub fn notify_scheduled_focus_start(&mut self, app: &AppHandle) {
+        let title = "Scheduled focus started";
+        let body = "Your planned focus block is underway 📅";
+
+        self.send(app, "scheduled_focus_start", title, body);
     }
 
     /// Send a focus warning notification (2 minutes before end)
-    pub fn notify_focus_warning(&self, app: &AppHandle, minutes_left: u32) {
+    pub fn notify_focus_warning(&mut self, app: &AppHandle, minutes_left: u32) {
         let title = "Focus session ending soon";
         let body = format!("{} minutes left. Time to wrap up ⏳", minutes_left);
 
-        let _ = app.notification().builder().title(title).body(&body).show();
+        self.send(app, "focus_warning", title, &body);
     }
 
     /// Send a focus end notification
-    pub fn notify_focus_end(&self, app: &AppHandle) {
+    pub fn notify_focus_end(&mut self, app: &AppHandle) {
         let title = "Great work!";
         let body = if let Some(name) = &self.user_name {
             format!("{}, time to take a break ✨", name)
@@ -48,19 +226,19 @@ impl NotificationService {
             "Time to take a break ✨".to_string()
         };
 
-        let _ = app.notification().builder().title(title).body(&body).show();
+        self.send(app, "focus_end", title, &body);
     }
 
     /// Send a break start notification
-    pub fn notify_break_start(&self, app: &AppHandle) {
+    pub fn notify_break_start(&mut self, app: &AppHandle) {
         let title = "Active break";
         let body = "Move. Stretch. Drink water ☕";
 
-        let _ = app.notification().builder().title(title).body(body).show();
+        self.send(app, "break_start", title, body);
     }
 
     /// Send a long break start notification
-    pub fn notify_long_break_start(&self, app: &AppHandle) {
+    pub fn notify_long_break_start(&mut self, app: &AppHandle) {
         let title = "Excellent progress!";
         let body = if let Some(name) = &self.user_name {
             format!("{}, take a long break. You've earned it 🌟", name)
@@ -68,23 +246,34 @@ impl NotificationService {
             "Take a long break. You've earned it 🌟".to_string()
         };
 
-        let _ = app.notification().builder().title(title).body(&body).show();
+        self.send(app, "long_break_start", title, &body);
     }
 
     /// Send a break end notification
-    pub fn notify_break_end(&self, app: &AppHandle) {
+    pub fn notify_break_end(&mut self, app: &AppHandle) {
         let title = "Ready";
         let body = "Shall we start another block? 💪";
 
-        let _ = app.notification().builder().title(title).body(body).show();
+        self.send(app, "break_end", title, body);
+    }
+
+    /// Nudge back to focus when a break ran well past its planned length
+    pub fn notify_break_overrun(&mut self, app: &AppHandle, overrun_minutes: u32) {
+        let title = "Break's over";
+        let body = format!(
+            "Your break ran {} min over. Ready to get back to focus? 🎯",
+            overrun_minutes
+        );
+
+        self.send(app, "break_overrun", title, &body);
     }
 
     /// Send a cycle complete notification
-    pub fn notify_cycle_complete(&self, app: &AppHandle, cycle_count: u32) {
+    pub fn notify_cycle_complete(&mut self, app: &AppHandle, cycle_count: u32) {
         let title = "Cycle completed!";
         let body = format!("You've completed {} cycles. Keep it up! 🎉", cycle_count);
 
-        let _ = app.notification().builder().title(title).body(&body).show();
+        self.send(app, "cycle_complete", title, &body);
     }
 }
 
@@ -93,3 +282,133 @@ impl Default for NotificationService {
         Self::new()
     }
 }
+
+/// Run a sound player until it exits, keeping it in `playing` meanwhile.
+/// Returns `None` when `stop_sounds` killed it first.
+fn run_player(mut command: Command, playing: &PlayingSounds) -> io::Result<Option<ExitStatus>> {
+    let player = command.spawn()?;
+    let id = player.id();
+    playing
+        .lock()
+        .map_err(|_| io::Error::other("sound player list poisoned"))?
+        .push(player);
+
+    loop {
+        {
+            let mut players = playing
+                .lock()
+                .map_err(|_| io::Error::other("sound player list poisoned"))?;
+            let Some(index) = players.iter().position(|player| player.id() == id) else {
+                return Ok(None);
+            };
+
+            match players[index].try_wait() {
+                Ok(None) => {}
+                Ok(Some(status)) => {
+                    players.swap_remove(index);
+                    return Ok(Some(status));
+                }
+                Err(e) => {
+                    // Don't leave a player we can no longer track running
+                    let mut player = players.swap_remove(index);
+                    let _ = player.kill();
+                    let _ = player.wait();
+                    return Err(e);
+                }
+            }
+        }
+
+        std::thread::sleep(PLAYER_POLL_INTERVAL);
+    }
+}
+
+/// Treat a player that was stopped like one that finished
+fn check_player_status(status: Option<ExitStatus>) -> io::Result<()> {
+    match status {
+        Some(status) if !status.success() => {
+            Err(io::Error::other(format!("player exited with {}", status)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Plays a wav file with the player every install of the OS ships with,
+/// returning once playback ends or the sound is stopped
+#[cfg(target_os = "macos")]
+mod player {
+    use std::io;
+    use std::path::Path;
+    use std::process::Command;
+
+    use super::{check_player_status, run_player, PlayingSounds};
+
+    pub fn play(path: &Path, playing: &PlayingSounds) -> io::Result<()> {
+        let mut command = Command::new("afplay");
+        command.arg(path);
+
+        check_player_status(run_player(command, playing)?)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod player {
+    use std::io;
+    use std::path::Path;
+    use std::process::Command;
+
+    use super::{check_player_status, run_player, PlayingSounds};
+
+    pub fn play(path: &Path, playing: &PlayingSounds) -> io::Result<()> {
+        // PulseAudio/PipeWire first, plain ALSA when there's no sound server
+        let mut paplay = Command::new("paplay");
+        paplay.arg(path);
+        match run_player(paplay, playing) {
+            // Stopped by `stop_sounds`; don't start it again through ALSA
+            Ok(None) => return Ok(()),
+            Ok(Some(status)) if status.success() => return Ok(()),
+            _ => {}
+        }
+
+        let mut aplay = Command::new("aplay");
+        aplay.arg("-q").arg(path);
+
+        check_player_status(run_player(aplay, playing)?)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod player {
+    use std::io;
+    use std::path::Path;
+    use std::process::Command;
+
+    use super::{check_player_status, run_player, PlayingSounds};
+
+    pub fn play(path: &Path, playing: &PlayingSounds) -> io::Result<()> {
+        let path = path.display().to_string().replace('\'', "''");
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            &format!("(New-Object Media.SoundPlayer '{}').PlaySync()", path),
+        ]);
+
+        check_player_status(run_player(command, playing)?)
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+mod player {
+    use std::io;
+    use std::path::Path;
+
+    use super::PlayingSounds;
+
+    pub fn play(_path: &Path, _playing: &PlayingSounds) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not supported on this platform",
+        ))
+    }
+}
@@ -212,8 +212,21 @@ impl BackupManager {
 
         // Restore user settings
         if let Some(user_settings_json) = backup.user_settings {
-            let user_settings: crate::database::models::UserSettings =
+            let mut user_settings: crate::database::models::UserSettings =
                 serde_json::from_value(user_settings_json)?;
+            user_settings
+                .validate_durations()
+                .map_err(|e| BackupError::InvalidFormat {
+                    reason: e.to_string(),
+                })?;
+
+            let current_settings = database
+                .get_user_settings()
+                .map_err(|e| {
+                    BackupError::Database(format!("Failed to read current settings: {}", e))
+                })?
+                .unwrap_or_default();
+            Self::apply_strict_mode_rules(&mut user_settings, &current_settings, Utc::now());
 
             database.save_user_settings(&user_settings).map_err(|e| {
                 BackupError::Database(format!("Failed to restore user settings: {}", e))
@@ -342,6 +355,33 @@ impl BackupManager {
         Ok(None)
     }
 
+    /// Restore strict mode the way the settings screen would turn it on or
+    /// off, so a backup can't skip the disable cool-down or shorten the delay
+    /// while strict mode is on (helper method)
+    fn apply_strict_mode_rules(
+        restored: &mut crate::database::models::UserSettings,
+        current: &crate::database::models::UserSettings,
+        now: DateTime<Utc>,
+    ) {
+        let requested = restored.strict_mode;
+
+        if current.strict_mode {
+            restored.strict_disable_delay_seconds = restored
+                .strict_disable_delay_seconds
+                .max(current.strict_disable_delay_seconds);
+        }
+        restored.strict_mode = current.strict_mode;
+        restored.strict_disable_at = current.strict_disable_at;
+
+        // Like update_settings, restoring it as on leaves a pending disable alone
+        if !requested || current.strict_disable_at.is_none() {
+            (restored.strict_mode, restored.strict_disable_at) =
+                crate::handlers::strict_mode_handler::resolve_strict_mode_request(
+                    restored, requested, now,
+                );
+        }
+    }
+
     /// Restore work schedule (helper method)
     fn restore_work_schedule(
         &self,
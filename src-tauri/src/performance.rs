@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Counters for benchmarking the tick loop and database writes, shared by
+/// `AppState` and the `DatabaseManager`. Counting uses relaxed atomics so it
+/// never waits on the code being measured.
+pub struct PerformanceCounters {
+    ticks: AtomicU64,
+    skipped_ticks: AtomicU64,
+    tick_time_micros: AtomicU64,
+    db_writes: AtomicU64,
+    db_retries: AtomicU64,
+    /// Interval the frontend is expected to tick at while a phase runs
+    tick_interval_ms: AtomicU64,
+    last_tick: Mutex<Option<Instant>>,
+    since: Mutex<DateTime<Utc>>,
+}
+
+/// Counter values since `since`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceSnapshot {
+    pub since: DateTime<Utc>,
+    pub ticks: u64,
+    /// Expected ticks that never arrived, e.g. while the webview was throttled
+    pub skipped_ticks: u64,
+    /// Mean time spent in `cycle_tick`; `None` before the first tick
+    pub average_tick_micros: Option<u64>,
+    /// Database calls that changed at least one row
+    pub db_writes: u64,
+    /// Database calls retried because the database was busy
+    pub db_retries: u64,
+}
+
+impl PerformanceCounters {
+    pub fn new() -> Self {
+        Self {
+            ticks: AtomicU64::new(0),
+            skipped_ticks: AtomicU64::new(0),
+            tick_time_micros: AtomicU64::new(0),
+            db_writes: AtomicU64::new(0),
+            db_retries: AtomicU64::new(0),
+            tick_interval_ms: AtomicU64::new(1000),
+            last_tick: Mutex::new(None),
+            since: Mutex::new(Utc::now()),
+        }
+    }
+
+    pub fn set_tick_interval_ms(&self, interval_ms: u32) {
+        self.tick_interval_ms
+            .store(interval_ms.max(1) as u64, Ordering::Relaxed);
+    }
+
+    /// Record a tick of a running phase that started at `started` and has
+    /// just finished. A gap of two or more intervals since the previous tick
+    /// counts the missing ticks as skipped.
+    pub fn record_tick(&self, started: Instant) {
+        let elapsed = started.elapsed();
+        self.ticks.fetch_add(1, Ordering::Relaxed);
+        self.tick_time_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+
+        let interval = Duration::from_millis(self.tick_interval_ms.load(Ordering::Relaxed));
+        if let Ok(mut last_tick) = self.last_tick.lock() {
+            if let Some(previous) = last_tick.replace(started) {
+                let missed = (started.duration_since(previous).as_millis()
+                    / interval.as_millis().max(1)) as u64;
+                if missed >= 2 {
+                    self.skipped_ticks.fetch_add(missed - 1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Forget the previous tick, so the gap while nothing runs isn't
+    /// counted as skipped ticks
+    pub fn clear_last_tick(&self) {
+        if let Ok(mut last_tick) = self.last_tick.lock() {
+            *last_tick = None;
+        }
+    }
+
+    pub fn record_db_write(&self) {
+        self.db_writes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_db_retry(&self) {
+        self.db_retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> PerformanceSnapshot {
+        let ticks = self.ticks.load(Ordering::Relaxed);
+        let tick_time_micros = self.tick_time_micros.load(Ordering::Relaxed);
+
+        PerformanceSnapshot {
+            since: self
+                .since
+                .lock()
+                .map(|since| *since)
+                .unwrap_or_else(|_| Utc::now()),
+            ticks,
+            skipped_ticks: self.skipped_ticks.load(Ordering::Relaxed),
+            average_tick_micros: (ticks > 0).then(|| tick_time_micros / ticks),
+            db_writes: self.db_writes.load(Ordering::Relaxed),
+            db_retries: self.db_retries.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Zero every counter and start measuring from now
+    pub fn reset(&self) {
+        for counter in [
+            &self.ticks,
+            &self.skipped_ticks,
+            &self.tick_time_micros,
+            &self.db_writes,
+            &self.db_retries,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.clear_last_tick();
+        if let Ok(mut since) = self.since.lock() {
+            *since = Utc::now();
+        }
+    }
+}
+
+impl Default for PerformanceCounters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
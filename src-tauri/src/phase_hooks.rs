@@ -0,0 +1,174 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use crate::cycle_orchestrator::{CycleEvent, CyclePhase};
+use crate::database::models::PhaseCommandHooks;
+
+/// How long a hook may run before it is killed
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The command configured for `phase`, if hooks are enabled
+pub fn command_for<'a>(hooks: &'a PhaseCommandHooks, phase: &CyclePhase) -> Option<&'a str> {
+    if !hooks.enabled {
+        return None;
+    }
+
+    match phase {
+        CyclePhase::Focus => hooks.focus.as_deref(),
+        CyclePhase::ShortBreak => hooks.short_break.as_deref(),
+        CyclePhase::LongBreak => hooks.long_break.as_deref(),
+        CyclePhase::Idle => None,
+    }
+}
+
+/// Run the hook for every phase started in `events`
+pub fn run_for_events(hooks: &PhaseCommandHooks, events: &[CycleEvent]) {
+    for event in events {
+        if let CycleEvent::PhaseStarted { phase, .. } = event {
+            if let Some(command) = command_for(hooks, phase) {
+                spawn(phase.clone(), command.to_string());
+            }
+        }
+    }
+}
+
+/// A single token with a URL scheme, like `spotify:playlist:...` or
+/// `https://...`. Deep links are opened with the system handler instead of
+/// going through the shell.
+fn is_deep_link(command: &str) -> bool {
+    if command.chars().any(char::is_whitespace) {
+        return false;
+    }
+
+    match command.split_once(':') {
+        // Two or more characters, so a Windows drive like `C:` isn't a scheme
+        Some((scheme, rest)) => {
+            scheme.len() > 1
+                && !rest.is_empty()
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+fn build_command(command: &str) -> tokio::process::Command {
+    if is_deep_link(command) {
+        #[cfg(target_os = "macos")]
+        let mut process = tokio::process::Command::new("open");
+        // Not `cmd /C start`: cmd would re-parse the link, so `&` or `|` in a
+        // URL would split it into further commands
+        #[cfg(target_os = "windows")]
+        let mut process = {
+            let mut process = tokio::process::Command::new("rundll32");
+            process.arg("url.dll,FileProtocolHandler");
+            process
+        };
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        let mut process = tokio::process::Command::new("xdg-open");
+
+        process.arg(command);
+        return process;
+    }
+
+    // The command is the user's own and is passed as a single argument,
+    // never spliced together with anything else
+    #[cfg(target_os = "windows")]
+    let mut process = {
+        let mut process = tokio::process::Command::new("cmd");
+        process.arg("/C");
+        process
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut process = {
+        let mut process = tokio::process::Command::new("sh");
+        process.arg("-c");
+        process
+    };
+
+    process.arg(command);
+    process
+}
+
+/// Start the hook in the background and kill it if it outlives `HOOK_TIMEOUT`
+fn spawn(phase: CyclePhase, command: String) {
+    tauri::async_runtime::spawn(async move {
+        println!("🎵 [PhaseHooks] Running {} hook: {}", phase, command);
+
+        let mut process = build_command(&command);
+        process
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let child = match process.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                eprintln!("❌ [PhaseHooks] Failed to start {} hook: {}", phase, e);
+                return;
+            }
+        };
+
+        match tokio::time::timeout(HOOK_TIMEOUT, child.wait_with_output()).await {
+            Ok(Ok(output)) if output.status.success() => {
+                println!("✅ [PhaseHooks] {} hook finished", phase);
+            }
+            Ok(Ok(output)) => {
+                eprintln!(
+                    "⚠️ [PhaseHooks] {} hook exited with {}: {}",
+                    phase,
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Ok(Err(e)) => {
+                eprintln!("❌ [PhaseHooks] {} hook failed: {}", phase, e);
+            }
+            Err(_) => {
+                // Dropping the child kills it
+                eprintln!(
+                    "⚠️ [PhaseHooks] {} hook timed out after {}s and was killed",
+                    phase,
+                    HOOK_TIMEOUT.as_secs()
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_deep_links() {
+        assert!(is_deep_link("spotify:playlist:37i9dQZF1DWZeKCadgRdKQ"));
+        assert!(is_deep_link("https://music.example.com/focus"));
+        assert!(!is_deep_link(
+            "osascript -e 'tell application \"Music\" to pause'"
+        ));
+        assert!(!is_deep_link("C:\\Tools\\pause.exe"));
+        assert!(!is_deep_link("playerctl"));
+    }
+
+    #[test]
+    fn test_disabled_hooks_run_nothing() {
+        let mut hooks = PhaseCommandHooks {
+            enabled: false,
+            focus: Some("playerctl play".to_string()),
+            short_break: None,
+            long_break: None,
+        };
+        assert_eq!(command_for(&hooks, &CyclePhase::Focus), None);
+
+        hooks.enabled = true;
+        assert_eq!(
+            command_for(&hooks, &CyclePhase::Focus),
+            Some("playerctl play")
+        );
+        assert_eq!(command_for(&hooks, &CyclePhase::ShortBreak), None);
+    }
+}
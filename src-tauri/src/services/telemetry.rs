@@ -180,6 +180,11 @@ impl TelemetryService {
         self.queue_event(event).await;
     }
 
+    /// Number of events waiting to be flushed
+    pub async fn queued_events(&self) -> usize {
+        self.queue.lock().await.len()
+    }
+
     async fn queue_event(&self, event: PostHogEvent) {
         let mut queue = self.queue.lock().await;
         queue.push(event);
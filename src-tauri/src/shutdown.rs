@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::state::AppState;
+
+/// Longest any single shutdown step may take before it is skipped
+const STEP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Set once the shutdown routine has run, so the exit event doesn't repeat it
+static SHUTDOWN_COMPLETE: AtomicBool = AtomicBool::new(false);
+
+/// Outcome of each shutdown step
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShutdownReport {
+    pub strict_mode_released: bool,
+    pub window_states_saved: bool,
+    pub sessions_closed: usize,
+    pub database_checkpointed: bool,
+    pub errors: Vec<String>,
+}
+
+/// Whether the shutdown routine has already run
+pub fn is_complete() -> bool {
+    SHUTDOWN_COMPLETE.load(Ordering::SeqCst)
+}
+
+/// Run every step needed before quitting, in order.
+///
+/// Strict mode is unlocked first so a failure later on can never leave the
+/// user locked out. Each step runs under a timeout, and a failed step does
+/// not stop the ones after it.
+pub async fn shutdown(app: &AppHandle) -> ShutdownReport {
+    println!("🛑 [Shutdown] Running shutdown routine");
+
+    let mut report = ShutdownReport::default();
+
+    // Let the display sleep again; this never blocks on anything else
+    if let Ok(mut inhibitor) = app.state::<AppState>().display_sleep_inhibitor.lock() {
+        inhibitor.release();
+    }
+
+    // 1. Unlock the system and hide strict mode windows
+    let handle = app.clone();
+    match run_step("Release strict mode", async move {
+        let state = handle.state::<AppState>();
+        let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+        if let Some(orchestrator) = strict_mode_orchestrator.as_mut() {
+            orchestrator.prepare_for_shutdown()?;
+        }
+        Ok(())
+    })
+    .await
+    {
+        Ok(()) => report.strict_mode_released = true,
+        Err(e) => report.errors.push(e),
+    }
+
+    // 2. Save window states
+    let handle = app.clone();
+    match run_step("Save window states", async move {
+        let state = handle.state::<AppState>();
+        let strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+        let Some(orchestrator) = strict_mode_orchestrator.as_ref() else {
+            return Ok(false);
+        };
+
+        let path = crate::config::window_states_path(&handle)?;
+        let window_manager = orchestrator.get_window_manager();
+        let window_manager = window_manager
+            .lock()
+            .map_err(|e| format!("Failed to lock window manager: {}", e))?;
+        window_manager.save_window_states_to_file(&path)?;
+        Ok(true)
+    })
+    .await
+    {
+        Ok(saved) => report.window_states_saved = saved,
+        Err(e) => report.errors.push(e),
+    }
+
+    // 3. Close the active session (and any other left open)
+    let handle = app.clone();
+    match run_step("Close active session", async move {
+        let state = handle.state::<AppState>();
+        state
+            .database
+            .close_incomplete_sessions(None)
+            .map_err(|e| format!("Failed to close sessions: {}", e))
+    })
+    .await
+    {
+        Ok(closed) => report.sessions_closed = closed,
+        Err(e) => report.errors.push(e),
+    }
+
+    // 4. Checkpoint the database last, after every write above
+    let handle = app.clone();
+    match run_step("Checkpoint database", async move {
+        let state = handle.state::<AppState>();
+        state
+            .database
+            .checkpoint()
+            .map_err(|e| format!("Failed to checkpoint database: {}", e))
+    })
+    .await
+    {
+        Ok(()) => report.database_checkpointed = true,
+        Err(e) => report.errors.push(e),
+    }
+
+    SHUTDOWN_COMPLETE.store(true, Ordering::SeqCst);
+
+    if report.errors.is_empty() {
+        println!("✅ [Shutdown] Shutdown routine completed");
+    } else {
+        for error in &report.errors {
+            eprintln!("⚠️ [Shutdown] {}", error);
+        }
+    }
+
+    report
+}
+
+/// Run one shutdown step on its own task, giving up after `STEP_TIMEOUT`
+async fn run_step<T, F>(name: &str, step: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T, String>> + Send + 'static,
+{
+    println!("🛑 [Shutdown] {}", name);
+
+    let task = tauri::async_runtime::spawn(step);
+    match tokio::time::timeout(STEP_TIMEOUT, task).await {
+        Ok(Ok(result)) => result.map_err(|e| format!("{}: {}", name, e)),
+        Ok(Err(e)) => Err(format!("{}: task failed: {}", name, e)),
+        Err(_) => Err(format!("{}: timed out after {:?}", name, STEP_TIMEOUT)),
+    }
+}
+
+/// Flush everything before the frontend calls `exit`
+#[tauri::command]
+pub async fn prepare_for_quit(app: AppHandle) -> Result<ShutdownReport, String> {
+    println!("🛑 [Rust] prepare_for_quit called");
+    Ok(shutdown(&app).await)
+}
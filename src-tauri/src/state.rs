@@ -1,15 +1,26 @@
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 use tokio::sync::Mutex;
 use std::sync::Arc;
 
-use crate::config::{tokens_path, AppConfig};
+use crate::api_models::BreakSession;
+use crate::break_content::{BreakContentProvider, DefaultBreakContent};
+use crate::config::{data_directory, tokens_path, AppConfig};
 use crate::cycle_orchestrator::CycleOrchestrator;
-use crate::database::DatabaseManager;
+use crate::database::{connection::DATABASE_FILE_NAME, DatabaseManager};
+use crate::display_sleep::DisplaySleepInhibitor;
 use crate::domain::tokens::TokenStorage;
 use crate::notification_service::NotificationService;
+use crate::performance::PerformanceCounters;
 use crate::services::{google_oauth::GoogleOAuthService, telemetry::TelemetryService};
+use crate::strict_mode::models::EmergencyHotkeyTest;
 use crate::strict_mode::StrictModeOrchestrator;
 
+/// Lock order: code that holds more than one of the async locks below at a time
+/// takes them as `cycle_orchestrator`, then `notification_service`, then
+/// `strict_mode_orchestrator`, never the other way round. Otherwise two tasks
+/// (e.g. `cycle_tick` and a command) can each hold one lock and wait forever on
+/// the other. Locks taken one at a time, released before the next, can come in
+/// any order.
 pub struct AppState {
     pub oauth_google: Mutex<GoogleOAuthService>,
     pub tokens_storage: TokenStorage,
@@ -19,6 +30,16 @@ pub struct AppState {
     pub notification_service: Mutex<NotificationService>,
     pub strict_mode_orchestrator: Mutex<Option<StrictModeOrchestrator>>,
     pub telemetry_service: Arc<TelemetryService>,
+    /// Picks the activity shown during breaks; replace to change break content
+    pub break_content_provider: Mutex<Box<dyn BreakContentProvider>>,
+    /// Sample break shown by `preview_break_overlay` until it hides again
+    pub break_preview: Mutex<Option<BreakSession>>,
+    /// Emergency hotkey check started by `test_emergency_hotkey`
+    pub emergency_hotkey_test: Mutex<Option<EmergencyHotkeyTest>>,
+    /// Tick and database write counters, shared with `database`
+    pub performance_counters: Arc<PerformanceCounters>,
+    /// Held while a focus phase runs with `prevent_display_sleep_during_focus`
+    pub display_sleep_inhibitor: std::sync::Mutex<DisplaySleepInhibitor>,
 }
 
 impl AppState {
@@ -28,16 +49,19 @@ impl AppState {
         let svc = GoogleOAuthService::new(cfg);
 
         // Initialize database
-        let app_data_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-        let db_path = app_data_dir.join("pausa.db");
+        let data_dir = data_directory(app)?;
+        let db_path = data_dir.join(DATABASE_FILE_NAME);
         let database = DatabaseManager::new(db_path)
             .map_err(|e| format!("Failed to initialize database: {}", e))?;
 
-        // Initialize notification service
-        let notification_service = NotificationService::new();
+        // Initialize notification service, keeping a mute that hasn't run out yet
+        let mut notification_service = NotificationService::new();
+        let performance_counters = database.performance_counters();
+        if let Ok(Some(settings)) = database.get_user_settings() {
+            notification_service.set_muted_until(settings.notifications_muted_until);
+            notification_service.set_sound_config(settings.sound_config.clone());
+            performance_counters.set_tick_interval_ms(settings.tick_interval_ms.max(0) as u32);
+        }
         
         // Initialize telemetry service
         let telemetry_service = Arc::new(TelemetryService::new());
@@ -51,6 +75,11 @@ impl AppState {
             notification_service: Mutex::new(notification_service),
             strict_mode_orchestrator: Mutex::new(None),
             telemetry_service,
+            break_content_provider: Mutex::new(Box::new(DefaultBreakContent)),
+            break_preview: Mutex::new(None),
+            emergency_hotkey_test: Mutex::new(None),
+            performance_counters,
+            display_sleep_inhibitor: std::sync::Mutex::new(DisplaySleepInhibitor::new()),
         })
     }
 }
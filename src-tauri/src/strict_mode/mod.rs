@@ -1,6 +1,8 @@
 pub mod models;
 pub mod orchestrator;
+pub mod shortcut;
 pub mod system_lock_manager;
 
 pub use models::{StrictModeConfig, StrictModeState};
 pub use orchestrator::StrictModeOrchestrator;
+pub use shortcut::Shortcut;
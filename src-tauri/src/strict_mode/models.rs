@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Configuration for strict mode
@@ -9,6 +10,20 @@ pub struct StrictModeConfig {
     pub emergency_key_combination: Option<String>,
     /// Countdown duration before break starts (in seconds)
     pub transition_countdown_seconds: u32,
+    /// Show the always-on-top focus widget during strict focus sessions
+    #[serde(default)]
+    pub show_focus_widget: bool,
+    /// Show the transition countdown before the break overlay
+    #[serde(default = "default_show_break_transition")]
+    pub show_break_transition: bool,
+    /// Wait for `acknowledge_transition` (up to a timeout) before the break
+    #[serde(default)]
+    pub require_ack: bool,
+}
+
+/// Matches `UserSettings`, where it's on to keep the pre-existing flow
+fn default_show_break_transition() -> bool {
+    true
 }
 
 impl Default for StrictModeConfig {
@@ -17,10 +32,23 @@ impl Default for StrictModeConfig {
             enabled: false,
             emergency_key_combination: None,
             transition_countdown_seconds: 10,
+            show_focus_widget: false,
+            show_break_transition: true,
+            require_ack: false,
         }
     }
 }
 
+/// A running `test_emergency_hotkey` check, waiting for the combination to
+/// be pressed until `expires_at`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyHotkeyTest {
+    pub id: String,
+    /// Canonical form, e.g. "Cmd+Shift+E"
+    pub combination: String,
+    pub expires_at: DateTime<Utc>,
+}
+
 /// Current state of strict mode
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StrictModeState {
@@ -3,9 +3,18 @@ use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager};
 
 use super::models::{StrictModeConfig, StrictModeState, StrictModeWindowType};
+use super::shortcut::Shortcut;
 use super::system_lock_manager::SystemLockManager;
 use crate::cycle_orchestrator::CycleEvent;
-use crate::window_manager::WindowManager;
+use crate::window_manager::{WindowManager, WindowType};
+
+/// How long a break waits for `acknowledge_transition` before starting anyway
+const ACK_TIMEOUT_SECONDS: u64 = 60;
+
+/// Delay before checking a freshly shown strict mode window, and how long
+/// its webview gets to answer the liveness probe
+const WINDOW_CHECK_DELAY: std::time::Duration = std::time::Duration::from_secs(3);
+const WINDOW_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
 
 /// Custom error types for StrictModeOrchestrator
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +66,11 @@ pub struct StrictModeOrchestrator {
     app_handle: AppHandle,
     window_manager: Arc<Mutex<WindowManager>>,
     system_lock_manager: Arc<Mutex<SystemLockManager>>,
+    /// Bumped whenever a transition countdown starts or is stopped, so a
+    /// pending countdown can tell whether it is still the current one
+    transition_countdown_id: u64,
+    /// Whether the current break transition is waiting for acknowledgment
+    awaiting_ack: bool,
 }
 
 impl StrictModeOrchestrator {
@@ -74,6 +88,8 @@ impl StrictModeOrchestrator {
             app_handle,
             window_manager,
             system_lock_manager,
+            transition_countdown_id: 0,
+            awaiting_ack: false,
         }
     }
 
@@ -83,7 +99,9 @@ impl StrictModeOrchestrator {
             return Err("Strict mode is already active".to_string());
         }
 
-        self.state.is_active = true;
+        self.set_active(true);
+        self.record_interval(None);
+        self.arm_emergency_hotkey();
 
         // Save state to database
         self.save_state_to_database()?;
@@ -107,7 +125,9 @@ impl StrictModeOrchestrator {
         // Hide any strict mode windows
         self.hide_all_strict_windows()?;
 
-        self.state.is_active = false;
+        self.set_active(false);
+        self.release_emergency_hotkey();
+        self.record_interval(Some("deactivate"));
         self.state.current_window_type = None;
 
         // Save state to database
@@ -118,6 +138,76 @@ impl StrictModeOrchestrator {
         Ok(())
     }
 
+    /// Flip strict mode on or off, locking the closable windows with it
+    fn set_active(&mut self, active: bool) {
+        self.state.is_active = active;
+
+        match self.window_manager.lock() {
+            Ok(window_manager) => window_manager.set_windows_closable(!active),
+            Err(e) => eprintln!(
+                "⚠️ [StrictModeOrchestrator] Failed to lock window manager: {}",
+                e
+            ),
+        }
+    }
+
+    /// Register the emergency hotkey for the stretch that just started, using
+    /// the default combination when none is configured. A failure is logged
+    /// and doesn't stop activation.
+    fn arm_emergency_hotkey(&mut self) {
+        let result = match self.config.emergency_key_combination.clone() {
+            Some(combination) => self.register_emergency_hotkey(combination),
+            None => self.register_default_emergency_key(),
+        };
+
+        if let Err(e) = result {
+            eprintln!(
+                "⚠️ [StrictModeOrchestrator] Failed to register emergency hotkey on activation: {}",
+                e
+            );
+        }
+    }
+
+    /// Release the emergency hotkey so it can't linger once strict mode is
+    /// off. The configured combination is kept for the next activation.
+    fn release_emergency_hotkey(&mut self) {
+        let result = match self.system_lock_manager.lock() {
+            Ok(mut lock_manager) if lock_manager.get_emergency_hotkey().is_some() => {
+                lock_manager.unregister_emergency_hotkey()
+            }
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("Failed to lock system lock manager: {}", e)),
+        };
+
+        if let Err(e) = result {
+            eprintln!(
+                "⚠️ [StrictModeOrchestrator] Failed to release emergency hotkey: {}",
+                e
+            );
+        }
+    }
+
+    /// Open the strict mode stretch used for uptime stats, or close it with
+    /// the reason it ended when `ended_by` is set
+    fn record_interval(&self, ended_by: Option<&str>) {
+        let Some(app_state) = self.app_handle.try_state::<crate::state::AppState>() else {
+            return;
+        };
+
+        let now = chrono::Utc::now();
+        let result = match ended_by {
+            None => app_state.database.start_strict_mode_interval(now),
+            Some(ended_by) => app_state.database.end_strict_mode_interval(now, ended_by),
+        };
+
+        if let Err(e) = result {
+            eprintln!(
+                "⚠️ [StrictModeOrchestrator] Failed to record strict mode interval: {}",
+                e
+            );
+        }
+    }
+
     /// Get the current strict mode state
     pub fn get_state(&self) -> StrictModeState {
         self.state.clone()
@@ -187,6 +277,13 @@ impl StrictModeOrchestrator {
             eprintln!("Warning: Failed to hide menu bar popover: {}", e);
         }
 
+        // Hide the strict focus widget if it was shown
+        if self.config.show_focus_widget {
+            if let Err(e) = window_manager.hide_focus_widget() {
+                eprintln!("Warning: Failed to hide focus widget: {}", e);
+            }
+        }
+
         println!("✅ [StrictModeOrchestrator] All strict mode windows hidden");
         Ok(())
     }
@@ -228,14 +325,22 @@ impl StrictModeOrchestrator {
                             .minimize_to_menu_bar()
                             .map_err(|e| format!("Failed to minimize to menu bar: {}", e))?;
 
+                        // Optionally keep an always-on-top countdown visible during focus
+                        if self.config.show_focus_widget {
+                            if let Err(e) = window_manager.show_focus_widget() {
+                                eprintln!("⚠️ [StrictMode] Failed to show focus widget: {}", e);
+                            }
+                        }
+
                         events.push(StrictModeEvent::MinimizeToMenuBar);
                         self.state.current_window_type = Some(StrictModeWindowType::MenuBarIcon);
                         let _ = self.save_state_to_database();
                     }
                     crate::cycle_orchestrator::CyclePhase::ShortBreak
                     | crate::cycle_orchestrator::CyclePhase::LongBreak => {
-                        // When break starts, show fullscreen break overlay directly
-                        println!("🖥️ [StrictMode] Break starting - showing overlay");
+                        // When break starts, show the transition countdown, or the
+                        // fullscreen break overlay directly if the transition is disabled
+                        println!("🖥️ [StrictMode] Break starting");
 
                         // Ensure main window is minimized first
                         {
@@ -244,6 +349,13 @@ impl StrictModeOrchestrator {
                                 .lock()
                                 .map_err(|e| format!("Failed to lock window manager: {}", e))?;
                             
+                            // The focus widget must not sit on top of the break overlay
+                            if self.config.show_focus_widget {
+                                if let Err(e) = window_manager.hide_focus_widget() {
+                                    eprintln!("⚠️ [StrictMode] Failed to hide focus widget: {}", e);
+                                }
+                            }
+
                             // Minimize main window if not already minimized
                             if let Some(main_window) = self.app_handle.get_webview_window("main") {
                                 if let Ok(is_visible) = main_window.is_visible() {
@@ -256,10 +368,24 @@ impl StrictModeOrchestrator {
                             }
                         } // Release lock before showing break overlay
 
-                        // Show the fullscreen break overlay directly
-                        self.show_fullscreen_break_overlay()?;
-
-                        events.push(StrictModeEvent::ShowBreakTransition);
+                        if self.config.require_ack {
+                            // Hold the break until acknowledge_transition, or the timeout
+                            self.show_break_transition()?;
+                            self.awaiting_ack = true;
+                            self.start_transition_countdown(ACK_TIMEOUT_SECONDS);
+                            events.push(StrictModeEvent::ShowBreakTransition);
+                        } else if self.config.show_break_transition {
+                            // The overlay follows once the countdown runs out, or
+                            // earlier via start_break_from_transition
+                            self.show_break_transition()?;
+                            self.start_transition_countdown(
+                                self.config.transition_countdown_seconds as u64,
+                            );
+                            events.push(StrictModeEvent::ShowBreakTransition);
+                        } else {
+                            self.show_fullscreen_break_overlay()?;
+                            events.push(StrictModeEvent::ShowBreakOverlay);
+                        }
                     }
                     _ => {
                         println!(
@@ -354,9 +480,66 @@ impl StrictModeOrchestrator {
             let _ = self.handle_error(StrictModeError::DatabaseError(e));
         }
 
+        self.schedule_window_check();
         Ok(())
     }
 
+    /// Move from the transition window to the break overlay after `seconds`,
+    /// unless the countdown is stopped or replaced first
+    fn start_transition_countdown(&mut self, seconds: u64) {
+        self.transition_countdown_id += 1;
+        let countdown_id = self.transition_countdown_id;
+        let countdown = std::time::Duration::from_secs(seconds);
+        let app_handle = self.app_handle.clone();
+
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(countdown).await;
+
+            let app_state = app_handle.state::<crate::state::AppState>();
+            let mut orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
+            let Some(orchestrator) = orchestrator_guard.as_mut() else {
+                return;
+            };
+
+            if orchestrator.transition_countdown_id == countdown_id
+                && orchestrator.state.current_window_type
+                    == Some(StrictModeWindowType::BreakTransition)
+            {
+                if let Err(e) = orchestrator.start_break_from_transition() {
+                    eprintln!("❌ [StrictMode] Failed to start break after countdown: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Stop a running transition countdown; the break then waits for
+    /// `start_break_from_transition`
+    pub fn stop_transition_countdown(&mut self) {
+        self.transition_countdown_id += 1;
+    }
+
+    /// Acknowledge a break transition held by `require_ack`.
+    ///
+    /// Continues with the regular countdown if the transition is enabled,
+    /// otherwise goes straight to the break overlay.
+    pub fn acknowledge_transition(&mut self) -> Result<(), String> {
+        if !self.awaiting_ack
+            || self.state.current_window_type != Some(StrictModeWindowType::BreakTransition)
+        {
+            return Err("No break transition is waiting for acknowledgment".to_string());
+        }
+
+        self.awaiting_ack = false;
+        println!("👍 [StrictMode] Break transition acknowledged");
+
+        if self.config.show_break_transition {
+            self.start_transition_countdown(self.config.transition_countdown_seconds as u64);
+            Ok(())
+        } else {
+            self.start_break_from_transition()
+        }
+    }
+
     /// Hide the break transition window
     pub fn hide_break_transition(&mut self) -> Result<(), String> {
 
@@ -374,6 +557,7 @@ impl StrictModeOrchestrator {
 
     /// Start break from transition (after countdown or manual trigger)
     pub fn start_break_from_transition(&mut self) -> Result<(), String> {
+        self.awaiting_ack = false;
 
         // Hide the break transition window first
         self.hide_break_transition()?;
@@ -440,6 +624,7 @@ impl StrictModeOrchestrator {
         }
 
         println!("🖥️ [StrictMode] Break overlay shown");
+        self.schedule_window_check();
         Ok(())
     }
 
@@ -546,7 +731,9 @@ impl StrictModeOrchestrator {
         drop(window_manager);
 
         // Deactivate strict mode
-        self.state.is_active = false;
+        self.set_active(false);
+        self.release_emergency_hotkey();
+        self.record_interval(Some("emergency_exit"));
         self.state.current_window_type = None;
 
         // Save state
@@ -603,6 +790,9 @@ impl StrictModeOrchestrator {
             combination
         );
 
+        // Reject unparseable or weak input outright instead of falling back
+        let combination = Shortcut::parse_emergency_key(&combination)?.to_string();
+
         let result = {
             let mut lock_manager = self
                 .system_lock_manager
@@ -630,6 +820,45 @@ impl StrictModeOrchestrator {
         }
     }
 
+    /// Check a combination with the same rules as onboarding, rejecting weak
+    /// and system shortcuts, and return it in canonical form
+    pub fn validate_emergency_hotkey(combination: &str) -> Result<String, StrictModeError> {
+        Shortcut::parse_emergency_key(combination)
+            .map(|shortcut| shortcut.to_string())
+            .map_err(StrictModeError::HotkeyRegistrationFailed)
+    }
+
+    /// Register a combination chosen by the user, checked with the same rules
+    /// as onboarding first. Unlike `register_emergency_hotkey` a rejected
+    /// combination doesn't fall back to the default key; the current one stays.
+    /// Returns the combination in canonical form.
+    pub fn validate_and_register_emergency_hotkey(
+        &mut self,
+        combination: &str,
+    ) -> Result<String, StrictModeError> {
+        println!(
+            "🔑 [StrictModeOrchestrator] Validating emergency hotkey: {}",
+            combination
+        );
+
+        let combination = Self::validate_emergency_hotkey(combination)?;
+
+        self.system_lock_manager
+            .lock()
+            .map_err(|e| {
+                StrictModeError::General(format!("Failed to lock system lock manager: {}", e))
+            })?
+            .register_emergency_hotkey(combination.clone())
+            .map_err(StrictModeError::HotkeyRegistrationFailed)?;
+
+        self.config.emergency_key_combination = Some(combination.clone());
+        println!(
+            "✅ [StrictModeOrchestrator] Emergency hotkey registered: {}",
+            combination
+        );
+        Ok(combination)
+    }
+
     /// Unregister the emergency hotkey
     pub fn unregister_emergency_hotkey(&mut self) -> Result<(), String> {
         println!("🔑 [StrictModeOrchestrator] Unregistering emergency hotkey");
@@ -655,6 +884,48 @@ impl StrictModeOrchestrator {
         self.system_lock_manager.clone()
     }
 
+    /// Get the window manager shared with this orchestrator
+    pub fn get_window_manager(&self) -> Arc<Mutex<WindowManager>> {
+        self.window_manager.clone()
+    }
+
+    /// Release the system before the app quits
+    ///
+    /// Unlike `deactivate`, strict mode stays active in the saved state so it
+    /// is restored on next launch; only the lock and the windows are released.
+    pub fn prepare_for_shutdown(&mut self) -> Result<(), String> {
+        println!("🛑 [StrictModeOrchestrator] Preparing for shutdown");
+
+        if self.state.is_locked {
+            if let Err(e) = self.unlock_system() {
+                eprintln!(
+                    "⚠️ [StrictModeOrchestrator] Failed to unlock system before quit, forcing: {}",
+                    e
+                );
+                self.system_lock_manager
+                    .lock()
+                    .map_err(|e| format!("Failed to lock system lock manager: {}", e))?
+                    .force_unlock()?;
+                self.state.is_locked = false;
+            }
+        }
+
+        if let Err(e) = self.hide_all_strict_windows() {
+            eprintln!(
+                "⚠️ [StrictModeOrchestrator] Failed to hide windows before quit: {}",
+                e
+            );
+        }
+
+        // Let the app close its own windows on the way out
+        if let Ok(window_manager) = self.window_manager.lock() {
+            window_manager.set_windows_closable(true);
+        }
+
+        self.state.current_window_type = None;
+        self.save_state_to_database()
+    }
+
     /// Save the current strict mode state to the database
     pub fn save_state_to_database(&self) -> Result<(), String> {
         println!("💾 [StrictModeOrchestrator] Saving state to database");
@@ -725,7 +996,7 @@ impl StrictModeOrchestrator {
         });
 
         // Update the orchestrator state
-        self.state.is_active = is_active;
+        self.set_active(is_active);
         self.state.is_locked = is_locked;
         self.state.current_window_type = window_type.clone();
 
@@ -886,7 +1157,9 @@ impl StrictModeOrchestrator {
         }
 
         // Update state
-        self.state.is_active = false;
+        self.set_active(false);
+        self.release_emergency_hotkey();
+        self.record_interval(Some("recovery"));
         self.state.current_window_type = None;
 
         // Try to save state
@@ -981,7 +1254,8 @@ impl StrictModeOrchestrator {
         drop(window_manager);
 
         // Deactivate strict mode
-        self.state.is_active = false;
+        self.set_active(false);
+        self.record_interval(Some("recovery"));
         self.state.current_window_type = None;
 
         // Try to save state
@@ -1094,6 +1368,31 @@ impl StrictModeOrchestrator {
         Ok(())
     }
 
+    /// The strict mode window the state expects to be up, if any
+    pub fn expected_window(&self) -> Option<WindowType> {
+        if self.state.is_locked {
+            return Some(WindowType::BreakOverlay);
+        }
+        match self.state.current_window_type {
+            Some(StrictModeWindowType::BreakTransition) => Some(WindowType::BreakTransition),
+            Some(StrictModeWindowType::FullscreenBreakOverlay) => Some(WindowType::BreakOverlay),
+            Some(StrictModeWindowType::MenuBarPopover) => Some(WindowType::MenuBarPopover),
+            _ => None,
+        }
+    }
+
+    /// Run `repair_windows` once the window just shown had time to load
+    fn schedule_window_check(&self) {
+        let app_handle = self.app_handle.clone();
+
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(WINDOW_CHECK_DELAY).await;
+            if let Err(e) = repair_windows(&app_handle).await {
+                eprintln!("❌ [StrictMode] Window check failed: {}", e);
+            }
+        });
+    }
+
     /// Get diagnostic information for debugging
     /// Returns a detailed snapshot of the current state
     pub fn get_diagnostics(&self) -> StrictModeDiagnostics {
@@ -1126,12 +1425,33 @@ impl StrictModeOrchestrator {
             }
         };
 
+        let mut warnings = Vec::new();
+        if let Some(hotkey) = lock_manager_status
+            .as_ref()
+            .and_then(|status| status.emergency_hotkey.as_ref())
+        {
+            if !self.state.is_active {
+                warnings.push(format!(
+                    "Emergency hotkey {} is still registered while strict mode is inactive",
+                    hotkey
+                ));
+            }
+        }
+
+        let pending_disable_at = self
+            .app_handle
+            .try_state::<crate::state::AppState>()
+            .and_then(|app_state| app_state.database.get_user_settings().ok().flatten())
+            .and_then(|settings| settings.strict_disable_at);
+
         StrictModeDiagnostics {
             state: self.state.clone(),
             config: self.config.clone(),
             windows_status,
             lock_manager_status,
             state_validation: self.validate_state().is_ok(),
+            warnings,
+            pending_disable_at,
         }
     }
 
@@ -1211,6 +1531,10 @@ pub struct StrictModeDiagnostics {
     pub windows_status: StrictModeWindowsStatus,
     pub lock_manager_status: Option<StrictModeLockStatus>,
     pub state_validation: bool,
+    /// Inconsistencies found while collecting the diagnostics
+    pub warnings: Vec<String>,
+    /// When turning strict mode off in settings takes effect, if pending
+    pub pending_disable_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Status of strict mode windows
@@ -1228,3 +1552,53 @@ pub struct StrictModeLockStatus {
     pub is_locked: bool,
     pub emergency_hotkey: Option<String>,
 }
+
+/// Check the window the strict mode state expects with `validate_state` and
+/// a liveness probe, and rebuild it if it is missing or does not answer.
+/// Returns whether a window had to be recreated.
+///
+/// The orchestrator is only locked to read the state, never while probing
+/// or waiting for the old window to go away.
+pub async fn repair_windows(app_handle: &AppHandle) -> Result<bool, String> {
+    let app_state = app_handle.state::<crate::state::AppState>();
+
+    let (expected, inconsistency, window_manager) = {
+        let orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
+        let orchestrator = orchestrator_guard
+            .as_ref()
+            .ok_or_else(|| "StrictModeOrchestrator not initialized".to_string())?;
+        (
+            orchestrator.expected_window(),
+            orchestrator.validate_state().err(),
+            orchestrator.get_window_manager(),
+        )
+    };
+
+    let Some(window_type) = expected else {
+        return inconsistency.map_or(Ok(false), Err);
+    };
+
+    let reason = match inconsistency {
+        Some(inconsistency) => inconsistency,
+        None => {
+            if crate::window_manager::probe_window(app_handle, window_type, WINDOW_PROBE_TIMEOUT)
+                .await
+            {
+                return Ok(false);
+            }
+            format!("{:?} is not responding", window_type)
+        }
+    };
+
+    println!(
+        "🔧 [StrictModeOrchestrator] Recovering from '{}' by recreating {:?}",
+        reason, window_type
+    );
+    crate::window_manager::rebuild_window(&window_manager, app_handle, window_type, true).await?;
+
+    let orchestrator_guard = app_state.strict_mode_orchestrator.lock().await;
+    if let Some(orchestrator) = orchestrator_guard.as_ref() {
+        orchestrator.validate_state()?;
+    }
+    Ok(true)
+}
@@ -0,0 +1,308 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::onboarding::OnboardingValidator;
+
+/// Combinations the operating system keeps for itself. Binding one either
+/// never fires or takes the system action away from the user.
+const SYSTEM_SHORTCUTS: &[&str] = &[
+    "Cmd+Q",
+    "Cmd+W",
+    "Cmd+H",
+    "Cmd+M",
+    "Cmd+Tab",
+    "Cmd+Space",
+    "Cmd+Alt+Esc",
+    "Cmd+Shift+3",
+    "Cmd+Shift+4",
+    "Cmd+Shift+5",
+    "Ctrl+Alt+Delete",
+    "Ctrl+Shift+Esc",
+    "Alt+Tab",
+    "Alt+F4",
+];
+
+/// Whether a candidate shortcut can be bound
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ShortcutAvailability {
+    Available { shortcut: String },
+    InternalConflict { shortcut: String, action: String },
+    SystemConflict { shortcut: String },
+    Invalid { reason: String },
+}
+
+/// Modifier keys, declared in the order they are written out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Modifier {
+    Cmd,
+    Ctrl,
+    Alt,
+    Shift,
+}
+
+impl Modifier {
+    fn parse(token: &str) -> Option<Self> {
+        match token.to_ascii_lowercase().as_str() {
+            "cmd" | "command" | "meta" | "super" | "⌘" => Some(Modifier::Cmd),
+            "ctrl" | "control" | "⌃" => Some(Modifier::Ctrl),
+            "alt" | "option" | "opt" | "⌥" => Some(Modifier::Alt),
+            "shift" | "⇧" => Some(Modifier::Shift),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Modifier::Cmd => write!(f, "Cmd"),
+            Modifier::Ctrl => write!(f, "Ctrl"),
+            Modifier::Alt => write!(f, "Alt"),
+            Modifier::Shift => write!(f, "Shift"),
+        }
+    }
+}
+
+/// A key combination in canonical form: modifiers deduplicated and ordered
+/// Cmd, Ctrl, Alt, Shift, followed by a single key name.
+/// "cmd+shift+esc", "Shift + Cmd + Escape" and "⌘+⇧+Esc" all become "Cmd+Shift+Esc".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    pub modifiers: Vec<Modifier>,
+    pub key: String,
+}
+
+impl Shortcut {
+    /// Parse an emergency key combination and reject weak or system shortcuts
+    pub fn parse_emergency_key(input: &str) -> Result<Self, String> {
+        let shortcut: Shortcut = input.parse()?;
+
+        if !OnboardingValidator::new().is_valid_emergency_key(&shortcut.to_string()) {
+            return Err(format!(
+                "'{}' is too common to use as the emergency key",
+                shortcut
+            ));
+        }
+
+        if let ShortcutAvailability::SystemConflict { shortcut: reserved } =
+            check_availability(input, &[])
+        {
+            return Err(format!(
+                "'{}' is reserved by the system and can't be the emergency key",
+                reserved
+            ));
+        }
+
+        Ok(shortcut)
+    }
+}
+
+impl Shortcut {
+    /// The same combination as the global shortcut plugin understands it,
+    /// for registering it with the operating system
+    pub fn to_global_shortcut(&self) -> Result<tauri_plugin_global_shortcut::Shortcut, String> {
+        self.to_string()
+            .parse()
+            .map_err(|e| format!("'{}' can't be used as a global shortcut: {}", self, e))
+    }
+}
+
+/// Check `input` against Pausa's own bindings (`(action, shortcut)` pairs)
+/// and the shortcuts reserved by the operating system
+pub fn check_availability(input: &str, registered: &[(&str, Shortcut)]) -> ShortcutAvailability {
+    let shortcut = match input.parse::<Shortcut>() {
+        Ok(shortcut) => shortcut,
+        Err(reason) => return ShortcutAvailability::Invalid { reason },
+    };
+
+    if let Some((action, _)) = registered.iter().find(|(_, bound)| *bound == shortcut) {
+        return ShortcutAvailability::InternalConflict {
+            shortcut: shortcut.to_string(),
+            action: action.to_string(),
+        };
+    }
+
+    let is_system = SYSTEM_SHORTCUTS
+        .iter()
+        .filter_map(|reserved| reserved.parse::<Shortcut>().ok())
+        .any(|reserved| reserved == shortcut);
+    if is_system {
+        return ShortcutAvailability::SystemConflict {
+            shortcut: shortcut.to_string(),
+        };
+    }
+
+    ShortcutAvailability::Available {
+        shortcut: shortcut.to_string(),
+    }
+}
+
+/// Canonical name for a non-modifier key
+fn parse_key(token: &str) -> Option<String> {
+    let mut chars = token.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return c
+            .is_ascii_graphic()
+            .then(|| c.to_ascii_uppercase().to_string());
+    }
+
+    let lower = token.to_ascii_lowercase();
+    let named = match lower.as_str() {
+        "esc" | "escape" => "Esc",
+        "space" => "Space",
+        "tab" => "Tab",
+        "enter" | "return" => "Enter",
+        "backspace" => "Backspace",
+        "delete" | "del" => "Delete",
+        "up" => "Up",
+        "down" => "Down",
+        "left" => "Left",
+        "right" => "Right",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" => "PageUp",
+        "pagedown" => "PageDown",
+        _ => {
+            let number = lower.strip_prefix('f')?.parse::<u8>().ok()?;
+            return (1..=24).contains(&number).then(|| format!("F{}", number));
+        }
+    };
+
+    Some(named.to_string())
+}
+
+impl FromStr for Shortcut {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Emergency hotkey combination cannot be empty".to_string());
+        }
+
+        let mut modifiers = Vec::new();
+        let mut key = None;
+
+        for token in input.split('+').map(str::trim) {
+            if token.is_empty() {
+                return Err(format!("'{}' has an empty key between '+' signs", input));
+            }
+
+            if let Some(modifier) = Modifier::parse(token) {
+                modifiers.push(modifier);
+            } else if let Some(name) = parse_key(token) {
+                if key.replace(name).is_some() {
+                    return Err(format!("'{}' has more than one non-modifier key", input));
+                }
+            } else {
+                return Err(format!("Unknown key '{}' in '{}'", token, input));
+            }
+        }
+
+        let key = key.ok_or_else(|| format!("'{}' has no key besides modifiers", input))?;
+
+        if modifiers.is_empty() {
+            return Err(
+                "Emergency hotkey must include at least one modifier key (Cmd, Ctrl, Alt, Shift)"
+                    .to_string(),
+            );
+        }
+
+        modifiers.sort();
+        modifiers.dedup();
+
+        Ok(Shortcut { modifiers, key })
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{}+", modifier)?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri_plugin_global_shortcut::{Code, Modifiers};
+
+    #[test]
+    fn test_normalizes_case_aliases_and_order() {
+        for input in [
+            "cmd+shift+esc",
+            "Shift + Cmd + Escape",
+            "⌘+⇧+Esc",
+            "CMD+Shift+Shift+esc",
+        ] {
+            let shortcut: Shortcut = input.parse().unwrap();
+            assert_eq!(shortcut.to_string(), "Cmd+Shift+Esc", "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_rejects_unparseable_input() {
+        assert!("".parse::<Shortcut>().is_err());
+        assert!("E".parse::<Shortcut>().is_err());
+        assert!("Cmd+Shift".parse::<Shortcut>().is_err());
+        assert!("Cmd++E".parse::<Shortcut>().is_err());
+        assert!("Cmd+E+F".parse::<Shortcut>().is_err());
+        assert!("Cmd+Banana".parse::<Shortcut>().is_err());
+    }
+
+    #[test]
+    fn test_reports_shortcut_availability() {
+        let emergency: Shortcut = "Cmd+Shift+E".parse().unwrap();
+        let registered = [("emergency_exit", emergency)];
+
+        assert_eq!(
+            check_availability("shift+cmd+e", &registered),
+            ShortcutAvailability::InternalConflict {
+                shortcut: "Cmd+Shift+E".to_string(),
+                action: "emergency_exit".to_string(),
+            }
+        );
+        assert_eq!(
+            check_availability("cmd+tab", &registered),
+            ShortcutAvailability::SystemConflict {
+                shortcut: "Cmd+Tab".to_string(),
+            }
+        );
+        assert!(matches!(
+            check_availability("Cmd+Banana", &registered),
+            ShortcutAvailability::Invalid { .. }
+        ));
+        assert_eq!(
+            check_availability("ctrl+alt+p", &registered),
+            ShortcutAvailability::Available {
+                shortcut: "Ctrl+Alt+P".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_rejects_weak_emergency_keys() {
+        assert!(Shortcut::parse_emergency_key("cmd+q").is_err());
+        assert!(Shortcut::parse_emergency_key("ctrl+c").is_err());
+        assert!(Shortcut::parse_emergency_key("cmd+tab").is_err());
+        assert!(Shortcut::parse_emergency_key("ctrl+alt+del").is_err());
+        assert!(Shortcut::parse_emergency_key("cmd+shift+e").is_ok());
+    }
+
+    #[test]
+    fn test_converts_to_global_shortcut() {
+        let shortcut: Shortcut = "cmd+shift+esc".parse().unwrap();
+        let global = shortcut.to_global_shortcut().unwrap();
+        assert_eq!(global.mods, Modifiers::SUPER | Modifiers::SHIFT);
+        assert_eq!(global.key, Code::Escape);
+
+        // A key the plugin has no code for can't be registered
+        let shortcut: Shortcut = "Ctrl+!".parse().unwrap();
+        assert!(shortcut.to_global_shortcut().is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Theme};
+
+use crate::database::models::AppTheme;
+use crate::state::AppState;
+
+/// Payload of the `theme-changed` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeChanged {
+    /// The configured theme
+    pub theme: AppTheme,
+    /// What windows should render: always `Light` or `Dark`
+    pub effective: AppTheme,
+}
+
+/// Apply a theme to every window and tell the frontend about it
+pub fn apply_theme(app_handle: &AppHandle, theme: AppTheme) -> Result<(), String> {
+    // `None` lets windows follow the OS appearance
+    app_handle.set_theme(match theme {
+        AppTheme::Light => Some(Theme::Light),
+        AppTheme::Dark => Some(Theme::Dark),
+        AppTheme::System => None,
+    });
+
+    let effective = match theme {
+        AppTheme::System => system_theme(app_handle),
+        theme => theme,
+    };
+    emit_theme_changed(app_handle, theme, effective)?;
+
+    println!("✅ [Theme] Applied theme: {} ({})", theme, effective);
+    Ok(())
+}
+
+/// Propagate an OS appearance change when the theme follows the system
+pub fn handle_system_theme_change(app_handle: &AppHandle, native: Theme) {
+    let theme = app_handle
+        .state::<AppState>()
+        .database
+        .get_user_settings()
+        .ok()
+        .flatten()
+        .map(|settings| settings.theme)
+        .unwrap_or_default();
+
+    if theme != AppTheme::System {
+        return;
+    }
+
+    if let Err(e) = emit_theme_changed(app_handle, theme, from_native(native)) {
+        eprintln!("⚠️ [Theme] {}", e);
+    }
+}
+
+fn emit_theme_changed(
+    app_handle: &AppHandle,
+    theme: AppTheme,
+    effective: AppTheme,
+) -> Result<(), String> {
+    app_handle
+        .emit("theme-changed", ThemeChanged { theme, effective })
+        .map_err(|e| format!("Failed to emit theme change: {}", e))
+}
+
+/// Current OS appearance, read from the main window (light if unknown)
+fn system_theme(app_handle: &AppHandle) -> AppTheme {
+    app_handle
+        .get_webview_window("main")
+        .and_then(|window| window.theme().ok())
+        .map(from_native)
+        .unwrap_or(AppTheme::Light)
+}
+
+fn from_native(theme: Theme) -> AppTheme {
+    match theme {
+        Theme::Dark => AppTheme::Dark,
+        _ => AppTheme::Light,
+    }
+}
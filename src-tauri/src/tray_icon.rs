@@ -0,0 +1,151 @@
+use tauri::image::Image;
+use tauri::{AppHandle, Manager};
+
+use crate::cycle_orchestrator::CyclePhase;
+use crate::database::models::{TrayClickAction, TrayIconStyle};
+use crate::handlers::cycle_handler;
+use crate::state::AppState;
+use crate::strict_mode::models::StrictModeWindowType;
+
+/// Id of the tray icon declared in tauri.conf.json
+pub const MAIN_TRAY_ID: &str = "main-tray";
+
+/// Apply a tray icon style to the main tray icon
+pub fn apply_tray_icon_style(app_handle: &AppHandle, style: TrayIconStyle) -> Result<(), String> {
+    let tray = app_handle
+        .tray_by_id(MAIN_TRAY_ID)
+        .ok_or_else(|| format!("Tray icon '{}' not found", MAIN_TRAY_ID))?;
+
+    let (icon, as_template) = icon_for_style(style);
+
+    tray.set_icon(Some(icon))
+        .map_err(|e| format!("Failed to set tray icon: {}", e))?;
+
+    // Template images are only meaningful on macOS, where the menu bar
+    // recolors them to match the light/dark appearance
+    #[cfg(target_os = "macos")]
+    tray.set_icon_as_template(as_template)
+        .map_err(|e| format!("Failed to set tray icon template mode: {}", e))?;
+    #[cfg(not(target_os = "macos"))]
+    let _ = as_template;
+
+    println!("✅ [TrayIcon] Applied tray icon style: {}", style);
+    Ok(())
+}
+
+/// Pick the bundled icon asset for a style, and whether it is a template image
+fn icon_for_style(style: TrayIconStyle) -> (Image<'static>, bool) {
+    match style {
+        #[cfg(target_os = "macos")]
+        TrayIconStyle::Auto => (tauri::include_image!("icons/tray/tray-dark.png"), true),
+        #[cfg(not(target_os = "macos"))]
+        TrayIconStyle::Auto => (tauri::include_image!("icons/32x32.png"), false),
+        TrayIconStyle::Light => (tauri::include_image!("icons/tray/tray-light.png"), false),
+        TrayIconStyle::Dark => (tauri::include_image!("icons/tray/tray-dark.png"), false),
+        TrayIconStyle::Colorful => (tauri::include_image!("icons/32x32.png"), false),
+    }
+}
+
+/// Run the configured left-click action on the tray icon.
+///
+/// Nothing happens while a strict mode break is on screen, whatever the setting.
+pub fn handle_left_click(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+
+        let action = state
+            .database
+            .get_user_settings()
+            .ok()
+            .flatten()
+            .map(|settings| settings.tray_click_action)
+            .unwrap_or_default();
+
+        let in_strict_break = {
+            let strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+            strict_mode_orchestrator
+                .as_ref()
+                .map(|orchestrator| {
+                    let strict_state = orchestrator.get_state();
+                    strict_state.is_active
+                        && (strict_state.is_locked
+                            || matches!(
+                                strict_state.current_window_type,
+                                Some(StrictModeWindowType::BreakTransition)
+                                    | Some(StrictModeWindowType::FullscreenBreakOverlay)
+                            ))
+                })
+                .unwrap_or(false)
+        };
+
+        if in_strict_break {
+            println!("🔒 [TrayIcon] Ignoring tray click during strict break");
+            return;
+        }
+
+        let result = match action {
+            TrayClickAction::None => Ok(()),
+            TrayClickAction::ShowMainWindow => {
+                toggle_main_window(&app_handle);
+                Ok(())
+            }
+            TrayClickAction::ShowPopover => {
+                let mut strict_mode_orchestrator = state.strict_mode_orchestrator.lock().await;
+                match strict_mode_orchestrator.as_mut() {
+                    Some(orchestrator) => orchestrator.show_menu_bar_popover(),
+                    // The popover belongs to strict mode; fall back to the main window
+                    None => {
+                        toggle_main_window(&app_handle);
+                        Ok(())
+                    }
+                }
+            }
+            TrayClickAction::ToggleFocus => toggle_focus(&app_handle).await,
+        };
+
+        if let Err(e) = result {
+            eprintln!("❌ [TrayIcon] Tray click action {} failed: {}", action, e);
+        }
+    });
+}
+
+/// Show the main window, or hide it if it's already visible
+fn toggle_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+/// Start a focus session when idle, otherwise pause or resume the current one
+async fn toggle_focus(app_handle: &AppHandle) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+
+    let cycle_state = {
+        let cycle_orchestrator = state.cycle_orchestrator.lock().await;
+        cycle_orchestrator
+            .as_ref()
+            .map(|orchestrator| orchestrator.get_state())
+            .ok_or_else(|| "Cycle orchestrator not initialized".to_string())?
+    };
+
+    match (cycle_state.phase, cycle_state.is_running) {
+        (CyclePhase::Idle, _) => {
+            cycle_handler::start_focus_session(None, state, app_handle.clone()).await?;
+        }
+        (_, true) => {
+            cycle_handler::pause_cycle(state).await?;
+        }
+        (_, false) => {
+            cycle_handler::resume_cycle(state).await?;
+        }
+    }
+
+    Ok(())
+}
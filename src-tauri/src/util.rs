@@ -0,0 +1,165 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Display style for a remaining-time value
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeFormatStyle {
+    /// Clock style, e.g. "12:34" or "1:30:00" (tray, status line)
+    #[default]
+    Clock,
+    /// Spelled out, e.g. "12m 34s" (focus widget)
+    Long,
+    /// Coarse, e.g. "12m" (narrow surfaces)
+    Compact,
+}
+
+/// Format a duration in MM:SS format, or H:MM:SS once it reaches an hour
+pub fn format_time(seconds: u32) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
+
+/// Format a duration spelled out, e.g. "12m 34s" or "1h 5m 0s"
+pub fn format_long(seconds: u32) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, secs)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Format a duration at minute precision, e.g. "12m" or "1h 5m"
+///
+/// Durations under a minute fall back to seconds so the last minute
+/// doesn't read as "0m".
+pub fn format_compact(seconds: u32) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Format a duration in the given style
+pub fn format_duration(seconds: u32, style: TimeFormatStyle) -> String {
+    match style {
+        TimeFormatStyle::Clock => format_time(seconds),
+        TimeFormatStyle::Long => format_long(seconds),
+        TimeFormatStyle::Compact => format_compact(seconds),
+    }
+}
+
+/// Parse a timestamp stored as text.
+///
+/// Accepts RFC 3339 (what the frontend and `to_rfc3339` write), SQLite's
+/// `YYYY-MM-DD HH:MM:SS[.fff]` with or without an offset (no offset means
+/// UTC, as with `CURRENT_TIMESTAMP`), and Unix epoch milliseconds.
+pub fn parse_stored_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(raw) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    if let Ok(parsed) = DateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%.f%:z") {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    if let Ok(parsed) = NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%.f") {
+        return Some(parsed.and_utc());
+    }
+
+    raw.parse::<i64>()
+        .ok()
+        .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0), "00:00");
+        assert_eq!(format_time(59), "00:59");
+        assert_eq!(format_time(754), "12:34");
+        assert_eq!(format_time(3599), "59:59");
+    }
+
+    #[test]
+    fn test_format_time_with_hours() {
+        assert_eq!(format_time(3600), "1:00:00");
+        assert_eq!(format_time(5400), "1:30:00");
+        assert_eq!(format_time(7384), "2:03:04");
+        assert_eq!(format_time(36000), "10:00:00");
+    }
+
+    #[test]
+    fn test_format_long() {
+        assert_eq!(format_long(0), "0s");
+        assert_eq!(format_long(59), "59s");
+        assert_eq!(format_long(754), "12m 34s");
+        assert_eq!(format_long(3600), "1h 0m 0s");
+        assert_eq!(format_long(5430), "1h 30m 30s");
+    }
+
+    #[test]
+    fn test_format_compact() {
+        assert_eq!(format_compact(0), "0s");
+        assert_eq!(format_compact(59), "59s");
+        assert_eq!(format_compact(754), "12m");
+        assert_eq!(format_compact(3600), "1h 0m");
+        assert_eq!(format_compact(5430), "1h 30m");
+    }
+
+    #[test]
+    fn test_format_duration_dispatches_on_style() {
+        assert_eq!(format_duration(754, TimeFormatStyle::Clock), "12:34");
+        assert_eq!(format_duration(754, TimeFormatStyle::Long), "12m 34s");
+        assert_eq!(format_duration(754, TimeFormatStyle::Compact), "12m");
+    }
+
+    #[test]
+    fn test_parse_stored_timestamp_formats() {
+        let expected = Utc.with_ymd_and_hms(2024, 3, 3, 14, 5, 0).unwrap();
+
+        for raw in [
+            "2024-03-03T14:05:00.000Z",
+            "2024-03-03T16:05:00+02:00",
+            "2024-03-03 14:05:00",
+            "2024-03-03 14:05:00.000+00:00",
+            " 1709474700000 ",
+        ] {
+            assert_eq!(
+                parse_stored_timestamp(raw),
+                Some(expected),
+                "raw: {:?}",
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_stored_timestamp_rejects_garbage() {
+        assert_eq!(parse_stored_timestamp(""), None);
+        assert_eq!(parse_stored_timestamp("yesterday"), None);
+        assert_eq!(parse_stored_timestamp("2024-13-40 25:00:00"), None);
+    }
+}
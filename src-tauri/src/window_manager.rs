@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tauri::{
-    AppHandle, LogicalPosition, Manager, Position, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+    AppHandle, Emitter, Listener, LogicalPosition, Manager, Position, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, WindowEvent,
 };
 // use tauri_plugin_positioner::{Position as PositionerPosition, WindowExt};
 
@@ -27,8 +30,37 @@ impl WindowType {
             WindowType::BreakTransition => "break-transition",
         }
     }
+
+    /// Window type for a label produced by `label`
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "command-palette" => Some(WindowType::CommandPalette),
+            "focus-widget" => Some(WindowType::FocusWidget),
+            "break-overlay" => Some(WindowType::BreakOverlay),
+            "settings" => Some(WindowType::Settings),
+            "menu-bar-popover" => Some(WindowType::MenuBarPopover),
+            "break-transition" => Some(WindowType::BreakTransition),
+            _ => None,
+        }
+    }
 }
 
+/// How long `rebuild_window` waits for a destroyed window to go away
+const RECREATE_WAIT_ATTEMPTS: u32 = 40;
+const RECREATE_WAIT_STEP: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Events for `probe_window`; the frontend answers each ping with a pong
+/// carrying the same nonce
+const PING_EVENT: &str = "window-ping";
+const PONG_EVENT: &str = "window-pong";
+
+/// Windows the user must not be able to close while strict mode is active
+const CLOSE_GUARDED_WINDOWS: [WindowType; 3] = [
+    WindowType::FocusWidget,
+    WindowType::BreakOverlay,
+    WindowType::BreakTransition,
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowPosition {
     pub x: f64,
@@ -52,9 +84,28 @@ impl Default for WindowState {
     }
 }
 
+/// Live geometry of a webview window, as reported by the OS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub label: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub monitor_index: Option<usize>,
+    pub monitor_name: Option<String>,
+    pub is_visible: bool,
+    pub is_fullscreen: bool,
+}
+
 pub struct WindowManager {
     app_handle: AppHandle,
     window_states: Arc<Mutex<HashMap<WindowType, WindowState>>>,
+    /// Read by the close handlers of guarded windows
+    windows_closable: Arc<AtomicBool>,
+    /// Labels of the windows that already have a close handler
+    close_guarded: Mutex<HashSet<String>>,
 }
 
 impl WindowManager {
@@ -62,7 +113,79 @@ impl WindowManager {
         Self {
             app_handle,
             window_states: Arc::new(Mutex::new(HashMap::new())),
+            windows_closable: Arc::new(AtomicBool::new(true)),
+            close_guarded: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Allow or block closing the focus widget and break windows.
+    ///
+    /// While blocked, close requests are cancelled and logged as bypass
+    /// attempts. Windows created later pick up the current setting.
+    pub fn set_windows_closable(&self, closable: bool) {
+        println!("🪟 [WindowManager] Windows closable: {}", closable);
+        self.windows_closable.store(closable, Ordering::SeqCst);
+
+        if !closable {
+            for window_type in CLOSE_GUARDED_WINDOWS {
+                if let Some(window) = self.app_handle.get_webview_window(window_type.label()) {
+                    self.guard_close(&window);
+                }
+            }
+        }
+    }
+
+    /// Attach the close handler to a window, once
+    fn guard_close(&self, window: &WebviewWindow) {
+        let label = window.label().to_string();
+        let newly_guarded = self
+            .close_guarded
+            .lock()
+            .map(|mut guarded| guarded.insert(label.clone()))
+            .unwrap_or(false);
+        if !newly_guarded {
+            return;
         }
+
+        let closable = self.windows_closable.clone();
+        let app_handle = self.app_handle.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                if closable.load(Ordering::SeqCst) {
+                    return;
+                }
+
+                api.prevent_close();
+                println!(
+                    "🚫 [WindowManager] Blocked closing '{}' in strict mode",
+                    label
+                );
+
+                let app_handle = app_handle.clone();
+                let label = label.clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = app_handle.state::<crate::state::AppState>();
+                    let session_id = state
+                        .cycle_orchestrator
+                        .lock()
+                        .await
+                        .as_ref()
+                        .and_then(|orchestrator| orchestrator.get_state().session_id)
+                        .unwrap_or_default();
+
+                    if let Err(e) = crate::handlers::cycle_handler::log_bypass_attempt(
+                        session_id,
+                        format!("window_close:{}", label),
+                        chrono::Utc::now().to_rfc3339(),
+                        state,
+                    )
+                    .await
+                    {
+                        eprintln!("❌ [WindowManager] Failed to log close attempt: {}", e);
+                    }
+                });
+            }
+        });
     }
 
     /// Show the command palette window
@@ -292,6 +415,18 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Show a specific window type
+    pub fn show_window(&self, window_type: WindowType) -> Result<(), Box<dyn std::error::Error>> {
+        match window_type {
+            WindowType::CommandPalette => self.show_command_palette(),
+            WindowType::FocusWidget => self.show_focus_widget(),
+            WindowType::BreakOverlay => self.show_break_overlay(),
+            WindowType::Settings => self.show_settings(),
+            WindowType::MenuBarPopover => self.show_menu_bar_popover(),
+            WindowType::BreakTransition => self.show_break_transition(),
+        }
+    }
+
     /// Hide a specific window type
     pub fn hide_window(&self, window_type: WindowType) -> Result<(), Box<dyn std::error::Error>> {
         match window_type {
@@ -325,6 +460,9 @@ impl WindowManager {
 
         // Try to get existing window
         if let Some(window) = self.app_handle.get_webview_window(label) {
+            if CLOSE_GUARDED_WINDOWS.contains(&window_type) {
+                self.guard_close(&window);
+            }
             return Ok(window);
         }
 
@@ -421,9 +559,59 @@ impl WindowManager {
             .visible(false)
             .build()?,
         };
+
+        if CLOSE_GUARDED_WINDOWS.contains(&window_type) {
+            self.guard_close(&window);
+        }
+
         Ok(window)
     }
 
+    /// Destroy the window of `window_type`, if there is one, as the first
+    /// half of `rebuild_window`. Returns whether it was visible.
+    ///
+    /// The label is only released once the event loop handles the destroy,
+    /// so the caller waits for that before `finish_rebuild`.
+    pub fn destroy_for_rebuild(
+        &self,
+        window_type: WindowType,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let label = window_type.label();
+        let was_visible = self.is_window_visible(window_type);
+
+        if let Some(window) = self.app_handle.get_webview_window(label) {
+            println!("♻️ [WindowManager] Destroying '{}' to recreate it", label);
+            window.destroy()?;
+        }
+
+        // The close handler went away with the old window
+        if let Ok(mut guarded) = self.close_guarded.lock() {
+            guarded.remove(label);
+        }
+        self.update_window_state(window_type, |state| {
+            state.is_visible = false;
+            state.monitor_index = None;
+        });
+
+        Ok(was_visible)
+    }
+
+    /// Build the window of `window_type` again after `destroy_for_rebuild`;
+    /// its saved position is kept
+    pub fn finish_rebuild(
+        &self,
+        window_type: WindowType,
+        show: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.get_or_create_window(window_type)?;
+        println!("✅ [WindowManager] Recreated '{}'", window_type.label());
+
+        if show {
+            self.show_window(window_type)?;
+        }
+        Ok(())
+    }
+
     /// Update window state
     fn update_window_state<F>(&self, window_type: WindowType, updater: F)
     where
@@ -469,6 +657,35 @@ impl WindowManager {
         }
     }
 
+    /// Write window states to disk so they survive a restart
+    pub fn save_window_states_to_file(&self, path: &Path) -> Result<(), String> {
+        let states = self.get_all_window_states();
+        let json = serde_json::to_string_pretty(&states)
+            .map_err(|e| format!("Failed to serialize window states: {}", e))?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create window states directory: {}", e))?;
+        }
+
+        std::fs::write(path, json).map_err(|e| format!("Failed to write window states: {}", e))
+    }
+
+    /// Load window states written by `save_window_states_to_file`, if any
+    pub fn load_window_states_from_file(&self, path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read window states: {}", e))?;
+        let states: HashMap<WindowType, WindowState> = serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse window states: {}", e))?;
+
+        self.restore_window_states(states);
+        Ok(())
+    }
+
     /// Handle window drag events for the focus widget
     pub fn handle_focus_widget_drag(
         &self,
@@ -485,6 +702,46 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Query the real geometry of every existing webview window.
+    ///
+    /// Unlike `get_all_window_states`, which returns the manager's cached view,
+    /// this asks each live window for its current position, size and monitor.
+    pub fn get_windows_geometry(app_handle: &AppHandle) -> Vec<WindowGeometry> {
+        let monitors = app_handle.available_monitors().unwrap_or_default();
+
+        let mut geometries: Vec<WindowGeometry> = app_handle
+            .webview_windows()
+            .into_iter()
+            .map(|(label, window)| {
+                let position = window.outer_position().ok();
+                let size = window.outer_size().ok();
+                let current_monitor = window.current_monitor().ok().flatten();
+
+                let monitor_index = current_monitor.as_ref().and_then(|current| {
+                    monitors.iter().position(|monitor| {
+                        monitor.position() == current.position() && monitor.size() == current.size()
+                    })
+                });
+
+                WindowGeometry {
+                    label,
+                    x: position.map(|p| p.x).unwrap_or(0),
+                    y: position.map(|p| p.y).unwrap_or(0),
+                    width: size.map(|s| s.width).unwrap_or(0),
+                    height: size.map(|s| s.height).unwrap_or(0),
+                    scale_factor: window.scale_factor().unwrap_or(1.0),
+                    monitor_index,
+                    monitor_name: current_monitor.and_then(|m| m.name().cloned()),
+                    is_visible: window.is_visible().unwrap_or(false),
+                    is_fullscreen: window.is_fullscreen().unwrap_or(false),
+                }
+            })
+            .collect();
+
+        geometries.sort_by(|a, b| a.label.cmp(&b.label));
+        geometries
+    }
+
     /// Close all windows
     pub fn close_all_windows(&self) -> Result<(), Box<dyn std::error::Error>> {
         for window_type in [
@@ -689,19 +946,108 @@ pub async fn is_window_visible(
         .lock()
         .map_err(|e| format!("Failed to lock window manager: {}", e))?;
 
-    let window_type = match window_type.as_str() {
-        "command-palette" => WindowType::CommandPalette,
-        "focus-widget" => WindowType::FocusWidget,
-        "break-overlay" => WindowType::BreakOverlay,
-        "settings" => WindowType::Settings,
-        "menu-bar-popover" => WindowType::MenuBarPopover,
-        "break-transition" => WindowType::BreakTransition,
-        _ => return Err("Invalid window type".to_string()),
-    };
+    let window_type =
+        WindowType::from_label(&window_type).ok_or_else(|| "Invalid window type".to_string())?;
 
     Ok(manager.is_window_visible(window_type))
 }
 
+/// Destroy the window of `window_type`, if there is one, and build it
+/// again from scratch.
+///
+/// For a window that still exists but stopped responding, where
+/// `get_or_create_window` would keep handing back the broken one. The new
+/// window is shown again if the old one was visible, or always with `show`.
+/// The manager is only locked for the destroy and the rebuild, not while
+/// waiting for the event loop in between.
+pub async fn rebuild_window(
+    window_manager: &Arc<Mutex<WindowManager>>,
+    app_handle: &AppHandle,
+    window_type: WindowType,
+    show: bool,
+) -> Result<(), String> {
+    let was_visible = window_manager
+        .lock()
+        .map_err(|e| format!("Failed to lock window manager: {}", e))?
+        .destroy_for_rebuild(window_type)
+        .map_err(|e| format!("Failed to destroy {:?}: {}", window_type, e))?;
+
+    // Building a window with a taken label fails
+    for _ in 0..RECREATE_WAIT_ATTEMPTS {
+        if app_handle.get_webview_window(window_type.label()).is_none() {
+            break;
+        }
+        tokio::time::sleep(RECREATE_WAIT_STEP).await;
+    }
+
+    window_manager
+        .lock()
+        .map_err(|e| format!("Failed to lock window manager: {}", e))?
+        .finish_rebuild(window_type, show || was_visible)
+        .map_err(|e| format!("Failed to recreate {:?}: {}", window_type, e))
+}
+
+/// Check that the webview of `window_type` still runs its frontend by
+/// sending it a ping and waiting up to `timeout` for the matching pong.
+///
+/// A window can exist while its webview hangs or crashed, which a plain
+/// `get_webview_window` check does not notice.
+pub async fn probe_window(
+    app_handle: &AppHandle,
+    window_type: WindowType,
+    timeout: std::time::Duration,
+) -> bool {
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let sender = Mutex::new(Some(sender));
+    let expected = nonce.clone();
+
+    let listener = app_handle.listen(PONG_EVENT, move |event| {
+        if serde_json::from_str::<String>(event.payload()).ok().as_deref() != Some(&expected) {
+            return;
+        }
+        if let Some(sender) = sender.lock().ok().and_then(|mut sender| sender.take()) {
+            let _ = sender.send(());
+        }
+    });
+
+    let answered = match app_handle.emit_to(window_type.label(), PING_EVENT, &nonce) {
+        Ok(()) => matches!(tokio::time::timeout(timeout, receiver).await, Ok(Ok(()))),
+        Err(e) => {
+            eprintln!(
+                "⚠️ [WindowManager] Failed to ping '{}': {}",
+                window_type.label(),
+                e
+            );
+            false
+        }
+    };
+    app_handle.unlisten(listener);
+
+    if !answered {
+        eprintln!(
+            "⚠️ [WindowManager] '{}' did not answer within {:?}",
+            window_type.label(),
+            timeout
+        );
+    }
+    answered
+}
+
+/// Destroy a window that is in a bad state (e.g. unresponsive) and build
+/// it again, showing it if it was visible
+#[tauri::command]
+pub async fn recreate_window(
+    window_type: String,
+    app_handle: AppHandle,
+    window_manager: tauri::State<'_, Arc<Mutex<WindowManager>>>,
+) -> Result<(), String> {
+    let window_type =
+        WindowType::from_label(&window_type).ok_or_else(|| "Invalid window type".to_string())?;
+
+    rebuild_window(&window_manager, &app_handle, window_type, false).await
+}
+
 #[tauri::command]
 pub async fn minimize_to_menu_bar(
     window_manager: tauri::State<'_, Arc<Mutex<WindowManager>>>,
@@ -749,3 +1095,8 @@ pub async fn hide_break_transition(
         .hide_break_transition()
         .map_err(|e| format!("Failed to hide break transition: {}", e))
 }
+
+#[tauri::command]
+pub async fn get_windows_geometry(app: AppHandle) -> Result<Vec<WindowGeometry>, String> {
+    Ok(WindowManager::get_windows_geometry(&app))
+}
@@ -0,0 +1,6 @@
+#[doc(hidden)]
+pub mod __private229 {
+    #[doc(hidden)]
+    pub use crate::private::*;
+}
+use serde_core::__private229 as serde_core_private;
@@ -0,0 +1,19 @@
+pub mod config { pub mod libdir {
+pub const xext: Option<&'static str> = Some("/usr/lib/x86_64-linux-gnu");
+pub const gl: Option<&'static str> = Some("/usr/lib/x86_64-linux-gnu");
+pub const xcursor: Option<&'static str> = None;
+pub const xxf86vm: Option<&'static str> = None;
+pub const xft: Option<&'static str> = Some("/usr/lib/x86_64-linux-gnu");
+pub const xinerama: Option<&'static str> = None;
+pub const xi: Option<&'static str> = None;
+pub const x11: Option<&'static str> = Some("");
+pub const xlib_xcb: Option<&'static str> = None;
+pub const xmu: Option<&'static str> = None;
+pub const xrandr: Option<&'static str> = None;
+pub const xtst: Option<&'static str> = None;
+pub const xrender: Option<&'static str> = Some("/usr/lib/x86_64-linux-gnu");
+pub const xpresent: Option<&'static str> = None;
+pub const xscrnsaver: Option<&'static str> = Some("/usr/lib/x86_64-linux-gnu");
+pub const xt: Option<&'static str> = Some("/usr/lib/x86_64-linux-gnu");
+}
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 import { HashRouter, Routes, Route } from "react-router-dom";
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { emit, listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { LogicalSize } from "@tauri-apps/api/dpi";
 import Login from "./pages/Login";
@@ -9,10 +10,16 @@ import OnboardingWizard from "./components/OnboardingWizard";
 import { ErrorBoundary } from "./components/ErrorBoundary";
 import { CycleSync } from "./components/CycleSync";
 import { BreakOverlay } from "./components/BreakOverlay";
+import { BreakTransition } from "./components/BreakTransition";
 import { errorHandler } from "./lib/errorHandler";
 import Stats from "./pages/Stats";
 import Settings from "./pages/Settings";
-import type { BreakSession, CycleState } from "./types";
+import type {
+  AppTheme,
+  BreakSession,
+  CycleState,
+  ThemeChangedEvent,
+} from "./types";
 
 export default function App() {
   const [needsOnboarding, setNeedsOnboarding] = useState<boolean | null>(null);
@@ -25,6 +32,46 @@ export default function App() {
   >(undefined);
   const [cycleState, setCycleState] = useState<CycleState | null>(null);
 
+  // Every window follows the theme tracked by the backend
+  useEffect(() => {
+    const applyTheme = (effective: Exclude<AppTheme, "system">) => {
+      document.documentElement.dataset.theme = effective;
+    };
+
+    invoke<AppTheme>("get_theme")
+      .then((theme) => {
+        if (theme === "system") {
+          const prefersDark = window.matchMedia(
+            "(prefers-color-scheme: dark)"
+          ).matches;
+          applyTheme(prefersDark ? "dark" : "light");
+        } else {
+          applyTheme(theme);
+        }
+      })
+      .catch((error) => {
+        console.error("❌ [App] Failed to load theme:", error);
+      });
+
+    const unlisten = listen<ThemeChangedEvent>("theme-changed", (event) => {
+      applyTheme(event.payload.effective);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Expose the current phase color to every window's styles
+  useEffect(() => {
+    const root = document.documentElement;
+    if (cycleState?.phase_color) {
+      root.style.setProperty("--phase-color", cycleState.phase_color);
+    } else {
+      root.style.removeProperty("--phase-color");
+    }
+  }, [cycleState?.phase_color]);
+
   useEffect(() => {
     const checkAppState = async () => {
       try {
@@ -33,6 +80,12 @@ export default function App() {
         const label = currentWindow.label;
         setWindowLabel(label);
 
+        // The break-transition window only shows a countdown
+        if (label === "break-transition") {
+          setIsLoading(false);
+          return;
+        }
+
         // If this is a special window (break-overlay), skip onboarding check
         if (label === "break-overlay") {
           setIsLoading(false);
@@ -111,6 +164,23 @@ export default function App() {
     checkAppState();
   }, []);
 
+  // Answer the backend's liveness probe so it can tell a hung webview apart
+  // from a working one
+  useEffect(() => {
+    const unlisten = getCurrentWindow().listen<string>(
+      "window-ping",
+      (event) => {
+        emit("window-pong", event.payload).catch((error) => {
+          console.error("❌ [App] Failed to answer window ping:", error);
+        });
+      }
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Force main window to a sane size/position on mount to avoid “esquina” issues
   useEffect(() => {
     if (windowLabel !== "main") return;
@@ -156,6 +226,7 @@ export default function App() {
     if (windowLabel !== "break-overlay") return;
 
     let unlisten: (() => void) | null = null;
+    let unlistenPreview: (() => void) | null = null;
 
     const setupBreakListener = async () => {
       try {
@@ -189,6 +260,18 @@ export default function App() {
             }
           }
         });
+
+        // preview_break_overlay sends its sample break, then null when it ends
+        unlistenPreview = await listen<BreakSession | null>(
+          "break-preview",
+          (event) => {
+            console.log(
+              "👀 [App] Break preview",
+              event.payload ? "started" : "ended"
+            );
+            setBreakSession(event.payload);
+          }
+        );
       } catch (error) {
         console.error("❌ [App] Failed to setup break listener:", error);
       }
@@ -200,6 +283,9 @@ export default function App() {
       if (unlisten) {
         unlisten();
       }
+      if (unlistenPreview) {
+        unlistenPreview();
+      }
     };
   }, [windowLabel]);
 
@@ -274,6 +360,15 @@ export default function App() {
     );
   }
 
+  // Render the countdown for the break-transition window
+  if (windowLabel === "break-transition") {
+    return (
+      <ErrorBoundary>
+        <BreakTransition />
+      </ErrorBoundary>
+    );
+  }
+
   // Show onboarding if needed
   if (needsOnboarding) {
     return (
@@ -40,6 +40,7 @@ export function BreakOverlay({
     setShowEmergencyModal,
     handleChecklistUpdate,
     handleEmergencyOverride,
+    dismissOverlay,
   } = useBreakOverlayLogic({
     breakSession,
     cycleState,
@@ -129,6 +130,14 @@ export function BreakOverlay({
                 onChecklistUpdate={handleChecklistUpdate}
               />
 
+              {/* Leave the break the way the dismiss setting says */}
+              <button
+                onClick={dismissOverlay}
+                className="absolute top-8 left-8 px-4 py-2 text-sm text-gray-400 hover:text-gray-200 rounded-lg hover:bg-white/5 transition-colors"
+              >
+                Leave break
+              </button>
+
               {/* Bypass attempts indicator (for debugging/awareness) */}
               {bypassAttempts > 0 && (
                 <div className="absolute top-8 right-8">
@@ -142,6 +151,13 @@ export function BreakOverlay({
             </>
           );
         })()}
+
+        {/* The user's own reminder for the break */}
+        {breakSession.message && (
+          <p className="mt-8 max-w-md text-center text-lg text-gray-200 italic">
+            {breakSession.message}
+          </p>
+        )}
       </div>
 
       {/* Emergency override modal - only show in normal mode */}
@@ -0,0 +1,83 @@
+import React, { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * Countdown shown in the break-transition window before a strict mode break.
+ * The backend moves to the break overlay when the countdown runs out; this
+ * view only mirrors it and lets the user start the break early. With
+ * `strict_require_ack` on, the countdown only begins once the user confirms.
+ */
+export const BreakTransition: React.FC = () => {
+  const [remaining, setRemaining] = useState<number | null>(null);
+  const [countdownSeconds, setCountdownSeconds] = useState(10);
+  const [awaitingAck, setAwaitingAck] = useState(false);
+
+  useEffect(() => {
+    Promise.all([
+      invoke<any>("get_settings"),
+      invoke<boolean>("get_strict_require_ack"),
+    ])
+      .then(([settings, requireAck]) => {
+        const seconds = settings.breakTransitionSeconds ?? 10;
+        setCountdownSeconds(seconds);
+        setAwaitingAck(requireAck);
+        if (!requireAck) setRemaining(seconds);
+      })
+      .catch((error) => {
+        console.error("❌ [BreakTransition] Failed to load settings:", error);
+        setRemaining(10);
+      });
+  }, []);
+
+  useEffect(() => {
+    if (remaining === null || remaining <= 0) return;
+    const timer = setTimeout(() => setRemaining(remaining - 1), 1000);
+    return () => clearTimeout(timer);
+  }, [remaining]);
+
+  const acknowledge = async () => {
+    try {
+      await invoke("acknowledge_transition");
+      setAwaitingAck(false);
+      setRemaining(countdownSeconds);
+    } catch (error) {
+      console.error("❌ [BreakTransition] Failed to acknowledge:", error);
+    }
+  };
+
+  const startNow = async () => {
+    try {
+      await invoke("start_break_from_transition");
+    } catch (error) {
+      console.error("❌ [BreakTransition] Failed to start break:", error);
+    }
+  };
+
+  return (
+    <div className="min-h-screen bg-zinc-900 text-white flex flex-col items-center justify-center gap-4 p-6">
+      <div className="text-sm uppercase tracking-wide text-zinc-400">
+        Break starting
+      </div>
+      {awaitingAck ? (
+        <button
+          onClick={acknowledge}
+          className="px-4 py-2 rounded-lg bg-zinc-700 hover:bg-zinc-600 transition-colors"
+        >
+          I'm ready
+        </button>
+      ) : (
+        <>
+          <div className="text-5xl font-semibold tabular-nums">
+            {remaining ?? ""}
+          </div>
+          <button
+            onClick={startNow}
+            className="px-4 py-2 rounded-lg bg-zinc-700 hover:bg-zinc-600 transition-colors"
+          >
+            Start break now
+          </button>
+        </>
+      )}
+    </div>
+  );
+};
@@ -16,6 +16,7 @@ import {
   WorkHoursStats,
   getWorkHoursStats,
 } from "../lib/cycleCommands";
+import { parseOutsideWorkHoursError } from "../lib/tauri";
 
 /**
  * Component to control work cycles (start focus, start break, end session)
@@ -27,6 +28,7 @@ export const CycleControl: React.FC = () => {
   const [workScheduleInfo, setWorkScheduleInfo] =
     useState<WorkScheduleInfo | null>(null);
   const [showOverrideConfirm, setShowOverrideConfirm] = useState(false);
+  const [nextWorkStart, setNextWorkStart] = useState<string | null>(null);
   const [workHoursStats, setWorkHoursStats] = useState<WorkHoursStats | null>(
     null
   );
@@ -69,10 +71,9 @@ export const CycleControl: React.FC = () => {
       console.error("Failed to start focus session:", error);
       setActionFeedback(null);
       // Show override option if outside work hours
-      if (
-        error instanceof Error &&
-        error.message.includes("outside work hours")
-      ) {
+      const outsideWorkHours = parseOutsideWorkHoursError(error);
+      if (outsideWorkHours) {
+        setNextWorkStart(outsideWorkHours.next_work_start);
         setShowOverrideConfirm(true);
       }
     }
@@ -349,6 +350,16 @@ export const CycleControl: React.FC = () => {
                 You're trying to start a focus session outside your configured
                 work hours. Would you like to proceed anyway?
               </p>
+              {nextWorkStart && (
+                <p className="text-xs text-gray-400 mb-3">
+                  Work hours start again at{" "}
+                  {new Date(nextWorkStart).toLocaleString([], {
+                    weekday: "short",
+                    hour: "2-digit",
+                    minute: "2-digit",
+                  })}
+                </p>
+              )}
               <div className="flex space-x-2">
                 <button
                   onClick={() => handleStartFocus(true)}
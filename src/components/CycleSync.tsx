@@ -1,10 +1,11 @@
 import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { LogicalSize } from "@tauri-apps/api/dpi";
 import { useAppStore } from "../store";
 import { CycleManager } from "../lib/cycleCommands";
-import type { CycleEventData } from "../types";
+import type { CycleEventData, TickInterval } from "../types";
 
 /**
  * Global component to sync cycle state across all pages
@@ -195,6 +196,30 @@ export function CycleSync() {
 
             case "long_break_reached":
               break;
+
+            case "paused":
+            case "resumed":
+              // Pause/resume may be triggered from another window; resync so
+              // every surface shows the correct running state
+              CycleManager.getState()
+                .then((state) => {
+                  setCycleState(state);
+                  // A snoozed break brings its overlay back when it resumes
+                  if (
+                    cycleEvent.type === "resumed" &&
+                    (cycleEvent.phase === "short_break" ||
+                      cycleEvent.phase === "long_break")
+                  ) {
+                    showBreakOverlay();
+                  }
+                })
+                .catch((error) => {
+                  console.error(
+                    `Failed to sync state after ${cycleEvent.type}:`,
+                    error
+                  );
+                });
+              break;
           }
         });
       } catch (error) {
@@ -217,10 +242,21 @@ export function CycleSync() {
     hideBreakOverlay,
   ]);
 
-  // Set up timer to tick every second and sync state
+  // Set up timer to tick and sync state at the configured interval
   // This runs globally and persists across page navigations
   useEffect(() => {
-    const mainTimer = setInterval(async () => {
+    let interval: TickInterval = {
+      interval_ms: 1000,
+      power_save: false,
+      idle_interval_ms: 1000,
+    };
+    let timer: ReturnType<typeof setTimeout> | null = null;
+    let stopped = false;
+    let unlisten: (() => void) | null = null;
+
+    const run = async () => {
+      timer = null;
+      let idle = false;
       try {
         const state = await CycleManager.getState();
 
@@ -228,20 +264,54 @@ export function CycleSync() {
         // This ensures smooth transitions when focus ends and break starts automatically
         if (state.is_running || state.phase !== "idle") {
           const updatedState = await CycleManager.tick();
-          if (updatedState.phase !== state.phase) {
-          }
           setCycleState(updatedState);
+          idle = updatedState.phase === "idle";
         } else {
           // Just sync state if idle and not running
           setCycleState(state);
+          idle = true;
         }
       } catch (error) {
         console.error("Failed to tick/sync cycle:", error);
       }
-    }, 1000);
+
+      if (!stopped) {
+        // The backend measures remaining time from the clock, so a wider
+        // interval only means fewer updates
+        timer = setTimeout(
+          run,
+          idle ? interval.idle_interval_ms : interval.interval_ms
+        );
+      }
+    };
+
+    invoke<TickInterval>("get_tick_interval")
+      .then((loaded) => {
+        interval = loaded;
+      })
+      .catch((error) =>
+        console.error("Failed to load tick interval:", error)
+      );
+
+    listen<TickInterval>("tick-interval-changed", (event) => {
+      interval = event.payload;
+      // Apply right away instead of waiting out a long idle interval
+      // (unless a tick is already in flight; it will reschedule itself)
+      if (timer && !stopped) {
+        clearTimeout(timer);
+        timer = setTimeout(run, 0);
+      }
+    }).then((fn) => {
+      if (stopped) fn();
+      else unlisten = fn;
+    });
+
+    timer = setTimeout(run, interval.interval_ms);
 
     return () => {
-      clearInterval(mainTimer);
+      stopped = true;
+      if (timer) clearTimeout(timer);
+      if (unlisten) unlisten();
     };
   }, [setCycleState]);
 
@@ -5,13 +5,20 @@ import {
   RotateCcw,
   MoreHorizontal,
   Shield,
+  Square,
   Target,
 } from "lucide-react";
+import { listen } from "@tauri-apps/api/event";
 import { useAppStore, useCurrentSession, useCycleState } from "../store";
 import { COLORS, FOCUS_WIDGET, SHADOWS, ANIMATIONS } from "../constants/design";
-import { setupEventListeners } from "../lib/tauri";
+import { setupEventListeners, tauriCommands } from "../lib/tauri";
 import { SessionManager } from "../lib/commands";
-import type { FocusSession, Position } from "../types";
+import type {
+  CycleEventData,
+  FocusSession,
+  FocusWidgetPauseBehavior,
+  Position,
+} from "../types";
 import { notificationHelper } from "../lib/notificationHelper";
 
 interface FocusWidgetProps {
@@ -192,6 +199,8 @@ export const FocusWidget: React.FC<FocusWidgetProps> = ({
   const [dragOffset, setDragOffset] = useState({ x: 0, y: 0 });
   const [position, setPosition] = useState<Position>(getStoredPosition);
   const [isHovered, setIsHovered] = useState(false);
+  const [pauseBehavior, setPauseBehavior] =
+    useState<FocusWidgetPauseBehavior>("show_paused");
   const widgetRef = useRef<HTMLDivElement>(null);
   const dragStartRef = useRef<Position | null>(null);
 
@@ -206,6 +215,41 @@ export const FocusWidget: React.FC<FocusWidgetProps> = ({
   // Check if session is in strict mode
   const isStrict = session?.isStrict || false;
 
+  // How a paused session shows, per the focus_widget_pause_behavior setting
+  const isPaused = session !== null && !session.isRunning;
+  const isPausePulsing = isPaused && pauseBehavior === "pulse";
+  const isPauseExpanded = isPaused && pauseBehavior === "expand_controls";
+  // Expanded controls carry text labels, so the widget grows to fit them
+  const widgetWidth = isPauseExpanded
+    ? FOCUS_WIDGET.defaultSize.width + 72
+    : FOCUS_WIDGET.defaultSize.width;
+
+  // Load the pause behavior; every pause event carries the current one
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+
+    tauriCommands
+      .getFocusWidgetPauseBehavior()
+      .then(setPauseBehavior)
+      .catch((error) =>
+        console.error("Failed to load focus widget pause behavior:", error)
+      );
+
+    listen<CycleEventData>("cycle-event", (event) => {
+      if (event.payload.type === "paused") {
+        setPauseBehavior(event.payload.pause_behavior);
+      }
+    }).then((fn) => {
+      unlisten = fn;
+    });
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
   // Update position with bounds checking and snapping
   const updatePosition = useCallback((newPosition: Position) => {
     const maxX = window.innerWidth - FOCUS_WIDGET.defaultSize.width;
@@ -336,12 +380,13 @@ export const FocusWidget: React.FC<FocusWidgetProps> = ({
             : "cursor-grab hover:bg-gray-800 z-50"
         }
         ${isPreAlert ? "ring-2 ring-blue-400 ring-opacity-50" : ""}
+        ${isPausePulsing ? "ring-2 ring-yellow-400 animate-pulse" : ""}
         ${isHovered ? "shadow-lg" : ""}
       `}
       style={{
         left: position.x,
         top: position.y,
-        width: FOCUS_WIDGET.defaultSize.width,
+        width: widgetWidth,
         height: FOCUS_WIDGET.defaultSize.height,
         boxShadow: isDragging ? SHADOWS.xl : SHADOWS.widget,
         animation: isPreAlert
@@ -384,7 +429,7 @@ export const FocusWidget: React.FC<FocusWidgetProps> = ({
             onToggleSession();
           }}
           className={`
-            p-1.5 rounded-full transition-colors duration-150
+            flex items-center p-1.5 rounded-full transition-colors duration-150
             hover:bg-gray-700 active:bg-gray-600
             ${session.isRunning ? "text-yellow-400" : "text-green-400"}
           `}
@@ -395,6 +440,9 @@ export const FocusWidget: React.FC<FocusWidgetProps> = ({
           ) : (
             <Play className="w-4 h-4" />
           )}
+          {isPauseExpanded && (
+            <span className="ml-1 text-xs font-semibold">Resume</span>
+          )}
         </button>
 
         {/* Reset button */}
@@ -403,14 +451,21 @@ export const FocusWidget: React.FC<FocusWidgetProps> = ({
             e.stopPropagation();
             onResetSession();
           }}
-          className="
-            p-1.5 rounded-full transition-colors duration-150
-            hover:bg-gray-700 active:bg-gray-600 text-gray-400
-            hover:text-red-400
-          "
-          title="Reset session"
+          className={`
+            flex items-center p-1.5 rounded-full transition-colors duration-150
+            hover:bg-gray-700 active:bg-gray-600 hover:text-red-400
+            ${isPauseExpanded ? "text-red-400" : "text-gray-400"}
+          `}
+          title={isPauseExpanded ? "End session" : "Reset session"}
         >
-          <RotateCcw className="w-4 h-4" />
+          {isPauseExpanded ? (
+            <>
+              <Square className="w-4 h-4" />
+              <span className="ml-1 text-xs font-semibold">End</span>
+            </>
+          ) : (
+            <RotateCcw className="w-4 h-4" />
+          )}
         </button>
 
         {/* Menu button */}
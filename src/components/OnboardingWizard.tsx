@@ -101,7 +101,7 @@ export default function OnboardingWizard({
       await invoke("get_configuration_health_check");
 
       // Reset onboarding if needed
-      await invoke("reset_onboarding_for_testing");
+      await invoke("reset_onboarding_for_testing", { confirm: "CONFIRM" });
 
       // Reinitialize
       await initializeOnboarding();
@@ -244,6 +244,9 @@ export default function OnboardingWizard({
           strictMode: stepData.StrictMode?.strictMode || false,
           emergencyKey: stepData.StrictMode?.emergencyKey || null,
           userName: stepData.StrictMode?.userName?.trim() || null,
+          // Drop straight into a focus session once settings are applied
+          startFocusAfterOnboarding:
+            stepData.Summary?.startFocusAfterOnboarding || false,
         };
 
         // Validate final configuration before completion
@@ -1,7 +1,7 @@
 import { useState, useEffect, useRef } from "react";
 import { Save, X, AlertTriangle } from "lucide-react";
 import { useSettings, useAppStore, useCycleState } from "../store";
-import { tauriCommands } from "../lib/tauri";
+import { tauriCommands, parseSettingsValidationError } from "../lib/tauri";
 import type { UserSettings } from "../types";
 
 interface SettingsProps {
@@ -277,9 +277,12 @@ export function Settings({ onClose }: SettingsProps) {
       }, 3000);
     } catch (error) {
       console.error("Failed to save settings:", error);
+      const validationError = parseSettingsValidationError(error);
       setSaveMessage({
         type: "error",
-        text: "Failed to save settings. Please try again.",
+        text:
+          validationError?.message ??
+          "Failed to save settings. Please try again.",
       });
     } finally {
       setIsSaving(false);
@@ -1,12 +1,18 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import type { BreakSession, CycleEventData, CycleState } from "../../types";
+import type {
+  BreakSession,
+  CycleEventData,
+  CycleState,
+  OverlayEscapeOutcome,
+} from "../../types";
 import { useCycleState, useSettings } from "../../store";
 import {
   activityCompletionTracker,
   breakActivityManager,
 } from "../../lib/breakActivities";
+import { CycleManager } from "../../lib/cycleCommands";
 
 interface UseBreakOverlayLogicParams {
   breakSession: BreakSession;
@@ -30,11 +36,14 @@ export function useBreakOverlayLogic({
       (breakSession as any).type ||
       (breakSession as any).break_type ||
       (breakSession as any).breakType;
-    return raw === "long" ? "long" : "short";
+    // The wind-down is a long break with its own activity
+    return raw === "long" || raw === "wind_down" ? "long" : "short";
   }, [breakSession]);
 
   const [checklistCompleted, setChecklistCompleted] = useState<boolean[]>([]);
   const [showEmergencyModal, setShowEmergencyModal] = useState(false);
+  // Set while the modal collects the PIN that Escape asked for
+  const escapePinPendingRef = useRef(false);
   const [bypassAttempts, setBypassAttempts] = useState(0);
   const storeCycleState = useCycleState();
   const settings = useSettings();
@@ -80,6 +89,45 @@ export function useBreakOverlayLogic({
     [breakSession.id]
   );
 
+  // Every way out of the overlay (Escape, closing the window, the leave
+  // button) goes through the backend, which applies the configured action;
+  // the overlay only hides when that action lets the user go
+  const hideOverlay = useCallback(() => {
+    invoke("hide_fullscreen_break_overlay").catch((error) => {
+      console.error("❌ [BreakOverlay] Failed to hide overlay:", error);
+    });
+  }, []);
+
+  const handleEscape = useCallback(
+    async (pin?: string): Promise<OverlayEscapeOutcome | null> => {
+      try {
+        const outcome = await invoke<OverlayEscapeOutcome>(
+          "handle_overlay_escape",
+          pin === undefined ? {} : { pin }
+        );
+        if (outcome === "break_ended") {
+          hideOverlay();
+        }
+        return outcome;
+      } catch (error) {
+        console.error("❌ [BreakOverlay] Escape handling failed:", error);
+        return null;
+      }
+    },
+    [hideOverlay]
+  );
+
+  const dismissOverlay = useCallback(async () => {
+    try {
+      const outcome = await CycleManager.dismissBreakOverlay();
+      if (outcome.type !== "blocked") {
+        hideOverlay();
+      }
+    } catch (error) {
+      console.error("❌ [BreakOverlay] Dismiss failed:", error);
+    }
+  }, [hideOverlay]);
+
   useEffect(() => {
     setRemaining(breakSession.remaining);
     setPhase(resolvedBreakType === "short" ? "short_break" : "long_break");
@@ -259,6 +307,32 @@ export function useBreakOverlayLogic({
     windowRef.current = getCurrentWindow();
   }, []);
 
+  // Outside strict mode the window can be closed; treat that as a dismiss
+  useEffect(() => {
+    if (isStrictMode) {
+      return;
+    }
+
+    let unlisten: (() => void) | null = null;
+    getCurrentWindow()
+      .onCloseRequested((event) => {
+        event.preventDefault();
+        dismissOverlay();
+      })
+      .then((fn) => {
+        unlisten = fn;
+      })
+      .catch((error) => {
+        console.error("❌ [BreakOverlay] Close listener failed:", error);
+      });
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [isStrictMode, dismissOverlay]);
+
   useEffect(() => {
     const blockKeyboardInput = (e: KeyboardEvent) => {
       if (emergencyKeyCombination) {
@@ -302,6 +376,19 @@ export function useBreakOverlayLogic({
       e.stopPropagation();
       e.stopImmediatePropagation();
 
+      // The backend decides what Escape does (and logs it when blocked)
+      if (e.key === "Escape" || e.key === "Esc") {
+        if (e.type === "keydown") {
+          handleEscape().then((outcome) => {
+            if (outcome === "pin_required") {
+              escapePinPendingRef.current = true;
+              setShowEmergencyModal(true);
+            }
+          });
+        }
+        return;
+      }
+
       if (e.metaKey || e.ctrlKey) {
         if (e.key === "q" || e.key === "Q") {
           logBypassAttempt("cmd_q_blocked");
@@ -322,10 +409,6 @@ export function useBreakOverlayLogic({
         logBypassAttempt(`function_key_blocked_${e.key}`);
       }
 
-      if (e.key === "Escape" || e.key === "Esc") {
-        logBypassAttempt("escape_blocked");
-      }
-
       logBypassAttempt(`keyboard_blocked_${e.key}`);
     };
 
@@ -338,7 +421,7 @@ export function useBreakOverlayLogic({
       window.removeEventListener("keyup", blockKeyboardInput, true);
       window.removeEventListener("keypress", blockKeyboardInput, true);
     };
-  }, [isStrictMode, emergencyKeyCombination, logBypassAttempt]);
+  }, [isStrictMode, emergencyKeyCombination, logBypassAttempt, handleEscape]);
 
   useEffect(() => {
     if (!isStrictMode) {
@@ -508,8 +591,25 @@ export function useBreakOverlayLogic({
     };
   }, []);
 
+  useEffect(() => {
+    if (!showEmergencyModal) {
+      escapePinPendingRef.current = false;
+    }
+  }, [showEmergencyModal]);
+
   const handleEmergencyOverride = useCallback(
     async (pin: string): Promise<boolean> => {
+      // Escape's PIN goes back to the backend, which ends the break or logs
+      // the rejected PIN as a bypass attempt
+      if (escapePinPendingRef.current) {
+        const outcome = await handleEscape(pin);
+        if (outcome === "break_ended") {
+          setShowEmergencyModal(false);
+          return true;
+        }
+        return false;
+      }
+
       const success = await onEmergencyOverride(pin);
       if (success) {
         setShowEmergencyModal(false);
@@ -519,7 +619,7 @@ export function useBreakOverlayLogic({
       }
       return success;
     },
-    [onEmergencyOverride, logBypassAttempt]
+    [onEmergencyOverride, logBypassAttempt, handleEscape]
   );
 
   const handleChecklistUpdate = useCallback(
@@ -581,5 +681,6 @@ export function useBreakOverlayLogic({
     setShowEmergencyModal,
     handleChecklistUpdate,
     handleEmergencyOverride,
+    dismissOverlay,
   };
 }
@@ -17,6 +17,7 @@ interface OnboardingConfig {
   strictMode: boolean;
   emergencyKey?: string;
   userName?: string;
+  startFocusAfterOnboarding: boolean;
 }
 
 export default function SummaryStep({
@@ -68,6 +69,7 @@ export default function SummaryStep({
         strictMode: (userSettings as any)?.strict_mode || false,
         emergencyKey: (userSettings as any)?.emergency_key_combination,
         userName: (userSettings as any)?.user_name,
+        startFocusAfterOnboarding: false,
       };
 
       setConfig(configData);
@@ -80,6 +82,14 @@ export default function SummaryStep({
     }
   };
 
+  const handleStartFocusToggle = (startFocusAfterOnboarding: boolean) => {
+    if (!config) return;
+
+    const updated = { ...config, startFocusAfterOnboarding };
+    setConfig(updated);
+    setStepData(updated);
+  };
+
   const handleCompleteOnboarding = async () => {
     if (!config) return;
 
@@ -194,6 +204,19 @@ export default function SummaryStep({
         </div>
       </div>
 
+      {/* Start Focus Toggle */}
+      <label className="flex items-center space-x-4 cursor-pointer mb-8">
+        <input
+          type="checkbox"
+          checked={config.startFocusAfterOnboarding}
+          onChange={(e) => handleStartFocusToggle(e.target.checked)}
+          className="w-5 h-5 text-white bg-gray-800 border-gray-600 rounded focus:ring-white focus:ring-2"
+        />
+        <span className="text-white">
+          Start my first focus session right away
+        </span>
+      </label>
+
       {/* Action Buttons */}
       <div className="flex gap-4">
         <button
@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { CycleState } from "../types";
+import type { BreakSession, CycleState, OverlayDismissOutcome } from "../types";
 import { errorHandler } from "./errorHandler";
 
 /**
@@ -60,6 +60,81 @@ export class CycleManager {
     }
   }
 
+  /**
+   * End the current focus session and take a break now, going through the
+   * strict mode transition when strict mode is active
+   */
+  static async breakNow(forceLong?: boolean): Promise<CycleState> {
+    try {
+      const state = await invoke<CycleState>("break_now", {
+        forceLong: forceLong || false,
+      });
+      console.log("✅ Break started:", state);
+      return state;
+    } catch (error) {
+      console.error("❌ Failed to start break now:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * Start a standalone focus sprint that doesn't count towards the cycle
+   * and returns to idle instead of starting a break
+   */
+  static async startFocusSprint(minutes: number): Promise<CycleState> {
+    try {
+      const state = await invoke<CycleState>("start_focus_sprint", { minutes });
+      console.log("✅ Focus sprint started:", state);
+      return state;
+    } catch (error) {
+      console.error("❌ Failed to start focus sprint:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * Make only the next focus session last `minutes`; pass null to drop a
+   * pending override
+   */
+  static async setNextFocusDuration(minutes: number | null): Promise<void> {
+    try {
+      await invoke("set_next_focus_duration", { minutes });
+    } catch (error) {
+      console.error("❌ Failed to set next focus duration:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * Skip the current break and start the next focus session; refused while
+   * strict mode is active
+   */
+  static async skipBreak(): Promise<CycleState> {
+    try {
+      const state = await invoke<CycleState>("skip_break_session");
+      console.log("✅ Break skipped:", state);
+      return state;
+    } catch (error) {
+      console.error("❌ Failed to skip break:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * Start the end-of-day wind-down offered by a `wind_down_suggested` event,
+   * ending a running break first
+   */
+  static async startWindDown(): Promise<CycleState> {
+    try {
+      const state = await invoke<CycleState>("start_wind_down");
+      console.log("✅ Wind-down started:", state);
+      return state;
+    } catch (error) {
+      console.error("❌ Failed to start wind-down:", error);
+      throw error;
+    }
+  }
+
   /**
    * Pause the current cycle
    */
@@ -162,6 +237,73 @@ export class CycleManager {
       throw error;
     }
   }
+
+  /**
+   * Dismiss the break overlay outside strict mode; the backend applies the
+   * configured action and the caller hides the overlay unless it was blocked
+   */
+  static async dismissBreakOverlay(): Promise<OverlayDismissOutcome> {
+    try {
+      return await invoke<OverlayDismissOutcome>("handle_overlay_dismiss");
+    } catch (error) {
+      console.error("❌ Failed to dismiss break overlay:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * Show the break overlay with sample content for a few seconds; no break
+   * is started, nothing is saved and the system is never locked
+   */
+  static async previewBreakOverlay(
+    durationSeconds: number
+  ): Promise<BreakSession> {
+    try {
+      return await invoke<BreakSession>("preview_break_overlay", {
+        durationSeconds,
+      });
+    } catch (error) {
+      console.error("❌ Failed to preview break overlay:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * Start a focus session automatically at `at` (replaces any earlier
+   * schedule). Resolves with the scheduled time as an ISO string.
+   */
+  static async scheduleFocus(at: Date): Promise<string> {
+    try {
+      return await invoke<string>("schedule_focus", { at: at.toISOString() });
+    } catch (error) {
+      console.error("❌ Failed to schedule focus:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * Drop the pending scheduled focus start
+   */
+  static async cancelScheduledFocus(): Promise<void> {
+    try {
+      await invoke("cancel_scheduled_focus");
+    } catch (error) {
+      console.error("❌ Failed to cancel scheduled focus:", error);
+      throw error;
+    }
+  }
+
+  /**
+   * When the pending scheduled focus start fires, or null
+   */
+  static async getScheduledFocus(): Promise<string | null> {
+    try {
+      return await invoke<string | null>("get_scheduled_focus");
+    } catch (error) {
+      console.error("❌ Failed to get scheduled focus:", error);
+      throw error;
+    }
+  }
 }
 
 /**
@@ -192,11 +334,13 @@ export interface WorkHoursStats {
  * Get work hours compliance statistics
  */
 export async function getWorkHoursStats(
-  days?: number
+  days?: number,
+  completedOnly: boolean = true
 ): Promise<WorkHoursStats> {
   try {
     const stats = await invoke<WorkHoursStats>("get_work_hours_stats", {
       days: days || 30,
+      completedOnly,
     });
     return stats;
   } catch (error) {
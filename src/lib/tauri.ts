@@ -6,8 +6,24 @@ import type {
   FocusSession,
   BreakSession,
   UserSettings,
+  SoundConfig,
+  CyclePreset,
   SessionStats,
+  TodaySessionCount,
+  DailyGoal,
+  DailyProgress,
+  SessionHistoryFilter,
+  SessionHistoryPage,
   BreakActivity,
+  ShortcutAvailability,
+  EmergencyHotkeyTest,
+  SystemLockStatus,
+  RuntimeStats,
+  PerformanceCounters,
+  CommandGroup,
+  SettingsValidationError,
+  FocusWidgetPauseBehavior,
+  OutsideWorkHoursError,
 } from "../types";
 
 // Generic invoke wrapper with error handling
@@ -25,6 +41,40 @@ export async function invokeCommand<T>(
   }
 }
 
+// Settings saves reject with a JSON SettingsValidationError when the
+// durations are invalid; returns null for any other error
+export function parseSettingsValidationError(
+  error: unknown
+): SettingsValidationError | null {
+  if (typeof error !== "string") return null;
+  try {
+    const parsed = JSON.parse(error);
+    if (Array.isArray(parsed?.fields) && typeof parsed?.message === "string") {
+      return parsed as SettingsValidationError;
+    }
+  } catch {
+    // Not JSON, so not a validation error
+  }
+  return null;
+}
+
+// start_focus_session rejects with a JSON OutsideWorkHoursError when work
+// hours enforcement blocks it; returns null for any other error
+export function parseOutsideWorkHoursError(
+  error: unknown
+): OutsideWorkHoursError | null {
+  if (typeof error !== "string") return null;
+  try {
+    const parsed = JSON.parse(error);
+    if (parsed?.type === "outside_work_hours") {
+      return parsed as OutsideWorkHoursError;
+    }
+  } catch {
+    // Not JSON, so not a work hours rejection
+  }
+  return null;
+}
+
 // Event listener setup with multiple event types
 export function setupEventListeners(
   onEvent: (event: AppEvent) => void
@@ -129,10 +179,74 @@ export const tauriCommands = {
   updateSettings: (settings: UserSettings) =>
     invokeCommand<void>("update_settings", { settings }),
 
+  getSoundConfig: () => invokeCommand<SoundConfig>("get_sound_config"),
+
+  // One-line summary of the settings in the UI's language
+  describeConfiguration: () =>
+    invokeCommand<string>("describe_configuration", {
+      locale: navigator.language,
+    }),
+
+  getFocusWidgetPauseBehavior: () =>
+    invokeCommand<FocusWidgetPauseBehavior>("get_focus_widget_pause_behavior"),
+
+  setSoundConfig: (config: SoundConfig) =>
+    invokeCommand<SoundConfig>("set_sound_config", { config }),
+
+  listCyclePresets: () => invokeCommand<CyclePreset[]>("list_cycle_presets"),
+
+  saveCyclePreset: (preset: CyclePreset) =>
+    invokeCommand<CyclePreset>("save_cycle_preset", { preset }),
+
+  // Removes the preset; confirm must be "CONFIRM"
+  deleteCyclePreset: (id: number, confirm: string) =>
+    invokeCommand<boolean>("delete_cycle_preset", { id, confirm }),
+
+  // Makes the preset the active cycle configuration, from the next phase on
+  applyCyclePreset: (id: number) =>
+    invokeCommand<CyclePreset>("apply_cycle_preset", { id }),
+
   // Statistics
   getSessionStats: (days: number) =>
     invokeCommand<SessionStats[]>("get_session_stats", { days }),
 
+  getTodaySessionCount: () =>
+    invokeCommand<TodaySessionCount>("get_today_session_count"),
+
+  // Sets the goal from today on; pass null for a target that isn't tracked
+  setDailyGoal: (targetMinutes: number | null, targetSessions: number | null) =>
+    invokeCommand<DailyGoal>("set_daily_goal", { targetMinutes, targetSessions }),
+
+  getDailyProgress: () => invokeCommand<DailyProgress>("get_daily_progress"),
+
+  // Newest first; leave the filter out to list every session
+  getSessionHistory: (
+    offset: number,
+    limit: number,
+    filter?: SessionHistoryFilter
+  ) =>
+    invokeCommand<SessionHistoryPage>("get_session_history", {
+      offset,
+      limit,
+      filter: filter ?? null,
+    }),
+
+  // Permanently deletes sessions started in [start, end); confirm must be "CONFIRM"
+  deleteSessionsInRange: (start: Date, end: Date, confirm: string) =>
+    invokeCommand<number>("delete_sessions_in_range", {
+      start: start.toISOString(),
+      end: end.toISOString(),
+      confirm,
+    }),
+
+  // Writes a full JSON report of [start, end] to an absolute path
+  exportAnalyticsJson: (start: Date, end: Date, path: string) =>
+    invokeCommand<void>("export_analytics_json", {
+      start: start.toISOString(),
+      end: end.toISOString(),
+      path,
+    }),
+
   // State information
   getAppState: () => invokeCommand<string>("get_app_state"),
 
@@ -141,10 +255,23 @@ export const tauriCommands = {
 
   getDatabaseStats: () => invokeCommand<string>("get_database_stats"),
 
+  // Data location
+  getDataDirectory: () => invokeCommand<string>("get_data_directory"),
+
+  moveDataDirectory: (newPath: string) =>
+    invokeCommand<string>("move_data_directory", { newPath }),
+
+  // Creates missing database tables and columns; resolves with what was added
+  // ("table" or "table.column")
+  ensureSchema: () => invokeCommand<string[]>("ensure_schema"),
+
   // Window management
   isWindowVisible: (windowType: string) =>
     invokeCommand<boolean>("is_window_visible", { windowType }),
 
+  recreateWindow: (windowType: string) =>
+    invokeCommand<void>("recreate_window", { windowType }),
+
   // Onboarding commands
   startOnboarding: () => invokeCommand<string>("start_onboarding"),
 
@@ -159,6 +286,14 @@ export const tauriCommands = {
 
   getOnboardingStatus: () => invokeCommand<boolean>("get_onboarding_status"),
 
+  // Hotkey commands
+  exportHotkeysJson: () => invokeCommand<string>("export_hotkeys_json"),
+
+  // Resolves to the number of hotkeys imported; rejects the whole file on
+  // unknown actions, invalid shortcuts or conflicts
+  importHotkeysJson: (json: string) =>
+    invokeCommand<number>("import_hotkeys_json", { json }),
+
   // Strict mode commands
   registerEmergencyHotkey: (combination: string) =>
     invokeCommand<void>("register_emergency_hotkey", { combination }),
@@ -166,6 +301,24 @@ export const tauriCommands = {
   unregisterEmergencyHotkey: () =>
     invokeCommand<void>("unregister_emergency_hotkey"),
 
+  // Registers the combination for 10 seconds; rejects if another app holds it
+  testEmergencyHotkey: (combination: string) =>
+    invokeCommand<EmergencyHotkeyTest>("test_emergency_hotkey", { combination }),
+
+  // For presses the window sees instead of the global shortcut; resolves true
+  // when it matches the running test
+  reportEmergencyHotkeyPress: (combination: string) =>
+    invokeCommand<boolean>("report_emergency_hotkey_press", { combination }),
+
+  repairStrictModeWindows: () =>
+    invokeCommand<boolean>("repair_strict_mode_windows"),
+
+  getSystemLockStatus: () =>
+    invokeCommand<SystemLockStatus>("get_system_lock_status"),
+
+  isShortcutAvailable: (shortcut: string) =>
+    invokeCommand<ShortcutAvailability>("is_shortcut_available", { shortcut }),
+
   emergencyExitStrictMode: () =>
     invokeCommand<void>("emergency_exit_strict_mode"),
 
@@ -221,4 +374,15 @@ export const tauriCommands = {
     }),
 
   flushTelemetry: () => invokeCommand<void>("flush_telemetry"),
+
+  // Diagnostics
+  getRuntimeStats: () => invokeCommand<RuntimeStats>("get_runtime_stats"),
+
+  getPerformanceCounters: () =>
+    invokeCommand<PerformanceCounters>("get_performance_counters"),
+
+  resetPerformanceCounters: () =>
+    invokeCommand<void>("reset_performance_counters"),
+
+  listCommands: () => invokeCommand<CommandGroup[]>("list_commands"),
 } as const;
@@ -117,7 +117,7 @@ export default function Login() {
             <button
               onClick={async () => {
                 try {
-                  await invoke("reset_onboarding_for_testing");
+                  await invoke("reset_onboarding_for_testing", { confirm: "CONFIRM" });
                   setStatus("Onboarding reset! Refresh the page to test onboarding flow.");
                 } catch (error) {
                   console.error("Error resetting onboarding:", error);
@@ -28,11 +28,12 @@ export type SessionState = "idle" | "running" | "pre-alert" | "ending";
 
 export interface BreakSession {
   id: string;
-  type: "short" | "long";
+  type: "short" | "long" | "wind_down";
   duration: number; // seconds
   remaining: number; // seconds
   activity: BreakActivity;
   allowEmergency: boolean;
+  message?: string | null;
 }
 
 export interface BreakActivity {
@@ -106,6 +107,352 @@ export interface Toast {
   };
 }
 
+// Native notification history entry
+export interface NotificationHistoryEntry {
+  id: number | null;
+  session_id: string | null;
+  notification_type: string;
+  title: string;
+  message: string;
+  sent_at: string;
+}
+
+// Rejection from a settings save whose cycle durations don't fit together
+export interface SettingsValidationError {
+  fields: string[];
+  message: string;
+}
+
+// Rejection from start_focus_session when work hours enforcement blocks it
+export interface OutsideWorkHoursError {
+  type: "outside_work_hours";
+  next_work_start: string | null;
+}
+
+// Result of the prepare_for_quit shutdown routine
+export interface ShutdownReport {
+  strict_mode_released: boolean;
+  window_states_saved: boolean;
+  sessions_closed: number;
+  database_checkpointed: boolean;
+  errors: string[];
+}
+
+// Focus statistics for one calendar period
+export type StatsPeriod = "day" | "week" | "month";
+
+export interface PeriodStats {
+  periodStart: string;
+  periodEnd: string;
+  focusMinutes: number;
+  sessionsCount: number;
+  sessionsCompleted: number;
+  completionRate: number;
+}
+
+export interface BypassAttemptsByHour {
+  /** Attempts per local hour, midnight first */
+  counts: number[];
+  days: number;
+  /** Rows skipped for an unreadable timestamp */
+  skipped: number;
+}
+
+export interface AverageDuration {
+  averageSeconds: number;
+  /** Sessions behind the average; flag small samples */
+  sessions: number;
+}
+
+export interface AverageDurations {
+  focus: AverageDuration;
+  shortBreak: AverageDuration;
+  longBreak: AverageDuration;
+  days: number;
+}
+
+export interface BreakFocusRatio {
+  focusMinutes: number;
+  shortBreakMinutes: number;
+  longBreakMinutes: number;
+  breakMinutes: number;
+  /** Break time per unit of focus time; null without any focus */
+  ratio: number | null;
+  underResting: boolean;
+  days: number;
+}
+
+export interface DailyMood {
+  /** Local date, YYYY-MM-DD */
+  date: string;
+  averageMood: number;
+  sessions: number;
+}
+
+export interface MoodTrends {
+  /** Days with at least one rated session, oldest first */
+  daily: DailyMood[];
+  ratedSessions: number;
+  averageMood: number | null;
+  averageMoodCompleted: number | null;
+  averageMoodIncomplete: number | null;
+  /** -1 to 1; null while mood or completion never varies */
+  moodCompletionCorrelation: number | null;
+  days: number;
+}
+
+export type SessionAnomalyKind =
+  | "end_before_start"
+  | "overlap"
+  | "duration_exceeds_plan"
+  | "missing_field";
+
+export interface SessionAnomaly {
+  sessionId: string;
+  kind: SessionAnomalyKind;
+  detail: string;
+  /** Repaired by find_session_anomalies with fix enabled */
+  fixable: boolean;
+}
+
+export interface SessionAnomalyReport {
+  anomalies: SessionAnomaly[];
+  fixed: number;
+}
+
+export interface WeeklyBudgetProgress {
+  usedMinutes: number;
+  /** null when no weekly budget is set */
+  budgetMinutes: number | null;
+  remainingMinutes: number | null;
+  exhausted: boolean;
+  weekStart: string;
+  weekEnd: string;
+}
+
+export interface DayRecord {
+  /** Local date, YYYY-MM-DD */
+  date: string;
+  value: number;
+}
+
+export interface PersonalRecords {
+  /** Minutes */
+  longestSession: DayRecord | null;
+  mostFocusMinutes: DayRecord | null;
+  mostSessions: DayRecord | null;
+  /** Days, dated by the streak's last day */
+  longestStreak: DayRecord | null;
+}
+
+export interface StrictModeUptime {
+  periodStart: string;
+  periodEnd: string;
+  activeSeconds: number;
+  activations: number;
+  emergencyExits: number;
+}
+
+export interface StrictModeInterval {
+  activatedAt: string;
+  /** null while strict mode is still on */
+  deactivatedAt: string | null;
+  durationSeconds: number;
+  endedBy: "deactivate" | "emergency_exit" | "recovery" | null;
+  emergencyExit: boolean;
+  bypassAttempts: number;
+}
+
+export interface SettingVsDefault {
+  /** Field name as returned by `get_user_settings` */
+  key: string;
+  current: unknown;
+  default: unknown;
+  customized: boolean;
+}
+
+export interface SettingsVsDefaults {
+  settings: SettingVsDefault[];
+  customizedCount: number;
+}
+
+export type ShortcutAvailability =
+  | { status: "available"; shortcut: string }
+  | { status: "internal_conflict"; shortcut: string; action: string }
+  | { status: "system_conflict"; shortcut: string }
+  | { status: "invalid"; reason: string };
+
+/** Running `test_emergency_hotkey` check, also sent as `emergency-hotkey-test` */
+export interface EmergencyHotkeyTest {
+  id: string;
+  combination: string;
+  expires_at: string;
+}
+
+export interface PeriodComparison {
+  current: PeriodStats;
+  previous: PeriodStats;
+  focusMinutesDelta: number | null;
+  sessionsDelta: number | null;
+  completionRateDelta: number | null;
+}
+
+export interface ConsistencyStats {
+  days: number;
+  activeDays: number;
+  totalFocusMinutes: number;
+  meanFocusMinutes: number;
+  stdDevFocusMinutes: number;
+  coefficientOfVariation: number | null;
+  minFocusMinutes: number;
+  maxFocusMinutes: number;
+}
+
+export interface DailyCycleCount {
+  date: string;
+  cyclesCompleted: number;
+  focusSessionsCompleted: number;
+}
+
+export interface WeekGridDay {
+  date: string;
+  focusMinutes: number;
+  sessionsCompleted: number;
+  goalMet: boolean;
+}
+
+export interface WorkHoursDay {
+  date: string;
+  withinMinutes: number;
+  outsideMinutes: number;
+}
+
+export interface FocusByWeekday {
+  /** Focus minutes per weekday, Monday first */
+  minutes: number[];
+  weeks: number;
+}
+
+export interface SessionsToGoal {
+  achievedMinutes: number;
+  goalMinutes: number;
+  sessionsRemaining: number;
+  leftoverMinutes: number;
+}
+
+/** Daily focus targets, in effect from `date` until a later goal */
+export interface DailyGoal {
+  date: string;
+  targetMinutes: number | null;
+  targetSessions: number | null;
+}
+
+/** Today's completed focus against the daily goal; percentages may exceed 100 */
+export interface DailyProgress {
+  date: string;
+  goal: DailyGoal | null;
+  minutesDone: number;
+  sessionsDone: number;
+  minutesPercent: number | null;
+  sessionsPercent: number | null;
+}
+
+/** Focus sessions started today, completed or not */
+export interface TodaySessionCount {
+  started: number;
+  completed: number;
+}
+
+/** Stored session row as returned by the database (snake_case) */
+export interface SessionRecord {
+  id: string;
+  session_type: "focus" | "short_break" | "long_break";
+  start_time: string;
+  end_time: string | null;
+  planned_duration: number;
+  actual_duration: number | null;
+  strict_mode: boolean;
+  completed: boolean;
+  notes: string | null;
+  created_at: string;
+  within_work_hours: boolean;
+  cycle_number: number | null;
+  is_long_break: boolean;
+  tag: string | null;
+  is_sprint: boolean;
+}
+
+export type SessionHistoryFilter = "focus" | "breaks";
+
+/** One page of session history, newest first; `total` counts every match */
+export interface SessionHistoryPage {
+  sessions: SessionRecord[];
+  total: number;
+}
+
+export interface DashboardTotals {
+  focusMinutes: number;
+  breakMinutes: number;
+  focusSessions: number;
+  focusSessionsCompleted: number;
+}
+
+export interface Dashboard {
+  /** Days in a row with completed focus, ending today or yesterday */
+  currentStreak: number;
+  /** null when no goal was passed */
+  dailyGoal: SessionsToGoal | null;
+  completionRate: number;
+  /** Focus minutes per local hour, hour 0 first */
+  focusByHour: number[];
+  /** Newest first */
+  recentSessions: SessionRecord[];
+  totals: DashboardTotals;
+  days: number;
+}
+
+/** Memory use and in-memory buffer sizes, for memory growth reports */
+export interface RuntimeStats {
+  /** null where the platform can't report it */
+  rssBytes: number | null;
+  openWindows: number;
+  windowLabels: string[];
+  cycleEvents: number;
+  cycleEventsCapacity: number;
+  notificationHistory: number;
+  notificationHistoryCapacity: number;
+  telemetryQueue: number;
+}
+
+/** Tick and database write counters since the last reset */
+export interface PerformanceCounters {
+  since: string;
+  ticks: number;
+  skippedTicks: number;
+  /** null before the first tick */
+  averageTickMicros: number | null;
+  dbWrites: number;
+  dbRetries: number;
+}
+
+export type CommandRequirement =
+  | "cycle_orchestrator"
+  | "strict_mode_orchestrator"
+  | "strict_mode_active";
+
+export interface CommandInfo {
+  name: string;
+  /** Whether the command can succeed given the current app state */
+  available: boolean;
+  requires: CommandRequirement | null;
+}
+
+/** Registered backend commands of one module */
+export interface CommandGroup {
+  module: string;
+  commands: CommandInfo[];
+}
+
 // Error types
 export type PausaError =
   | "database-error"
@@ -155,6 +502,79 @@ export interface CycleState {
   can_start: boolean;
   session_id?: string;
   started_at?: string;
+  is_sprint?: boolean;
+  is_wind_down?: boolean;
+  phase_color?: string | null;
+}
+
+export interface PhaseColors {
+  focus: string;
+  short_break: string;
+  long_break: string;
+}
+
+export type SoundName = "chime" | "bell" | "none";
+
+// Sounds played when a focus session or break ends; off by default
+export interface SoundConfig {
+  enabled: boolean;
+  focus_end: SoundName;
+  break_end: SoundName;
+}
+
+// Named cycle durations in minutes; leave id out to add a new preset
+export interface CyclePreset {
+  id?: number | null;
+  name: string;
+  focus_duration: number;
+  break_duration: number;
+  long_break_duration: number;
+  cycles_per_long_break: number;
+}
+
+// Opt-in shell command or deep link run when each phase starts
+export interface PhaseCommandHooks {
+  enabled: boolean;
+  focus?: string | null;
+  short_break?: string | null;
+  long_break?: string | null;
+}
+
+export interface DayProjection {
+  window_minutes: number;
+  focus_sessions: number;
+  planned_focus_minutes: number;
+  projected_focus_minutes: number;
+  assumptions: {
+    compliance_percent: number;
+    schedule_enabled: boolean;
+    work_start_time?: string | null;
+    work_end_time?: string | null;
+    focus_minutes: number;
+    short_break_minutes: number;
+    long_break_minutes: number;
+    cycles_per_long_break: number;
+  };
+}
+
+export interface PhaseEndTime {
+  phase: CyclePhase;
+  ends_at?: string | null;
+  paused: boolean;
+}
+
+// is_work_day_today resolves to null while the work schedule has no
+// per-weekday settings to tell days off apart
+export interface WorkDayStatus {
+  is_work_day: boolean;
+  work_start_time?: string;
+  work_end_time?: string;
+}
+
+export interface TickInterval {
+  interval_ms: number;
+  power_save: boolean;
+  idle_interval_ms: number;
 }
 
 export interface CycleEvent {
@@ -168,7 +588,77 @@ export type CycleEventData =
   | { type: "tick"; remaining: number }
   | { type: "pre_alert"; remaining: number }
   | { type: "cycle_completed"; cycle_count: number }
-  | { type: "long_break_reached"; cycles_completed: number };
+  | { type: "long_break_reached"; cycles_completed: number }
+  | {
+      type: "paused";
+      phase: CyclePhase;
+      remaining: number;
+      pause_behavior: FocusWidgetPauseBehavior;
+    }
+  | { type: "resumed"; phase: CyclePhase; remaining: number }
+  | { type: "outside_work_hours"; next_work_start: string | null }
+  | { type: "clock_changed"; divergence_seconds: number; remaining: number }
+  | { type: "weekly_budget_reached"; used_minutes: number; budget_minutes: number }
+  | { type: "break_overran"; phase: CyclePhase; overrun_seconds: number }
+  | { type: "wind_down_suggested"; minutes_until_work_end: number; duration: number };
+
+export interface RecordedCycleEvent {
+  event: CycleEventData;
+  timestamp: string;
+}
+
+export interface ConfigDrift {
+  field: string;
+  saved_value: unknown;
+  active_value: unknown;
+}
+
+export type FocusWidgetPauseBehavior = "show_paused" | "pulse" | "expand_controls";
+
+export type WorkHoursEnforcement = "off" | "warn" | "block";
+
+export type TrayIconStyle = "auto" | "light" | "dark" | "colorful";
+
+export type TrayClickAction =
+  | "show_popover"
+  | "show_main_window"
+  | "toggle_focus"
+  | "none";
+
+export type AppTheme = "light" | "dark" | "system";
+
+export interface ThemeChangedEvent {
+  theme: AppTheme;
+  effective: Exclude<AppTheme, "system">;
+}
+
+export type OverlayEscapeAction =
+  | "ignore"
+  | "log_bypass"
+  | "end_break"
+  | "require_pin";
+
+export type OverlayEscapeOutcome =
+  | "ignored"
+  | "bypass_logged"
+  | "break_ended"
+  | "pin_required"
+  | "pin_rejected";
+
+export type SoftOverlayDismissAction = "end_break" | "snooze" | "hide_overlay";
+
+export interface SoftOverlayDismissConfig {
+  action: SoftOverlayDismissAction;
+  snooze_minutes: number;
+}
+
+export type OverlayDismissOutcome =
+  | { type: "blocked" }
+  | { type: "break_ended" }
+  | { type: "snoozed"; minutes: number }
+  | { type: "hidden" };
+
+export type TimeFormatStyle = "clock" | "long" | "compact";
 
 // Utility types
 export interface Position {
@@ -204,6 +694,12 @@ export interface StrictModeState {
   currentWindowType?: StrictModeWindowType;
 }
 
+/** System lock manager status (snake_case, as sent by the backend) */
+export interface SystemLockStatus {
+  is_locked: boolean;
+  emergency_hotkey: string | null;
+}
+
 export type StrictModeWindowType =
   | "menu_bar_icon"
   | "menu_bar_popover"